@@ -20,11 +20,14 @@ use crate::error::AccountCompressionError;
 use crate::events::ChangeLogEvent;
 use anchor_lang::prelude::*;
 use bytemuck::{cast_slice, cast_slice_mut};
+use spl_concurrent_merkle_tree::hash::hash_to_parent;
 use spl_concurrent_merkle_tree::node::{empty_node_cached, Node, EMPTY};
 use std::mem::size_of;
 
 #[inline(always)]
 pub fn check_canopy_bytes(canopy_bytes: &[u8]) -> Result<()> {
+    // `usize::is_multiple_of` needs Rust 1.87, newer than the pinned toolchain
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
     if canopy_bytes.len() % size_of::<Node>() != 0 {
         msg!(
             "Canopy byte length {} is not a multiple of {}",
@@ -88,6 +91,48 @@ pub fn update_canopy(
     Ok(())
 }
 
+/// Updates the canopy from the changelog event of `leaves` that were set at once, as a single
+/// subtree that ends with the changelog's leaf and is padded with `EMPTY` to a power of two.
+///
+/// Such a changelog only holds the path of the last leaf of the subtree, so the other cached
+/// nodes of the subtree are computed from `leaves`. The subtree is only hashed again when the
+/// canopy caches some of the levels below its root.
+pub fn update_canopy_subtree(
+    canopy_bytes: &mut [u8],
+    max_depth: u32,
+    change_log: &ChangeLogEvent,
+    leaves: &[Node],
+) -> Result<()> {
+    update_canopy(canopy_bytes, max_depth, Some(change_log))?;
+    let canopy = cast_slice_mut::<u8, Node>(canopy_bytes);
+    let path_len = get_cached_path_length(canopy, max_depth)?;
+    // The lowest level of the tree that is cached in the canopy
+    let cached_level = max_depth - path_len;
+    let height = leaves.len().next_power_of_two().trailing_zeros();
+    if height <= cached_level {
+        return Ok(());
+    }
+    let start_index = match change_log {
+        ChangeLogEvent::V1(cl) => cl.index + 1 - leaves.len() as u32,
+    };
+    let mut nodes = leaves.to_vec();
+    nodes.resize(1 << height, EMPTY);
+    for level in 0..height {
+        if level >= cached_level {
+            // node_idx - 2 maps to the canopy index
+            let node_idx = (((1 << max_depth) + start_index) >> level) as usize;
+            canopy[node_idx - 2..node_idx - 2 + nodes.len()].copy_from_slice(&nodes);
+        }
+        for i in 0..nodes.len() / 2 {
+            let mut parent = nodes[2 * i];
+            hash_to_parent(&mut parent, &nodes[2 * i + 1], true);
+            nodes[i] = parent;
+        }
+        nodes.truncate(nodes.len() / 2);
+    }
+    Ok(())
+}
+
 pub fn fill_in_proof_from_canopy(
     canopy_bytes: &[u8],
     max_depth: u32,
@@ -107,6 +152,8 @@ pub fn fill_in_proof_from_canopy(
     while node_idx > 1 {
         // node_idx - 2 maps to the canopy index
         let shifted_index = node_idx as usize - 2;
+        // `usize::is_multiple_of` needs Rust 1.87, newer than the pinned toolchain
+        #[allow(unknown_lints, clippy::manual_is_multiple_of)]
         let cached_idx = if shifted_index % 2 == 0 {
             shifted_index + 1
         } else {
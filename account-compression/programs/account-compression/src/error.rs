@@ -47,6 +47,16 @@ pub enum AccountCompressionError {
     /// is out of bounds of tree's maximum leaf capacity
     #[msg("Leaf index of concurrent merkle tree is out of bounds")]
     LeafIndexOutOfBounds,
+
+    /// The leaves of a precomputed tree are still being posted, and the tree
+    /// must be finalized before it can be used
+    #[msg("Tree is being staged and has not been finalized")]
+    TreeIsStaging,
+
+    /// Staging instructions can only be used on a tree that was prepared for staging
+    /// and has not been finalized yet
+    #[msg("Tree is not being staged")]
+    TreeIsNotStaging,
 }
 
 impl From<&ConcurrentMerkleTreeError> for AccountCompressionError {
@@ -39,15 +39,17 @@ pub mod zero_copy;
 
 pub use crate::noop::{wrap_application_data_v1, Noop};
 
-use crate::canopy::{fill_in_proof_from_canopy, update_canopy};
+use crate::canopy::{fill_in_proof_from_canopy, update_canopy, update_canopy_subtree};
 pub use crate::error::AccountCompressionError;
 pub use crate::events::{AccountCompressionEvent, ChangeLogEvent};
 use crate::noop::wrap_event;
 use crate::state::{
-    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+    merkle_tree_get_size, split_staged_root, ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, STAGED_ROOT_SIZE,
 };
 use crate::zero_copy::ZeroCopy;
 
+use spl_concurrent_merkle_tree::{concurrent_merkle_tree::batch_subtrees, node::EMPTY};
 /// Exported for Anchor / Solita
 pub use spl_concurrent_merkle_tree::{
     concurrent_merkle_tree::ConcurrentMerkleTree, error::ConcurrentMerkleTreeError, node::Node,
@@ -173,69 +175,151 @@ pub mod spl_account_compression {
         update_canopy(canopy_bytes, header.get_max_depth(), None)
     }
 
-    /// Note:
-    /// Supporting this instruction open a security vulnerability for indexers.
-    /// This instruction has been deemed unusable for publicly indexed compressed NFTs.
-    /// Indexing batched data in this way requires indexers to read in the `uri`s onto physical storage
-    /// and then into their database. This opens up a DOS attack vector, whereby this instruction is
-    /// repeatedly invoked, causing indexers to fail.
+    /// Creates a new merkle tree, like `init_empty_merkle_tree`, into which the leaves of
+    /// a tree that was computed off-chain can be posted with `append_staged_leaves`.
     ///
-    /// Because this instruction was deemed insecure, this instruction has been removed
-    /// until secure usage is available on-chain.
-    // pub fn init_merkle_tree_with_root(
-    //     ctx: Context<Initialize>,
-    //     max_depth: u32,
-    //     max_buffer_size: u32,
-    //     root: [u8; 32],
-    //     leaf: [u8; 32],
-    //     index: u32,
-    //     _changelog_db_uri: String,
-    //     _metadata_db_uri: String,
-    // ) -> Result<()> {
-    //     require_eq!(
-    //         *ctx.accounts.merkle_tree.owner,
-    //         crate::id(),
-    //         AccountCompressionError::IncorrectAccountOwner
-    //     );
-    //     let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
-
-    //     let (mut header_bytes, rest) =
-    //         merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
-
-    //     let mut header = ConcurrentMerkleTreeHeader::try_from_slice(&header_bytes)?;
-    //     header.initialize(
-    //         max_depth,
-    //         max_buffer_size,
-    //         &ctx.accounts.authority.key(),
-    //         Clock::get()?.slot,
-    //     );
-    //     header.serialize(&mut header_bytes)?;
-    //     let merkle_tree_size = merkle_tree_get_size(&header)?;
-    //     let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
-
-    //     // Get rightmost proof from accounts
-    //     let mut proof = vec![];
-    //     for node in ctx.remaining_accounts.iter() {
-    //         proof.push(node.key().to_bytes());
-    //     }
-    //     fill_in_proof_from_canopy(canopy_bytes, header.max_depth, index, &mut proof)?;
-    //     assert_eq!(proof.len(), max_depth as usize);
-
-    //     let id = ctx.accounts.merkle_tree.key();
-    //     // A call is made to ConcurrentMerkleTree::initialize_with_root(root, leaf, proof, index)
-    //     let change_log = merkle_tree_apply_fn!(
-    //         header,
-    //         id,
-    //         tree_bytes,
-    //         initialize_with_root,
-    //         root,
-    //         leaf,
-    //         &proof,
-    //         index
-    //     )?;
-    //     wrap_event(change_log.try_to_vec()?, &ctx.accounts.log_wrapper)?;
-    //     update_canopy(canopy_bytes, header.max_depth, Some(change_log))
-    // }
+    /// The tree cannot be used by any other instruction until `finalize_staged_merkle_tree`
+    /// has checked that every leaf was posted. Since every leaf is posted on-chain and emits
+    /// a changelog event, indexers can rebuild the tree from the ledger alone, which was not the
+    /// case for the removed `init_merkle_tree_with_root` instruction.
+    ///
+    /// `root` is the root of the precomputed tree, which every chunk of leaves is checked
+    /// against. It is stored at the end of the account, which must be allocated with
+    /// `STAGED_ROOT_SIZE` more bytes than a regular tree with the same canopy.
+    pub fn prepare_staged_merkle_tree(
+        ctx: Context<Initialize>,
+        max_depth: u32,
+        max_buffer_size: u32,
+        root: [u8; 32],
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+
+        let (mut header_bytes, rest) =
+            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+
+        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.initialize(
+            max_depth,
+            max_buffer_size,
+            &ctx.accounts.authority.key(),
+            Clock::get()?.slot,
+        );
+        header.set_is_staging(true);
+        header.serialize(&mut header_bytes)?;
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, rest) = rest.split_at_mut(merkle_tree_size);
+        let (canopy_bytes, staged_root) = split_staged_root(rest)?;
+        *staged_root = root;
+        let id = ctx.accounts.merkle_tree.key();
+        let change_log_event = merkle_tree_apply_fn_mut!(header, id, tree_bytes, initialize,)?;
+        wrap_event(
+            &AccountCompressionEvent::ChangeLog(*change_log_event),
+            &ctx.accounts.noop,
+        )?;
+        update_canopy(canopy_bytes, header.get_max_depth(), None)
+    }
+
+    /// Posts the next chunk of leaves of a tree prepared with `prepare_staged_merkle_tree`.
+    ///
+    /// The chunk is checked against the root of the precomputed tree stored by
+    /// `prepare_staged_merkle_tree` before being appended: the chunk must cover a whole subtree,
+    /// and the proof of the subtree's root must be provided as 32-byte nodes via
+    /// "remaining accounts", without using the canopy. Only the last chunk can be shorter than
+    /// a power of two. See `ConcurrentMerkleTree::append_subtree`.
+    ///
+    /// A single changelog event is emitted for the whole chunk, holding the path of its last
+    /// leaf. The other leaves of the chunk are only recorded in the instruction data.
+    pub fn append_staged_leaves(ctx: Context<Modify>, leaves: Vec<[u8; 32]>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let (header_bytes, rest) =
+            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_staging_authority(&ctx.accounts.authority.key())?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, rest) = rest.split_at_mut(merkle_tree_size);
+        let (canopy_bytes, staged_root) = split_staged_root(rest)?;
+        let root = *staged_root;
+
+        let mut proof = vec![];
+        for node in ctx.remaining_accounts.iter() {
+            proof.push(node.key().to_bytes());
+        }
+        let id = ctx.accounts.merkle_tree.key();
+        // A call is made to ConcurrentMerkleTree::append_subtree(root, leaves, proof)
+        let change_log_events = merkle_tree_apply_fn_mut_batch!(
+            header,
+            id,
+            tree_bytes,
+            append_subtree,
+            root,
+            &leaves,
+            &proof,
+        )?;
+        for change_log_event in change_log_events {
+            update_canopy_subtree(
+                canopy_bytes,
+                header.get_max_depth(),
+                &change_log_event,
+                &leaves,
+            )?;
+            wrap_event(
+                &AccountCompressionEvent::ChangeLog(*change_log_event),
+                &ctx.accounts.noop,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Finalizes a tree prepared with `prepare_staged_merkle_tree`, after which it can be used
+    /// like any other tree.
+    ///
+    /// Fails unless the current root of the tree is the root of the precomputed tree stored by
+    /// `prepare_staged_merkle_tree`, which means that every leaf of the precomputed tree was
+    /// posted. The stored root is then removed from the end of the account.
+    pub fn finalize_staged_merkle_tree(ctx: Context<Modify>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let (mut header_bytes, rest) =
+            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+
+        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_staging_authority(&ctx.accounts.authority.key())?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, rest) = rest.split_at_mut(merkle_tree_size);
+        let (_canopy_bytes, staged_root) = split_staged_root(rest)?;
+        let root = *staged_root;
+        staged_root.fill(0);
+
+        let id = ctx.accounts.merkle_tree.key();
+        merkle_tree_apply_fn!(header, id, tree_bytes, prove_root, root)?;
+
+        header.set_is_staging(false);
+        header.serialize(&mut header_bytes)?;
+
+        let data_len = merkle_tree_bytes.len();
+        drop(merkle_tree_bytes);
+        ctx.accounts
+            .merkle_tree
+            .realloc(data_len - STAGED_ROOT_SIZE, false)?;
+        Ok(())
+    }
 
     /// Executes an instruction that overwrites a leaf node.
     /// Composing programs should check that the data hashed into previous_leaf
@@ -292,6 +376,77 @@ pub mod spl_account_compression {
         )
     }
 
+    /// Executes an instruction that overwrites a chunk of consecutive leaves at once, starting
+    /// at `index`. Composing programs should check that the data hashed into `previous_leaves`
+    /// matches the authority information necessary to execute this instruction.
+    ///
+    /// The chunk must cover a whole subtree: its length rounded up to the next power of two must
+    /// divide `index`, and only a chunk that ends at the rightmost leaf can be shorter. The proof
+    /// of the subtree's root must be provided as 32-byte nodes via "remaining accounts", without
+    /// the nodes cached in the canopy. See `ConcurrentMerkleTree::replace_subtree`.
+    ///
+    /// A single changelog event is emitted for the whole chunk, holding the path of its last
+    /// leaf. The other new leaves are only recorded in the instruction data.
+    pub fn replace_leaves(
+        ctx: Context<Modify>,
+        root: [u8; 32],
+        previous_leaves: Vec<[u8; 32]>,
+        new_leaves: Vec<[u8; 32]>,
+        index: u32,
+    ) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let (header_bytes, rest) =
+            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+        header.assert_valid_leaf_index(index)?;
+
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+
+        // The proof starts above the subtree, so placeholders stand for the levels below it
+        // while the proof is completed from the canopy
+        let height = new_leaves.len().next_power_of_two().trailing_zeros() as usize;
+        let mut proof = vec![EMPTY; height];
+        for node in ctx.remaining_accounts.iter() {
+            proof.push(node.key().to_bytes());
+        }
+        fill_in_proof_from_canopy(canopy_bytes, header.get_max_depth(), index, &mut proof)?;
+        proof.drain(..height);
+        let id = ctx.accounts.merkle_tree.key();
+        // A call is made to ConcurrentMerkleTree::replace_subtree(root, previous_leaves, new_leaves, proof, index)
+        let change_log_events = merkle_tree_apply_fn_mut_batch!(
+            header,
+            id,
+            tree_bytes,
+            replace_subtree,
+            root,
+            &previous_leaves,
+            &new_leaves,
+            &proof,
+            index,
+        )?;
+        for change_log_event in change_log_events {
+            update_canopy_subtree(
+                canopy_bytes,
+                header.get_max_depth(),
+                &change_log_event,
+                &new_leaves,
+            )?;
+            wrap_event(
+                &AccountCompressionEvent::ChangeLog(*change_log_event),
+                &ctx.accounts.noop,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Transfers `authority`.
     /// Requires `authority` to sign
     pub fn transfer_authority(
@@ -385,6 +540,62 @@ pub mod spl_account_compression {
         )
     }
 
+    /// This instruction allows the tree's `authority` to append multiple leaves to
+    /// the tree at once, without having to supply a proof.
+    ///
+    /// The leaves are appended in order, and split into the largest subtrees that are aligned
+    /// in the tree. Each subtree is hashed once and emits a single changelog event, holding the
+    /// path of its last leaf, so the other leaves are only recorded in the instruction data.
+    /// Either all of the leaves are appended, or none of them are. The leaves cannot be split
+    /// into more subtrees than the tree's `max_buffer_size`.
+    ///
+    /// Proofs of leaves outside of the appended subtrees are fast-forwarded as usual. See
+    /// `ConcurrentMerkleTree::append_batch`.
+    pub fn append_batch(ctx: Context<Modify>, leaves: Vec<[u8; 32]>) -> Result<()> {
+        require_eq!(
+            *ctx.accounts.merkle_tree.owner,
+            crate::id(),
+            AccountCompressionError::IncorrectAccountOwner
+        );
+        let mut merkle_tree_bytes = ctx.accounts.merkle_tree.try_borrow_mut_data()?;
+        let (header_bytes, rest) =
+            merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        header.assert_valid_authority(&ctx.accounts.authority.key())?;
+
+        let id = ctx.accounts.merkle_tree.key();
+        let merkle_tree_size = merkle_tree_get_size(&header)?;
+        let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
+        // A call is made to ConcurrentMerkleTree::append_batch(leaves)
+        let change_log_events =
+            merkle_tree_apply_fn_mut_batch!(header, id, tree_bytes, append_batch, &leaves,)?;
+        // One changelog event is emitted per subtree, and the last one ends with the last leaf
+        let start_index = match change_log_events.last().map(|event| &**event) {
+            Some(ChangeLogEvent::V1(change_log)) => change_log.index + 1 - leaves.len() as u32,
+            None => 0,
+        };
+        let mut remaining_leaves = leaves.as_slice();
+        for (change_log_event, (_, height)) in change_log_events
+            .into_iter()
+            .zip(batch_subtrees(start_index, leaves.len()))
+        {
+            let (subtree_leaves, rest) = remaining_leaves.split_at(1 << height);
+            update_canopy_subtree(
+                canopy_bytes,
+                header.get_max_depth(),
+                &change_log_event,
+                subtree_leaves,
+            )?;
+            wrap_event(
+                &AccountCompressionEvent::ChangeLog(*change_log_event),
+                &ctx.accounts.noop,
+            )?;
+            remaining_leaves = rest;
+        }
+        Ok(())
+    }
+
     /// This instruction takes a proof, and will attempt to write the given leaf
     /// to the specified index in the tree. If the insert operation fails, the leaf will be `append`-ed
     /// to the tree.
@@ -439,6 +650,9 @@ pub mod spl_account_compression {
         )
     }
 
+    /// Closes an empty tree, sending its lamports to `recipient`.
+    ///
+    /// A tree that is still being staged can be closed even if it is not empty.
     pub fn close_empty_tree(ctx: Context<CloseTree>) -> Result<()> {
         require_eq!(
             *ctx.accounts.merkle_tree.owner,
@@ -450,13 +664,19 @@ pub mod spl_account_compression {
             merkle_tree_bytes.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
 
         let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
-        header.assert_valid_authority(&ctx.accounts.authority.key())?;
 
         let merkle_tree_size = merkle_tree_get_size(&header)?;
         let (tree_bytes, canopy_bytes) = rest.split_at_mut(merkle_tree_size);
 
-        let id = ctx.accounts.merkle_tree.key();
-        merkle_tree_apply_fn_mut!(header, id, tree_bytes, prove_tree_is_empty,)?;
+        if header.get_is_staging() {
+            // A tree that is being staged was never usable, so it can be abandoned
+            // whether or not leaves were already posted
+            header.assert_valid_staging_authority(&ctx.accounts.authority.key())?;
+        } else {
+            header.assert_valid_authority(&ctx.accounts.authority.key())?;
+            let id = ctx.accounts.merkle_tree.key();
+            merkle_tree_apply_fn_mut!(header, id, tree_bytes, prove_tree_is_empty,)?;
+        }
 
         // Close merkle tree account
         // 1. Move lamports
@@ -2,6 +2,7 @@
 enum TreeLoad {
     Immutable,
     Mutable,
+    MutableBatch,
 }
 
 /// This macro applies functions on a ConcurrentMerkleT:ee and emits leaf information
@@ -28,6 +29,43 @@ macro_rules! _merkle_tree_depth_size_apply_fn {
             }
         }
     };
+    ($max_depth:literal, $max_size:literal, $id:ident, $bytes:ident, $func:ident, TreeLoad::MutableBatch, $($arg:tt)*)
+     => {
+        match ConcurrentMerkleTree::<$max_depth, $max_size>::load_mut_bytes($bytes) {
+            Ok(merkle_tree) => {
+                let sequence_number = merkle_tree.sequence_number;
+                match merkle_tree.$func($($arg)*) {
+                    Ok(_) => {
+                        // Collect the changelogs of the batch, oldest first
+                        let num_changes = merkle_tree.sequence_number - sequence_number;
+                        let mut change_log_events = Vec::with_capacity(num_changes as usize);
+                        for offset in (0..num_changes).rev() {
+                            match merkle_tree.get_past_change_log(offset) {
+                                Some(change_log) => change_log_events.push(Box::<ChangeLogEvent>::from((
+                                    change_log,
+                                    $id,
+                                    merkle_tree.sequence_number - offset,
+                                ))),
+                                None => {
+                                    msg!("Changelog {} of the batch is no longer in the buffer", offset);
+                                    return err!(AccountCompressionError::ConcurrentMerkleTreeError);
+                                }
+                            }
+                        }
+                        Ok(change_log_events)
+                    }
+                    Err(err) => {
+                        msg!("Error using concurrent merkle tree: {}", err);
+                        err!(AccountCompressionError::ConcurrentMerkleTreeError)
+                    }
+                }
+            }
+            Err(err) => {
+                msg!("Error zero copying concurrent merkle tree: {}", err);
+                err!(AccountCompressionError::ZeroCopyError)
+            }
+        }
+    };
     ($max_depth:literal, $max_size:literal, $id:ident, $bytes:ident, $func:ident, TreeLoad::Immutable, $($arg:tt)*) => {
         match ConcurrentMerkleTree::<$max_depth, $max_size>::load_bytes($bytes) {
             Ok(merkle_tree) => {
@@ -118,3 +156,12 @@ macro_rules! merkle_tree_apply_fn {
         _merkle_tree_apply_fn!($header, $id, $bytes, $func, TreeLoad::Immutable, $($arg)*)
     };
 }
+
+/// This applies a given batch function on a mutable ConcurrentMerkleTree, and
+/// returns the changelog events of every tree operation it recorded, oldest first
+#[macro_export]
+macro_rules! merkle_tree_apply_fn_mut_batch {
+    ($header:ident, $id:ident, $bytes:ident, $func:ident, $($arg:tt)*) => {
+        _merkle_tree_apply_fn!($header, $id, $bytes, $func, TreeLoad::MutableBatch, $($arg)*)
+    };
+}
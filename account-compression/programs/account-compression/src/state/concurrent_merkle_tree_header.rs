@@ -8,6 +8,11 @@ use crate::error::AccountCompressionError;
 
 pub const CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1: usize = 2 + 54;
 
+/// Size of the root of the precomputed tree, which is stored at the end of the account
+/// of a tree that is being staged, after the canopy.
+/// The account shrinks back to the size of a regular tree when the tree is finalized.
+pub const STAGED_ROOT_SIZE: usize = 32;
+
 #[derive(Debug, Copy, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
 #[repr(u8)]
 pub enum CompressionAccountType {
@@ -65,9 +70,14 @@ pub struct ConcurrentMerkleTreeHeaderDataV1 {
     /// Provides a lower-bound on what slot to start (re-)building a tree from.
     creation_slot: u64,
 
+    /// Whether the leaves of a precomputed tree are still being posted.
+    /// A tree that is being staged can only be modified by the staging instructions,
+    /// until it is finalized.
+    is_staging: bool,
+
     /// Needs padding for the account to be 8-byte aligned
     /// 8-byte alignment is necessary to zero-copy the SPL ConcurrentMerkleTree
-    _padding: [u8; 6],
+    _padding: [u8; 5],
 }
 
 #[repr(C)]
@@ -117,6 +127,20 @@ impl ConcurrentMerkleTreeHeader {
         }
     }
 
+    pub fn get_is_staging(&self) -> bool {
+        match &self.header {
+            ConcurrentMerkleTreeHeaderData::V1(header) => header.is_staging,
+        }
+    }
+
+    pub fn set_is_staging(&mut self, is_staging: bool) {
+        match self.header {
+            ConcurrentMerkleTreeHeaderData::V1(ref mut header) => {
+                header.is_staging = is_staging;
+            }
+        }
+    }
+
     pub fn set_new_authority(&mut self, new_authority: &Pubkey) {
         match self.header {
             ConcurrentMerkleTreeHeaderData::V1(ref mut header) => {
@@ -127,6 +151,28 @@ impl ConcurrentMerkleTreeHeader {
     }
 
     pub fn assert_valid(&self) -> Result<()> {
+        self.assert_valid_account_type()?;
+        if self.get_is_staging() {
+            return err!(AccountCompressionError::TreeIsStaging);
+        }
+        Ok(())
+    }
+
+    pub fn assert_valid_authority(&self, expected_authority: &Pubkey) -> Result<()> {
+        self.assert_valid()?;
+        self.assert_authority(expected_authority)
+    }
+
+    /// Checks that the tree is being staged, and that `expected_authority` is the tree's authority
+    pub fn assert_valid_staging_authority(&self, expected_authority: &Pubkey) -> Result<()> {
+        self.assert_valid_account_type()?;
+        if !self.get_is_staging() {
+            return err!(AccountCompressionError::TreeIsNotStaging);
+        }
+        self.assert_authority(expected_authority)
+    }
+
+    fn assert_valid_account_type(&self) -> Result<()> {
         require_eq!(
             self.account_type,
             CompressionAccountType::ConcurrentMerkleTree,
@@ -135,8 +181,7 @@ impl ConcurrentMerkleTreeHeader {
         Ok(())
     }
 
-    pub fn assert_valid_authority(&self, expected_authority: &Pubkey) -> Result<()> {
-        self.assert_valid()?;
+    fn assert_authority(&self, expected_authority: &Pubkey) -> Result<()> {
         match &self.header {
             ConcurrentMerkleTreeHeaderData::V1(header) => {
                 require_eq!(
@@ -157,6 +202,20 @@ impl ConcurrentMerkleTreeHeader {
     }
 }
 
+/// Splits the bytes following the tree of a tree that is being staged into the canopy
+/// and the root of the precomputed tree
+pub fn split_staged_root(bytes: &mut [u8]) -> Result<(&mut [u8], &mut [u8; 32])> {
+    if bytes.len() < STAGED_ROOT_SIZE {
+        msg!(
+            "Staged trees need {} additional bytes to store the root of the precomputed tree",
+            STAGED_ROOT_SIZE
+        );
+        return err!(AccountCompressionError::CanopyLengthMismatch);
+    }
+    let (canopy_bytes, root_bytes) = bytes.split_at_mut(bytes.len() - STAGED_ROOT_SIZE);
+    Ok((canopy_bytes, root_bytes.try_into().unwrap()))
+}
+
 pub fn merkle_tree_get_size(header: &ConcurrentMerkleTreeHeader) -> Result<usize> {
     // Note: max_buffer_size MUST be a power of 2
     match (header.get_max_depth(), header.get_max_buffer_size()) {
@@ -6,6 +6,7 @@ import {
     ConcurrentMerkleTreeHeader,
     concurrentMerkleTreeHeaderBeet,
 } from '../generated/types/ConcurrentMerkleTreeHeader';
+import { STAGED_ROOT_SIZE } from '../constants';
 import { Canopy, canopyBeetFactory, ConcurrentMerkleTree, concurrentMerkleTreeBeetFactory } from '../types';
 
 /**
@@ -16,11 +17,13 @@ export class ConcurrentMerkleTreeAccount {
     public header: ConcurrentMerkleTreeHeader;
     public tree: ConcurrentMerkleTree;
     public canopy: Canopy;
+    public stagedRoot?: Buffer;
 
-    constructor(header: ConcurrentMerkleTreeHeader, tree: ConcurrentMerkleTree, canopy: Canopy) {
+    constructor(header: ConcurrentMerkleTreeHeader, tree: ConcurrentMerkleTree, canopy: Canopy, stagedRoot?: Buffer) {
         this.header = header;
         this.tree = tree;
         this.canopy = canopy;
+        this.stagedRoot = stagedRoot;
     }
 
     static fromBuffer(buffer: Buffer): ConcurrentMerkleTreeAccount {
@@ -104,6 +107,24 @@ export class ConcurrentMerkleTreeAccount {
         return new BN(this.getHeaderV1().creationSlot);
     }
 
+    /**
+     * Returns whether the leaves of a precomputed tree are still being posted
+     * to this tree. Such a tree cannot be used until it is finalized.
+     * @returns
+     */
+    isStaging(): boolean {
+        return this.getHeaderV1().isStaging;
+    }
+
+    /**
+     * Returns the root of the precomputed tree whose leaves are being posted
+     * to this tree, or `undefined` if the tree is not being staged.
+     * @returns
+     */
+    getStagedRoot(): Buffer | undefined {
+        return this.stagedRoot;
+    }
+
     /**
      * Returns the number of modifying operations that have been performed
      * on this tree.
@@ -153,7 +174,15 @@ function deserializeConcurrentMerkleTree(buffer: Buffer): ConcurrentMerkleTreeAc
     );
     offset = offsetIncr2;
 
-    const canopyDepth = getCanopyDepth(buffer.byteLength - offset);
+    // A tree that is being staged stores the root of the precomputed tree after the canopy
+    let canopyEnd = buffer.byteLength;
+    let stagedRoot: Buffer | undefined;
+    if (header.isStaging) {
+        canopyEnd -= STAGED_ROOT_SIZE;
+        stagedRoot = buffer.subarray(canopyEnd);
+    }
+
+    const canopyDepth = getCanopyDepth(canopyEnd - offset);
     let canopy: Canopy = {
         canopyBytes: [],
     };
@@ -163,10 +192,10 @@ function deserializeConcurrentMerkleTree(buffer: Buffer): ConcurrentMerkleTreeAc
         offset = offsetIncr3;
     }
 
-    if (buffer.byteLength !== offset) {
+    if (canopyEnd !== offset) {
         throw new Error('Failed to process whole buffer when deserializing Merkle Account Data');
     }
-    return new ConcurrentMerkleTreeAccount(versionedHeader, tree, canopy);
+    return new ConcurrentMerkleTreeAccount(versionedHeader, tree, canopy, stagedRoot);
 }
 
 /**
@@ -3,6 +3,12 @@ import { PublicKey } from '@solana/web3.js';
 export const SPL_NOOP_ADDRESS = 'noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV';
 export const SPL_NOOP_PROGRAM_ID = new PublicKey(SPL_NOOP_ADDRESS);
 
+/**
+ * Number of bytes used to store the root of the precomputed tree at the end of
+ * the account of a tree that is being staged
+ */
+export const STAGED_ROOT_SIZE = 32;
+
 /**
  * DepthSizePair is a valid (`maxDepth`, `maxBufferSize`) tuple for an SPL ConcurrentMerkleTree
  * Only the tuples listed in {@link ALL_DEPTH_SIZE_PAIRS} are valid for
@@ -194,6 +194,46 @@ export class LeafIndexOutOfBoundsError extends Error {
 createErrorFromCodeLookup.set(0x1778, () => new LeafIndexOutOfBoundsError());
 createErrorFromNameLookup.set('LeafIndexOutOfBounds', () => new LeafIndexOutOfBoundsError());
 
+/**
+ * TreeIsStaging: 'Tree is being staged and has not been finalized'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class TreeIsStagingError extends Error {
+    readonly code: number = 0x1779;
+    readonly name: string = 'TreeIsStaging';
+    constructor() {
+        super('Tree is being staged and has not been finalized');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, TreeIsStagingError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x1779, () => new TreeIsStagingError());
+createErrorFromNameLookup.set('TreeIsStaging', () => new TreeIsStagingError());
+
+/**
+ * TreeIsNotStaging: 'Tree is not being staged'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class TreeIsNotStagingError extends Error {
+    readonly code: number = 0x177a;
+    readonly name: string = 'TreeIsNotStaging';
+    constructor() {
+        super('Tree is not being staged');
+        if (typeof Error.captureStackTrace === 'function') {
+            Error.captureStackTrace(this, TreeIsNotStagingError);
+        }
+    }
+}
+
+createErrorFromCodeLookup.set(0x177a, () => new TreeIsNotStagingError());
+createErrorFromNameLookup.set('TreeIsNotStaging', () => new TreeIsNotStagingError());
+
 /**
  * Attempts to resolve a custom program error from the provided error code.
  * @category Errors
@@ -1,7 +1,12 @@
 export * from './append';
+export * from './appendBatch';
+export * from './appendStagedLeaves';
 export * from './closeEmptyTree';
+export * from './finalizeStagedMerkleTree';
 export * from './initEmptyMerkleTree';
 export * from './insertOrAppend';
+export * from './prepareStagedMerkleTree';
 export * from './replaceLeaf';
+export * from './replaceLeaves';
 export * from './transferAuthority';
 export * from './verifyLeaf';
@@ -1,12 +1,17 @@
 import { Connection, PublicKey, SystemProgram, TransactionInstruction } from '@solana/web3.js';
 
 import { getConcurrentMerkleTreeAccountSize } from '../accounts';
-import { SPL_NOOP_PROGRAM_ID, ValidDepthSizePair } from '../constants';
+import { SPL_NOOP_PROGRAM_ID, STAGED_ROOT_SIZE, ValidDepthSizePair } from '../constants';
 import {
+    createAppendBatchInstruction,
     createAppendInstruction,
+    createAppendStagedLeavesInstruction,
     createCloseEmptyTreeInstruction,
+    createFinalizeStagedMerkleTreeInstruction,
     createInitEmptyMerkleTreeInstruction,
+    createPrepareStagedMerkleTreeInstruction,
     createReplaceLeafInstruction,
+    createReplaceLeavesInstruction,
     createTransferAuthorityInstruction,
     createVerifyLeafInstruction,
     PROGRAM_ID,
@@ -53,6 +58,83 @@ export function createInitEmptyMerkleTreeIx(
     );
 }
 
+/**
+ * Helper function for {@link createPrepareStagedMerkleTreeInstruction}
+ *
+ * The tree account must be allocated with {@link createAllocTreeIx} with `staged` set,
+ * so that it has room for the `root` of the precomputed tree.
+ * @param merkleTree
+ * @param authority
+ * @param depthSizePair
+ * @param root
+ * @returns
+ */
+export function createPrepareStagedMerkleTreeIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    depthSizePair: ValidDepthSizePair,
+    root: Buffer
+): TransactionInstruction {
+    return createPrepareStagedMerkleTreeInstruction(
+        {
+            authority: authority,
+            merkleTree,
+            noop: SPL_NOOP_PROGRAM_ID,
+        },
+        {
+            ...depthSizePair,
+            root: Array.from(root),
+        }
+    );
+}
+
+/**
+ * Helper function for {@link createAppendStagedLeavesInstruction}
+ *
+ * `subtreeProof` is the proof of the root of the subtree covered by `leaves`,
+ * which is the proof of the first leaf without its first `log2(leaves.length)` nodes,
+ * rounded up.
+ * @param merkleTree
+ * @param authority
+ * @param leaves
+ * @param subtreeProof
+ * @returns
+ */
+export function createAppendStagedLeavesIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    leaves: (Buffer | ArrayLike<number>)[],
+    subtreeProof: Buffer[]
+): TransactionInstruction {
+    return addProof(
+        createAppendStagedLeavesInstruction(
+            {
+                authority: authority,
+                merkleTree,
+                noop: SPL_NOOP_PROGRAM_ID,
+            },
+            {
+                leaves: leaves.map(leaf => Array.from(leaf)),
+            }
+        ),
+        subtreeProof
+    );
+}
+
+/**
+ * Helper function for {@link createFinalizeStagedMerkleTreeInstruction}
+ * @param merkleTree
+ * @param authority
+ * @returns
+ */
+export function createFinalizeStagedMerkleTreeIx(merkleTree: PublicKey, authority: PublicKey): TransactionInstruction {
+    return createFinalizeStagedMerkleTreeInstruction({
+        authority: authority,
+        merkleTree,
+        noop: SPL_NOOP_PROGRAM_ID,
+    });
+}
+
 /**
  * Helper function for {@link createReplaceLeafInstruction}
  * @param merkleTree
@@ -85,6 +167,48 @@ export function createReplaceIx(
     );
 }
 
+/**
+ * Helper function for {@link createReplaceLeavesInstruction}
+ *
+ * `subtreeProof` is the proof of the root of the subtree covered by the leaves starting at `index`,
+ * which is the proof of the first leaf without its first `log2(newLeaves.length)` nodes,
+ * rounded up. Its nodes that are cached in the canopy can be omitted.
+ * @param merkleTree
+ * @param authority
+ * @param root
+ * @param previousLeaves
+ * @param newLeaves
+ * @param index
+ * @param subtreeProof
+ * @returns
+ */
+export function createReplaceLeavesIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    root: Buffer,
+    previousLeaves: (Buffer | ArrayLike<number>)[],
+    newLeaves: (Buffer | ArrayLike<number>)[],
+    index: number,
+    subtreeProof: Buffer[]
+): TransactionInstruction {
+    return addProof(
+        createReplaceLeavesInstruction(
+            {
+                authority: authority,
+                merkleTree,
+                noop: SPL_NOOP_PROGRAM_ID,
+            },
+            {
+                index,
+                newLeaves: newLeaves.map(leaf => Array.from(leaf)),
+                previousLeaves: previousLeaves.map(leaf => Array.from(leaf)),
+                root: Array.from(root),
+            }
+        ),
+        subtreeProof
+    );
+}
+
 /**
  * Helper function for {@link createAppendInstruction}
  * @param merkleTree
@@ -109,6 +233,30 @@ export function createAppendIx(
     );
 }
 
+/**
+ * Helper function for {@link createAppendBatchInstruction}
+ * @param merkleTree
+ * @param authority
+ * @param newLeaves
+ * @returns
+ */
+export function createAppendBatchIx(
+    merkleTree: PublicKey,
+    authority: PublicKey,
+    newLeaves: (Buffer | ArrayLike<number>)[]
+): TransactionInstruction {
+    return createAppendBatchInstruction(
+        {
+            authority: authority,
+            merkleTree,
+            noop: SPL_NOOP_PROGRAM_ID,
+        },
+        {
+            leaves: newLeaves.map(leaf => Array.from(leaf)),
+        }
+    );
+}
+
 /**
  * Helper function for {@link createTransferAuthorityIx}
  * @param merkleTree
@@ -164,6 +312,8 @@ export function createVerifyLeafIx(merkleTree: PublicKey, proof: MerkleTreeProof
  * @param payer
  * @param depthSizePair
  * @param canopyDepth
+ * @param staged whether the tree will be prepared with {@link createPrepareStagedMerkleTreeIx},
+ * which needs room for the root of the precomputed tree
  * @returns
  */
 export async function createAllocTreeIx(
@@ -171,13 +321,12 @@ export async function createAllocTreeIx(
     merkleTree: PublicKey,
     payer: PublicKey,
     depthSizePair: ValidDepthSizePair,
-    canopyDepth: number
+    canopyDepth: number,
+    staged = false
 ): Promise<TransactionInstruction> {
-    const requiredSpace = getConcurrentMerkleTreeAccountSize(
-        depthSizePair.maxDepth,
-        depthSizePair.maxBufferSize,
-        canopyDepth ?? 0
-    );
+    const requiredSpace =
+        getConcurrentMerkleTreeAccountSize(depthSizePair.maxDepth, depthSizePair.maxBufferSize, canopyDepth ?? 0) +
+        (staged ? STAGED_ROOT_SIZE : 0);
     return SystemProgram.createAccount({
         fromPubkey: payer,
         lamports: await connection.getMinimumBalanceForRentExemption(requiredSpace),
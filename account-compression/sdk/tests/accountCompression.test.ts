@@ -7,11 +7,18 @@ import * as crypto from 'crypto';
 
 import {
     ConcurrentMerkleTreeAccount,
+    createAllocTreeIx,
+    createAppendBatchIx,
     createAppendIx,
+    createAppendStagedLeavesIx,
     createCloseEmptyTreeInstruction,
+    createFinalizeStagedMerkleTreeIx,
+    createPrepareStagedMerkleTreeIx,
     createReplaceIx,
+    createReplaceLeavesIx,
     createTransferAuthorityIx,
     createVerifyLeafIx,
+    getConcurrentMerkleTreeAccountSize,
     ValidDepthSizePair,
 } from '../src';
 import { hash, MerkleTree } from '../src/merkle-tree';
@@ -73,6 +80,49 @@ describe('Account Compression', () => {
                 'Updated on chain root matches root of updated off chain tree'
             );
         });
+        it('Append a batch of leaves', async () => {
+            const newLeaves = [crypto.randomBytes(32), crypto.randomBytes(32), crypto.randomBytes(32)];
+            const appendBatchIx = createAppendBatchIx(cmt, payer, newLeaves);
+
+            await execute(provider, [appendBatchIx], [payerKeypair]);
+            newLeaves.forEach((leaf, i) => offChainTree.updateLeaf(1 + i, leaf));
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            const onChainRoot = splCMT.getCurrentRoot();
+
+            assert(
+                Buffer.from(onChainRoot).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree'
+            );
+        });
+        it('Replace a batch of leaves', async () => {
+            const appendedLeaves = [crypto.randomBytes(32), crypto.randomBytes(32), crypto.randomBytes(32)];
+            await execute(provider, [createAppendBatchIx(cmt, payer, appendedLeaves)], [payerKeypair]);
+            appendedLeaves.forEach((leaf, i) => offChainTree.updateLeaf(1 + i, leaf));
+
+            const previousLeaves = offChainTree.leaves.slice(0, 4).map(leaf => leaf.node);
+            const newLeaves = [...Array(4)].map(() => crypto.randomBytes(32));
+            const replaceLeavesIx = createReplaceLeavesIx(
+                cmt,
+                payer,
+                offChainTree.root,
+                previousLeaves,
+                newLeaves,
+                0,
+                offChainTree.getProof(0).proof.slice(2)
+            );
+
+            await execute(provider, [replaceLeavesIx], [payerKeypair]);
+            newLeaves.forEach((leaf, i) => offChainTree.updateLeaf(i, leaf));
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            const onChainRoot = splCMT.getCurrentRoot();
+
+            assert(
+                Buffer.from(onChainRoot).equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree'
+            );
+        });
         it('Verify proof works for that leaf', async () => {
             const newLeaf = crypto.randomBytes(32);
             const index = 0;
@@ -476,6 +526,63 @@ describe('Account Compression', () => {
             }
         });
     });
+    describe(`Canopy test for batches`, () => {
+        const DEPTH = 5;
+        it('Testing canopy for batch appends and replaces on a full on chain tree', async () => {
+            [cmtKeypair, offChainTree] = await createTreeOnChain(
+                provider,
+                payerKeypair,
+                0,
+                { maxBufferSize: 8, maxDepth: DEPTH },
+                DEPTH // Store full tree on chain
+            );
+            cmt = cmtKeypair.publicKey;
+
+            // Batches record a single changelog per subtree, so the canopy must be
+            // updated for every leaf of the subtree
+            const batchSize = 8;
+            for (let i = 0; i < 2 ** DEPTH; i += batchSize) {
+                const batch = [...Array(batchSize)].map(() => crypto.randomBytes(32));
+                await execute(provider, [createAppendBatchIx(cmt, payer, batch)], [payerKeypair]);
+                batch.forEach((leaf, j) => offChainTree.updateLeaf(i + j, leaf));
+            }
+
+            const newLeaves = [...Array(batchSize)].map(() => crypto.randomBytes(32));
+            const replaceLeavesIx = createReplaceLeavesIx(
+                cmt,
+                payer,
+                offChainTree.root,
+                offChainTree.leaves.slice(batchSize, 2 * batchSize).map(leaf => leaf.node),
+                newLeaves,
+                batchSize,
+                [] // No proof necessary
+            );
+            await execute(provider, [replaceLeavesIx], [payerKeypair]);
+            newLeaves.forEach((leaf, j) => offChainTree.updateLeaf(batchSize + j, leaf));
+
+            // Every leaf can be replaced with a proof inferred from the canopy
+            for (let i = 0; i < 2 ** DEPTH; i += 4) {
+                const root = offChainTree.root;
+                const replacements = [...Array(4)].map(() => crypto.randomBytes(32));
+                const ixs = replacements.map((newLeaf, j) =>
+                    createReplaceIx(cmt, payer, newLeaf, {
+                        leaf: offChainTree.leaves[i + j].node,
+                        leafIndex: i + j,
+                        proof: [],
+                        root, // No proof necessary
+                    })
+                );
+                await execute(provider, ixs, [payerKeypair]);
+                replacements.forEach((leaf, j) => offChainTree.updateLeaf(i + j, leaf));
+            }
+
+            const splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(
+                splCMT.getCurrentRoot().equals(offChainTree.root),
+                'Updated on chain root matches root of updated off chain tree'
+            );
+        });
+    });
     describe(`Having created a tree with 8 leaves`, () => {
         beforeEach(async () => {
             [cmtKeypair, offChainTree] = await createTreeOnChain(provider, payerKeypair, 1 << 3, {
@@ -505,4 +612,96 @@ describe('Account Compression', () => {
             } catch (_e) {}
         });
     });
+    describe(`Having staged a precomputed tree with depth 5`, () => {
+        const DEPTH = 5;
+        const NUM_LEAVES = 20;
+        const CHUNK_SIZE = 8;
+        let leaves: Buffer[];
+
+        beforeEach(async () => {
+            const depthSizePair: ValidDepthSizePair = { maxBufferSize: 8, maxDepth: DEPTH };
+            cmtKeypair = Keypair.generate();
+            cmt = cmtKeypair.publicKey;
+            leaves = [...Array(NUM_LEAVES)].map(() => crypto.randomBytes(32));
+            offChainTree = MerkleTree.sparseMerkleTreeFromLeaves(leaves, DEPTH);
+
+            const allocAccountIx = await createAllocTreeIx(connection, cmt, payer, depthSizePair, 0, true);
+            const prepareIx = createPrepareStagedMerkleTreeIx(cmt, payer, depthSizePair, offChainTree.root);
+            await execute(provider, [allocAccountIx, prepareIx], [payerKeypair, cmtKeypair]);
+        });
+        it('Post every chunk of leaves and finalize the tree', async () => {
+            const root = offChainTree.root;
+            for (let start = 0; start < NUM_LEAVES; start += CHUNK_SIZE) {
+                const chunk = leaves.slice(start, start + CHUNK_SIZE);
+                const subtreeHeight = Math.ceil(Math.log2(chunk.length));
+                const subtreeProof = offChainTree.getProof(start).proof.slice(subtreeHeight);
+                const appendIx = createAppendStagedLeavesIx(cmt, payer, chunk, subtreeProof);
+                await execute(provider, [appendIx], [payerKeypair]);
+            }
+
+            let splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(splCMT.isStaging(), 'Tree should still be staging');
+            assert(splCMT.getStagedRoot()?.equals(root), 'Precomputed root is stored in the account');
+            assert(splCMT.getCurrentRoot().equals(root), 'On chain root matches the precomputed root');
+
+            // The tree cannot be used before it is finalized
+            try {
+                await execute(provider, [createAppendIx(cmt, payer, crypto.randomBytes(32))], [payerKeypair]);
+                assert(false, 'Appending to a tree that is still staging should fail');
+            } catch {}
+
+            await execute(provider, [createFinalizeStagedMerkleTreeIx(cmt, payer)], [payerKeypair]);
+            splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(!splCMT.isStaging(), 'Tree should be finalized');
+            assert(splCMT.getStagedRoot() === undefined, 'Precomputed root is removed from the account');
+            const accountInfo = await connection.getAccountInfo(cmt);
+            assert(
+                accountInfo!.data.length === getConcurrentMerkleTreeAccountSize(DEPTH, 8),
+                'Finalized tree has the size of a regular tree'
+            );
+
+            const newLeaf = crypto.randomBytes(32);
+            await execute(provider, [createAppendIx(cmt, payer, newLeaf)], [payerKeypair]);
+            offChainTree.updateLeaf(NUM_LEAVES, newLeaf);
+            splCMT = await ConcurrentMerkleTreeAccount.fromAccountAddress(connection, cmt);
+            assert(splCMT.getCurrentRoot().equals(offChainTree.root), 'Finalized tree can be appended to');
+        });
+        it('Cannot finalize the tree before every leaf is posted', async () => {
+            const chunk = leaves.slice(0, CHUNK_SIZE);
+            const subtreeProof = offChainTree.getProof(0).proof.slice(3);
+            await execute(provider, [createAppendStagedLeavesIx(cmt, payer, chunk, subtreeProof)], [payerKeypair]);
+
+            try {
+                await execute(provider, [createFinalizeStagedMerkleTreeIx(cmt, payer)], [payerKeypair]);
+                assert(false, 'Finalizing a partially posted tree should fail');
+            } catch {}
+        });
+        it('Cannot post leaves that do not belong to the tree', async () => {
+            const chunk = leaves.slice(0, CHUNK_SIZE);
+            chunk[3] = crypto.randomBytes(32);
+            const subtreeProof = offChainTree.getProof(0).proof.slice(3);
+            try {
+                await execute(
+                    provider,
+                    [createAppendStagedLeavesIx(cmt, payer, chunk, subtreeProof)],
+                    [payerKeypair]
+                );
+                assert(false, 'Posting leaves with an invalid proof should fail');
+            } catch {}
+        });
+        it('Cannot post the leaves of another precomputed tree', async () => {
+            const otherLeaves = [...Array(NUM_LEAVES)].map(() => crypto.randomBytes(32));
+            const otherTree = MerkleTree.sparseMerkleTreeFromLeaves(otherLeaves, DEPTH);
+            const chunk = otherLeaves.slice(0, CHUNK_SIZE);
+            const subtreeProof = otherTree.getProof(0).proof.slice(3);
+            try {
+                await execute(
+                    provider,
+                    [createAppendStagedLeavesIx(cmt, payer, chunk, subtreeProof)],
+                    [payerKeypair]
+                );
+                assert(false, 'Posting leaves of another tree should fail');
+            } catch {}
+        });
+    });
 });
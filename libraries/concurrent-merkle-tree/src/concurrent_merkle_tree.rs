@@ -26,6 +26,79 @@ fn check_leaf_index(leaf_index: u32, max_depth: usize) -> Result<(), ConcurrentM
     Ok(())
 }
 
+/// Returns the `(start index, height)` of the subtrees that `append_batch`
+/// splits `len` leaves appended at `start_index` into, in order.
+///
+/// Each subtree is the largest one that starts at its start index and holds
+/// no more than the remaining leaves, and is recorded with one changelog.
+pub fn batch_subtrees(mut start_index: u32, mut len: usize) -> Vec<(u32, usize)> {
+    let mut subtrees = vec![];
+    while len > 0 {
+        let mut height = len.ilog2() as usize;
+        if start_index != 0 {
+            height = height.min(start_index.trailing_zeros() as usize);
+        }
+        subtrees.push((start_index, height));
+        start_index += 1 << height;
+        len -= 1 << height;
+    }
+    subtrees
+}
+
+/// Returns the hashes of empty nodes by level
+fn empty_node_cache<const MAX_DEPTH: usize>() -> [Node; MAX_DEPTH] {
+    let mut empty_node_cache = [EMPTY; MAX_DEPTH];
+    for i in 1..MAX_DEPTH {
+        empty_node_cache[i] = empty_node_cached::<MAX_DEPTH>(i as u32, &empty_node_cache);
+    }
+    empty_node_cache
+}
+
+/// Hashes `leaves`, padded with `EMPTY` to a whole subtree of the given
+/// `height`, computing each node of the subtree once.
+///
+/// Fills the nodes of the path and of the proof of the last leaf of `leaves`
+/// below `height`, and returns the root of the subtree.
+fn hash_subtree<const MAX_DEPTH: usize>(
+    leaves: &[Node],
+    height: usize,
+    path: &mut [Node; MAX_DEPTH],
+    proof: &mut [Node; MAX_DEPTH],
+) -> Node {
+    let position = leaves.len() - 1;
+    let mut nodes = leaves.to_vec();
+    nodes.resize(1 << height, EMPTY);
+    for level in 0..height {
+        log_compute!();
+        path[level] = nodes[position >> level];
+        proof[level] = nodes[(position >> level) ^ 1];
+        for i in 0..nodes.len() / 2 {
+            let mut parent = nodes[2 * i];
+            hash_to_parent(&mut parent, &nodes[2 * i + 1], true);
+            nodes[i] = parent;
+        }
+        nodes.truncate(nodes.len() / 2);
+    }
+    nodes[0]
+}
+
+/// Hashes the root of a subtree of the given `height` up to the root of the
+/// tree with `proof`, filling the nodes of the path of the leaf at `index`
+/// from `height`, and returns the root of the tree.
+fn hash_to_root<const MAX_DEPTH: usize>(
+    mut node: Node,
+    proof: &[Node; MAX_DEPTH],
+    path: &mut [Node; MAX_DEPTH],
+    index: u32,
+    height: usize,
+) -> Node {
+    for i in height..MAX_DEPTH {
+        path[i] = node;
+        hash_to_parent(&mut node, &proof[i], (index >> i) & 1 == 0);
+    }
+    node
+}
+
 /// Conurrent Merkle Tree is a Merkle Tree that allows
 /// multiple tree operations targeted for the same tree root to succeed.
 ///
@@ -123,6 +196,9 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
     /// At the time of this crate's publishing, there is no supported way to efficiently verify
     /// a pre-initialized root on-chain. Using this method before having a method for on-chain verification
     /// will prevent other applications from indexing the leaf data stored in this tree.
+    ///
+    /// To verifiably initialize a tree with precomputed leaves, `initialize` the tree
+    /// and post its leaves with `append_subtree` instead.
     pub fn initialize_with_root(
         &mut self,
         root: Node,
@@ -168,6 +244,17 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
         Ok(())
     }
 
+    /// Errors if the current root of the merkle tree is not `root`
+    pub fn prove_root(&self, root: Node) -> Result<(), ConcurrentMerkleTreeError> {
+        if !self.is_initialized() {
+            return Err(ConcurrentMerkleTreeError::TreeNotInitialized);
+        }
+        if self.get_root() != root {
+            return Err(ConcurrentMerkleTreeError::RootMismatch);
+        }
+        Ok(())
+    }
+
     /// Returns the current root of the merkle tree
     pub fn get_root(&self) -> [u8; 32] {
         self.get_change_log().root
@@ -182,6 +269,19 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
         Box::new(self.change_logs[self.active_index as usize])
     }
 
+    /// Returns the changelog recorded `offset` operations before the most
+    /// recent one, so `get_past_change_log(0)` is the same as `get_change_log`.
+    ///
+    /// Returns `None` if that changelog is no longer stored in the buffer.
+    pub fn get_past_change_log(&self, offset: u64) -> Option<Box<ChangeLog<MAX_DEPTH>>> {
+        if !self.is_initialized() || offset >= self.buffer_size {
+            return None;
+        }
+        let mask = MAX_BUFFER_SIZE as u64 - 1;
+        let index = self.active_index.wrapping_sub(offset) & mask;
+        Some(Box::new(self.change_logs[index as usize]))
+    }
+
     /// This method will fail if the leaf cannot be proven
     /// to exist in the current tree root.
     ///
@@ -242,7 +342,7 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
     }
 
     /// Appending a non-empty Node will always succeed .
-    pub fn append(&mut self, mut node: Node) -> Result<Node, ConcurrentMerkleTreeError> {
+    pub fn append(&mut self, node: Node) -> Result<Node, ConcurrentMerkleTreeError> {
         check_bounds(MAX_DEPTH, MAX_BUFFER_SIZE);
         if !self.is_initialized() {
             return Err(ConcurrentMerkleTreeError::TreeNotInitialized);
@@ -253,6 +353,298 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
         if self.rightmost_proof.index >= 1 << MAX_DEPTH {
             return Err(ConcurrentMerkleTreeError::TreeFull);
         }
+        let empty_node_cache = [Node::default(); MAX_DEPTH];
+        self.append_unchecked(node, &empty_node_cache)
+    }
+
+    /// Appends multiple non-empty Nodes, in order, with the same resulting tree
+    /// as calling `append` on each of them.
+    ///
+    /// The leaves are split into the largest subtrees that are aligned in the
+    /// tree (see [batch_subtrees]), and each subtree is appended at once: its
+    /// nodes are hashed a single time, the path above it is hashed once, and a
+    /// single changelog is recorded for it. That changelog holds the path of the
+    /// last leaf of the subtree, so it fast-forwards the proofs of every leaf
+    /// outside of the subtree, but operations whose proofs target a leaf inside
+    /// of it fail instead of being fast-forwarded.
+    ///
+    /// The whole batch is validated before the tree is modified, so either all
+    /// of the leaves are appended or none of them are. A batch cannot be split
+    /// into more subtrees than the changelog buffer can hold, so that every
+    /// changelog of the batch can be read back with `get_past_change_log`.
+    pub fn append_batch(&mut self, leaves: &[Node]) -> Result<Node, ConcurrentMerkleTreeError> {
+        check_bounds(MAX_DEPTH, MAX_BUFFER_SIZE);
+        if !self.is_initialized() {
+            return Err(ConcurrentMerkleTreeError::TreeNotInitialized);
+        }
+        if leaves.contains(&EMPTY) {
+            return Err(ConcurrentMerkleTreeError::CannotAppendEmptyNode);
+        }
+        let remaining_capacity = (1u64 << MAX_DEPTH) - self.rightmost_proof.index as u64;
+        if leaves.len() as u64 > remaining_capacity {
+            return Err(ConcurrentMerkleTreeError::TreeFull);
+        }
+        let subtrees = batch_subtrees(self.rightmost_proof.index, leaves.len());
+        if subtrees.len() > MAX_BUFFER_SIZE {
+            return Err(ConcurrentMerkleTreeError::BatchTooLarge);
+        }
+
+        let empty_node_cache = empty_node_cache::<MAX_DEPTH>();
+        let mut root = self.get_root();
+        let mut leaves = leaves;
+        for (_, height) in subtrees {
+            let (subtree_leaves, rest) = leaves.split_at(1 << height);
+            // Only the first subtree can fail, when the tree is not empty
+            // although no leaf was appended, so the batch stays atomic
+            let (_, change_log, rightmost_proof) =
+                self.compute_subtree_append(subtree_leaves, height, &empty_node_cache)?;
+            root = self.apply_subtree_append(change_log, rightmost_proof);
+            leaves = rest;
+        }
+        Ok(root)
+    }
+
+    /// Appends a chunk of leaves of a tree that was computed off-chain, after
+    /// proving that the chunk belongs to the tree with the given `root`.
+    ///
+    /// The chunk must start at the current rightmost index and cover a whole
+    /// subtree: its length rounded up to the next power of two must divide the
+    /// index of its first leaf. The missing leaves of a shorter chunk are
+    /// treated as `EMPTY`, so only the last chunk of a tree can be shorter.
+    /// `proof_vec` is the proof of the root of the chunk's subtree, and must
+    /// contain exactly one node per level above the subtree.
+    ///
+    /// The subtree is hashed once, and is both checked against `root` and
+    /// appended with a single changelog, as with `append_batch`.
+    pub fn append_subtree(
+        &mut self,
+        root: Node,
+        leaves: &[Node],
+        proof_vec: &[Node],
+    ) -> Result<Node, ConcurrentMerkleTreeError> {
+        check_bounds(MAX_DEPTH, MAX_BUFFER_SIZE);
+        if !self.is_initialized() {
+            return Err(ConcurrentMerkleTreeError::TreeNotInitialized);
+        }
+        if leaves.is_empty() {
+            return Err(ConcurrentMerkleTreeError::SubtreeNotAligned);
+        }
+        if leaves.contains(&EMPTY) {
+            return Err(ConcurrentMerkleTreeError::CannotAppendEmptyNode);
+        }
+        let subtree_size = leaves.len().next_power_of_two();
+        let subtree_height = subtree_size.trailing_zeros() as usize;
+        let start_index = self.rightmost_proof.index;
+        if subtree_height > MAX_DEPTH || start_index as usize & (subtree_size - 1) != 0 {
+            return Err(ConcurrentMerkleTreeError::SubtreeNotAligned);
+        }
+        if start_index as u64 + leaves.len() as u64 > 1 << MAX_DEPTH {
+            return Err(ConcurrentMerkleTreeError::TreeFull);
+        }
+        if proof_vec.len() != MAX_DEPTH - subtree_height {
+            solana_logging!(
+                "Expected a proof of {} nodes, received {}",
+                MAX_DEPTH - subtree_height,
+                proof_vec.len()
+            );
+            return Err(ConcurrentMerkleTreeError::InvalidProof);
+        }
+
+        let empty_node_cache = empty_node_cache::<MAX_DEPTH>();
+        let (subtree_root, change_log, rightmost_proof) =
+            self.compute_subtree_append(leaves, subtree_height, &empty_node_cache)?;
+        if recompute(subtree_root, proof_vec, start_index >> subtree_height) != root {
+            solana_logging!("Proof failed to verify");
+            return Err(ConcurrentMerkleTreeError::InvalidProof);
+        }
+        Ok(self.apply_subtree_append(change_log, rightmost_proof))
+    }
+
+    /// Replaces a chunk of consecutive leaves at once, after proving that the
+    /// chunk currently holds `previous_leaves`.
+    ///
+    /// The chunk must cover a whole subtree, like in `append_subtree`, and
+    /// `proof_vec` is the proof of the root of the chunk's subtree for
+    /// `current_root`, with exactly one node per level above the subtree. A
+    /// chunk whose length is not a power of two must end at the rightmost index,
+    /// since the rest of its subtree is then known to be `EMPTY`.
+    ///
+    /// The proof is fast-forwarded through the changelog buffer like the one of
+    /// `set_leaf`, and the replacement fails with `LeafContentsModified` if any
+    /// leaf of the chunk was modified since `current_root`. The new subtree is
+    /// hashed once and recorded with a single changelog, as with `append_batch`.
+    pub fn replace_subtree(
+        &mut self,
+        current_root: Node,
+        previous_leaves: &[Node],
+        new_leaves: &[Node],
+        proof_vec: &[Node],
+        start_index: u32,
+    ) -> Result<Node, ConcurrentMerkleTreeError> {
+        check_bounds(MAX_DEPTH, MAX_BUFFER_SIZE);
+        check_leaf_index(start_index, MAX_DEPTH)?;
+        if !self.is_initialized() {
+            return Err(ConcurrentMerkleTreeError::TreeNotInitialized);
+        }
+        if previous_leaves.len() != new_leaves.len() {
+            return Err(ConcurrentMerkleTreeError::BatchLengthMismatch);
+        }
+        if new_leaves.is_empty() {
+            return Err(ConcurrentMerkleTreeError::SubtreeNotAligned);
+        }
+        let subtree_size = new_leaves.len().next_power_of_two();
+        let subtree_height = subtree_size.trailing_zeros() as usize;
+        if subtree_height > MAX_DEPTH || start_index as usize & (subtree_size - 1) != 0 {
+            return Err(ConcurrentMerkleTreeError::SubtreeNotAligned);
+        }
+        let end_index = start_index as u64 + new_leaves.len() as u64;
+        if end_index > self.rightmost_proof.index as u64 {
+            return Err(ConcurrentMerkleTreeError::LeafIndexOutOfBounds);
+        }
+        if new_leaves.len() != subtree_size && end_index != self.rightmost_proof.index as u64 {
+            return Err(ConcurrentMerkleTreeError::SubtreeNotAligned);
+        }
+        if proof_vec.len() != MAX_DEPTH - subtree_height {
+            solana_logging!(
+                "Expected a proof of {} nodes, received {}",
+                MAX_DEPTH - subtree_height,
+                proof_vec.len()
+            );
+            return Err(ConcurrentMerkleTreeError::InvalidProof);
+        }
+
+        // Prove the previous subtree with the proof of its last leaf
+        let last_index = (end_index - 1) as u32;
+        let mut path = [EMPTY; MAX_DEPTH];
+        let mut proof = [EMPTY; MAX_DEPTH];
+        hash_subtree(previous_leaves, subtree_height, &mut path, &mut proof);
+        proof[subtree_height..].copy_from_slice(proof_vec);
+        let subtree_proof = proof;
+        log_compute!();
+        let valid_root = self.check_valid_leaf(
+            current_root,
+            previous_leaves[previous_leaves.len() - 1],
+            &mut proof,
+            last_index,
+            true,
+        )?;
+        // The changelog of a modified leaf of the subtree replaces a node of
+        // the proof below the subtree's root
+        if proof[..subtree_height] != subtree_proof[..subtree_height] {
+            return Err(ConcurrentMerkleTreeError::LeafContentsModified);
+        }
+        if !valid_root {
+            return Err(ConcurrentMerkleTreeError::InvalidProof);
+        }
+
+        let subtree_root = hash_subtree(new_leaves, subtree_height, &mut path, &mut proof);
+        let root = hash_to_root(subtree_root, &proof, &mut path, last_index, subtree_height);
+        let change_log = ChangeLog::<MAX_DEPTH>::new(root, path, last_index);
+        self.update_internal_counters();
+        self.change_logs[self.active_index as usize] = change_log;
+        // Update rightmost path if possible
+        if self.rightmost_proof.index < (1 << MAX_DEPTH) {
+            if last_index + 1 == self.rightmost_proof.index {
+                self.rightmost_proof.proof = proof;
+                self.rightmost_proof.leaf = change_log.get_leaf();
+            } else {
+                change_log.update_proof_or_leaf(
+                    self.rightmost_proof.index - 1,
+                    &mut self.rightmost_proof.proof,
+                    &mut self.rightmost_proof.leaf,
+                );
+            }
+        }
+        Ok(root)
+    }
+
+    /// Computes the changelog and the rightmost proof that result from
+    /// appending `leaves`, padded with `EMPTY` to a whole subtree of the given
+    /// `height`, at the rightmost index, which must be aligned to that height.
+    ///
+    /// Returns the root of the subtree along with them, without modifying the
+    /// tree, so that the subtree can be checked before it is applied.
+    fn compute_subtree_append(
+        &self,
+        leaves: &[Node],
+        height: usize,
+        empty_node_cache: &[Node; MAX_DEPTH],
+    ) -> Result<(Node, ChangeLog<MAX_DEPTH>, Path<MAX_DEPTH>), ConcurrentMerkleTreeError> {
+        let start_index = self.rightmost_proof.index;
+        let last_index = start_index + leaves.len() as u32 - 1;
+        let mut path = [EMPTY; MAX_DEPTH];
+        let mut proof = [EMPTY; MAX_DEPTH];
+        if start_index == 0 {
+            if self.get_root() != empty_node_cached::<MAX_DEPTH>(MAX_DEPTH as u32, empty_node_cache)
+            {
+                return Err(ConcurrentMerkleTreeError::TreeAlreadyInitialized);
+            }
+            for (i, node) in proof.iter_mut().enumerate().skip(height) {
+                *node = empty_node_cached::<MAX_DEPTH>(i as u32, empty_node_cache);
+            }
+        } else {
+            // Above the subtree, the proof is made of empty nodes up to where
+            // it intersects the path of the current rightmost leaf, and of the
+            // proof of that leaf from there
+            let intersection = start_index.trailing_zeros() as usize;
+            let mut intersection_node = self.rightmost_proof.leaf;
+            for (i, sibling) in self.rightmost_proof.proof[..intersection]
+                .iter()
+                .enumerate()
+            {
+                hash_to_parent(
+                    &mut intersection_node,
+                    sibling,
+                    ((start_index - 1) >> i) & 1 == 0,
+                );
+                if i >= height {
+                    proof[i] = empty_node_cached::<MAX_DEPTH>(i as u32, empty_node_cache);
+                }
+            }
+            proof[intersection] = intersection_node;
+            proof[intersection + 1..]
+                .copy_from_slice(&self.rightmost_proof.proof[intersection + 1..]);
+        }
+
+        let subtree_root = hash_subtree(leaves, height, &mut path, &mut proof);
+        let root = hash_to_root(subtree_root, &proof, &mut path, last_index, height);
+        let rightmost_proof = Path {
+            proof,
+            leaf: leaves[leaves.len() - 1],
+            index: last_index + 1,
+            _padding: 0,
+        };
+        Ok((
+            subtree_root,
+            ChangeLog::<MAX_DEPTH>::new(root, path, last_index),
+            rightmost_proof,
+        ))
+    }
+
+    /// Records the changelog and the rightmost proof computed by
+    /// `compute_subtree_append`, and returns the new root
+    fn apply_subtree_append(
+        &mut self,
+        change_log: ChangeLog<MAX_DEPTH>,
+        rightmost_proof: Path<MAX_DEPTH>,
+    ) -> Node {
+        self.update_internal_counters();
+        self.change_logs[self.active_index as usize] = change_log;
+        self.rightmost_proof = rightmost_proof;
+        change_log.root
+    }
+
+    /// Appends a leaf, assuming that the tree is initialized, not full, and that
+    /// the leaf is not empty.
+    ///
+    /// `empty_node_cache` may contain the hashes of empty nodes by level, or
+    /// `EMPTY`s if they should be computed on the fly.
+    #[inline(always)]
+    fn append_unchecked(
+        &mut self,
+        mut node: Node,
+        empty_node_cache: &[Node; MAX_DEPTH],
+    ) -> Result<Node, ConcurrentMerkleTreeError> {
         if self.rightmost_proof.index == 0 {
             return self.initialize_tree_from_append(node, self.rightmost_proof.proof);
         }
@@ -260,14 +652,13 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>
         let intersection = self.rightmost_proof.index.trailing_zeros() as usize;
         let mut change_list = [EMPTY; MAX_DEPTH];
         let mut intersection_node = self.rightmost_proof.leaf;
-        let empty_node_cache = [Node::default(); MAX_DEPTH];
 
         for (i, cl_item) in change_list.iter_mut().enumerate().take(MAX_DEPTH) {
             *cl_item = node;
             match i {
                 i if i < intersection => {
                     // Compute proof to the appended node from empty nodes
-                    let sibling = empty_node_cached::<MAX_DEPTH>(i as u32, &empty_node_cache);
+                    let sibling = empty_node_cached::<MAX_DEPTH>(i as u32, empty_node_cache);
                     hash_to_parent(
                         &mut intersection_node,
                         &self.rightmost_proof.proof[i],
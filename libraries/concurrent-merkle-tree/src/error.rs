@@ -38,4 +38,20 @@ pub enum ConcurrentMerkleTreeError {
     /// Tree has at least 1 non-EMTPY leaf
     #[error("Tree is not empty")]
     TreeNonEmpty,
+
+    /// A batch of leaves cannot need more changelogs than the changelog buffer holds
+    #[error("Batch of leaves needs more changelogs than the changelog buffer holds")]
+    BatchTooLarge,
+
+    /// The previous and new leaves of a batch differ in number
+    #[error("Batch has a different number of previous and new leaves")]
+    BatchLengthMismatch,
+
+    /// A chunk of leaves does not start at the beginning of a subtree of its size
+    #[error("Chunk of leaves is not aligned to a subtree of the tree")]
+    SubtreeNotAligned,
+
+    /// The tree's current root does not match the expected root
+    #[error("Root does not match the tree's current root")]
+    RootMismatch,
 }
@@ -1,7 +1,7 @@
 #![allow(clippy::arithmetic_side_effects)]
 use rand::thread_rng;
 use rand::{self, Rng};
-use spl_concurrent_merkle_tree::concurrent_merkle_tree::ConcurrentMerkleTree;
+use spl_concurrent_merkle_tree::concurrent_merkle_tree::{batch_subtrees, ConcurrentMerkleTree};
 use spl_concurrent_merkle_tree::error::ConcurrentMerkleTreeError;
 use spl_concurrent_merkle_tree::node::{Node, EMPTY};
 use spl_merkle_tree_reference::MerkleTree;
@@ -548,3 +548,288 @@ async fn test_prove_tree_empty_batched() {
     // Check that the last leaf was successfully removed
     cmt.prove_tree_is_empty().unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_append_batch() {
+    let (mut cmt, mut tree) = setup();
+    let (mut sequential_cmt, _) = setup();
+    let mut rng = thread_rng();
+    cmt.initialize().unwrap();
+    sequential_cmt.initialize().unwrap();
+
+    let mut tree_size = 0;
+    while tree_size < (1 << DEPTH) {
+        let batch_size = rng.gen_range(1..=BUFFER_SIZE).min((1 << DEPTH) - tree_size);
+        let leaves: Vec<Node> = (0..batch_size).map(|_| rng.gen::<Node>()).collect();
+        let subtrees = batch_subtrees(tree_size as u32, batch_size);
+        let seq_before = cmt.get_seq();
+
+        let root = cmt.append_batch(&leaves).unwrap();
+        let mut sequential_change_logs = vec![];
+        for (i, leaf) in leaves.iter().enumerate() {
+            tree.add_leaf(*leaf, tree_size + i);
+            sequential_cmt.append(*leaf).unwrap();
+            sequential_change_logs.push(sequential_cmt.get_change_log());
+        }
+
+        assert_eq!(root, tree.get_root());
+        assert_eq!(cmt.get_change_log().root, tree.get_root());
+        assert_eq!(cmt.rightmost_proof, sequential_cmt.rightmost_proof);
+
+        // One changelog is recorded per aligned subtree, and it is the same as
+        // the changelog of appending the last leaf of the subtree on its own
+        assert_eq!(cmt.get_seq(), seq_before + subtrees.len() as u64);
+        for (offset, (start, height)) in subtrees.iter().rev().enumerate() {
+            assert_eq!(*start as usize % (1 << height), 0);
+            let last = *start as usize + (1 << height) - 1 - tree_size;
+            assert_eq!(
+                cmt.get_past_change_log(offset as u64).unwrap(),
+                sequential_change_logs[last],
+            );
+        }
+        tree_size += batch_size;
+    }
+
+    assert_eq!(
+        ConcurrentMerkleTreeError::TreeFull,
+        cmt.append_batch(&[rng.gen::<Node>()]).unwrap_err(),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_append_batch_is_atomic() {
+    let (mut cmt, mut tree) = setup();
+    let mut rng = thread_rng();
+
+    assert_eq!(
+        ConcurrentMerkleTreeError::TreeNotInitialized,
+        cmt.append_batch(&[rng.gen::<Node>()]).unwrap_err(),
+    );
+    cmt.initialize().unwrap();
+
+    let leaf = rng.gen::<Node>();
+    cmt.append(leaf).unwrap();
+    tree.add_leaf(leaf, 0);
+    let seq = cmt.get_seq();
+
+    assert_eq!(
+        ConcurrentMerkleTreeError::CannotAppendEmptyNode,
+        cmt.append_batch(&[rng.gen::<Node>(), EMPTY]).unwrap_err(),
+    );
+    assert_eq!(
+        ConcurrentMerkleTreeError::TreeFull,
+        cmt.append_batch(&vec![rng.gen::<Node>(); 1 << DEPTH])
+            .unwrap_err(),
+    );
+    assert_eq!(cmt.get_seq(), seq);
+    assert_eq!(cmt.get_root(), tree.get_root());
+
+    // Proofs from before the batch can still be fast-forwarded
+    let root = tree.get_root();
+    let proof = tree.get_proof_of_leaf(0);
+    let leaves: Vec<Node> = (0..BUFFER_SIZE - 1).map(|_| rng.gen::<Node>()).collect();
+    cmt.append_batch(&leaves).unwrap();
+    cmt.prove_leaf(root, leaf, &proof, 0).unwrap();
+
+    let new_leaf = rng.gen::<Node>();
+    cmt.set_leaf(root, leaf, new_leaf, &proof, 0).unwrap();
+    for (i, leaf) in leaves.iter().enumerate() {
+        tree.add_leaf(*leaf, i + 1);
+    }
+    tree.add_leaf(new_leaf, 0);
+    assert_eq!(cmt.get_root(), tree.get_root());
+
+    // A batch cannot need more changelogs than the buffer holds
+    let mut small_cmt = ConcurrentMerkleTree::<DEPTH, 8>::new();
+    small_cmt.initialize().unwrap();
+    small_cmt.append(leaf).unwrap();
+    let seq = small_cmt.get_seq();
+    assert_eq!(batch_subtrees(1, 511).len(), 9);
+    assert_eq!(
+        ConcurrentMerkleTreeError::BatchTooLarge,
+        small_cmt
+            .append_batch(&vec![rng.gen::<Node>(); 511])
+            .unwrap_err(),
+    );
+    assert_eq!(small_cmt.get_seq(), seq);
+    small_cmt
+        .append_batch(&vec![rng.gen::<Node>(); 255])
+        .unwrap();
+    assert_eq!(small_cmt.get_seq(), seq + 8);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_append_subtree() {
+    let (mut cmt, mut tree) = setup();
+    let mut rng = thread_rng();
+    cmt.initialize().unwrap();
+
+    // Precompute a tree that is not full off-chain
+    let num_leaves = (1 << DEPTH) - 24;
+    for i in 0..num_leaves {
+        tree.add_leaf(rng.gen::<Node>(), i);
+    }
+    let root = tree.get_root();
+    let leaves: Vec<Node> = (0..num_leaves).map(|i| tree.get_leaf(i)).collect();
+
+    // Chunks must cover a whole subtree
+    let chunk_proof =
+        |start: usize, height: usize| tree.get_proof_of_leaf(start)[height..].to_vec();
+    assert_eq!(
+        ConcurrentMerkleTreeError::InvalidProof,
+        cmt.append_subtree(root, &leaves[..32], &chunk_proof(0, 4))
+            .unwrap_err(),
+    );
+    cmt.append_subtree(root, &leaves[..16], &chunk_proof(0, 4))
+        .unwrap();
+    assert_eq!(
+        ConcurrentMerkleTreeError::SubtreeNotAligned,
+        cmt.append_subtree(root, &leaves[16..48], &chunk_proof(16, 5))
+            .unwrap_err(),
+    );
+    cmt.append_subtree(root, &leaves[16..32], &chunk_proof(16, 4))
+        .unwrap();
+
+    // Leaves that do not belong to the tree are rejected
+    let mut wrong_leaves = leaves[32..64].to_vec();
+    wrong_leaves[7] = rng.gen::<Node>();
+    assert_eq!(
+        ConcurrentMerkleTreeError::InvalidProof,
+        cmt.append_subtree(root, &wrong_leaves, &chunk_proof(32, 5))
+            .unwrap_err(),
+    );
+    assert_eq!(
+        ConcurrentMerkleTreeError::RootMismatch,
+        cmt.prove_root(root).unwrap_err(),
+    );
+
+    let mut start = 32;
+    while start < num_leaves {
+        let end = (start + 32).min(num_leaves);
+        let height = (end - start).next_power_of_two().trailing_zeros() as usize;
+        let seq = cmt.get_seq();
+        cmt.append_subtree(root, &leaves[start..end], &chunk_proof(start, height))
+            .unwrap();
+        // One changelog is recorded per chunk
+        assert_eq!(cmt.get_seq(), seq + 1);
+        assert_eq!(cmt.get_change_log().index as usize, end - 1);
+        start = end;
+    }
+    assert_eq!(cmt.get_root(), root);
+    cmt.prove_root(root).unwrap();
+
+    // The rest of the tree is empty, so nothing else can be appended
+    assert_eq!(
+        ConcurrentMerkleTreeError::InvalidProof,
+        cmt.append_subtree(root, &[rng.gen::<Node>()], &chunk_proof(num_leaves, 0))
+            .unwrap_err(),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replace_subtree() {
+    let (mut cmt, mut tree) = setup();
+    let mut rng = thread_rng();
+    cmt.initialize().unwrap();
+
+    let num_leaves = 99;
+    let leaves: Vec<Node> = (0..num_leaves).map(|_| rng.gen::<Node>()).collect();
+    cmt.append_batch(&leaves).unwrap();
+    for (i, leaf) in leaves.iter().enumerate() {
+        tree.add_leaf(*leaf, i);
+    }
+
+    // A proof of the subtree is fast-forwarded past changes to other leaves
+    let root = tree.get_root();
+    let subtree_proof = tree.get_proof_of_leaf(32)[4..].to_vec();
+    let new_leaf = rng.gen::<Node>();
+    cmt.set_leaf(root, leaves[5], new_leaf, &tree.get_proof_of_leaf(5), 5)
+        .unwrap();
+    tree.add_leaf(new_leaf, 5);
+
+    let new_leaves: Vec<Node> = (0..16).map(|_| rng.gen::<Node>()).collect();
+    let seq = cmt.get_seq();
+    let new_root = cmt
+        .replace_subtree(root, &leaves[32..48], &new_leaves, &subtree_proof, 32)
+        .unwrap();
+    for (i, leaf) in new_leaves.iter().enumerate() {
+        tree.add_leaf(*leaf, 32 + i);
+    }
+    assert_eq!(new_root, tree.get_root());
+    assert_eq!(cmt.get_seq(), seq + 1);
+    assert_eq!(cmt.get_change_log().index, 47);
+
+    // The replacement fails if any leaf of the subtree was modified since
+    let root = tree.get_root();
+    let subtree_proof = tree.get_proof_of_leaf(64)[4..].to_vec();
+    let new_leaves: Vec<Node> = (0..16).map(|_| rng.gen::<Node>()).collect();
+    for index in [70, 79] {
+        let mut modified_cmt = cmt;
+        modified_cmt
+            .set_leaf(
+                root,
+                leaves[index],
+                rng.gen::<Node>(),
+                &tree.get_proof_of_leaf(index),
+                index as u32,
+            )
+            .unwrap();
+        assert_eq!(
+            ConcurrentMerkleTreeError::LeafContentsModified,
+            modified_cmt
+                .replace_subtree(root, &leaves[64..80], &new_leaves, &subtree_proof, 64)
+                .unwrap_err(),
+        );
+    }
+
+    // Leaves that do not belong to the tree are rejected
+    let mut wrong_leaves = leaves[64..80].to_vec();
+    wrong_leaves[3] = rng.gen::<Node>();
+    assert_eq!(
+        ConcurrentMerkleTreeError::InvalidProof,
+        cmt.replace_subtree(root, &wrong_leaves, &new_leaves, &subtree_proof, 64)
+            .unwrap_err(),
+    );
+    assert_eq!(
+        ConcurrentMerkleTreeError::BatchLengthMismatch,
+        cmt.replace_subtree(root, &leaves[64..80], &new_leaves[1..], &subtree_proof, 64)
+            .unwrap_err(),
+    );
+    assert_eq!(
+        ConcurrentMerkleTreeError::SubtreeNotAligned,
+        cmt.replace_subtree(root, &leaves[60..76], &new_leaves, &subtree_proof, 60)
+            .unwrap_err(),
+    );
+    assert_eq!(
+        ConcurrentMerkleTreeError::LeafIndexOutOfBounds,
+        cmt.replace_subtree(root, &[EMPTY; 8], &[EMPTY; 8], &subtree_proof[1..], 96)
+            .unwrap_err(),
+    );
+
+    // Only a chunk that ends at the rightmost index can be shorter than its
+    // subtree
+    let subtree_proof = tree.get_proof_of_leaf(96)[2..].to_vec();
+    let new_leaves: Vec<Node> = (0..3).map(|_| rng.gen::<Node>()).collect();
+    assert_eq!(
+        ConcurrentMerkleTreeError::SubtreeNotAligned,
+        cmt.replace_subtree(root, &leaves[64..67], &new_leaves, &subtree_proof, 64)
+            .unwrap_err(),
+    );
+    cmt.replace_subtree(root, &leaves[96..99], &new_leaves, &subtree_proof, 96)
+        .unwrap();
+    for (i, leaf) in new_leaves.iter().enumerate() {
+        tree.add_leaf(*leaf, 96 + i);
+    }
+    assert_eq!(cmt.get_root(), tree.get_root());
+    assert_eq!(cmt.rightmost_proof.leaf, new_leaves[2]);
+    assert_eq!(
+        cmt.rightmost_proof.proof.to_vec(),
+        tree.get_proof_of_leaf(98)
+    );
+
+    // Appending after replacements extends the replaced tree
+    let leaf = rng.gen::<Node>();
+    cmt.append(leaf).unwrap();
+    tree.add_leaf(leaf, num_leaves);
+    assert_eq!(cmt.get_root(), tree.get_root());
+}
@@ -0,0 +1,650 @@
+#![allow(clippy::arithmetic_side_effects)]
+//! Defines Decimal, a signed fixed-point number with transcendental functions
+//!
+//! All operations are implemented with integer arithmetic only, so that they
+//! give the same results on every machine, and every operation that cannot be
+//! computed exactly takes a `Rounding` to choose the direction of the error.
+
+use {
+    crate::uint::U256,
+    borsh::{BorshDeserialize, BorshSerialize},
+    std::fmt,
+};
+
+/// Number of decimal digits after the decimal point
+pub const DECIMALS: u32 = 18;
+
+/// The representation of the number one as a Decimal, 10^18
+const SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Scale used for intermediate results of `ln` and `exp`, 10^36, which gives
+/// 18 extra digits of precision before rounding to the final result
+fn hp_scale() -> U256 {
+    U256::from(SCALE as u128 * SCALE as u128)
+}
+
+/// ln(2) at the intermediate scale of 10^36
+const LN_2_HP: i128 = 693_147_180_559_945_309_417_232_121_458_176_568;
+
+/// Any exponent above this would overflow the result of `exp`, 47 at the
+/// intermediate scale
+const MAX_EXP_HP: i128 = 47 * SCALE * SCALE;
+
+/// The result of `exp` is smaller than the smallest positive Decimal for any
+/// exponent below this, -42 at the intermediate scale
+const MIN_EXP_HP: i128 = -42 * SCALE * SCALE;
+
+/// Direction in which to round a result that cannot be represented exactly
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards positive infinity
+    Ceiling,
+    /// Round towards zero, discarding the extra digits
+    TowardZero,
+    /// Round to the nearest value, and away from zero when exactly halfway
+    Nearest,
+}
+
+/// Signed fixed-point number with 18 decimals, stored as an `i128`
+///
+/// Values range roughly from -1.7 * 10^20 to 1.7 * 10^20.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct Decimal(i128);
+
+/// Divides `numerator` by `denominator`, and applies the sign given by
+/// `negative` to the result, rounding in the given direction
+fn div_rounded(
+    numerator: U256,
+    denominator: U256,
+    negative: bool,
+    rounding: Rounding,
+) -> Option<i128> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let (quotient, remainder) = numerator.div_mod(denominator);
+    let round_away_from_zero = !remainder.is_zero()
+        && match rounding {
+            Rounding::Floor => negative,
+            Rounding::Ceiling => !negative,
+            Rounding::TowardZero => false,
+            Rounding::Nearest => remainder >= denominator - remainder,
+        };
+    let magnitude = if round_away_from_zero {
+        quotient.checked_add(U256::one())?
+    } else {
+        quotient
+    };
+    if magnitude > U256::from(i128::MAX as u128) {
+        return None;
+    }
+    let magnitude = magnitude.as_u128() as i128;
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Natural logarithm of `raw` / 10^18 at the intermediate scale of 10^36
+///
+/// The argument is first written as m * 2^k, with 1 <= m < 2, so that
+/// ln(x) = k * ln(2) + ln(m), and ln(m) is computed with the series
+/// ln(m) = 2 * (s + s^3 / 3 + s^5 / 5 + ...), where s = (m - 1) / (m + 1).
+fn ln_hp(raw: u128) -> i128 {
+    let one = hp_scale();
+    let two = one << 1;
+    let value = U256::from(raw) * U256::from(SCALE as u128);
+
+    let mut k = value.bits() as i32 - one.bits() as i32;
+    let normalize = |k: i32| {
+        if k >= 0 {
+            value >> k as usize
+        } else {
+            value << (-k) as usize
+        }
+    };
+    let mut m = normalize(k);
+    if m < one {
+        k -= 1;
+        m = normalize(k);
+    } else if m >= two {
+        k += 1;
+        m = normalize(k);
+    }
+
+    let s = (m - one) * one / (m + one);
+    let s_squared = s * s / one;
+    let mut power = s;
+    let mut sum = s;
+    let mut n = 3u64;
+    loop {
+        power = power * s_squared / one;
+        let term = power / U256::from(n);
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+        n += 2;
+    }
+    k as i128 * LN_2_HP + (sum << 1).as_u128() as i128
+}
+
+/// Exponential of `exponent` / 10^36, with the sign given by `negative`
+/// applied to the result, rounded to a Decimal in the given direction
+///
+/// The exponent is first written as k * ln(2) + r, with 0 <= r < ln(2), so
+/// that e^x = 2^k * e^r, and e^r is computed with its Taylor series, whose
+/// terms are all positive.
+fn exp_hp(exponent: i128, negative: bool, rounding: Rounding) -> Option<Decimal> {
+    if exponent > MAX_EXP_HP {
+        return None;
+    }
+    if exponent < MIN_EXP_HP {
+        // The result is positive, but smaller than the smallest Decimal
+        let round_up = match rounding {
+            Rounding::Floor => negative,
+            Rounding::Ceiling => !negative,
+            Rounding::TowardZero | Rounding::Nearest => false,
+        };
+        let raw = match (round_up, negative) {
+            (false, _) => 0,
+            (true, false) => 1,
+            (true, true) => -1,
+        };
+        return Some(Decimal(raw));
+    }
+    let one = hp_scale();
+    let k = exponent.div_euclid(LN_2_HP);
+    let r = U256::from(exponent.rem_euclid(LN_2_HP) as u128);
+
+    let mut term = one;
+    let mut sum = one;
+    let mut n = 1u64;
+    loop {
+        term = term * r / one / U256::from(n);
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+        n += 1;
+    }
+
+    let scale = U256::from(SCALE as u128);
+    let (numerator, denominator) = if k >= 0 {
+        (sum << k as usize, scale)
+    } else {
+        (sum, scale << (-k) as usize)
+    };
+    div_rounded(numerator, denominator, negative, rounding).map(Decimal)
+}
+
+impl Decimal {
+    /// The number zero
+    pub const ZERO: Self = Self(0);
+
+    /// The number one
+    pub const ONE: Self = Self(SCALE);
+
+    /// Smallest representable value
+    pub const MIN: Self = Self(i128::MIN);
+
+    /// Largest representable value
+    pub const MAX: Self = Self(i128::MAX);
+
+    /// Create a Decimal from its raw representation, the value multiplied by
+    /// 10^18
+    pub const fn from_raw(raw: i128) -> Self {
+        Self(raw)
+    }
+
+    /// Get the raw representation of the Decimal, the value multiplied by
+    /// 10^18
+    pub const fn to_raw(self) -> i128 {
+        self.0
+    }
+
+    /// Create a Decimal from an integer, failing if it is out of range
+    pub fn from_i128(value: i128) -> Option<Self> {
+        value.checked_mul(SCALE).map(Self)
+    }
+
+    /// Create a Decimal from an unsigned integer, such as a token amount
+    pub fn from_u64(value: u64) -> Self {
+        Self(value as i128 * SCALE)
+    }
+
+    /// Create a Decimal from the fraction `numerator / denominator`, rounding
+    /// in the given direction
+    pub fn from_ratio(numerator: i128, denominator: i128, rounding: Rounding) -> Option<Self> {
+        div_rounded(
+            U256::from(numerator.unsigned_abs()) * U256::from(SCALE as u128),
+            U256::from(denominator.unsigned_abs()),
+            (numerator < 0) != (denominator < 0),
+            rounding,
+        )
+        .map(Self)
+    }
+
+    /// Convert the Decimal to an integer, rounding in the given direction
+    pub fn to_i128(self, rounding: Rounding) -> Option<i128> {
+        div_rounded(
+            U256::from(self.0.unsigned_abs()),
+            U256::from(SCALE as u128),
+            self.is_negative(),
+            rounding,
+        )
+    }
+
+    /// Convert the Decimal to an unsigned integer, such as a token amount,
+    /// rounding in the given direction, and failing if it is negative or too
+    /// large
+    pub fn to_u64(self, rounding: Rounding) -> Option<u64> {
+        self.to_i128(rounding)
+            .and_then(|value| u64::try_from(value).ok())
+    }
+
+    /// Round the Decimal to an integer value in the given direction
+    pub fn round(self, rounding: Rounding) -> Option<Self> {
+        self.to_i128(rounding).and_then(Self::from_i128)
+    }
+
+    /// Indicates whether the number is strictly negative
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// Indicates whether the number is zero
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Indicates whether the number is an integer
+    pub fn is_integer(self) -> bool {
+        self.0 % SCALE == 0
+    }
+
+    /// Absolute value, failing only for `Decimal::MIN`
+    pub fn checked_abs(self) -> Option<Self> {
+        self.0.checked_abs().map(Self)
+    }
+
+    /// Negation, failing only for `Decimal::MIN`
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+
+    /// Performs addition of two Decimals
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Subtracts the argument from self
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Performs a multiplication of two Decimals, rounding in the given
+    /// direction
+    pub fn checked_mul(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        div_rounded(
+            U256::from(self.0.unsigned_abs()) * U256::from(rhs.0.unsigned_abs()),
+            U256::from(SCALE as u128),
+            self.is_negative() != rhs.is_negative(),
+            rounding,
+        )
+        .map(Self)
+    }
+
+    /// Performs a division of two Decimals, rounding in the given direction
+    pub fn checked_div(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        div_rounded(
+            U256::from(self.0.unsigned_abs()) * U256::from(SCALE as u128),
+            U256::from(rhs.0.unsigned_abs()),
+            self.is_negative() != rhs.is_negative(),
+            rounding,
+        )
+        .map(Self)
+    }
+
+    /// Natural logarithm, failing if the number is not strictly positive.
+    ///
+    /// The result is computed with 36 decimals before being rounded in the
+    /// given direction.
+    pub fn ln(self, rounding: Rounding) -> Option<Self> {
+        if self.0 <= 0 {
+            return None;
+        }
+        let ln = ln_hp(self.0 as u128);
+        div_rounded(
+            U256::from(ln.unsigned_abs()),
+            U256::from(SCALE as u128),
+            ln < 0,
+            rounding,
+        )
+        .map(Self)
+    }
+
+    /// Exponential function, failing if the result is out of range.
+    ///
+    /// The result is computed with 36 decimals before being rounded in the
+    /// given direction.
+    pub fn exp(self, rounding: Rounding) -> Option<Self> {
+        // saturated values are far outside of the supported range either way
+        exp_hp(self.0.saturating_mul(SCALE), false, rounding)
+    }
+
+    /// Raise the number to the power of `exponent`, which can be fractional,
+    /// computed as `exp(exponent * ln(self))`.
+    ///
+    /// Negative numbers can only be raised to integer powers, and zero can only
+    /// be raised to non-negative powers. The result is computed with 36
+    /// decimals before being rounded in the given direction, so it may differ
+    /// from the exact result by one unit in the last place even when the exact
+    /// result is representable, e.g. `2^3` with `Rounding::Floor` could give
+    /// `7.999999999999999999`.
+    pub fn checked_pow(self, exponent: Self, rounding: Rounding) -> Option<Self> {
+        if exponent.is_zero() {
+            return Some(Self::ONE);
+        }
+        if self.is_zero() {
+            return if exponent.is_negative() {
+                None
+            } else {
+                Some(Self::ZERO)
+            };
+        }
+        let negative = if self.is_negative() {
+            if !exponent.is_integer() {
+                return None;
+            }
+            (exponent.0 / SCALE) % 2 != 0
+        } else {
+            false
+        };
+
+        // exponent * ln(|self|), at the intermediate scale
+        let ln = ln_hp(self.0.unsigned_abs());
+        let product = U256::from(ln.unsigned_abs()) * U256::from(exponent.0.unsigned_abs())
+            / U256::from(SCALE as u128);
+        let product_negative = (ln < 0) != exponent.is_negative();
+        // anything beyond the bounds of `exp` saturates the same way
+        let product = product.min(U256::from(MAX_EXP_HP as u128 + 1)).as_u128() as i128;
+        let product = if product_negative { -product } else { product };
+        exp_hp(product, negative, rounding)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let magnitude = self.0.unsigned_abs();
+        let scale = SCALE as u128;
+        write!(f, "{}{}.{:018}", sign, magnitude / scale, magnitude % scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, proptest::prelude::*};
+
+    fn decimal(value: f64) -> Decimal {
+        Decimal((value * SCALE as f64).round() as i128)
+    }
+
+    fn assert_close(actual: Decimal, expected: f64, relative_tolerance: f64) {
+        let actual = actual.0 as f64 / SCALE as f64;
+        let tolerance = (expected.abs() * relative_tolerance).max(1e-18);
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{} is not within {} of {}",
+            actual,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        let two_thirds = Decimal::from_ratio(2, 3, Rounding::Floor).unwrap();
+        assert_eq!(two_thirds.to_raw(), 666_666_666_666_666_666);
+        let two_thirds = Decimal::from_ratio(2, 3, Rounding::Ceiling).unwrap();
+        assert_eq!(two_thirds.to_raw(), 666_666_666_666_666_667);
+        let two_thirds = Decimal::from_ratio(2, 3, Rounding::Nearest).unwrap();
+        assert_eq!(two_thirds.to_raw(), 666_666_666_666_666_667);
+        let two_thirds = Decimal::from_ratio(2, 3, Rounding::TowardZero).unwrap();
+        assert_eq!(two_thirds.to_raw(), 666_666_666_666_666_666);
+
+        let minus_two_thirds = Decimal::from_ratio(-2, 3, Rounding::Floor).unwrap();
+        assert_eq!(minus_two_thirds.to_raw(), -666_666_666_666_666_667);
+        let minus_two_thirds = Decimal::from_ratio(2, -3, Rounding::Ceiling).unwrap();
+        assert_eq!(minus_two_thirds.to_raw(), -666_666_666_666_666_666);
+        let minus_two_thirds = Decimal::from_ratio(-2, 3, Rounding::TowardZero).unwrap();
+        assert_eq!(minus_two_thirds.to_raw(), -666_666_666_666_666_666);
+
+        let minus_half = decimal(-2.5);
+        assert_eq!(minus_half.to_i128(Rounding::Floor), Some(-3));
+        assert_eq!(minus_half.to_i128(Rounding::Ceiling), Some(-2));
+        assert_eq!(minus_half.to_i128(Rounding::TowardZero), Some(-2));
+        assert_eq!(minus_half.to_i128(Rounding::Nearest), Some(-3));
+        assert_eq!(decimal(2.4).to_i128(Rounding::Nearest), Some(2));
+        assert_eq!(
+            decimal(2.4).round(Rounding::Ceiling),
+            Some(Decimal::from_u64(3))
+        );
+
+        assert_eq!(decimal(-1.0).to_u64(Rounding::Floor), None);
+        assert_eq!(decimal(-0.5).to_u64(Rounding::Ceiling), Some(0));
+        assert_eq!(Decimal::from_ratio(1, 0, Rounding::Floor), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = decimal(1.5);
+        let b = decimal(-0.25);
+        assert_eq!(a.checked_add(b), Some(decimal(1.25)));
+        assert_eq!(a.checked_sub(b), Some(decimal(1.75)));
+        assert_eq!(a.checked_mul(b, Rounding::Floor), Some(decimal(-0.375)));
+        assert_eq!(a.checked_div(b, Rounding::Floor), Some(decimal(-6.0)));
+        assert_eq!(a.checked_div(Decimal::ZERO, Rounding::Floor), None);
+
+        let third = Decimal::ONE
+            .checked_div(decimal(3.0), Rounding::Floor)
+            .unwrap();
+        let minus_third = Decimal::ONE
+            .checked_div(decimal(-3.0), Rounding::Floor)
+            .unwrap();
+        assert_eq!(third.to_raw(), 333_333_333_333_333_333);
+        assert_eq!(minus_third.to_raw(), -333_333_333_333_333_334);
+
+        let tiny = Decimal::from_raw(1);
+        assert_eq!(tiny.checked_mul(tiny, Rounding::Floor), Some(Decimal::ZERO));
+        assert_eq!(tiny.checked_mul(tiny, Rounding::Ceiling), Some(tiny));
+
+        assert_eq!(Decimal::MAX.checked_add(tiny), None);
+        assert_eq!(
+            Decimal::MAX.checked_mul(decimal(2.0), Rounding::Floor),
+            None
+        );
+        assert_eq!(Decimal::MIN.checked_neg(), None);
+        assert_eq!(Decimal::from_i128(i128::MAX), None);
+        assert_eq!(decimal(-2.5).to_string(), "-2.500000000000000000");
+    }
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(Decimal::ONE.ln(Rounding::Floor), Some(Decimal::ZERO));
+        assert_eq!(Decimal::ZERO.ln(Rounding::Floor), None);
+        assert_eq!(decimal(-1.0).ln(Rounding::Floor), None);
+
+        // ln(2) = 0.693147180559945309417...
+        let ln_2 = decimal(2.0);
+        assert_eq!(
+            ln_2.ln(Rounding::Floor).unwrap().to_raw(),
+            693_147_180_559_945_309
+        );
+        assert_eq!(
+            ln_2.ln(Rounding::Ceiling).unwrap().to_raw(),
+            693_147_180_559_945_310
+        );
+        // ln(10^-18) = -41.446531673892822312...
+        assert_eq!(
+            Decimal::from_raw(1).ln(Rounding::Floor).unwrap().to_raw(),
+            -41_446_531_673_892_822_313
+        );
+        // ln(0.5) = -0.693147180559945309417...
+        assert_eq!(
+            decimal(0.5).ln(Rounding::TowardZero).unwrap().to_raw(),
+            -693_147_180_559_945_309
+        );
+
+        // the inputs go through an f64, so results close to 0 lose some digits
+        for value in [1e-12, 0.1, 0.9999, 1.0001, 2.5, 1e6, 1e19] {
+            assert_close(
+                decimal(value).ln(Rounding::Nearest).unwrap(),
+                value.ln(),
+                1e-12,
+            );
+        }
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(Decimal::ZERO.exp(Rounding::Floor), Some(Decimal::ONE));
+        // e = 2.718281828459045235360...
+        assert_eq!(
+            Decimal::ONE.exp(Rounding::Floor).unwrap().to_raw(),
+            2_718_281_828_459_045_235
+        );
+        assert_eq!(
+            Decimal::ONE.exp(Rounding::Ceiling).unwrap().to_raw(),
+            2_718_281_828_459_045_236
+        );
+        // e^-1 = 0.367879441171442321595...
+        assert_eq!(
+            decimal(-1.0).exp(Rounding::Nearest).unwrap().to_raw(),
+            367_879_441_171_442_322
+        );
+
+        for value in [-30.0, -0.5, 0.001, 3.3, 20.0, 46.0] {
+            assert_close(
+                decimal(value).exp(Rounding::Nearest).unwrap(),
+                value.exp(),
+                1e-14,
+            );
+        }
+
+        // out of range
+        assert_eq!(decimal(47.0).exp(Rounding::Floor), None);
+        assert_eq!(Decimal::MAX.exp(Rounding::Floor), None);
+        assert_eq!(decimal(-50.0).exp(Rounding::Floor), Some(Decimal::ZERO));
+        assert_eq!(
+            Decimal::MIN.exp(Rounding::Ceiling),
+            Some(Decimal::from_raw(1))
+        );
+    }
+
+    #[test]
+    fn test_pow() {
+        let two = decimal(2.0);
+        assert_eq!(
+            two.checked_pow(Decimal::ZERO, Rounding::Floor),
+            Some(Decimal::ONE)
+        );
+        assert_eq!(
+            two.checked_pow(decimal(3.0), Rounding::Nearest),
+            Some(decimal(8.0))
+        );
+        assert_eq!(
+            two.checked_pow(decimal(-2.0), Rounding::Nearest),
+            Some(decimal(0.25))
+        );
+        assert_eq!(
+            decimal(-2.0).checked_pow(decimal(3.0), Rounding::Nearest),
+            Some(decimal(-8.0))
+        );
+        assert_eq!(
+            decimal(-2.0).checked_pow(decimal(2.0), Rounding::Nearest),
+            Some(decimal(4.0))
+        );
+        assert_eq!(
+            decimal(-2.0).checked_pow(decimal(0.5), Rounding::Floor),
+            None
+        );
+        assert_eq!(
+            Decimal::ZERO.checked_pow(decimal(0.5), Rounding::Floor),
+            Some(Decimal::ZERO)
+        );
+        assert_eq!(
+            Decimal::ZERO.checked_pow(decimal(-1.0), Rounding::Floor),
+            None
+        );
+        assert_eq!(two.checked_pow(decimal(100.0), Rounding::Floor), None);
+
+        // sqrt(2) = 1.414213562373095048801...
+        assert_eq!(
+            two.checked_pow(decimal(0.5), Rounding::Floor)
+                .unwrap()
+                .to_raw(),
+            1_414_213_562_373_095_048
+        );
+        assert_eq!(
+            two.checked_pow(decimal(0.5), Rounding::Ceiling)
+                .unwrap()
+                .to_raw(),
+            1_414_213_562_373_095_049
+        );
+
+        // Rounding direction is kept for negative results
+        let minus_cube = decimal(-1.1)
+            .checked_pow(decimal(3.0), Rounding::Floor)
+            .unwrap();
+        assert!(minus_cube <= decimal(-1.331));
+
+        // compounding interest: 1.05^10.5
+        assert_close(
+            decimal(1.05)
+                .checked_pow(decimal(10.5), Rounding::Nearest)
+                .unwrap(),
+            1.05f64.powf(10.5),
+            1e-14,
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_exp_ln_round_trip(raw in 1_000_000i128..100_000_000_000_000_000_000i128) {
+            let x = Decimal::from_raw(raw);
+            let floor = x.ln(Rounding::Floor).unwrap();
+            let ceiling = x.ln(Rounding::Ceiling).unwrap();
+            prop_assert!(floor <= ceiling);
+            prop_assert!(ceiling.to_raw() - floor.to_raw() <= 1);
+            let round_trip = floor.exp(Rounding::Nearest).unwrap();
+            // the error of ln is magnified by x when applying exp
+            let tolerance = raw / 100_000_000_000_000_000 + 2;
+            prop_assert!((round_trip.to_raw() - raw).abs() <= tolerance);
+        }
+
+        #[test]
+        fn test_rounding_bounds(a in any::<i64>(), b in any::<i64>()) {
+            let a = Decimal::from_raw(a as i128 * 1_000);
+            let b = Decimal::from_raw(b as i128);
+            if let Some(floor) = a.checked_div(b, Rounding::Floor) {
+                let ceiling = a.checked_div(b, Rounding::Ceiling).unwrap();
+                let nearest = a.checked_div(b, Rounding::Nearest).unwrap();
+                prop_assert!(floor <= nearest && nearest <= ceiling);
+                prop_assert!(ceiling.to_raw() - floor.to_raw() <= 1);
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Program instructions, used for end-to-end testing and instruction counts
 
 use {
-    crate::id,
+    crate::{decimal::Decimal, id},
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::instruction::Instruction,
 };
@@ -107,6 +107,52 @@ pub enum MathInstruction {
         exponent: f64,
     },
 
+    /// Multiply two Decimal values
+    ///
+    /// No accounts required for this instruction
+    DecimalMultiply {
+        /// The multiplicand
+        multiplicand: Decimal,
+        /// The multipier
+        multiplier: Decimal,
+    },
+
+    /// Divide two Decimal values
+    ///
+    /// No accounts required for this instruction
+    DecimalDivide {
+        /// The dividend
+        dividend: Decimal,
+        /// The divisor
+        divisor: Decimal,
+    },
+
+    /// Natural Log of a Decimal
+    ///
+    /// No accounts required for this instruction
+    DecimalNaturalLog {
+        /// The argument
+        argument: Decimal,
+    },
+
+    /// Exponential of a Decimal
+    ///
+    /// No accounts required for this instruction
+    DecimalExponential {
+        /// The exponent
+        exponent: Decimal,
+    },
+
+    /// Pow two Decimal values
+    ///
+    /// No accounts required for this instruction
+    DecimalPow {
+        /// The base
+        base: Decimal,
+        /// The exponent
+        exponent: Decimal,
+    },
+
     /// Don't do anything for comparison
     ///
     /// No accounts required for this instruction
@@ -240,6 +286,64 @@ pub fn f64_pow(base: f64, exponent: f64) -> Instruction {
     }
 }
 
+/// Create Decimal Multiplication instruction
+pub fn decimal_multiply(multiplicand: Decimal, multiplier: Decimal) -> Instruction {
+    Instruction {
+        program_id: id(),
+        accounts: vec![],
+        data: MathInstruction::DecimalMultiply {
+            multiplicand,
+            multiplier,
+        }
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Create Decimal Division instruction
+pub fn decimal_divide(dividend: Decimal, divisor: Decimal) -> Instruction {
+    Instruction {
+        program_id: id(),
+        accounts: vec![],
+        data: MathInstruction::DecimalDivide { dividend, divisor }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Create Decimal Natural Log instruction
+pub fn decimal_natural_log(argument: Decimal) -> Instruction {
+    Instruction {
+        program_id: id(),
+        accounts: vec![],
+        data: MathInstruction::DecimalNaturalLog { argument }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Create Decimal Exponential instruction
+pub fn decimal_exponential(exponent: Decimal) -> Instruction {
+    Instruction {
+        program_id: id(),
+        accounts: vec![],
+        data: MathInstruction::DecimalExponential { exponent }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Create Decimal Pow instruction
+pub fn decimal_pow(base: Decimal, exponent: Decimal) -> Instruction {
+    Instruction {
+        program_id: id(),
+        accounts: vec![],
+        data: MathInstruction::DecimalPow { base, exponent }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 /// Create Noop instruction
 pub fn noop() -> Instruction {
     Instruction {
@@ -400,6 +504,22 @@ mod tests {
         assert_eq!(instruction.program_id, crate::id())
     }
 
+    #[test]
+    fn test_decimal_pow() {
+        let instruction = decimal_pow(Decimal::MAX, Decimal::MAX);
+        assert_eq!(0, instruction.accounts.len());
+        assert_eq!(
+            instruction.data,
+            MathInstruction::DecimalPow {
+                base: Decimal::MAX,
+                exponent: Decimal::MAX
+            }
+            .try_to_vec()
+            .unwrap()
+        );
+        assert_eq!(instruction.program_id, crate::id())
+    }
+
     #[test]
     fn test_noop() {
         let instruction = noop();
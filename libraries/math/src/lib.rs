@@ -5,6 +5,7 @@
 
 pub mod approximations;
 pub mod checked_ceil_div;
+pub mod decimal;
 mod entrypoint;
 pub mod error;
 pub mod instruction;
@@ -4,6 +4,7 @@
 use {
     crate::{
         approximations::{f32_normal_cdf, sqrt},
+        decimal::Rounding,
         instruction::MathInstruction,
         precise_number::PreciseNumber,
     },
@@ -157,6 +158,51 @@ pub fn process_instruction(
             msg!("{}", result as u64);
             Ok(())
         }
+        MathInstruction::DecimalMultiply {
+            multiplicand,
+            multiplier,
+        } => {
+            msg!("Calculating Decimal Multiply");
+            sol_log_compute_units();
+            let result = multiplicand
+                .checked_mul(multiplier, Rounding::Nearest)
+                .unwrap();
+            sol_log_compute_units();
+            msg!("{}", result);
+            Ok(())
+        }
+        MathInstruction::DecimalDivide { dividend, divisor } => {
+            msg!("Calculating Decimal Divide");
+            sol_log_compute_units();
+            let result = dividend.checked_div(divisor, Rounding::Nearest).unwrap();
+            sol_log_compute_units();
+            msg!("{}", result);
+            Ok(())
+        }
+        MathInstruction::DecimalNaturalLog { argument } => {
+            msg!("Calculating Decimal Natural Log");
+            sol_log_compute_units();
+            let result = argument.ln(Rounding::Nearest).unwrap();
+            sol_log_compute_units();
+            msg!("{}", result);
+            Ok(())
+        }
+        MathInstruction::DecimalExponential { exponent } => {
+            msg!("Calculating Decimal Exponential");
+            sol_log_compute_units();
+            let result = exponent.exp(Rounding::Nearest).unwrap();
+            sol_log_compute_units();
+            msg!("{}", result);
+            Ok(())
+        }
+        MathInstruction::DecimalPow { base, exponent } => {
+            msg!("Calculating Decimal Pow");
+            sol_log_compute_units();
+            let result = base.checked_pow(exponent, Rounding::Nearest).unwrap();
+            sol_log_compute_units();
+            msg!("{}", result);
+            Ok(())
+        }
         MathInstruction::Noop => {
             msg!("Do nothing");
             msg!("{}", 0_u64);
@@ -168,7 +214,7 @@ pub fn process_instruction(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::instruction::MathInstruction;
+    use crate::{decimal::Decimal, instruction::MathInstruction};
     use borsh::BorshSerialize;
 
     #[test]
@@ -245,6 +291,24 @@ mod tests {
             MathInstruction::F32NaturalLog {
                 argument: std::f32::consts::E,
             },
+            MathInstruction::DecimalMultiply {
+                multiplicand: Decimal::from_u64(3),
+                multiplier: Decimal::from_u64(4),
+            },
+            MathInstruction::DecimalDivide {
+                dividend: Decimal::from_u64(2),
+                divisor: Decimal::from_u64(3),
+            },
+            MathInstruction::DecimalNaturalLog {
+                argument: Decimal::from_u64(10),
+            },
+            MathInstruction::DecimalExponential {
+                exponent: Decimal::ONE,
+            },
+            MathInstruction::DecimalPow {
+                base: Decimal::from_u64(2),
+                exponent: Decimal::from_ratio(1, 2, Rounding::Nearest).unwrap(),
+            },
             MathInstruction::Noop,
         ] {
             let input = math_instruction.try_to_vec().unwrap();
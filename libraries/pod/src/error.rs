@@ -13,4 +13,7 @@ pub enum PodSliceError {
     /// Provided byte buffer too large for expected type
     #[error("Provided byte buffer too large for expected type")]
     BufferTooLarge,
+    /// Index out of bounds for the slice
+    #[error("Index out of bounds for the slice")]
+    IndexOutOfBounds,
 }
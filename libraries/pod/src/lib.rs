@@ -2,9 +2,12 @@
 
 pub mod bytemuck;
 pub mod error;
+pub mod option;
 pub mod optional_keys;
 pub mod primitives;
 pub mod slice;
+pub mod string;
+pub mod vec;
 
 // Export current sdk types for downstream users building with a different sdk
 // version
@@ -106,6 +106,30 @@ impl_int_conversion!(PodU64, u64);
 pub struct PodI64([u8; 8]);
 impl_int_conversion!(PodI64, i64);
 
+/// `u128` type that can be used in Pods
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(from = "u128", into = "u128"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodU128([u8; 16]);
+impl_int_conversion!(PodU128, u128);
+
+/// `i128` type that can be used in Pods
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(from = "i128", into = "i128"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodI128([u8; 16]);
+impl_int_conversion!(PodI128, i128);
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::bytemuck::pod_from_bytes};
@@ -221,4 +245,63 @@ mod tests {
         let deserialized = serde_json::from_str::<PodI64>(&serialized).unwrap();
         assert_eq!(pod_i64, deserialized);
     }
+
+    #[test]
+    fn test_pod_u128() {
+        assert!(pod_from_bytes::<PodU128>(&[]).is_err());
+        assert!(pod_from_bytes::<PodU128>(&[0; 8]).is_err());
+        let mut bytes = [0; 16];
+        bytes[0] = 1;
+        assert_eq!(
+            1u128,
+            u128::from(*pod_from_bytes::<PodU128>(&bytes).unwrap())
+        );
+        assert_eq!(
+            u128::MAX,
+            u128::from(*pod_from_bytes::<PodU128>(&[255; 16]).unwrap())
+        );
+    }
+
+    #[cfg(feature = "serde-traits")]
+    #[test]
+    fn test_pod_u128_serde() {
+        let pod_u128: PodU128 = u128::MAX.into();
+
+        let serialized = serde_json::to_string(&pod_u128).unwrap();
+        assert_eq!(&serialized, "340282366920938463463374607431768211455");
+
+        let deserialized = serde_json::from_str::<PodU128>(&serialized).unwrap();
+        assert_eq!(pod_u128, deserialized);
+    }
+
+    #[test]
+    fn test_pod_i128() {
+        assert!(pod_from_bytes::<PodI128>(&[]).is_err());
+        assert_eq!(
+            -1i128,
+            i128::from(*pod_from_bytes::<PodI128>(&[255; 16]).unwrap())
+        );
+    }
+
+    #[cfg(feature = "serde-traits")]
+    #[test]
+    fn test_pod_i128_serde() {
+        let pod_i128: PodI128 = i128::MIN.into();
+
+        let serialized = serde_json::to_string(&pod_i128).unwrap();
+        assert_eq!(&serialized, "-170141183460469231731687303715884105728");
+
+        let deserialized = serde_json::from_str::<PodI128>(&serialized).unwrap();
+        assert_eq!(pod_i128, deserialized);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_pod_u128_borsh() {
+        let pod_u128: PodU128 = 42u128.into();
+        let serialized = borsh::to_vec(&pod_u128).unwrap();
+        assert_eq!(serialized, 42u128.to_le_bytes());
+        let deserialized = borsh::from_slice::<PodU128>(&serialized).unwrap();
+        assert_eq!(pod_u128, deserialized);
+    }
 }
@@ -10,6 +10,7 @@ use {
     },
     bytemuck::Pod,
     solana_program::program_error::ProgramError,
+    std::cmp::Ordering,
 };
 
 const LENGTH_SIZE: usize = std::mem::size_of::<PodU32>();
@@ -96,6 +97,33 @@ impl<'data, T: Pod> PodSliceMut<'data, T> {
         Self::unpack_internal(data, /* init */ true)
     }
 
+    /// Get the number of items in the slice
+    pub fn len(&self) -> usize {
+        u32::from(*self.length) as usize
+    }
+
+    /// Indicates whether the slice contains no items
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the maximum number of items that the buffer can hold
+    pub fn capacity(&self) -> usize {
+        self.max_length
+    }
+
+    /// Get the slice data
+    pub fn data(&self) -> &[T] {
+        let length = self.len();
+        &self.data[..length]
+    }
+
+    /// Get the mutable slice data
+    pub fn data_mut(&mut self) -> &mut [T] {
+        let length = self.len();
+        &mut self.data[..length]
+    }
+
     /// Add another item to the slice
     pub fn push(&mut self, t: T) -> Result<(), ProgramError> {
         let length = u32::from(*self.length);
@@ -107,6 +135,89 @@ impl<'data, T: Pod> PodSliceMut<'data, T> {
             Ok(())
         }
     }
+
+    /// Remove and return the last item of the slice, if any
+    pub fn pop(&mut self) -> Option<T> {
+        let length = self.len().checked_sub(1)?;
+        self.set_len(length);
+        Some(self.data[length])
+    }
+
+    /// Insert an item at position `index`, shifting all items after it to the
+    /// right
+    pub fn insert(&mut self, index: usize, t: T) -> Result<(), ProgramError> {
+        let length = self.len();
+        if index > length {
+            return Err(PodSliceError::IndexOutOfBounds.into());
+        }
+        if length == self.max_length {
+            return Err(PodSliceError::BufferTooSmall.into());
+        }
+        self.data
+            .copy_within(index..length, index.saturating_add(1));
+        self.data[index] = t;
+        self.set_len(length.saturating_add(1));
+        Ok(())
+    }
+
+    /// Remove and return the item at position `index`, shifting all items
+    /// after it to the left
+    pub fn remove(&mut self, index: usize) -> Result<T, ProgramError> {
+        let length = self.len();
+        if index >= length {
+            return Err(PodSliceError::IndexOutOfBounds.into());
+        }
+        let t = self.data[index];
+        self.data
+            .copy_within(index.saturating_add(1)..length, index);
+        self.set_len(length.saturating_sub(1));
+        Ok(t)
+    }
+
+    /// Remove and return the item at position `index`, replacing it with the
+    /// last item of the slice.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    pub fn swap_remove(&mut self, index: usize) -> Result<T, ProgramError> {
+        let length = self.len();
+        if index >= length {
+            return Err(PodSliceError::IndexOutOfBounds.into());
+        }
+        let last = length.saturating_sub(1);
+        let t = self.data[index];
+        self.data[index] = self.data[last];
+        self.set_len(last);
+        Ok(t)
+    }
+
+    /// Binary search the sorted slice with a comparator function, following
+    /// the semantics of `slice::binary_search_by`
+    pub fn binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        self.data().binary_search_by(f)
+    }
+
+    /// Insert an item into a slice sorted according to `compare`, keeping it
+    /// sorted. The item is placed after any equal items.
+    ///
+    /// Returns the position of the inserted item.
+    pub fn insert_sorted_by<F>(&mut self, t: T, mut compare: F) -> Result<usize, ProgramError>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let index = self
+            .data()
+            .partition_point(|probe| compare(probe, &t) != Ordering::Greater);
+        self.insert(index, t)?;
+        Ok(index)
+    }
+
+    fn set_len(&mut self, length: usize) {
+        // the length is always bounded by `max_length`, which came from a u32
+        *self.length = (length as u32).into();
+    }
 }
 
 fn max_len_for_type<T>(data_len: usize) -> Result<usize, ProgramError> {
@@ -213,4 +324,101 @@ mod tests {
             .expect_err("Expected an `PodSliceError::BufferTooSmall` error");
         assert_eq!(err, PodSliceError::BufferTooSmall.into());
     }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+    struct TestItem(u8);
+
+    fn item_slice<'a>(data: &'a mut [u8], items: &[u8]) -> PodSliceMut<'a, TestItem> {
+        let mut pod_slice = PodSliceMut::<TestItem>::init(data).unwrap();
+        for item in items {
+            pod_slice.push(TestItem(*item)).unwrap();
+        }
+        pod_slice
+    }
+
+    fn values(pod_slice: &PodSliceMut<'_, TestItem>) -> Vec<u8> {
+        pod_slice.data().iter().map(|item| item.0).collect()
+    }
+
+    #[test]
+    fn test_pod_slice_mut_remove() {
+        let mut data = [0; LENGTH_SIZE + 5];
+        let mut pod_slice = item_slice(&mut data, &[0, 1, 2, 3]);
+
+        assert_eq!(pod_slice.remove(1).unwrap(), TestItem(1));
+        assert_eq!(values(&pod_slice), vec![0, 2, 3]);
+        assert_eq!(pod_slice.remove(2).unwrap(), TestItem(3));
+        assert_eq!(values(&pod_slice), vec![0, 2]);
+        assert_eq!(
+            pod_slice.remove(2).unwrap_err(),
+            PodSliceError::IndexOutOfBounds.into()
+        );
+
+        assert_eq!(pod_slice.pop(), Some(TestItem(2)));
+        assert_eq!(pod_slice.pop(), Some(TestItem(0)));
+        assert_eq!(pod_slice.pop(), None);
+        assert!(pod_slice.is_empty());
+    }
+
+    #[test]
+    fn test_pod_slice_mut_swap_remove() {
+        let mut data = [0; LENGTH_SIZE + 5];
+        let mut pod_slice = item_slice(&mut data, &[0, 1, 2, 3]);
+
+        assert_eq!(pod_slice.swap_remove(0).unwrap(), TestItem(0));
+        assert_eq!(values(&pod_slice), vec![3, 1, 2]);
+        assert_eq!(pod_slice.swap_remove(2).unwrap(), TestItem(2));
+        assert_eq!(values(&pod_slice), vec![3, 1]);
+        assert_eq!(
+            pod_slice.swap_remove(2).unwrap_err(),
+            PodSliceError::IndexOutOfBounds.into()
+        );
+    }
+
+    #[test]
+    fn test_pod_slice_mut_insert() {
+        let mut data = [0; LENGTH_SIZE + 4];
+        let mut pod_slice = item_slice(&mut data, &[1, 3]);
+
+        pod_slice.insert(0, TestItem(0)).unwrap();
+        pod_slice.insert(2, TestItem(2)).unwrap();
+        assert_eq!(values(&pod_slice), vec![0, 1, 2, 3]);
+        assert_eq!(pod_slice.capacity(), 4);
+
+        assert_eq!(
+            pod_slice.insert(0, TestItem(4)).unwrap_err(),
+            PodSliceError::BufferTooSmall.into()
+        );
+        pod_slice.pop();
+        assert_eq!(
+            pod_slice.insert(4, TestItem(4)).unwrap_err(),
+            PodSliceError::IndexOutOfBounds.into()
+        );
+        pod_slice.insert(3, TestItem(4)).unwrap();
+        assert_eq!(values(&pod_slice), vec![0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_pod_slice_mut_sorted() {
+        let mut data = [0; LENGTH_SIZE + 6];
+        let mut pod_slice = item_slice(&mut data, &[]);
+
+        for item in [5, 1, 4, 1, 3] {
+            pod_slice
+                .insert_sorted_by(TestItem(item), |a, b| a.0.cmp(&b.0))
+                .unwrap();
+        }
+        assert_eq!(values(&pod_slice), vec![1, 1, 3, 4, 5]);
+        assert_eq!(
+            pod_slice
+                .insert_sorted_by(TestItem(2), |a, b| a.0.cmp(&b.0))
+                .unwrap(),
+            2
+        );
+
+        assert_eq!(pod_slice.binary_search_by(|probe| probe.0.cmp(&4)), Ok(4));
+        assert_eq!(pod_slice.binary_search_by(|probe| probe.0.cmp(&0)), Err(0));
+        assert_eq!(pod_slice.binary_search_by(|probe| probe.0.cmp(&9)), Err(6));
+    }
 }
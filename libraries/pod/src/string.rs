@@ -0,0 +1,215 @@
+//! Fixed-capacity UTF-8 string that can be used in `Pod`s
+use {
+    bytemuck::{Pod, Zeroable},
+    solana_program::program_error::ProgramError,
+    std::str,
+};
+
+#[cfg(feature = "serde-traits")]
+use serde::{
+    de::{Error, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+
+/// A UTF-8 string of at most `N` bytes, stored inline and padded with `0`s.
+///
+/// Since the padding byte is `0`, strings containing a nul character cannot
+/// be represented. Any byte buffer is a valid `PodStr`, so the UTF-8 encoding
+/// is checked whenever the string is read.
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct PodStr<const N: usize>([u8; N]);
+
+/// ## Safety
+///
+/// `PodStr` is a transparent wrapper around a byte array, so any bit pattern
+/// is valid and there is no padding.
+unsafe impl<const N: usize> Pod for PodStr<N> {}
+
+/// ## Safety
+///
+/// `PodStr` is a transparent wrapper around a byte array, so the all-zero
+/// value (the empty string) is valid.
+unsafe impl<const N: usize> Zeroable for PodStr<N> {}
+
+impl<const N: usize> Default for PodStr<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> PodStr<N> {
+    /// Maximum number of bytes that the string can hold
+    pub const CAPACITY: usize = N;
+
+    /// Length of the string in bytes, excluding the `0` padding
+    pub fn len(&self) -> usize {
+        self.0.iter().position(|b| *b == 0).unwrap_or(N)
+    }
+
+    /// Indicates whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the string bytes, without the `0` padding
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0[..self.len()]
+    }
+
+    /// Get the string, failing if the bytes are not valid UTF-8
+    pub fn as_str(&self) -> Result<&str, ProgramError> {
+        str::from_utf8(self.as_bytes()).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for PodStr<N> {
+    type Error = ProgramError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let bytes = s.as_bytes();
+        if bytes.len() > N || bytes.contains(&0) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut data = [0; N];
+        data[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self(data))
+    }
+}
+
+impl<const N: usize> TryFrom<String> for PodStr<N> {
+    type Error = ProgramError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_str())
+    }
+}
+
+impl<const N: usize> TryFrom<PodStr<N>> for String {
+    type Error = ProgramError;
+    fn try_from(s: PodStr<N>) -> Result<Self, Self::Error> {
+        s.as_str().map(ToString::to_string)
+    }
+}
+
+#[cfg(feature = "serde-traits")]
+impl<const N: usize> Serialize for PodStr<N> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let string = self
+            .as_str()
+            .map_err(|_| serde::ser::Error::custom("invalid UTF-8 in PodStr"))?;
+        s.serialize_str(string)
+    }
+}
+
+#[cfg(feature = "serde-traits")]
+/// Visitor for deserializing PodStr
+struct PodStrVisitor<const N: usize>;
+
+#[cfg(feature = "serde-traits")]
+impl<'de, const N: usize> Visitor<'de> for PodStrVisitor<N> {
+    type Value = PodStr<N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a string of at most {} bytes without nul characters",
+            N
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        PodStr::try_from(v).map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+#[cfg(feature = "serde-traits")]
+impl<'de, const N: usize> Deserialize<'de> for PodStr<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(PodStrVisitor::<N>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::bytemuck::pod_from_bytes};
+
+    #[test]
+    fn test_pod_str() {
+        let pod_str = PodStr::<8>::try_from("hello").unwrap();
+        assert_eq!(pod_str.len(), 5);
+        assert_eq!(pod_str.as_str().unwrap(), "hello");
+        assert_eq!(bytemuck::bytes_of(&pod_str), b"hello\0\0\0");
+
+        let full = PodStr::<5>::try_from("hello").unwrap();
+        assert_eq!(full.len(), 5);
+        assert_eq!(full.as_str().unwrap(), "hello");
+
+        assert!(PodStr::<8>::default().is_empty());
+        assert_eq!(PodStr::<8>::default().as_str().unwrap(), "");
+
+        assert_eq!(
+            PodStr::<4>::try_from("hello").unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            PodStr::<8>::try_from("he\0llo").unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_pod_str_from_bytes() {
+        assert!(pod_from_bytes::<PodStr<4>>(&[]).is_err());
+        assert!(pod_from_bytes::<PodStr<4>>(&[0; 5]).is_err());
+
+        let pod_str = pod_from_bytes::<PodStr<4>>(b"ab\0\0").unwrap();
+        assert_eq!(pod_str.as_str().unwrap(), "ab");
+
+        let invalid = pod_from_bytes::<PodStr<4>>(&[0xff, 0xfe, 0, 0]).unwrap();
+        assert_eq!(
+            invalid.as_str().unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[cfg(feature = "serde-traits")]
+    #[test]
+    fn test_pod_str_serde() {
+        let pod_str = PodStr::<16>::try_from("spl-pod").unwrap();
+
+        let serialized = serde_json::to_string(&pod_str).unwrap();
+        assert_eq!(&serialized, "\"spl-pod\"");
+
+        let deserialized = serde_json::from_str::<PodStr<16>>(&serialized).unwrap();
+        assert_eq!(pod_str, deserialized);
+
+        assert!(serde_json::from_str::<PodStr<4>>(&serialized).is_err());
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_pod_str_borsh() {
+        let pod_str = PodStr::<4>::try_from("ab").unwrap();
+        let serialized = borsh::to_vec(&pod_str).unwrap();
+        assert_eq!(serialized, b"ab\0\0");
+        assert_eq!(
+            borsh::from_slice::<PodStr<4>>(&serialized).unwrap(),
+            pod_str
+        );
+    }
+}
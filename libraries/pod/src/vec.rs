@@ -0,0 +1,333 @@
+//! Growable vector of `Pod`s, stored at the end of an account
+
+use {
+    crate::{
+        bytemuck::pod_from_bytes,
+        error::PodSliceError,
+        primitives::PodU32,
+        slice::{PodSlice, PodSliceMut},
+    },
+    bytemuck::Pod,
+    solana_program::{account_info::AccountInfo, program_error::ProgramError},
+    std::{cmp::Ordering, marker::PhantomData},
+};
+
+const LENGTH_SIZE: usize = std::mem::size_of::<PodU32>();
+
+/// Zero-copy, length-prefixed vector of `Pod`s, which takes up all of an
+/// account's data starting from `offset`.
+///
+/// Adding or removing items reallocates the account so that the vector always
+/// fills the data exactly. The caller is responsible for keeping the account
+/// rent-exempt after it grows, and for reclaiming the excess lamports after it
+/// shrinks.
+pub struct PodVec<'a, 'info, T: Pod> {
+    account_info: &'a AccountInfo<'info>,
+    offset: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, 'info, T: Pod> PodVec<'a, 'info, T> {
+    /// Load an existing vector starting at `offset` in the account data
+    pub fn unpack(
+        account_info: &'a AccountInfo<'info>,
+        offset: usize,
+    ) -> Result<Self, ProgramError> {
+        {
+            let data = account_info.try_borrow_data()?;
+            let length_end = offset
+                .checked_add(LENGTH_SIZE)
+                .ok_or(PodSliceError::CalculationFailure)?;
+            let length = data
+                .get(offset..length_end)
+                .ok_or(PodSliceError::BufferTooSmall)
+                .and_then(|bytes| {
+                    pod_from_bytes::<PodU32>(bytes).map_err(|_| PodSliceError::BufferTooSmall)
+                })?;
+            let expected_len = Self::account_len(offset, u32::from(*length) as usize)?;
+            if expected_len > data.len() {
+                return Err(PodSliceError::BufferTooSmall.into());
+            } else if expected_len < data.len() {
+                return Err(PodSliceError::BufferTooLarge.into());
+            }
+        }
+        Ok(Self {
+            account_info,
+            offset,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Create an empty vector starting at `offset`, reallocating the account
+    /// to fit an empty vector
+    pub fn init(account_info: &'a AccountInfo<'info>, offset: usize) -> Result<Self, ProgramError> {
+        account_info.realloc(Self::account_len(offset, 0)?, false)?;
+        {
+            let mut data = account_info.try_borrow_mut_data()?;
+            PodSliceMut::<T>::init(&mut data[offset..])?;
+        }
+        Ok(Self {
+            account_info,
+            offset,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Get the number of items in the vector
+    pub fn len(&self) -> Result<usize, ProgramError> {
+        let data = self.account_info.try_borrow_data()?;
+        let slice = PodSlice::<T>::unpack(self.vec_data(&data)?)?;
+        Ok(slice.data().len())
+    }
+
+    /// Indicates whether the vector contains no items
+    pub fn is_empty(&self) -> Result<bool, ProgramError> {
+        self.len().map(|length| length == 0)
+    }
+
+    /// Get a copy of the item at position `index`
+    pub fn get(&self, index: usize) -> Result<T, ProgramError> {
+        let data = self.account_info.try_borrow_data()?;
+        let slice = PodSlice::<T>::unpack(self.vec_data(&data)?)?;
+        slice
+            .data()
+            .get(index)
+            .copied()
+            .ok_or_else(|| PodSliceError::IndexOutOfBounds.into())
+    }
+
+    /// Overwrite the item at position `index`
+    pub fn set(&self, index: usize, t: T) -> Result<(), ProgramError> {
+        self.with_slice_mut(|slice| {
+            let item = slice
+                .data_mut()
+                .get_mut(index)
+                .ok_or(PodSliceError::IndexOutOfBounds)?;
+            *item = t;
+            Ok(())
+        })
+    }
+
+    /// Binary search the sorted vector with a comparator function, following
+    /// the semantics of `slice::binary_search_by`
+    pub fn binary_search_by<F>(&self, f: F) -> Result<Result<usize, usize>, ProgramError>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let data = self.account_info.try_borrow_data()?;
+        let slice = PodSlice::<T>::unpack(self.vec_data(&data)?)?;
+        Ok(slice.data().binary_search_by(f))
+    }
+
+    /// Add an item to the end of the vector, growing the account
+    pub fn push(&self, t: T) -> Result<(), ProgramError> {
+        self.grow()?;
+        self.with_slice_mut(|slice| slice.push(t))
+    }
+
+    /// Remove and return the last item of the vector, shrinking the account
+    pub fn pop(&self) -> Result<Option<T>, ProgramError> {
+        let t = self.with_slice_mut(|slice| Ok(slice.pop()))?;
+        if t.is_some() {
+            self.shrink()?;
+        }
+        Ok(t)
+    }
+
+    /// Insert an item at position `index`, growing the account
+    pub fn insert(&self, index: usize, t: T) -> Result<(), ProgramError> {
+        if index > self.len()? {
+            return Err(PodSliceError::IndexOutOfBounds.into());
+        }
+        self.grow()?;
+        self.with_slice_mut(|slice| slice.insert(index, t))
+    }
+
+    /// Insert an item into a vector sorted according to `compare`, keeping it
+    /// sorted and growing the account.
+    ///
+    /// Returns the position of the inserted item.
+    pub fn insert_sorted_by<F>(&self, t: T, compare: F) -> Result<usize, ProgramError>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.grow()?;
+        self.with_slice_mut(|slice| slice.insert_sorted_by(t, compare))
+    }
+
+    /// Remove and return the item at position `index`, shrinking the account
+    pub fn remove(&self, index: usize) -> Result<T, ProgramError> {
+        let t = self.with_slice_mut(|slice| slice.remove(index))?;
+        self.shrink()?;
+        Ok(t)
+    }
+
+    /// Remove and return the item at position `index`, replacing it with the
+    /// last item and shrinking the account
+    pub fn swap_remove(&self, index: usize) -> Result<T, ProgramError> {
+        let t = self.with_slice_mut(|slice| slice.swap_remove(index))?;
+        self.shrink()?;
+        Ok(t)
+    }
+
+    /// Get the account data length required for a vector of `num_items`
+    /// starting at `offset`
+    pub fn account_len(offset: usize, num_items: usize) -> Result<usize, ProgramError> {
+        PodSlice::<T>::size_of(num_items)?
+            .checked_add(offset)
+            .ok_or_else(|| PodSliceError::CalculationFailure.into())
+    }
+
+    fn vec_data<'b>(&self, data: &'b [u8]) -> Result<&'b [u8], ProgramError> {
+        data.get(self.offset..)
+            .ok_or_else(|| PodSliceError::BufferTooSmall.into())
+    }
+
+    fn with_slice_mut<F, R>(&self, f: F) -> Result<R, ProgramError>
+    where
+        F: FnOnce(&mut PodSliceMut<T>) -> Result<R, ProgramError>,
+    {
+        let mut data = self.account_info.try_borrow_mut_data()?;
+        let vec_data = data
+            .get_mut(self.offset..)
+            .ok_or(PodSliceError::BufferTooSmall)?;
+        let mut slice = PodSliceMut::<T>::unpack(vec_data)?;
+        f(&mut slice)
+    }
+
+    /// Make room for exactly one more item
+    fn grow(&self) -> Result<(), ProgramError> {
+        let length = self.len()?;
+        let new_account_len = Self::account_len(self.offset, length.saturating_add(1))?;
+        self.account_info.realloc(new_account_len, false)
+    }
+
+    /// Trim the account to exactly fit the current items
+    fn shrink(&self) -> Result<(), ProgramError> {
+        let length = self.len()?;
+        let new_account_len = Self::account_len(self.offset, length)?;
+        self.account_info.realloc(new_account_len, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        bytemuck::Zeroable,
+        solana_program::{entrypoint::MAX_PERMITTED_DATA_INCREASE, pubkey::Pubkey},
+    };
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+    struct TestItem([u8; 2]);
+
+    const HEADER: usize = 3;
+
+    /// Mimics the layout of an on-chain account, where the original data
+    /// length is stored right before the key and the current data length
+    /// right before the data, to allow reallocs as the runtime does it
+    #[repr(C)]
+    struct TestKey {
+        original_data_len: u32,
+        key: Pubkey,
+    }
+
+    struct TestAccount {
+        key: TestKey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+    impl TestAccount {
+        fn new(account_data: &[u8]) -> Self {
+            let mut data = vec![];
+            data.extend_from_slice(&(account_data.len() as u64).to_le_bytes());
+            data.extend_from_slice(account_data);
+            data.extend_from_slice(&[0; MAX_PERMITTED_DATA_INCREASE]);
+            Self {
+                key: TestKey {
+                    original_data_len: account_data.len() as u32,
+                    key: Pubkey::new_unique(),
+                },
+                owner: Pubkey::new_unique(),
+                lamports: 0,
+                data,
+            }
+        }
+
+        fn account_info(&mut self) -> AccountInfo<'_> {
+            let start = std::mem::size_of::<u64>();
+            let len = self.key.original_data_len as usize;
+            AccountInfo::new(
+                &self.key.key,
+                false,
+                true,
+                &mut self.lamports,
+                &mut self.data[start..start + len],
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn values(pod_vec: &PodVec<TestItem>) -> Vec<u8> {
+        (0..pod_vec.len().unwrap())
+            .map(|i| pod_vec.get(i).unwrap().0[0])
+            .collect()
+    }
+
+    #[test]
+    fn test_pod_vec() {
+        let mut account = TestAccount::new(&[7; HEADER]);
+        let account_info = account.account_info();
+
+        let pod_vec = PodVec::<TestItem>::init(&account_info, HEADER).unwrap();
+        assert!(pod_vec.is_empty().unwrap());
+        assert_eq!(account_info.data_len(), HEADER + 4);
+
+        for i in 0..5 {
+            pod_vec.push(TestItem([i, i])).unwrap();
+        }
+        assert_eq!(values(&pod_vec), vec![0, 1, 2, 3, 4]);
+        assert_eq!(account_info.data_len(), HEADER + 4 + 5 * 2);
+
+        assert_eq!(pod_vec.remove(1).unwrap(), TestItem([1, 1]));
+        assert_eq!(pod_vec.swap_remove(0).unwrap(), TestItem([0, 0]));
+        assert_eq!(values(&pod_vec), vec![4, 2, 3]);
+        assert_eq!(account_info.data_len(), HEADER + 4 + 3 * 2);
+
+        pod_vec.insert(3, TestItem([9, 9])).unwrap();
+        pod_vec.set(0, TestItem([1, 1])).unwrap();
+        assert_eq!(values(&pod_vec), vec![1, 2, 3, 9]);
+        assert_eq!(
+            pod_vec.insert(5, TestItem::default()).unwrap_err(),
+            PodSliceError::IndexOutOfBounds.into()
+        );
+
+        assert_eq!(
+            pod_vec
+                .insert_sorted_by(TestItem([5, 5]), |a, b| a.0.cmp(&b.0))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            pod_vec
+                .binary_search_by(|probe| probe.0.cmp(&[9, 9]))
+                .unwrap(),
+            Ok(4)
+        );
+
+        while pod_vec.pop().unwrap().is_some() {}
+        assert!(pod_vec.is_empty().unwrap());
+        assert_eq!(account_info.data_len(), HEADER + 4);
+        assert_eq!(&account_info.data.borrow()[..HEADER], &[7; HEADER]);
+
+        // reloading checks that the vector fills the rest of the account
+        let pod_vec = PodVec::<TestItem>::unpack(&account_info, HEADER).unwrap();
+        assert_eq!(pod_vec.len().unwrap(), 0);
+        account_info.realloc(HEADER + 4 + 3, false).unwrap();
+        assert!(PodVec::<TestItem>::unpack(&account_info, HEADER).is_err());
+    }
+}
@@ -70,10 +70,19 @@ fn main() {
             .join("associated-token-account")
             .join("program");
         rerun_if_changed(&spl_associated_token_account_dir);
+        let spl_token_upgrade_dir = cwd
+            .parent()
+            .expect("Unable to get parent directory of current working dir")
+            .parent()
+            .expect("Unable to get parent directory of current working dir")
+            .join("token-upgrade")
+            .join("program");
+        rerun_if_changed(&spl_token_upgrade_dir);
 
         build_bpf(&spl_token_dir);
         build_bpf(&spl_token_2022_dir);
         build_bpf(&spl_associated_token_account_dir);
+        build_bpf(&spl_token_upgrade_dir);
     }
     println!("cargo:rerun-if-changed=build.rs");
 }
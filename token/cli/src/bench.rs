@@ -6,14 +6,11 @@ use {
         input_parsers::pubkey_of_signer,
         input_validators::{is_amount, is_parsable, is_valid_pubkey},
     },
-    solana_client::{
-        nonblocking::rpc_client::RpcClient, rpc_client::RpcClient as BlockingRpcClient,
-        tpu_client::TpuClient, tpu_client::TpuClientConfig,
-    },
+    solana_client::nonblocking::rpc_client::RpcClient,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_sdk::{
-        message::Message, native_token::Sol, program_pack::Pack, pubkey::Pubkey, signature::Signer,
-        system_instruction,
+        instruction::Instruction, message::Message, native_token::Sol, program_pack::Pack,
+        pubkey::Pubkey, signature::Signer, system_instruction,
     },
     spl_associated_token_account::*,
     spl_token_2022::{
@@ -21,6 +18,7 @@ use {
         instruction,
         state::{Account, Mint},
     },
+    spl_token_client::{batch_sender::BatchSender, token::ComputeUnitLimit},
     std::{rc::Rc, sync::Arc, time::Instant},
 };
 
@@ -290,7 +288,7 @@ async fn command_create_accounts(
     let mut lamports_required = 0;
 
     let token_addresses_with_seed = get_token_addresses_with_seed(&program_id, token, owner, n);
-    let mut messages = vec![];
+    let mut instruction_sets = vec![];
     for address_chunk in token_addresses_with_seed.chunks(100) {
         let accounts_chunk = rpc_client
             .get_multiple_accounts(&address_chunk.iter().map(|x| x.0).collect::<Vec<_>>())
@@ -299,26 +297,23 @@ async fn command_create_accounts(
         for (account, (address, seed)) in accounts_chunk.iter().zip(address_chunk) {
             if account.is_none() {
                 lamports_required += minimum_balance_for_rent_exemption;
-                messages.push(Message::new(
-                    &[
-                        system_instruction::create_account_with_seed(
-                            &config.fee_payer()?.pubkey(),
-                            address,
-                            owner,
-                            seed,
-                            minimum_balance_for_rent_exemption,
-                            Account::get_packed_len() as u64,
-                            &program_id,
-                        ),
-                        instruction::initialize_account(&program_id, address, token, owner)?,
-                    ],
-                    Some(&config.fee_payer()?.pubkey()),
-                ));
+                instruction_sets.push(vec![
+                    system_instruction::create_account_with_seed(
+                        &config.fee_payer()?.pubkey(),
+                        address,
+                        owner,
+                        seed,
+                        minimum_balance_for_rent_exemption,
+                        Account::get_packed_len() as u64,
+                        &program_id,
+                    ),
+                    instruction::initialize_account(&program_id, address, token, owner)?,
+                ]);
             }
         }
     }
 
-    send_messages(config, &messages, lamports_required, signers).await
+    send_instruction_sets(config, &instruction_sets, lamports_required, signers).await
 }
 
 async fn command_close_accounts(
@@ -334,7 +329,7 @@ async fn command_close_accounts(
     let program_id = get_valid_mint_program_id(rpc_client, token).await?;
 
     let token_addresses_with_seed = get_token_addresses_with_seed(&program_id, token, owner, n);
-    let mut messages = vec![];
+    let mut instruction_sets = vec![];
     for address_chunk in token_addresses_with_seed.chunks(100) {
         let accounts_chunk = rpc_client
             .get_multiple_accounts(&address_chunk.iter().map(|x| x.0).collect::<Vec<_>>())
@@ -350,16 +345,13 @@ async fn command_close_accounts(
                                 address,
                             );
                         } else {
-                            messages.push(Message::new(
-                                &[instruction::close_account(
-                                    &program_id,
-                                    address,
-                                    owner,
-                                    owner,
-                                    &[],
-                                )?],
-                                Some(&config.fee_payer()?.pubkey()),
-                            ));
+                            instruction_sets.push(vec![instruction::close_account(
+                                &program_id,
+                                address,
+                                owner,
+                                owner,
+                                &[],
+                            )?]);
                         }
                     }
                     Err(err) => {
@@ -370,7 +362,7 @@ async fn command_close_accounts(
         }
     }
 
-    send_messages(config, &messages, 0, signers).await
+    send_instruction_sets(config, &instruction_sets, 0, signers).await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -396,7 +388,7 @@ async fn command_deposit_into_or_withdraw_from(
     let amount = spl_token::ui_amount_to_amount(ui_amount, mint_info.decimals);
 
     let token_addresses_with_seed = get_token_addresses_with_seed(&program_id, token, owner, n);
-    let mut messages = vec![];
+    let mut instruction_sets = vec![];
     for address_chunk in token_addresses_with_seed.chunks(100) {
         let accounts_chunk = rpc_client
             .get_multiple_accounts(&address_chunk.iter().map(|x| x.0).collect::<Vec<_>>())
@@ -404,70 +396,71 @@ async fn command_deposit_into_or_withdraw_from(
 
         for (account, (address, _seed)) in accounts_chunk.iter().zip(address_chunk) {
             if account.is_some() {
-                messages.push(Message::new(
-                    &[instruction::transfer_checked(
-                        &program_id,
-                        if deposit_into { &from_or_to } else { address },
-                        token,
-                        if deposit_into { address } else { &from_or_to },
-                        owner,
-                        &[],
-                        amount,
-                        mint_info.decimals,
-                    )?],
-                    Some(&config.fee_payer()?.pubkey()),
-                ));
+                instruction_sets.push(vec![instruction::transfer_checked(
+                    &program_id,
+                    if deposit_into { &from_or_to } else { address },
+                    token,
+                    if deposit_into { address } else { &from_or_to },
+                    owner,
+                    &[],
+                    amount,
+                    mint_info.decimals,
+                )?]);
             } else {
                 eprintln!("Token account does not exist: {}", address)
             }
         }
     }
 
-    send_messages(config, &messages, 0, signers).await
+    send_instruction_sets(config, &instruction_sets, 0, signers).await
 }
 
-async fn send_messages(
+/// Pack the instruction sets into as few transactions as possible and send
+/// them in parallel. Limits are not simulated for each of the transactions, so
+/// only static ones are used.
+async fn send_instruction_sets(
     config: &Config<'_>,
-    messages: &[Message],
+    instruction_sets: &[Vec<Instruction>],
     mut lamports_required: u64,
     signers: Vec<Arc<dyn Signer>>,
 ) -> Result<(), Error> {
-    if messages.is_empty() {
+    if instruction_sets.is_empty() {
         println!("Nothing to do");
         return Ok(());
     }
 
-    let blockhash = config.rpc_client.get_latest_blockhash().await?;
-    let mut message = messages[0].clone();
-    message.recent_blockhash = blockhash;
+    let mut sender =
+        BatchSender::new(config.program_client.clone(), config.fee_payer()?).with_signers(signers);
+    if let Some(compute_unit_price) = config.compute_unit_price {
+        sender = sender.with_compute_unit_price(compute_unit_price);
+    }
+    if let ComputeUnitLimit::Static(compute_unit_limit) = config.compute_unit_limit {
+        sender = sender.with_compute_unit_limit(compute_unit_limit);
+    }
+
+    let packed = sender.pack(instruction_sets);
+    let mut message = Message::new(&packed[0].1, Some(&config.fee_payer()?.pubkey()));
+    message.recent_blockhash = config.rpc_client.get_latest_blockhash().await?;
     lamports_required +=
-        config.rpc_client.get_fee_for_message(&message).await? * messages.len() as u64;
+        config.rpc_client.get_fee_for_message(&message).await? * packed.len() as u64;
 
     println!(
-        "Sending {:?} messages for ~{}",
-        messages.len(),
+        "Sending {:?} transactions for ~{}",
+        packed.len(),
         Sol(lamports_required)
     );
 
     crate::check_fee_payer_balance(config, lamports_required).await?;
 
-    // TODO use async tpu client once it's available in 1.11
     let start = Instant::now();
-    let rpc_client = BlockingRpcClient::new(config.rpc_client.url());
-    let tpu_client = TpuClient::new(
-        Arc::new(rpc_client),
-        &config.websocket_url,
-        TpuClientConfig::default(),
-    )?;
-    let transaction_errors =
-        tpu_client.send_and_confirm_messages_with_spinner(messages, &signers)?;
-    for (i, transaction_error) in transaction_errors.into_iter().enumerate() {
-        if let Some(transaction_error) = transaction_error {
-            println!("Message {} failed with {:?}", i, transaction_error);
+    let report = sender.send(instruction_sets).await?;
+    for (i, outcome) in report.transactions.iter().enumerate() {
+        if let Err(err) = &outcome.result {
+            println!("Transaction {} failed with {}", i, err);
         }
     }
     let elapsed = Instant::now().duration_since(start);
-    let tps = messages.len() as f64 / elapsed.as_secs_f64();
+    let tps = report.transactions.len() as f64 / elapsed.as_secs_f64();
     println!(
         "Average TPS: {:.2}\nElapsed time: {} seconds",
         tps,
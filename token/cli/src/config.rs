@@ -1,26 +1,35 @@
-use crate::{signers_of, Error, MULTISIG_SIGNER_ARG};
+use crate::{signers_of, Error, COMPUTE_UNIT_LIMIT_ARG, LOOKUP_TABLE_ARG, MULTISIG_SIGNER_ARG};
 use clap::ArgMatches;
 use solana_clap_utils::{
-    input_parsers::{pubkey_of_signer, value_of},
+    compute_unit_price::COMPUTE_UNIT_PRICE_ARG,
+    input_parsers::{pubkey_of_signer, pubkeys_of, value_of},
     input_validators::normalize_to_url_if_moniker,
     keypair::{signer_from_path, signer_from_path_with_config, SignerFromPathConfig},
     nonce::{NONCE_ARG, NONCE_AUTHORITY_ARG},
     offline::{BLOCKHASH_ARG, DUMP_TRANSACTION_MESSAGE, SIGN_ONLY_ARG},
 };
+use solana_cli_config::ConfigInput;
 use solana_cli_output::OutputFormat;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
-    account::Account as RawAccount, commitment_config::CommitmentConfig, hash::Hash,
-    pubkey::Pubkey, signature::Signer,
+    account::Account as RawAccount,
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signer,
 };
 use spl_associated_token_account::*;
 use spl_token_2022::{
     extension::StateWithExtensionsOwned,
     state::{Account, Mint},
 };
-use spl_token_client::client::{
-    ProgramClient, ProgramOfflineClient, ProgramRpcClient, ProgramRpcClientSendTransaction,
+use spl_token_client::{
+    client::{
+        ProgramClient, ProgramOfflineClient, ProgramRpcClient, ProgramRpcClientSendTransaction,
+    },
+    token::ComputeUnitLimit,
 };
 use std::{process::exit, rc::Rc, sync::Arc};
 
@@ -33,8 +42,8 @@ pub(crate) struct MintInfo {
 pub(crate) struct Config<'a> {
     pub(crate) default_signer: Option<Arc<dyn Signer>>,
     pub(crate) rpc_client: Arc<RpcClient>,
-    pub(crate) program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>>,
     pub(crate) websocket_url: String,
+    pub(crate) program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>>,
     pub(crate) output_format: OutputFormat,
     pub(crate) fee_payer: Option<Arc<dyn Signer>>,
     pub(crate) nonce_account: Option<Pubkey>,
@@ -45,6 +54,9 @@ pub(crate) struct Config<'a> {
     pub(crate) multisigner_pubkeys: Vec<&'a Pubkey>,
     pub(crate) program_id: Pubkey,
     pub(crate) restrict_to_program_id: bool,
+    pub(crate) compute_unit_price: Option<u64>,
+    pub(crate) compute_unit_limit: ComputeUnitLimit,
+    pub(crate) address_lookup_tables: Vec<AddressLookupTableAccount>,
 }
 
 impl<'a> Config<'a> {
@@ -69,7 +81,6 @@ impl<'a> Config<'a> {
                 .value_of("json_rpc_url")
                 .unwrap_or(&cli_config.json_rpc_url),
         );
-        let websocket_url = solana_cli_config::Config::compute_websocket_url(&json_rpc_url);
         let rpc_client = Arc::new(RpcClient::new_with_commitment(
             json_rpc_url,
             CommitmentConfig::confirmed(),
@@ -87,14 +98,13 @@ impl<'a> Config<'a> {
                 ProgramRpcClientSendTransaction,
             ))
         };
-        Self::new_with_clients_and_ws_url(
+        Self::new_with_clients(
             matches,
             wallet_manager,
             bulk_signers,
             multisigner_ids,
             rpc_client,
             program_client,
-            websocket_url,
         )
         .await
     }
@@ -119,14 +129,13 @@ impl<'a> Config<'a> {
         multisigner_ids.iter().collect::<Vec<_>>()
     }
 
-    pub(crate) async fn new_with_clients_and_ws_url(
+    pub(crate) async fn new_with_clients(
         matches: &ArgMatches<'_>,
         wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
         bulk_signers: &mut Vec<Arc<dyn Signer>>,
         multisigner_ids: &'a mut Vec<Pubkey>,
         rpc_client: Arc<RpcClient>,
         program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>>,
-        websocket_url: String,
     ) -> Config<'a> {
         let cli_config = if let Some(config_file) = matches.value_of("config_file") {
             solana_cli_config::Config::load(config_file).unwrap_or_else(|_| {
@@ -138,6 +147,12 @@ impl<'a> Config<'a> {
         } else {
             solana_cli_config::Config::default()
         };
+        let (_, websocket_url) = ConfigInput::compute_websocket_url_setting(
+            matches.value_of("websocket_url").unwrap_or(""),
+            &cli_config.websocket_url,
+            matches.value_of("json_rpc_url").unwrap_or(""),
+            &cli_config.json_rpc_url,
+        );
         let multisigner_pubkeys =
             Self::extract_multisig_signers(matches, wallet_manager, bulk_signers, multisigner_ids);
 
@@ -257,11 +272,54 @@ impl<'a> Config<'a> {
             };
 
         let nonce_blockhash = value_of(matches, BLOCKHASH_ARG.name);
+
+        let compute_unit_price = value_of(matches, COMPUTE_UNIT_PRICE_ARG.name);
+        let compute_unit_limit = value_of(matches, COMPUTE_UNIT_LIMIT_ARG.name)
+            .map(ComputeUnitLimit::Static)
+            .unwrap_or_else(|| {
+                // only pay the price for the units actually used, which requires
+                // a simulation, so not possible offline
+                if compute_unit_price.is_some() && !sign_only {
+                    ComputeUnitLimit::Simulated
+                } else {
+                    ComputeUnitLimit::Default
+                }
+            });
+
+        let lookup_table_addresses = pubkeys_of(matches, LOOKUP_TABLE_ARG.name).unwrap_or_default();
+        if !lookup_table_addresses.is_empty() && sign_only {
+            eprintln!(
+                "error: --{} cannot be used with --{}",
+                LOOKUP_TABLE_ARG.long, SIGN_ONLY_ARG.long
+            );
+            exit(1);
+        }
+        let mut address_lookup_tables = vec![];
+        for address in lookup_table_addresses {
+            let table = rpc_client
+                .get_account(&address)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|account| {
+                    AddressLookupTable::deserialize(&account.data)
+                        .map(|table| table.addresses.to_vec())
+                        .map_err(|e| e.to_string())
+                })
+                .unwrap_or_else(|e| {
+                    eprintln!("error: Could not load lookup table {}: {}", address, e);
+                    exit(1);
+                });
+            address_lookup_tables.push(AddressLookupTableAccount {
+                key: address,
+                addresses: table,
+            });
+        }
+
         Self {
             default_signer,
             rpc_client,
-            program_client,
             websocket_url,
+            program_client,
             output_format,
             fee_payer,
             nonce_account,
@@ -272,6 +330,9 @@ impl<'a> Config<'a> {
             multisigner_pubkeys,
             program_id,
             restrict_to_program_id,
+            compute_unit_price,
+            compute_unit_limit,
+            address_lookup_tables,
         }
     }
 
@@ -0,0 +1,560 @@
+/// The `distribute` subcommand
+use {
+    crate::{
+        config::Config,
+        format_output, multisig_signer_arg,
+        output::{CliDistribution, CliDistributionStatus, CliDistributionTransfer},
+        owner_keypair_arg_with_value_name, print_output_and_fail, println_display,
+        push_signer_with_dedup, token_client_from_config, write_json_file, BulkSigners,
+        CommandName, CommandResult, Error,
+    },
+    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    serde::{Deserialize, Serialize},
+    solana_clap_utils::{
+        input_parsers::pubkey_of_signer,
+        input_validators::{is_parsable, is_valid_pubkey},
+    },
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, signer::signers::Signers},
+    spl_token_client::{client::ProgramRpcClientSendTransaction, token::Token},
+    std::{
+        collections::{HashMap, HashSet},
+        fs,
+        path::Path,
+        rc::Rc,
+        str::FromStr,
+    },
+};
+
+pub(crate) trait DistributeSubCommand {
+    fn distribute_subcommand(self) -> Self;
+}
+
+impl DistributeSubCommand for App<'_, '_> {
+    fn distribute_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name(CommandName::Distribute.into())
+                .about("Transfer tokens to many recipients listed in a CSV file")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Token to distribute"),
+                )
+                .arg(
+                    Arg::with_name("recipients")
+                        .value_name("RECIPIENTS_CSV")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("CSV file with one `recipient,amount` line per transfer. \
+                            Recipients are wallet addresses, and amounts are in tokens. \
+                            A header line is allowed."),
+                )
+                .arg(
+                    Arg::with_name("from")
+                        .validator(is_valid_pubkey)
+                        .value_name("SENDER_TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .long("from")
+                        .help("Specify the sending token account \
+                            [default: owner's associated token account]")
+                )
+                .arg(owner_keypair_arg_with_value_name("SENDER_TOKEN_OWNER_KEYPAIR")
+                        .help(
+                            "Specify the owner of the sending token account. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("fund_recipient")
+                        .long("fund-recipient")
+                        .takes_value(false)
+                        .help("Create the associated token accounts for recipients if they don't already exist")
+                )
+                .arg(
+                    Arg::with_name("batch_size")
+                        .long("batch-size")
+                        .validator(is_parsable::<usize>)
+                        .value_name("N")
+                        .takes_value(true)
+                        .default_value("5")
+                        .help("Number of transfers to pack into each transaction"),
+                )
+                .arg(
+                    Arg::with_name("state_file")
+                        .long("state-file")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("File recording completed transfers, used to resume an interrupted \
+                            distribution [default: RECIPIENTS_CSV with a `.state.json` suffix]"),
+                )
+                .arg(multisig_signer_arg()),
+        )
+    }
+}
+
+pub(crate) async fn distribute_process_command(
+    matches: &ArgMatches<'_>,
+    config: &Config<'_>,
+    mut bulk_signers: BulkSigners,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let token = pubkey_of_signer(matches, "token", wallet_manager)
+        .unwrap()
+        .unwrap();
+    let recipients = matches.value_of("recipients").unwrap();
+    let sender = pubkey_of_signer(matches, "from", wallet_manager).unwrap();
+    let (owner_signer, owner) = config.signer_or_default(matches, "owner", wallet_manager);
+    if config.multisigner_pubkeys.is_empty() {
+        push_signer_with_dedup(owner_signer, &mut bulk_signers);
+    }
+    let batch_size = value_t_or_exit!(matches, "batch_size", usize);
+    if batch_size == 0 {
+        return Err("Error: batch size must be at least 1".into());
+    }
+    let state_file = matches
+        .value_of("state_file")
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{}.state.json", recipients));
+
+    command_distribute(
+        config,
+        token,
+        recipients,
+        sender,
+        owner,
+        matches.is_present("fund_recipient"),
+        batch_size,
+        &state_file,
+        bulk_signers,
+    )
+    .await
+}
+
+/// A transfer requested by a line of the recipients file
+struct DistributionEntry {
+    line: usize,
+    recipient: Pubkey,
+    ui_amount: f64,
+}
+
+/// Progress of a distribution, saved after every transaction
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DistributionState {
+    token: String,
+    sender: String,
+    completed: Vec<CompletedTransfer>,
+    #[serde(default)]
+    pending: Vec<PendingBatch>,
+}
+
+/// Transfers of a transaction that was sent but not confirmed yet
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingBatch {
+    signature: String,
+    blockhash: String,
+    transfers: Vec<CompletedTransfer>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompletedTransfer {
+    line: usize,
+    recipient: String,
+    amount: u64,
+    signature: String,
+}
+
+fn parse_recipients(contents: &str) -> Result<Vec<DistributionEntry>, Error> {
+    let mut entries = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let [recipient, amount] = fields.as_slice() else {
+            return Err(format!(
+                "Error: line {}: expected `recipient,amount`, found `{}`",
+                line_number, line
+            )
+            .into());
+        };
+        let recipient = match Pubkey::from_str(recipient) {
+            Ok(recipient) => recipient,
+            // allow a header before the first transfer
+            Err(_) if entries.is_empty() && amount.parse::<f64>().is_err() => continue,
+            Err(_) => {
+                return Err(format!(
+                    "Error: line {}: invalid recipient address `{}`",
+                    line_number, recipient
+                )
+                .into())
+            }
+        };
+        let ui_amount = match amount.parse::<f64>() {
+            Ok(ui_amount) if ui_amount > 0.0 => ui_amount,
+            _ => {
+                return Err(
+                    format!("Error: line {}: invalid amount `{}`", line_number, amount).into(),
+                )
+            }
+        };
+        entries.push(DistributionEntry {
+            line: line_number,
+            recipient,
+            ui_amount,
+        });
+    }
+    Ok(entries)
+}
+
+fn read_state(path: &str, token: &Pubkey, sender: &Pubkey) -> Result<DistributionState, Error> {
+    if !Path::new(path).exists() {
+        return Ok(DistributionState {
+            token: token.to_string(),
+            sender: sender.to_string(),
+            completed: vec![],
+            pending: vec![],
+        });
+    }
+    let state: DistributionState = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|err| format!("Error: invalid state file {}: {}", path, err))?;
+    if state.token != token.to_string() || state.sender != sender.to_string() {
+        return Err(format!(
+            "Error: state file {} records a distribution of {} from {}",
+            path, state.token, state.sender
+        )
+        .into());
+    }
+    Ok(state)
+}
+
+/// Check whether a transaction recorded in the state file landed, waiting for
+/// it while its blockhash is still valid
+async fn batch_landed(config: &Config<'_>, batch: &PendingBatch) -> Result<bool, Error> {
+    let signature = Signature::from_str(&batch.signature)?;
+    let blockhash = Hash::from_str(&batch.blockhash)?;
+    let commitment = config.rpc_client.commitment();
+    if config
+        .rpc_client
+        .is_blockhash_valid(&blockhash, commitment)
+        .await?
+    {
+        // the outcome is read from the signature status below
+        let _ = config
+            .rpc_client
+            .confirm_transaction_with_spinner(&signature, &blockhash, commitment)
+            .await;
+    }
+    match config
+        .rpc_client
+        .get_signature_status_with_commitment_and_history(&signature, commitment, true)
+        .await?
+    {
+        Some(result) => Ok(result.is_ok()),
+        None if config
+            .rpc_client
+            .is_blockhash_valid(&blockhash, commitment)
+            .await? =>
+        {
+            Err(format!(
+                "Error: transaction {} has not landed yet; run the command again to check it",
+                signature
+            )
+            .into())
+        }
+        None => Ok(false),
+    }
+}
+
+/// Sign and send a batch of transfers, recording its signature in the state
+/// file before waiting for confirmation
+#[allow(clippy::too_many_arguments)]
+async fn send_batch(
+    config: &Config<'_>,
+    token: &Token<ProgramRpcClientSendTransaction>,
+    sender: &Pubkey,
+    sender_owner: &Pubkey,
+    batch: &[(&DistributionEntry, &u64)],
+    fund_recipient: bool,
+    bulk_signers: &BulkSigners,
+    state: &mut DistributionState,
+    state_path: &str,
+) -> Result<Signature, Error> {
+    let recipients = batch
+        .iter()
+        .map(|(entry, amount)| (entry.recipient, **amount))
+        .collect::<Vec<_>>();
+    let instructions = token
+        .transfer_to_associated_accounts_instructions(
+            sender,
+            sender_owner,
+            &recipients,
+            fund_recipient,
+            &bulk_signers.pubkeys(),
+        )
+        .await?;
+    let transaction = token.sign_ixs(&instructions, bulk_signers).await?;
+    let signature = transaction.signatures[0];
+    let blockhash = *transaction.message.recent_blockhash();
+
+    state.pending.push(PendingBatch {
+        signature: signature.to_string(),
+        blockhash: blockhash.to_string(),
+        transfers: batch
+            .iter()
+            .map(|(entry, amount)| CompletedTransfer {
+                line: entry.line,
+                recipient: entry.recipient.to_string(),
+                amount: **amount,
+                signature: signature.to_string(),
+            })
+            .collect(),
+    });
+    write_json_file(state_path, state)?;
+
+    // on failure the batch stays pending, so that the next run checks whether
+    // it landed before sending it again
+    config.rpc_client.send_transaction(&transaction).await?;
+    config
+        .rpc_client
+        .confirm_transaction_with_spinner(&signature, &blockhash, config.rpc_client.commitment())
+        .await?;
+
+    let batch = state.pending.pop().unwrap();
+    state.completed.extend(batch.transfers);
+    write_json_file(state_path, state)?;
+    Ok(signature)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn command_distribute(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    recipients_path: &str,
+    sender: Option<Pubkey>,
+    sender_owner: Pubkey,
+    fund_recipient: bool,
+    batch_size: usize,
+    state_path: &str,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mint_info = config.get_mint_info(&token_pubkey, None).await?;
+    let token = token_client_from_config(config, &token_pubkey, Some(mint_info.decimals))?;
+    let sender = sender.unwrap_or_else(|| token.get_associated_token_address(&sender_owner));
+
+    let contents = fs::read_to_string(recipients_path)
+        .map_err(|err| format!("Error: unable to read {}: {}", recipients_path, err))?;
+    let entries = parse_recipients(&contents)?;
+    let amounts = entries
+        .iter()
+        .map(|entry| spl_token::ui_amount_to_amount(entry.ui_amount, mint_info.decimals))
+        .collect::<Vec<_>>();
+
+    // transfers recorded in the state file must still match the recipients file
+    let mut state = read_state(state_path, &token_pubkey, &sender)?;
+    let recorded = state
+        .completed
+        .iter()
+        .chain(state.pending.iter().flat_map(|batch| &batch.transfers));
+    for transfer in recorded {
+        let matches = entries.iter().zip(&amounts).any(|(entry, amount)| {
+            entry.line == transfer.line
+                && entry.recipient.to_string() == transfer.recipient
+                && *amount == transfer.amount
+        });
+        if !matches {
+            return Err(format!(
+                "Error: state file {} records a transfer of {} to {} on line {}, \
+                which does not match {}",
+                state_path, transfer.amount, transfer.recipient, transfer.line, recipients_path
+            )
+            .into());
+        }
+    }
+
+    // transactions sent by an interrupted run may have landed after all
+    while let Some(batch) = state.pending.first() {
+        let landed = batch_landed(config, batch).await?;
+        let batch = state.pending.remove(0);
+        if landed {
+            state.completed.extend(batch.transfers);
+        } else {
+            println_display(
+                config,
+                format!(
+                    "Transaction {} did not land, its transfers will be sent again",
+                    batch.signature
+                ),
+            );
+        }
+        write_json_file(state_path, &state)?;
+    }
+    let completed = state
+        .completed
+        .iter()
+        .map(|transfer| transfer.line)
+        .collect::<HashSet<_>>();
+
+    let pending = entries
+        .iter()
+        .zip(&amounts)
+        .filter(|(entry, _)| !completed.contains(&entry.line))
+        .collect::<Vec<_>>();
+
+    println_display(
+        config,
+        format!(
+            "Distribute {} to {} recipients\n  Sender: {}\n  Previously completed: {}",
+            token_pubkey,
+            entries.len(),
+            sender,
+            completed.len(),
+        ),
+    );
+
+    if !pending.is_empty() {
+        let pending_amount = pending.iter().map(|(_, amount)| **amount).sum::<u64>();
+        let sender_balance = token.get_account_info(&sender).await?.base.amount;
+        if pending_amount > sender_balance {
+            return Err(format!(
+                "Error: Sender has insufficient funds, current balance is {} but {} is required",
+                spl_token_2022::amount_to_ui_amount_string_trimmed(
+                    sender_balance,
+                    mint_info.decimals
+                ),
+                spl_token_2022::amount_to_ui_amount_string_trimmed(
+                    pending_amount,
+                    mint_info.decimals
+                ),
+            )
+            .into());
+        }
+
+        if !fund_recipient {
+            let addresses = pending
+                .iter()
+                .map(|(entry, _)| token.get_associated_token_address(&entry.recipient))
+                .collect::<Vec<_>>();
+            for address_chunk in addresses.chunks(100) {
+                let accounts = config
+                    .rpc_client
+                    .get_multiple_accounts(address_chunk)
+                    .await?;
+                if let Some((address, _)) = address_chunk
+                    .iter()
+                    .zip(accounts)
+                    .find(|(_, account)| account.is_none())
+                {
+                    return Err(format!(
+                        "Error: Recipient's associated token account {} does not exist. \
+                        Add `--fund-recipient` to fund their accounts",
+                        address
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+
+    let mut failed = HashMap::new();
+    let batch_count = pending.len().div_ceil(batch_size);
+    for (i, batch) in pending.chunks(batch_size).enumerate() {
+        let result = send_batch(
+            config,
+            &token,
+            &sender,
+            &sender_owner,
+            batch,
+            fund_recipient,
+            &bulk_signers,
+            &mut state,
+            state_path,
+        )
+        .await;
+
+        match result {
+            Ok(signature) => {
+                println_display(
+                    config,
+                    format!("Batch {}/{} sent: {}", i + 1, batch_count, signature),
+                );
+            }
+            Err(err) => {
+                println_display(
+                    config,
+                    format!("Batch {}/{} failed: {}", i + 1, batch_count, err),
+                );
+                for (entry, _) in batch {
+                    failed.insert(entry.line, err.to_string());
+                }
+            }
+        }
+    }
+
+    let signatures = state
+        .completed
+        .iter()
+        .map(|transfer| (transfer.line, transfer.signature.clone()))
+        .collect::<HashMap<_, _>>();
+    let mut total_distributed = 0;
+    let transfers = entries
+        .iter()
+        .zip(&amounts)
+        .map(|(entry, amount)| {
+            let status = if completed.contains(&entry.line) {
+                CliDistributionStatus::PreviouslySent
+            } else if failed.contains_key(&entry.line) {
+                CliDistributionStatus::Failed
+            } else {
+                CliDistributionStatus::Sent
+            };
+            if status != CliDistributionStatus::Failed {
+                total_distributed += amount;
+            }
+            CliDistributionTransfer {
+                line: entry.line,
+                recipient: entry.recipient.to_string(),
+                amount: spl_token_2022::amount_to_ui_amount_string_trimmed(
+                    *amount,
+                    mint_info.decimals,
+                ),
+                status,
+                signature: signatures.get(&entry.line).cloned(),
+                error: failed.get(&entry.line).cloned(),
+            }
+        })
+        .collect();
+
+    let report = CliDistribution {
+        token: token_pubkey.to_string(),
+        sender: sender.to_string(),
+        state_file: state_path.to_string(),
+        total_distributed: spl_token_2022::amount_to_ui_amount_string_trimmed(
+            total_distributed,
+            mint_info.decimals,
+        ),
+        transfers,
+    };
+    let output = format_output(report, &CommandName::Distribute, config);
+    if failed.is_empty() {
+        Ok(output)
+    } else {
+        print_output_and_fail(
+            output,
+            format!(
+                "Error: {} transfers failed; run the command again to retry them",
+                failed.len()
+            ),
+        )
+    }
+}
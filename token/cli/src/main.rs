@@ -6,15 +6,18 @@ use clap::{
 use futures::try_join;
 use serde::Serialize;
 use solana_account_decoder::{
-    parse_token::{get_token_account_mint, parse_token, TokenAccountType, UiAccountState},
+    parse_token::{
+        get_token_account_mint, parse_token, TokenAccountType, UiAccountState, UiTokenAmount,
+    },
     UiAccountData,
 };
 use solana_clap_utils::{
+    compute_unit_price::compute_unit_price_arg,
     fee_payer::fee_payer_arg,
-    input_parsers::{pubkey_of_signer, pubkeys_of_multiple_signers, value_of},
+    input_parsers::{pubkey_of_signer, pubkeys_of_multiple_signers, value_of, values_of},
     input_validators::{
-        is_amount, is_amount_or_all, is_parsable, is_pubkey, is_url_or_moniker, is_valid_pubkey,
-        is_valid_signer,
+        is_amount, is_amount_or_all, is_parsable, is_pubkey, is_url, is_url_or_moniker,
+        is_valid_pubkey, is_valid_signer,
     },
     keypair::signer_from_path,
     memo::memo_arg,
@@ -29,12 +32,14 @@ use solana_cli_output::{
 use solana_client::rpc_request::TokenAccountsFilter;
 use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
+    account::from_account,
+    clock::Clock,
     instruction::AccountMeta,
     native_token::*,
     program_option::COption,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    system_program,
+    system_program, sysvar,
 };
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_token_2022::{
@@ -43,38 +48,50 @@ use spl_token_2022::{
             account_info::{
                 ApplyPendingBalanceAccountInfo, TransferAccountInfo, WithdrawAccountInfo,
             },
-            instruction::TransferSplitContextStateAccounts,
+            instruction::{
+                TransferSplitContextStateAccounts, TransferWithFeeSplitContextStateAccounts,
+            },
             ConfidentialTransferAccount, ConfidentialTransferMint,
         },
-        confidential_transfer_fee::ConfidentialTransferFeeConfig,
+        confidential_transfer_fee::{
+            account_info::WithheldTokensInfo, ConfidentialTransferFeeAmount,
+            ConfidentialTransferFeeConfig,
+        },
         cpi_guard::CpiGuard,
         default_account_state::DefaultAccountState,
+        group_pointer::GroupPointer,
         interest_bearing_mint::InterestBearingConfig,
         memo_transfer::MemoTransfer,
         metadata_pointer::MetadataPointer,
         mint_close_authority::MintCloseAuthority,
+        pausable::PausableConfig,
         permanent_delegate::PermanentDelegate,
+        scaled_ui_amount::ScaledUiAmountConfig,
         transfer_fee::{TransferFeeAmount, TransferFeeConfig},
         transfer_hook::TransferHook,
-        BaseStateWithExtensions, ExtensionType, StateWithExtensionsOwned,
+        vesting::VestingSchedule,
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsOwned,
     },
     instruction::*,
     solana_zk_token_sdk::{
         encryption::{
             auth_encryption::AeKey,
-            elgamal::{self, ElGamalKeypair},
+            elgamal::{self, ElGamalCiphertext, ElGamalKeypair},
         },
+        instruction::transfer::FeeParameters,
         zk_token_elgamal::pod::ElGamalPubkey,
     },
     state::{Account, AccountState, Mint},
 };
 use spl_token_client::{
     client::{ProgramRpcClientSendTransaction, RpcClientResponse},
+    proof_generation::transfer_with_fee_split_proof_data,
     token::{ExtensionInitializationParams, Token},
 };
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
 use spl_token_metadata_interface::state::{Field, TokenMetadata};
 use std::{
-    collections::HashMap, fmt, fmt::Display, process::exit, rc::Rc, str::FromStr, sync::Arc,
+    collections::HashMap, fmt, fmt::Display, fs, process::exit, rc::Rc, str::FromStr, sync::Arc,
 };
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumString, IntoStaticStr};
@@ -90,6 +107,14 @@ use sort::{sort_and_parse_token_accounts, AccountFilter};
 
 mod bench;
 use bench::*;
+mod distribute;
+use distribute::*;
+mod migrate;
+use migrate::*;
+mod proposal;
+use proposal::*;
+mod watch;
+use watch::*;
 
 // NOTE: this submodule should be removed in the next Solana upgrade
 mod encryption_keypair;
@@ -137,6 +162,21 @@ pub const MULTISIG_SIGNER_ARG: ArgConstant<'static> = ArgConstant {
     help: "Member signer of a multisig account",
 };
 
+pub const COMPUTE_UNIT_LIMIT_ARG: ArgConstant<'static> = ArgConstant {
+    name: "compute_unit_limit",
+    long: "with-compute-unit-limit",
+    help: "Set compute unit limit for transaction, in compute units. \
+           [default: simulated when --with-compute-unit-price is set, \
+           unless signing offline]",
+};
+
+pub const LOOKUP_TABLE_ARG: ArgConstant<'static> = ArgConstant {
+    name: "lookup_table",
+    long: "lookup-table",
+    help: "Address lookup table to compile the transaction against, sending a \
+           versioned transaction. May be specified multiple times",
+};
+
 static VALID_TOKEN_PROGRAM_IDS: [Pubkey; 2] = [spl_token_2022::ID, spl_token::ID];
 
 #[derive(Debug, Clone, Copy, PartialEq, EnumString, IntoStaticStr)]
@@ -150,7 +190,10 @@ pub enum CommandName {
     CreateMultisig,
     Authorize,
     SetInterestRate,
+    SetUiMultiplier,
     Transfer,
+    Distribute,
+    MigrateMint,
     Burn,
     Mint,
     Freeze,
@@ -173,6 +216,8 @@ pub enum CommandName {
     EnableCpiGuard,
     DisableCpiGuard,
     UpdateDefaultAccountState,
+    Pause,
+    Resume,
     UpdateMetadataAddress,
     WithdrawWithheldTokens,
     SetTransferFee,
@@ -180,7 +225,11 @@ pub enum CommandName {
     SetTransferHookProgram,
     InitializeMetadata,
     UpdateMetadata,
+    InitializeGroup,
+    UpdateGroupMaxSize,
+    InitializeMember,
     UpdateConfidentialTransferSettings,
+    RotateConfidentialTransferAuditor,
     ConfigureConfidentialTransferAccount,
     EnableConfidentialCredits,
     DisableConfidentialCredits,
@@ -189,6 +238,11 @@ pub enum CommandName {
     DepositConfidentialTokens,
     WithdrawConfidentialTokens,
     ApplyPendingBalance,
+    ConfidentialBalance,
+    Propose,
+    Sign,
+    Submit,
+    Watch,
 }
 impl fmt::Display for CommandName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -259,6 +313,10 @@ pub enum CliAuthorityType {
     ConfidentialTransferFee,
     MetadataPointer,
     Metadata,
+    Pause,
+    UiMultiplier,
+    GroupPointer,
+    Group,
 }
 impl TryFrom<CliAuthorityType> for AuthorityType {
     type Error = Error;
@@ -284,6 +342,12 @@ impl TryFrom<CliAuthorityType> for AuthorityType {
             CliAuthorityType::Metadata => {
                 Err("Metadata authority does not map to a token authority type".into())
             }
+            CliAuthorityType::Pause => Ok(AuthorityType::Pause),
+            CliAuthorityType::UiMultiplier => Ok(AuthorityType::ScaledUiAmount),
+            CliAuthorityType::GroupPointer => Ok(AuthorityType::GroupPointer),
+            CliAuthorityType::Group => {
+                Err("Group update authority does not map to a token authority type".into())
+            }
         }
     }
 }
@@ -369,16 +433,15 @@ pub fn multisig_signer_arg<'a, 'b>() -> Arg<'a, 'b> {
         .takes_value(true)
         .multiple(true)
         .min_values(0u64)
-        .max_values(MAX_SIGNERS as u64)
+        .max_values(MAX_WEIGHTED_SIGNERS as u64)
         .help(MULTISIG_SIGNER_ARG.help)
 }
 
 fn is_multisig_minimum_signers(string: String) -> Result<(), String> {
-    let v = u8::from_str(&string).map_err(|e| e.to_string())? as usize;
+    // weighted multisigs take a total weight, which may exceed MAX_SIGNERS
+    let v = u16::from_str(&string).map_err(|e| e.to_string())? as usize;
     if v < MIN_SIGNERS {
         Err(format!("must be at least {}", MIN_SIGNERS))
-    } else if v > MAX_SIGNERS {
-        Err(format!("must be at most {}", MAX_SIGNERS))
     } else {
         Ok(())
     }
@@ -506,7 +569,14 @@ fn token_client_from_config(
         token_pubkey,
         decimals,
         config.fee_payer()?.clone(),
-    );
+    )
+    .with_compute_unit_limit(config.compute_unit_limit)
+    .with_address_lookup_tables(config.address_lookup_tables.clone());
+    let token = if let Some(compute_unit_price) = config.compute_unit_price {
+        token.with_compute_unit_price(compute_unit_price)
+    } else {
+        token
+    };
 
     if let (Some(nonce_account), Some(nonce_authority), Some(nonce_blockhash)) = (
         config.nonce_account,
@@ -530,7 +600,14 @@ fn native_token_client_from_config(
         config.program_client.clone(),
         &config.program_id,
         config.fee_payer()?.clone(),
-    );
+    )
+    .with_compute_unit_limit(config.compute_unit_limit)
+    .with_address_lookup_tables(config.address_lookup_tables.clone());
+    let token = if let Some(compute_unit_price) = config.compute_unit_price {
+        token.with_compute_unit_price(compute_unit_price)
+    } else {
+        token
+    };
 
     if let (Some(nonce_account), Some(nonce_authority), Some(nonce_blockhash)) = (
         config.nonce_account,
@@ -557,14 +634,19 @@ async fn command_create_token(
     enable_close: bool,
     enable_non_transferable: bool,
     enable_permanent_delegate: bool,
+    enable_pause: bool,
     memo: Option<String>,
     metadata_address: Option<Pubkey>,
+    group_address: Option<Pubkey>,
     rate_bps: Option<i16>,
+    ui_multiplier: Option<f64>,
     default_account_state: Option<AccountState>,
     transfer_fee: Option<(u16, u64)>,
     confidential_transfer_auto_approve: Option<bool>,
+    confidential_transfer_fee_elgamal_pubkey: Option<ElGamalPubkey>,
     transfer_hook_program_id: Option<Pubkey>,
     enable_metadata: bool,
+    enable_group: bool,
     bulk_signers: Vec<Arc<dyn Signer>>,
 ) -> CommandResult {
     println_display(
@@ -600,6 +682,13 @@ async fn command_create_token(
         })
     }
 
+    if let Some(multiplier) = ui_multiplier {
+        extensions.push(ExtensionInitializationParams::ScaledUiAmountConfig {
+            authority: Some(authority),
+            multiplier,
+        })
+    }
+
     if enable_non_transferable {
         extensions.push(ExtensionInitializationParams::NonTransferable);
     }
@@ -629,6 +718,17 @@ async fn command_create_token(
         });
     }
 
+    if let Some(withdraw_withheld_authority_elgamal_pubkey) =
+        confidential_transfer_fee_elgamal_pubkey
+    {
+        extensions.push(
+            ExtensionInitializationParams::ConfidentialTransferFeeConfig {
+                authority: Some(authority),
+                withdraw_withheld_authority_elgamal_pubkey,
+            },
+        );
+    }
+
     if let Some(program_id) = transfer_hook_program_id {
         extensions.push(ExtensionInitializationParams::TransferHook {
             authority: Some(authority),
@@ -636,6 +736,10 @@ async fn command_create_token(
         });
     }
 
+    if enable_pause {
+        extensions.push(ExtensionInitializationParams::PausableConfig { authority });
+    }
+
     if let Some(text) = memo {
         token.with_memo(text, vec![config.default_signer()?.pubkey()]);
     }
@@ -653,6 +757,19 @@ async fn command_create_token(
         });
     }
 
+    // CLI checks that only one is set
+    if group_address.is_some() || enable_group {
+        let group_address = if enable_group {
+            Some(token_pubkey)
+        } else {
+            group_address
+        };
+        extensions.push(ExtensionInitializationParams::GroupPointer {
+            authority: Some(authority),
+            group_address,
+        });
+    }
+
     let res = token
         .create_mint(
             &authority,
@@ -675,6 +792,17 @@ async fn command_create_token(
         );
     }
 
+    if enable_group {
+        println_display(
+            config,
+            format!(
+                "To initialize group configurations inside the mint, please run \
+                `spl-token initialize-group {token_pubkey} <MAX_SIZE>`, \
+                and sign with the mint authority.",
+            ),
+        );
+    }
+
     Ok(match tx_return {
         TransactionReturnData::CliSignature(cli_signature) => format_output(
             CliCreateToken {
@@ -749,6 +877,84 @@ async fn command_set_interest_rate(
     })
 }
 
+async fn command_set_ui_multiplier(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    multiplier_authority: Pubkey,
+    multiplier: f64,
+    effective_timestamp: Option<i64>,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(scaled_ui_amount_config) = mint_state.get_extension::<ScaledUiAmountConfig>() {
+            let mint_multiplier_authority_pubkey =
+                Option::<Pubkey>::from(scaled_ui_amount_config.authority);
+
+            if mint_multiplier_authority_pubkey != Some(multiplier_authority) {
+                return Err(format!(
+                    "Mint {} has UI multiplier authority {}, but {} was provided",
+                    token_pubkey,
+                    mint_multiplier_authority_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    multiplier_authority
+                )
+                .into());
+            }
+        } else {
+            return Err(
+                format!("Mint {} does not have a UI amount multiplier", token_pubkey).into(),
+            );
+        }
+    }
+
+    // a timestamp of 0 is always in the past, so the multiplier takes effect immediately
+    let effective_timestamp = effective_timestamp.unwrap_or_default();
+    if effective_timestamp == 0 {
+        println_display(
+            config,
+            format!(
+                "Setting UI Multiplier for {} to {}",
+                token_pubkey, multiplier
+            ),
+        );
+    } else {
+        println_display(
+            config,
+            format!(
+                "Setting UI Multiplier for {} to {} at unix timestamp {}",
+                token_pubkey, multiplier, effective_timestamp
+            ),
+        );
+    }
+
+    let res = token
+        .update_multiplier(
+            &multiplier_authority,
+            multiplier,
+            effective_timestamp,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 async fn command_set_transfer_hook_program(
     config: &Config<'_>,
     token_pubkey: Pubkey,
@@ -897,6 +1103,92 @@ async fn command_update_metadata(
     })
 }
 
+async fn command_initialize_group(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    mint_authority: Pubkey,
+    update_authority: Pubkey,
+    max_size: u32,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    let res = token
+        .token_group_initialize_with_rent_transfer(
+            &config.fee_payer()?.pubkey(),
+            &mint_authority,
+            &update_authority,
+            max_size,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_update_group_max_size(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    update_authority: Pubkey,
+    new_max_size: u32,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    let res = token
+        .token_group_update_max_size(&update_authority, new_max_size, &bulk_signers)
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_initialize_member(
+    config: &Config<'_>,
+    member_token_pubkey: Pubkey,
+    mint_authority: Pubkey,
+    group_token_pubkey: Pubkey,
+    group_update_authority: Pubkey,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &member_token_pubkey, None)?;
+
+    let res = token
+        .token_group_initialize_member_with_rent_transfer(
+            &config.fee_payer()?.pubkey(),
+            &mint_authority,
+            &group_token_pubkey,
+            &group_update_authority,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 async fn command_set_transfer_fee(
     config: &Config<'_>,
     token_pubkey: Pubkey,
@@ -1085,6 +1377,60 @@ async fn command_create_multisig(
     })
 }
 
+async fn command_create_weighted_multisig(
+    config: &Config<'_>,
+    multisig: Arc<dyn Signer>,
+    threshold: u16,
+    multisig_members: Vec<Pubkey>,
+    weights: Vec<u8>,
+    min_delay: i64,
+) -> CommandResult {
+    let total_weight = weights.iter().map(|weight| *weight as u16).sum::<u16>();
+    println_display(
+        config,
+        format!(
+            "Creating {}/{} weighted multisig {} with {} members under program {}",
+            threshold,
+            total_weight,
+            multisig.pubkey(),
+            multisig_members.len(),
+            config.program_id,
+        ),
+    );
+    if min_delay > 0 {
+        println_display(
+            config,
+            format!(
+                "  Minting and authority changes require proposals, delayed by {} seconds",
+                min_delay
+            ),
+        );
+    }
+
+    // default is safe here because create_weighted_multisig doesnt use it
+    let token = token_client_from_config(config, &Pubkey::default(), None)?;
+
+    let res = token
+        .create_weighted_multisig(
+            &*multisig,
+            &multisig_members.iter().collect::<Vec<_>>(),
+            &weights,
+            threshold,
+            min_delay,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn command_authorize(
     config: &Config<'_>,
@@ -1209,6 +1555,36 @@ async fn command_authorize(
                         Err(format!("Mint `{account}` does not support metadata"))
                     }
                 }
+                CliAuthorityType::Pause => {
+                    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
+                        Ok(Option::<Pubkey>::from(extension.authority))
+                    } else {
+                        Err(format!("Mint `{account}` is not pausable"))
+                    }
+                }
+                CliAuthorityType::UiMultiplier => {
+                    if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
+                        Ok(Option::<Pubkey>::from(extension.authority))
+                    } else {
+                        Err(format!(
+                            "Mint `{account}` does not have a UI amount multiplier"
+                        ))
+                    }
+                }
+                CliAuthorityType::GroupPointer => {
+                    if let Ok(extension) = mint.get_extension::<GroupPointer>() {
+                        Ok(Option::<Pubkey>::from(extension.authority))
+                    } else {
+                        Err(format!("Mint `{account}` does not support a group pointer"))
+                    }
+                }
+                CliAuthorityType::Group => {
+                    if let Ok(extension) = mint.get_extension::<TokenGroup>() {
+                        Ok(Option::<Pubkey>::from(extension.update_authority))
+                    } else {
+                        Err(format!("Mint `{account}` is not a token group"))
+                    }
+                }
             }?;
 
             Ok((account, previous_authority))
@@ -1247,7 +1623,11 @@ async fn command_authorize(
                 | CliAuthorityType::TransferHookProgramId
                 | CliAuthorityType::ConfidentialTransferFee
                 | CliAuthorityType::MetadataPointer
-                | CliAuthorityType::Metadata => Err(format!(
+                | CliAuthorityType::Metadata
+                | CliAuthorityType::Pause
+                | CliAuthorityType::UiMultiplier
+                | CliAuthorityType::GroupPointer
+                | CliAuthorityType::Group => Err(format!(
                     "Authority type `{auth_str}` not supported for SPL Token accounts",
                 )),
                 CliAuthorityType::Owner => {
@@ -1302,6 +1682,10 @@ async fn command_authorize(
         token
             .token_metadata_update_authority(&authority, new_authority, &bulk_signers)
             .await?
+    } else if let CliAuthorityType::Group = authority_type {
+        token
+            .token_group_update_authority(&authority, new_authority, &bulk_signers)
+            .await?
     } else {
         token
             .set_authority(
@@ -1583,7 +1967,9 @@ async fn command_transfer(
         token.with_memo(text, vec![config.default_signer()?.pubkey()]);
     }
 
-    // fetch confidential transfer info for recipient and auditor
+    // fetch confidential transfer info for recipient and auditor, and the fee
+    // configuration if the mint withholds transfer fees
+    let mut confidential_transfer_fee = None;
     let (recipient_elgamal_pubkey, auditor_elgamal_pubkey) = if let Some(args) =
         confidential_transfer_args
     {
@@ -1629,6 +2015,24 @@ async fn command_transfer(
                 .into());
             };
 
+            // fees are withheld encrypted under the withdraw withheld authority's ElGamal pubkey
+            if let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() {
+                let withdraw_withheld_authority_elgamal_pubkey = mint_state
+                    .get_extension::<ConfidentialTransferFeeConfig>()
+                    .map_err(|_| {
+                        format!(
+                            "Mint {} has a transfer fee, but no confidential transfer fee config",
+                            token_pubkey
+                        )
+                    })?
+                    .withdraw_withheld_authority_elgamal_pubkey;
+                let epoch = config.rpc_client.get_epoch_info().await?.epoch;
+                let transfer_fee = *transfer_fee_config.get_epoch_fee(epoch);
+
+                confidential_transfer_fee =
+                    Some((withdraw_withheld_authority_elgamal_pubkey, transfer_fee));
+            }
+
             let recipient_account = config.get_account_checked(&recipient_token_account).await?;
             let recipient_elgamal_pubkey =
                 StateWithExtensionsOwned::<Account>::unpack(recipient_account.data)?
@@ -1680,7 +2084,7 @@ async fn command_transfer(
                 )
                 .await?
         }
-        (None, None, Some(args)) => {
+        (None, None, Some(args)) if confidential_transfer_fee.is_none() => {
             // deserialize `pod` ElGamal pubkeys
             let recipient_elgamal_pubkey: elgamal::ElGamalPubkey = recipient_elgamal_pubkey
                 .unwrap()
@@ -1791,24 +2195,204 @@ async fn command_transfer(
 
             transfer_result
         }
-        (None, Some(_), Some(_)) => {
-            panic!("Confidential transfer with fee is not yet supported.");
-        }
-        (None, None, None) => {
-            token
-                .transfer(
-                    &sender,
-                    &recipient_token_account,
-                    &sender_owner,
-                    transfer_balance,
-                    &bulk_signers,
-                )
-                .await?
-        }
-    };
+        (None, maybe_fee, Some(args)) => {
+            let (withdraw_withheld_authority_elgamal_pubkey, transfer_fee) =
+                confidential_transfer_fee.ok_or_else(|| {
+                    format!(
+                        "Mint {} does not have a transfer fee configured",
+                        token_pubkey
+                    )
+                })?;
+
+            // the fee is encrypted, so check the expected one before generating any proofs
+            if let Some(expected_fee) = maybe_fee {
+                let fee = transfer_fee
+                    .calculate_fee(transfer_balance)
+                    .ok_or("Transfer fee calculation overflowed")?;
+                if fee != expected_fee {
+                    return Err(format!(
+                        "Expected a transfer fee of {}, but the mint would withhold {}",
+                        spl_token_2022::amount_to_ui_amount_string_trimmed(
+                            expected_fee,
+                            mint_info.decimals
+                        ),
+                        spl_token_2022::amount_to_ui_amount_string_trimmed(fee, mint_info.decimals),
+                    )
+                    .into());
+                }
+            }
 
-    let tx_return = finish_tx(config, &res, no_wait).await?;
-    Ok(match tx_return {
+            // deserialize `pod` ElGamal pubkeys
+            let recipient_elgamal_pubkey: elgamal::ElGamalPubkey = recipient_elgamal_pubkey
+                .unwrap()
+                .try_into()
+                .expect("Invalid recipient ElGamal pubkey");
+            let auditor_elgamal_pubkey = auditor_elgamal_pubkey.map(|pubkey| {
+                let auditor_elgamal_pubkey: elgamal::ElGamalPubkey =
+                    pubkey.try_into().expect("Invalid auditor ElGamal pubkey");
+                auditor_elgamal_pubkey
+            });
+            let withdraw_withheld_authority_elgamal_pubkey: elgamal::ElGamalPubkey =
+                withdraw_withheld_authority_elgamal_pubkey
+                    .try_into()
+                    .expect("Invalid withdraw withheld authority ElGamal pubkey");
+
+            let context_state_authority = config.fee_payer()?;
+            let equality_proof_context_state_account = Keypair::new();
+            let equality_proof_pubkey = equality_proof_context_state_account.pubkey();
+            let transfer_amount_ciphertext_validity_proof_context_state_account = Keypair::new();
+            let transfer_amount_ciphertext_validity_proof_pubkey =
+                transfer_amount_ciphertext_validity_proof_context_state_account.pubkey();
+            let fee_sigma_proof_context_state_account = Keypair::new();
+            let fee_sigma_proof_pubkey = fee_sigma_proof_context_state_account.pubkey();
+            let fee_ciphertext_validity_proof_context_state_account = Keypair::new();
+            let fee_ciphertext_validity_proof_pubkey =
+                fee_ciphertext_validity_proof_context_state_account.pubkey();
+            let range_proof_context_state_account = Keypair::new();
+            let range_proof_pubkey = range_proof_context_state_account.pubkey();
+
+            let transfer_context_state_accounts = TransferWithFeeSplitContextStateAccounts {
+                equality_proof: &equality_proof_pubkey,
+                transfer_amount_ciphertext_validity_proof:
+                    &transfer_amount_ciphertext_validity_proof_pubkey,
+                fee_sigma_proof: &fee_sigma_proof_pubkey,
+                fee_ciphertext_validity_proof: &fee_ciphertext_validity_proof_pubkey,
+                range_proof: &range_proof_pubkey,
+                authority: &context_state_authority.pubkey(),
+                no_op_on_uninitialized_split_context_state: false,
+                close_split_context_state_accounts: None,
+            };
+
+            let state = token.get_account_info(&sender).await?;
+            let extension = state.get_extension::<ConfidentialTransferAccount>()?;
+            let transfer_account_info = TransferAccountInfo::new(extension);
+
+            let (
+                equality_proof_data,
+                transfer_amount_ciphertext_validity_proof_data,
+                fee_sigma_proof_data,
+                fee_ciphertext_validity_proof_data,
+                range_proof_data,
+                source_decrypt_handles,
+            ) = transfer_with_fee_split_proof_data(
+                &transfer_account_info
+                    .available_balance
+                    .try_into()
+                    .map_err(|_| "Could not decode the sender's available balance")?,
+                &transfer_account_info
+                    .decryptable_available_balance
+                    .try_into()
+                    .map_err(|_| "Could not decode the sender's decryptable available balance")?,
+                transfer_balance,
+                &args.sender_elgamal_keypair,
+                &args.sender_aes_key,
+                &recipient_elgamal_pubkey,
+                auditor_elgamal_pubkey.as_ref(),
+                &withdraw_withheld_authority_elgamal_pubkey,
+                &FeeParameters {
+                    fee_rate_basis_points: u16::from(transfer_fee.transfer_fee_basis_points),
+                    maximum_fee: u64::from(transfer_fee.maximum_fee),
+                },
+            )?;
+
+            // setup proofs
+            let equality_and_ciphertext_validity_signers = [
+                &equality_proof_context_state_account,
+                &transfer_amount_ciphertext_validity_proof_context_state_account,
+            ];
+            let fee_sigma_and_ciphertext_validity_signers = [
+                &fee_sigma_proof_context_state_account,
+                &fee_ciphertext_validity_proof_context_state_account,
+            ];
+            let range_signers = [&range_proof_context_state_account];
+            let _ = try_join!(
+                token.create_equality_and_ciphertext_validity_proof_context_states_for_transfer_with_fee(
+                    transfer_context_state_accounts,
+                    &equality_proof_data,
+                    &transfer_amount_ciphertext_validity_proof_data,
+                    &equality_and_ciphertext_validity_signers,
+                ),
+                token.create_fee_sigma_and_ciphertext_validity_proof_context_states_for_transfer_with_fee(
+                    transfer_context_state_accounts,
+                    &fee_sigma_proof_data,
+                    &fee_ciphertext_validity_proof_data,
+                    &fee_sigma_and_ciphertext_validity_signers,
+                ),
+                token.create_range_proof_context_state_for_transfer_with_fee(
+                    transfer_context_state_accounts,
+                    &range_proof_data,
+                    &range_signers,
+                )
+            )?;
+
+            // do the transfer
+            let transfer_result = token
+                .confidential_transfer_transfer_with_fee_and_split_proofs(
+                    &sender,
+                    &recipient_token_account,
+                    &sender_owner,
+                    transfer_context_state_accounts,
+                    transfer_balance,
+                    Some(transfer_account_info),
+                    &args.sender_aes_key,
+                    &source_decrypt_handles,
+                    &bulk_signers,
+                )
+                .await?;
+
+            // close context state accounts
+            let context_state_authority_pubkey = context_state_authority.pubkey();
+            let close_context_state_signers = &[context_state_authority];
+            let _ = try_join!(
+                token.confidential_transfer_close_context_state(
+                    &equality_proof_pubkey,
+                    &sender,
+                    &context_state_authority_pubkey,
+                    close_context_state_signers,
+                ),
+                token.confidential_transfer_close_context_state(
+                    &transfer_amount_ciphertext_validity_proof_pubkey,
+                    &sender,
+                    &context_state_authority_pubkey,
+                    close_context_state_signers,
+                ),
+                token.confidential_transfer_close_context_state(
+                    &fee_sigma_proof_pubkey,
+                    &sender,
+                    &context_state_authority_pubkey,
+                    close_context_state_signers,
+                ),
+                token.confidential_transfer_close_context_state(
+                    &fee_ciphertext_validity_proof_pubkey,
+                    &sender,
+                    &context_state_authority_pubkey,
+                    close_context_state_signers,
+                ),
+                token.confidential_transfer_close_context_state(
+                    &range_proof_pubkey,
+                    &sender,
+                    &context_state_authority_pubkey,
+                    close_context_state_signers,
+                ),
+            )?;
+
+            transfer_result
+        }
+        (None, None, None) => {
+            token
+                .transfer(
+                    &sender,
+                    &recipient_token_account,
+                    &sender_owner,
+                    transfer_balance,
+                    &bulk_signers,
+                )
+                .await?
+        }
+    };
+
+    let tx_return = finish_tx(config, &res, no_wait).await?;
+    Ok(match tx_return {
         TransactionReturnData::CliSignature(signature) => {
             config.output_format.formatted_string(&signature)
         }
@@ -2305,18 +2889,58 @@ async fn command_close_mint(
     })
 }
 
+// The RPC node doesn't know about the UI amount multiplier, so the UI amounts
+// have to be rescaled on the client
+async fn apply_ui_multiplier<'a>(
+    config: &Config<'_>,
+    mint_address: &Pubkey,
+    ui_token_amounts: impl IntoIterator<Item = &'a mut UiTokenAmount>,
+) -> Result<(), Error> {
+    let mint_account = config.get_account_checked(mint_address).await?;
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+        .map_err(|_| format!("Could not deserialize token mint {}", mint_address))?;
+    apply_mint_ui_multiplier(config, &mint_state, ui_token_amounts).await
+}
+
+async fn apply_mint_ui_multiplier<'a>(
+    config: &Config<'_>,
+    mint_state: &StateWithExtensionsOwned<Mint>,
+    ui_token_amounts: impl IntoIterator<Item = &'a mut UiTokenAmount>,
+) -> Result<(), Error> {
+    if let Ok(scaled_ui_amount_config) = mint_state.get_extension::<ScaledUiAmountConfig>() {
+        let clock_account = config.get_account_checked(&sysvar::clock::id()).await?;
+        let clock = from_account::<Clock, _>(&clock_account)
+            .ok_or_else(|| "Could not deserialize clock sysvar".to_string())?;
+        for ui_token_amount in ui_token_amounts {
+            let amount = ui_token_amount.amount.parse::<u64>()?;
+            let ui_amount = scaled_ui_amount_config
+                .amount_to_ui_amount(amount, ui_token_amount.decimals, clock.unix_timestamp)
+                .ok_or_else(|| format!("Could not scale amount {amount}"))?;
+            ui_token_amount.ui_amount = ui_amount.parse::<f64>().ok();
+            ui_token_amount.ui_amount_string = ui_amount;
+        }
+    }
+
+    Ok(())
+}
+
 async fn command_balance(config: &Config<'_>, address: Pubkey) -> CommandResult {
-    let balance = config
+    let mut balance = config
         .rpc_client
         .get_token_account_balance(&address)
         .await
         .map_err(|_| format!("Could not find token account {}", address))?;
+    let account = config.get_account_checked(&address).await?;
+    if let Some(mint_address) = get_token_account_mint(&account.data) {
+        apply_ui_multiplier(config, &mint_address, [&mut balance]).await?;
+    }
     let cli_token_amount = CliTokenAmount { amount: balance };
     Ok(config.output_format.formatted_string(&cli_token_amount))
 }
 
 async fn command_supply(config: &Config<'_>, token: Pubkey) -> CommandResult {
-    let supply = config.rpc_client.get_token_supply(&token).await?;
+    let mut supply = config.rpc_client.get_token_supply(&token).await?;
+    apply_ui_multiplier(config, &token, [&mut supply]).await?;
     let cli_token_amount = CliTokenAmount { amount: supply };
     Ok(config.output_format.formatted_string(&cli_token_amount))
 }
@@ -2351,9 +2975,35 @@ async fn command_accounts(
     }
     let accounts = accounts.into_iter().flatten().collect();
 
-    let cli_token_accounts =
+    let mut cli_token_accounts =
         sort_and_parse_token_accounts(&owner, accounts, maybe_token.is_some(), account_filter)?;
 
+    if !print_addresses_only {
+        let token_2022_program_id = spl_token_2022::id().to_string();
+        for accounts in cli_token_accounts.accounts.iter_mut() {
+            let Some(first) = accounts.first() else {
+                continue;
+            };
+            if first.program_id != token_2022_program_id {
+                continue;
+            }
+            let mint_address = Pubkey::from_str(&first.account.mint)?;
+            apply_ui_multiplier(
+                config,
+                &mint_address,
+                accounts.iter_mut().map(|a| &mut a.account.token_amount),
+            )
+            .await?;
+        }
+        cli_token_accounts.max_len_balance = cli_token_accounts
+            .accounts
+            .iter()
+            .flatten()
+            .map(|a| a.account.token_amount.real_number_string_trimmed().len())
+            .max()
+            .unwrap_or_default();
+    }
+
     if print_addresses_only {
         Ok(cli_token_accounts
             .accounts
@@ -2385,6 +3035,43 @@ async fn command_address(
     Ok(config.output_format.formatted_string(&cli_address))
 }
 
+fn has_permanent_delegate(mint_state: &StateWithExtensionsOwned<Mint>) -> bool {
+    if let Ok(permanent_delegate) = mint_state.get_extension::<PermanentDelegate>() {
+        Option::<Pubkey>::from(permanent_delegate.delegate).is_some()
+    } else {
+        false
+    }
+}
+
+async fn account_vesting(
+    config: &Config<'_>,
+    account_data: &[u8],
+    decimals: u8,
+) -> Result<Option<CliVesting>, Error> {
+    let Some(schedule) = StateWithExtensions::<Account>::unpack(account_data)
+        .ok()
+        .and_then(|state| state.get_extension::<VestingSchedule>().ok().copied())
+    else {
+        return Ok(None);
+    };
+    let clock_account = config.get_account_checked(&sysvar::clock::id()).await?;
+    let clock = from_account::<Clock, _>(&clock_account)
+        .ok_or_else(|| "Could not deserialize clock sysvar".to_string())?;
+    let locked_amount = schedule
+        .locked_amount(clock.unix_timestamp)
+        .ok_or("Could not calculate locked amount")?;
+    Ok(Some(CliVesting {
+        total_amount: spl_token_2022::amount_to_ui_amount_string_trimmed(
+            schedule.total_amount.into(),
+            decimals,
+        ),
+        locked_amount: spl_token_2022::amount_to_ui_amount_string_trimmed(locked_amount, decimals),
+        start_timestamp: schedule.start_timestamp.into(),
+        cliff_timestamp: schedule.cliff_timestamp.into(),
+        end_timestamp: schedule.end_timestamp.into(),
+    }))
+}
+
 async fn command_display(config: &Config<'_>, address: Pubkey) -> CommandResult {
     let account_data = config.get_account_checked(&address).await?;
 
@@ -2394,14 +3081,10 @@ async fn command_display(config: &Config<'_>, address: Pubkey) -> CommandResult
             let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
                 .map_err(|_| format!("Could not deserialize token mint {}", mint_address))?;
 
-            let has_permanent_delegate =
-                if let Ok(permanent_delegate) = mint_state.get_extension::<PermanentDelegate>() {
-                    Option::<Pubkey>::from(permanent_delegate.delegate).is_some()
-                } else {
-                    false
-                };
-
-            (Some(mint_state.base.decimals), has_permanent_delegate)
+            (
+                Some(mint_state.base.decimals),
+                has_permanent_delegate(&mint_state),
+            )
         } else {
             (None, false)
         };
@@ -2418,23 +3101,56 @@ async fn command_display(config: &Config<'_>, address: Pubkey) -> CommandResult
                 &config.program_id,
             );
 
+            let vesting =
+                account_vesting(config, &account_data.data, account.token_amount.decimals).await?;
+
             let cli_output = CliTokenAccount {
                 address: address.to_string(),
                 program_id: config.program_id.to_string(),
                 is_associated: associated_address == address,
                 account,
                 has_permanent_delegate,
+                vesting,
             };
 
             Ok(config.output_format.formatted_string(&cli_output))
         }
         Ok(TokenAccountType::Mint(mint)) => {
             let epoch_info = config.rpc_client.get_epoch_info().await?;
+            let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account_data.data).ok();
+            let scaled_ui_amount = mint_state.as_ref().and_then(|mint_state| {
+                mint_state
+                    .get_extension::<ScaledUiAmountConfig>()
+                    .ok()
+                    .map(CliScaledUiAmount::from)
+            });
+            let group_pointer = mint_state.as_ref().and_then(|mint_state| {
+                mint_state
+                    .get_extension::<GroupPointer>()
+                    .ok()
+                    .map(CliGroupPointer::from)
+            });
+            let group = mint_state.as_ref().and_then(|mint_state| {
+                mint_state
+                    .get_extension::<TokenGroup>()
+                    .ok()
+                    .map(CliTokenGroup::from)
+            });
+            let group_member = mint_state.as_ref().and_then(|mint_state| {
+                mint_state
+                    .get_extension::<TokenGroupMember>()
+                    .ok()
+                    .map(CliTokenGroupMember::from)
+            });
             let cli_output = CliMint {
                 address: address.to_string(),
                 epoch: epoch_info.epoch,
                 program_id: config.program_id.to_string(),
                 mint,
+                scaled_ui_amount,
+                group_pointer,
+                group,
+                group_member,
             };
 
             Ok(config.output_format.formatted_string(&cli_output))
@@ -2911,12 +3627,80 @@ async fn command_update_default_account_state(
     })
 }
 
+async fn command_pause(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    authority: Pubkey,
+    bulk_signers: BulkSigners,
+    pause: bool,
+) -> CommandResult {
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(pausable_config) = mint_state.get_extension::<PausableConfig>() {
+            let mint_pause_authority = Option::<Pubkey>::from(pausable_config.authority);
+            if mint_pause_authority != Some(authority) {
+                return Err(format!(
+                    "Mint {} has pause authority {}, but {} was provided",
+                    token_pubkey,
+                    mint_pause_authority
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    authority
+                )
+                .into());
+            }
+
+            if bool::from(pausable_config.paused) == pause {
+                return Err(format!(
+                    "Mint {} is already {}",
+                    token_pubkey,
+                    if pause { "paused" } else { "resumed" }
+                )
+                .into());
+            }
+        } else {
+            return Err(format!("Mint {} is not pausable", token_pubkey).into());
+        }
+    }
+
+    println_display(
+        config,
+        format!(
+            "{} minting, transferring and burning for {}",
+            if pause { "Pausing" } else { "Resuming" },
+            token_pubkey
+        ),
+    );
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = if pause {
+        token.pause(&authority, &bulk_signers).await
+    } else {
+        token.resume(&authority, &bulk_signers).await
+    }?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 async fn command_withdraw_withheld_tokens(
     config: &Config<'_>,
     destination_token_account: Pubkey,
     source_token_accounts: Vec<Pubkey>,
     authority: Pubkey,
     include_mint: bool,
+    confidential_keys: Option<&(ElGamalKeypair, AeKey)>,
     bulk_signers: BulkSigners,
 ) -> CommandResult {
     if config.sign_only {
@@ -2938,6 +3722,22 @@ async fn command_withdraw_withheld_tokens(
         .map_err(|_| format!("Token mint {} has no transfer fee configured", token_pubkey))?;
 
     let token = token_client_from_config(config, &token_pubkey, None)?;
+    if let Some((authority_elgamal_keypair, destination_aes_key)) = confidential_keys {
+        return withdraw_withheld_confidential_tokens(
+            config,
+            &token,
+            destination_token_account,
+            &destination_state,
+            &source_token_accounts,
+            authority,
+            include_mint,
+            authority_elgamal_keypair,
+            destination_aes_key,
+            &bulk_signers,
+        )
+        .await;
+    }
+
     let mut results = vec![];
     if include_mint {
         let res = token
@@ -2984,25 +3784,168 @@ async fn command_withdraw_withheld_tokens(
     Ok(results.join(""))
 }
 
-async fn command_update_confidential_transfer_settings(
+fn decrypt_withheld_amount(
+    withheld_amount: ElGamalCiphertext,
+    withdraw_withheld_authority_elgamal_keypair: &ElGamalKeypair,
+) -> Result<u64, Error> {
+    withheld_amount
+        .decrypt_u32(withdraw_withheld_authority_elgamal_keypair.secret())
+        .ok_or_else(|| {
+            "Could not decrypt the withheld amount with the authority's ElGamal key".into()
+        })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn withdraw_withheld_confidential_tokens(
     config: &Config<'_>,
-    token_pubkey: Pubkey,
+    token: &Token<ProgramRpcClientSendTransaction>,
+    destination_token_account: Pubkey,
+    destination_state: &StateWithExtensionsOwned<Account>,
+    source_token_accounts: &[Pubkey],
     authority: Pubkey,
-    auto_approve: Option<bool>,
-    auditor_pubkey: Option<ElGamalPubkeyOrNone>,
-    bulk_signers: Vec<Arc<dyn Signer>>,
+    include_mint: bool,
+    authority_elgamal_keypair: &ElGamalKeypair,
+    destination_aes_key: &AeKey,
+    bulk_signers: &BulkSigners,
 ) -> CommandResult {
-    let (new_auto_approve, new_auditor_pubkey) = if !config.sign_only {
-        let confidential_transfer_account = config.get_account_checked(&token_pubkey).await?;
+    let destination_extension = destination_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| {
+            format!(
+                "Token account {} is not configured for confidential transfers",
+                destination_token_account
+            )
+        })?;
+    let destination_elgamal_pubkey: elgamal::ElGamalPubkey = destination_extension
+        .elgamal_pubkey
+        .try_into()
+        .map_err(|_| "Invalid destination ElGamal pubkey")?;
+
+    // withheld fees are credited straight to the available balance, so its
+    // decryptable counterpart is re-encrypted with the running total
+    let mut available_balance = ApplyPendingBalanceAccountInfo::new(destination_extension)
+        .decrypted_available_balance(destination_aes_key)
+        .map_err(|_| {
+            "Could not decrypt the destination's available balance with the owner's AES key"
+        })?;
 
-        let mint_state =
-            StateWithExtensionsOwned::<Mint>::unpack(confidential_transfer_account.data)
-                .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+    let mut results = vec![];
+    if include_mint {
+        let mint_state = token.get_mint_info().await?;
+        let withheld_amount = mint_state
+            .get_extension::<ConfidentialTransferFeeConfig>()
+            .map_err(|_| {
+                format!(
+                    "Mint {} does not support confidential transfer fees",
+                    token.get_address()
+                )
+            })?
+            .withheld_amount
+            .try_into()
+            .map_err(|_| "Invalid withheld amount in mint")?;
+        available_balance = available_balance
+            .checked_add(decrypt_withheld_amount(
+                withheld_amount,
+                authority_elgamal_keypair,
+            )?)
+            .ok_or("Available balance overflow")?;
 
-        if let Ok(confidential_transfer_mint) =
-            mint_state.get_extension::<ConfidentialTransferMint>()
-        {
-            let expected_authority = Option::<Pubkey>::from(confidential_transfer_mint.authority);
+        let res = token
+            .confidential_transfer_withdraw_withheld_tokens_from_mint(
+                &destination_token_account,
+                &authority,
+                None,
+                Some(WithheldTokensInfo::new(&withheld_amount.into())),
+                authority_elgamal_keypair,
+                &destination_elgamal_pubkey,
+                &destination_aes_key.encrypt(available_balance).into(),
+                bulk_signers,
+            )
+            .await;
+        let tx_return = finish_tx(config, &res?, false).await?;
+        results.push(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
+    let source_refs = source_token_accounts.iter().collect::<Vec<_>>();
+    // the equality proof shares the transaction with the sources, so fewer fit
+    const MAX_CONFIDENTIAL_WITHDRAWAL_ACCOUNTS: usize = 10;
+    for sources in source_refs.chunks(MAX_CONFIDENTIAL_WITHDRAWAL_ACCOUNTS) {
+        let mut withheld_amount = ElGamalCiphertext::default();
+        for source in sources {
+            let source_state = token.get_account_info(source).await?;
+            let source_withheld_amount: ElGamalCiphertext = source_state
+                .get_extension::<ConfidentialTransferFeeAmount>()
+                .map_err(|_| {
+                    format!(
+                        "Token account {} does not hold confidential transfer fees",
+                        source
+                    )
+                })?
+                .withheld_amount
+                .try_into()
+                .map_err(|_| format!("Invalid withheld amount in token account {}", source))?;
+            withheld_amount = withheld_amount + source_withheld_amount;
+        }
+        available_balance = available_balance
+            .checked_add(decrypt_withheld_amount(
+                withheld_amount,
+                authority_elgamal_keypair,
+            )?)
+            .ok_or("Available balance overflow")?;
+
+        let res = token
+            .confidential_transfer_withdraw_withheld_tokens_from_accounts(
+                &destination_token_account,
+                &authority,
+                None,
+                Some(WithheldTokensInfo::new(&withheld_amount.into())),
+                authority_elgamal_keypair,
+                &destination_elgamal_pubkey,
+                &destination_aes_key.encrypt(available_balance).into(),
+                sources,
+                bulk_signers,
+            )
+            .await;
+        let tx_return = finish_tx(config, &res?, false).await?;
+        results.push(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
+    Ok(results.join(""))
+}
+
+async fn command_update_confidential_transfer_settings(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    authority: Pubkey,
+    auto_approve: Option<bool>,
+    auditor_pubkey: Option<ElGamalPubkeyOrNone>,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let (new_auto_approve, new_auditor_pubkey) = if !config.sign_only {
+        let confidential_transfer_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state =
+            StateWithExtensionsOwned::<Mint>::unpack(confidential_transfer_account.data)
+                .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(confidential_transfer_mint) =
+            mint_state.get_extension::<ConfidentialTransferMint>()
+        {
+            let expected_authority = Option::<Pubkey>::from(confidential_transfer_mint.authority);
 
             if expected_authority != Some(authority) {
                 return Err(format!(
@@ -3095,6 +4038,95 @@ async fn command_update_confidential_transfer_settings(
     })
 }
 
+async fn command_rotate_confidential_transfer_auditor(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    authority: Pubkey,
+    previous_auditor_elgamal_keypair: &ElGamalKeypair,
+    new_auditor_elgamal_keypair: &ElGamalKeypair,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    if !config.sign_only {
+        let confidential_transfer_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state =
+            StateWithExtensionsOwned::<Mint>::unpack(confidential_transfer_account.data)
+                .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(confidential_transfer_mint) =
+            mint_state.get_extension::<ConfidentialTransferMint>()
+        {
+            let expected_authority = Option::<Pubkey>::from(confidential_transfer_mint.authority);
+
+            if expected_authority != Some(authority) {
+                return Err(format!(
+                    "Mint {} has confidential transfer authority {}, but {} was provided",
+                    token_pubkey,
+                    expected_authority
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    authority
+                )
+                .into());
+            }
+
+            let expected_auditor_pubkey: Option<ElGamalPubkey> =
+                confidential_transfer_mint.auditor_elgamal_pubkey.into();
+            let previous_auditor_pubkey: ElGamalPubkey =
+                (*previous_auditor_elgamal_keypair.pubkey()).into();
+
+            if expected_auditor_pubkey != Some(previous_auditor_pubkey) {
+                return Err(format!(
+                    "Mint {} has confidential transfer auditor {}, but {} was provided",
+                    token_pubkey,
+                    expected_auditor_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    previous_auditor_pubkey
+                )
+                .into());
+            }
+        } else {
+            return Err(format!(
+                "Mint {} does not support confidential transfers",
+                token_pubkey
+            )
+            .into());
+        }
+    }
+
+    let new_auditor_pubkey: ElGamalPubkey = (*new_auditor_elgamal_keypair.pubkey()).into();
+    println_display(
+        config,
+        format!(
+            "Rotating confidential transfer auditor for {}:\n  auditor encryption pubkey set to {}",
+            token_pubkey, new_auditor_pubkey,
+        ),
+    );
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &authority,
+            None,
+            None,
+            previous_auditor_elgamal_keypair,
+            new_auditor_elgamal_keypair,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn command_configure_confidential_transfer_account(
     config: &Config<'_>,
@@ -3128,7 +4160,12 @@ async fn command_configure_confidential_transfer_account(
     // Reallocation (if needed)
     let mut existing_extensions: Vec<ExtensionType> = state_with_extension.get_extension_types()?;
     if !existing_extensions.contains(&ExtensionType::ConfidentialTransferAccount) {
-        existing_extensions.push(ExtensionType::ConfidentialTransferAccount);
+        let mut new_extensions = vec![ExtensionType::ConfidentialTransferAccount];
+        // fees withheld from confidential transfers are kept encrypted in their own extension
+        if existing_extensions.contains(&ExtensionType::TransferFeeAmount) {
+            new_extensions.push(ExtensionType::ConfidentialTransferFeeAmount);
+        }
+        existing_extensions.extend_from_slice(&new_extensions);
         let needed_account_len =
             ExtensionType::try_calculate_account_len::<Account>(&existing_extensions)?;
         if needed_account_len > current_account_len {
@@ -3136,7 +4173,7 @@ async fn command_configure_confidential_transfer_account(
                 .reallocate(
                     &token_account_address,
                     &owner,
-                    &[ExtensionType::ConfidentialTransferAccount],
+                    &new_extensions,
                     &bulk_signers,
                 )
                 .await?;
@@ -3520,6 +4557,66 @@ async fn command_apply_pending_balance(
     })
 }
 
+async fn command_confidential_balance(
+    config: &Config<'_>,
+    maybe_token: Option<Pubkey>,
+    owner: Pubkey,
+    maybe_account: Option<Pubkey>,
+    elgamal_keypair: &ElGamalKeypair,
+    aes_key: &AeKey,
+) -> CommandResult {
+    // derive ATA if account address not provided
+    let token_account_address = if let Some(account) = maybe_account {
+        account
+    } else {
+        let token_pubkey =
+            maybe_token.expect("Either a valid token or account address must be provided");
+        let token = token_client_from_config(config, &token_pubkey, None)?;
+        token.get_associated_token_address(&owner)
+    };
+
+    let account = config.get_account_checked(&token_account_address).await?;
+
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+    let mint_info = config
+        .get_mint_info(&state_with_extension.base.mint, None)
+        .await?;
+
+    let extension_state = state_with_extension
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| {
+            format!(
+                "Token account {} is not configured for confidential transfers",
+                token_account_address
+            )
+        })?;
+    let account_info = ApplyPendingBalanceAccountInfo::new(extension_state);
+
+    let pending_balance = account_info
+        .decrypted_pending_balance(elgamal_keypair.secret())
+        .map_err(|_| "Could not decrypt the pending balance with the owner's ElGamal key")?;
+    let available_balance = account_info
+        .decrypted_available_balance(aes_key)
+        .map_err(|_| "Could not decrypt the available balance with the owner's AES key")?;
+
+    let cli_confidential_balance = CliConfidentialBalance {
+        address: token_account_address.to_string(),
+        mint: mint_info.address.to_string(),
+        pending_balance: spl_token_2022::amount_to_ui_amount_string_trimmed(
+            pending_balance,
+            mint_info.decimals,
+        ),
+        available_balance: spl_token_2022::amount_to_ui_amount_string_trimmed(
+            available_balance,
+            mint_info.decimals,
+        ),
+        pending_balance_credit_counter: account_info.pending_balance_credit_counter(),
+    };
+    Ok(config
+        .output_format
+        .formatted_string(&cli_confidential_balance))
+}
+
 struct SignOnlyNeedsFullMintSpec {}
 impl offline::ArgsConfig for SignOnlyNeedsFullMintSpec {
     fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
@@ -3579,15 +4676,17 @@ struct ConfidentialTransferArgs {
 
 fn minimum_signers_help_string() -> String {
     format!(
-        "The minimum number of signers required to allow the operation. [{} <= M <= N]",
+        "The minimum number of signers required to allow the operation. [{} <= M <= N] \
+         For a weighted multisig, the total weight of signers required instead.",
         MIN_SIGNERS
     )
 }
 
 fn multisig_member_help_string() -> String {
     format!(
-        "The public keys for each of the N signing members of this account. [{} <= N <= {}]",
-        MIN_SIGNERS, MAX_SIGNERS
+        "The public keys for each of the N signing members of this account. [{} <= N <= {}] \
+         More than {} members creates a weighted multisig.",
+        MIN_SIGNERS, MAX_WEIGHTED_SIGNERS, MAX_SIGNERS
     )
 }
 
@@ -3650,7 +4749,44 @@ fn app<'a, 'b>(
                     Default from the configuration file."
                 ),
         )
+        .arg(
+            Arg::with_name("websocket_url")
+                .long("ws")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url)
+                .help(
+                    "WebSocket URL for Solana's pubsub service. \
+                    Default from the configuration file, or derived from the JSON RPC URL."
+                ),
+        )
         .arg(fee_payer_arg().global(true))
+        .arg(
+            compute_unit_price_arg()
+                .validator(is_parsable::<u64>)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name(COMPUTE_UNIT_LIMIT_ARG.name)
+                .long(COMPUTE_UNIT_LIMIT_ARG.long)
+                .value_name("COMPUTE-UNIT-LIMIT")
+                .takes_value(true)
+                .global(true)
+                .validator(is_parsable::<u32>)
+                .help(COMPUTE_UNIT_LIMIT_ARG.help),
+        )
+        .arg(
+            Arg::with_name(LOOKUP_TABLE_ARG.name)
+                .long(LOOKUP_TABLE_ARG.long)
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .global(true)
+                .validator(is_valid_pubkey)
+                .help(LOOKUP_TABLE_ARG.help),
+        )
         .arg(
             Arg::with_name("use_unchecked_instruction")
                 .long("use-unchecked-instruction")
@@ -3720,6 +4856,18 @@ fn app<'a, 'b>(
                             Rate authority defaults to the mint authority."
                         ),
                 )
+                .arg(
+                    Arg::with_name("ui_multiplier")
+                        .long("ui-multiplier")
+                        .value_name("MULTIPLIER")
+                        .validator(is_parsable::<f64>)
+                        .takes_value(true)
+                        .conflicts_with("interest_rate")
+                        .help(
+                            "Specify the multiplier applied to amounts when displayed \
+                            in the UI. Multiplier authority defaults to the mint authority."
+                        ),
+                )
                 .arg(
                     Arg::with_name("metadata_address")
                         .long("metadata-address")
@@ -3730,6 +4878,16 @@ fn app<'a, 'b>(
                             "Specify address that stores token metadata."
                         ),
                 )
+                .arg(
+                    Arg::with_name("group_address")
+                        .long("group-address")
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .conflicts_with("enable_group")
+                        .help(
+                            "Specify address that stores token group configurations."
+                        ),
+                )
                 .arg(
                     Arg::with_name("enable_non_transferable")
                         .long("enable-non-transferable")
@@ -3792,6 +4950,15 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .help("Enable the mint authority to set the transfer hook program for this mint"),
                 )
+                .arg(
+                    Arg::with_name("enable_pause")
+                        .long("enable-pause")
+                        .takes_value(false)
+                        .help(
+                            "Enable the mint authority to pause and resume minting, \
+                            transferring and burning for this mint"
+                        ),
+                )
                 .arg(
                     Arg::with_name("enable_metadata")
                         .long("enable-metadata")
@@ -3799,6 +4966,13 @@ fn app<'a, 'b>(
                         .takes_value(false)
                         .help("Enables metadata in the mint. The mint authority must initialize the metadata."),
                 )
+                .arg(
+                    Arg::with_name("enable_group")
+                        .long("enable-group")
+                        .conflicts_with("group_address")
+                        .takes_value(false)
+                        .help("Enables group configurations in the mint. The mint authority must initialize the group."),
+                )
                 .nonce_args(true)
                 .arg(memo_arg())
         )
@@ -3832,6 +5006,48 @@ fn app<'a, 'b>(
                     )
                 )
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::SetUiMultiplier.into())
+                .about("Set the UI amount multiplier for a token")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The token address with a UI amount multiplier"),
+                )
+                .arg(
+                    Arg::with_name("multiplier")
+                        .value_name("MULTIPLIER")
+                        .validator(is_parsable::<f64>)
+                        .takes_value(true)
+                        .required(true)
+                        .help("The new UI amount multiplier"),
+                )
+                .arg(
+                    Arg::with_name("timestamp")
+                        .long("timestamp")
+                        .value_name("UNIX_TIMESTAMP")
+                        .validator(is_parsable::<i64>)
+                        .takes_value(true)
+                        .help(
+                            "Unix timestamp at which the new multiplier takes effect. \
+                            Defaults to taking effect immediately."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("multiplier_authority")
+                    .long("multiplier-authority")
+                    .validator(is_valid_signer)
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the multiplier authority keypair. \
+                        Defaults to the client keypair address."
+                    )
+                )
+        )
         .subcommand(
             SubCommand::with_name(CommandName::SetTransferHookProgram.into())
                 .about("Set the transfer hook program id for a token")
@@ -3979,51 +5195,178 @@ fn app<'a, 'b>(
                 .offline_args_config(&SignOnlyNeedsTransferLamports{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::CreateAccount.into())
-                .about("Create a new token account")
+            SubCommand::with_name(CommandName::InitializeGroup.into())
+                .about("Initialize group extension on a token mint")
                 .arg(
                     Arg::with_name("token")
                         .validator(is_valid_pubkey)
                         .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
+                        .required(true)
                         .index(1)
+                        .help("The token address of the group account."),
+                )
+                .arg(
+                    Arg::with_name("max_size")
+                        .value_name("MAX_SIZE")
+                        .validator(is_parsable::<u32>)
+                        .takes_value(true)
                         .required(true)
-                        .help("The token that the account will hold"),
+                        .index(2)
+                        .help("The number of members in the group."),
                 )
                 .arg(
-                    Arg::with_name("account_keypair")
-                        .value_name("ACCOUNT_KEYPAIR")
+                    Arg::with_name("mint_authority")
+                        .long("mint-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
-                        .index(2)
                         .help(
-                            "Specify the account keypair. \
+                            "Specify the mint authority keypair. \
                              This may be a keypair file or the ASK keyword. \
-                             [default: associated token account for --owner]"
+                             Defaults to the client keypair."
                         ),
                 )
                 .arg(
-                    Arg::with_name("immutable")
-                        .long("immutable")
-                        .takes_value(false)
+                    Arg::with_name("update_authority")
+                        .long("update-authority")
+                        .value_name("ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
                         .help(
-                            "Lock the owner of this token account from ever being changed"
+                            "Specify the update authority address. \
+                             Defaults to the client keypair address."
                         ),
                 )
-                .arg(owner_address_arg())
-                .nonce_args(true)
         )
         .subcommand(
-            SubCommand::with_name(CommandName::CreateMultisig.into())
-                .about("Create a new account describing an M:N multisignature")
+            SubCommand::with_name(CommandName::UpdateGroupMaxSize.into())
+                .about("Updates the maximum number of members for a group.")
                 .arg(
-                    Arg::with_name("minimum_signers")
-                        .value_name("MINIMUM_SIGNERS")
-                        .validator(is_multisig_minimum_signers)
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
-                        .index(1)
                         .required(true)
-                        .help(minimum_signers_help),
+                        .index(1)
+                        .help("The token address of the group account."),
+                )
+                .arg(
+                    Arg::with_name("new_max_size")
+                        .value_name("NEW_MAX_SIZE")
+                        .validator(is_parsable::<u32>)
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("The number of members in the group."),
+                )
+                .arg(
+                    Arg::with_name("update_authority")
+                        .long("update-authority")
+                        .value_name("SIGNER")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the update authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::InitializeMember.into())
+                .about("Initialize group member extension on a token mint")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("The token address of the member account."),
+                )
+                .arg(
+                    Arg::with_name("group_token")
+                        .validator(is_valid_pubkey)
+                        .value_name("GROUP_TOKEN_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("The token address of the group account that the member belongs to."),
+                )
+                .arg(
+                    Arg::with_name("mint_authority")
+                        .long("mint-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the mint authority keypair of the member. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("group_update_authority")
+                        .long("group-update-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the update authority keypair of the group. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::CreateAccount.into())
+                .about("Create a new token account")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token that the account will hold"),
+                )
+                .arg(
+                    Arg::with_name("account_keypair")
+                        .value_name("ACCOUNT_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .index(2)
+                        .help(
+                            "Specify the account keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             [default: associated token account for --owner]"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("immutable")
+                        .long("immutable")
+                        .takes_value(false)
+                        .help(
+                            "Lock the owner of this token account from ever being changed"
+                        ),
+                )
+                .arg(owner_address_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::CreateMultisig.into())
+                .about("Create a new account describing an M:N multisignature")
+                .arg(
+                    Arg::with_name("minimum_signers")
+                        .value_name("MINIMUM_SIGNERS")
+                        .validator(is_multisig_minimum_signers)
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help(minimum_signers_help),
                 )
                 .arg(
                     Arg::with_name("multisig_member")
@@ -4033,9 +5376,35 @@ fn app<'a, 'b>(
                         .index(2)
                         .required(true)
                         .min_values(MIN_SIGNERS as u64)
-                        .max_values(MAX_SIGNERS as u64)
+                        .max_values(MAX_WEIGHTED_SIGNERS as u64)
                         .help(multisig_member_help),
                 )
+                .arg(
+                    Arg::with_name("weights")
+                        .long("weights")
+                        .value_name("WEIGHT")
+                        .validator(is_parsable::<u8>)
+                        .takes_value(true)
+                        .multiple(true)
+                        .use_delimiter(true)
+                        .help(
+                            "Create a weighted multisig, giving each member the weight \
+                             at the same position, e.g. `--weights 2,1,1`. \
+                             [default: 1 for each member]"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("min_delay")
+                        .long("min-delay")
+                        .value_name("SECONDS")
+                        .validator(is_parsable::<i64>)
+                        .takes_value(true)
+                        .help(
+                            "Create a weighted multisig that can only mint or change \
+                             authorities through proposals, executed at least this many \
+                             seconds after they are approved."
+                        ),
+                )
                 .arg(
                     Arg::with_name("address_keypair")
                         .long("address-keypair")
@@ -4242,6 +5611,8 @@ fn app<'a, 'b>(
                 .arg(memo_arg())
                 .offline_args_config(&SignOnlyNeedsMintDecimals{}),
         )
+        .distribute_subcommand()
+        .migrate_mint_subcommand()
         .subcommand(
             SubCommand::with_name(CommandName::Burn.into())
                 .about("Burn tokens from an account")
@@ -4914,6 +6285,62 @@ fn app<'a, 'b>(
                 .nonce_args(true)
                 .offline_args(),
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::Pause.into())
+                .about("Pause minting, transferring and burning for the mint. Requires the pausable extension.")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token mint to pause"),
+                )
+                .arg(
+                    Arg::with_name("pause_authority")
+                        .long("pause-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the token's pause authority. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
+                        ),
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Resume.into())
+                .about("Resume minting, transferring and burning for the mint. Requires the pausable extension.")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token mint to resume"),
+                )
+                .arg(
+                    Arg::with_name("pause_authority")
+                        .long("pause-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the token's pause authority. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
+                        ),
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args(),
+        )
         .subcommand(
             SubCommand::with_name(CommandName::UpdateMetadataAddress.into())
                 .about("Updates metadata pointer address for the mint. Requires the metadata pointer extension.")
@@ -4997,6 +6424,27 @@ fn app<'a, 'b>(
                              Defaults to the client keypair."
                         ),
                 )
+                .arg(
+                    Arg::with_name("confidential")
+                        .long("confidential")
+                        .takes_value(false)
+                        .help("Withdraw the confidentially withheld fees into the \
+                            destination's confidential available balance"),
+                )
+                .arg(
+                    Arg::with_name("destination_owner")
+                        .long("destination-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .requires("confidential")
+                        .help(
+                            "Specify the owner keypair of the destination account, used to \
+                             encrypt its new available balance. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
                 .arg(owner_address_arg())
                 .arg(multisig_signer_arg())
         )
@@ -5121,6 +6569,57 @@ fn app<'a, 'b>(
                 .nonce_args(true)
                 .offline_args(),
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::RotateConfidentialTransferAuditor.into())
+                .about("Rotate the confidential transfer auditor of a token")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token mint to rotate the auditor for")
+                )
+                .arg(
+                    Arg::with_name("previous_auditor_keypair")
+                        .value_name("PREVIOUS_AUDITOR_KEYPAIR")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help(
+                            "Path to the ElGamal keypair JSON file of the current auditor of the \
+                            mint. The keypair is used to hand the auditor role over to the new \
+                            auditor."
+                        )
+                )
+                .arg(
+                    Arg::with_name("new_auditor_keypair")
+                        .value_name("NEW_AUDITOR_KEYPAIR")
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help(
+                            "Path to the ElGamal keypair JSON file of the new auditor. The keypair \
+                            is used to prove that the new auditor can decrypt future confidential \
+                            transfers. Ciphertexts under the previous auditor public key must be \
+                            re-encrypted to the new auditor off-chain."
+                        )
+                )
+                .arg(
+                    Arg::with_name("confidential_transfer_authority")
+                        .long("confidential-transfer-authority")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
+                        .takes_value(true)
+                        .help(
+                            "Specify the confidential transfer authority keypair. \
+                            Defaults to the client keypair address."
+                        )
+                )
+                .nonce_args(true)
+                .offline_args(),
+        )
         .subcommand(
             SubCommand::with_name(CommandName::ConfigureConfidentialTransferAccount.into())
                 .about("Configure confidential transfers for token account")
@@ -5383,6 +6882,34 @@ fn app<'a, 'b>(
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::ConfidentialBalance.into())
+                .about("Display the decrypted pending and available confidential balances of an account")
+                .arg(
+                    Arg::with_name("token")
+                        .long("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required_unless("address")
+                        .help("The token address with confidential transfers enabled"),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .help("The address of the token account to display the balances of \
+                            [default: owner's associated token account]")
+                )
+                .arg(
+                    owner_address_arg()
+                )
+        )
+        .proposal_subcommands()
+        .watch_subcommand()
 }
 
 #[tokio::main]
@@ -5443,7 +6970,9 @@ async fn process_command<'a>(
                 config.pubkey_or_default(arg_matches, "mint_authority", &mut wallet_manager)?;
             let memo = value_t!(arg_matches, "memo", String).ok();
             let rate_bps = value_t!(arg_matches, "interest_rate", i16).ok();
+            let ui_multiplier = value_t!(arg_matches, "ui_multiplier", f64).ok();
             let metadata_address = value_t!(arg_matches, "metadata_address", Pubkey).ok();
+            let group_address = value_t!(arg_matches, "group_address", Pubkey).ok();
 
             let transfer_fee = arg_matches.values_of("transfer_fee").map(|mut v| {
                 (
@@ -5477,6 +7006,26 @@ async fn process_command<'a>(
                 .value_of("enable_confidential_transfers")
                 .map(|b| b == "auto");
 
+            // Confidential transfer fees are withheld encrypted under the withdraw withheld
+            // authority's ElGamal pubkey, which is derived from its signer like the other
+            // confidential transfer keys.
+            let confidential_transfer_fee_elgamal_pubkey =
+                if transfer_fee.is_some() && confidential_transfer_auto_approve.is_some() {
+                    let authority_signer = config.default_signer()?;
+                    if authority_signer.pubkey() != mint_authority {
+                        return Err(
+                            "Error: Transfer fees with confidential transfers require the \
+                                    mint authority to be the default signer"
+                                .into(),
+                        );
+                    }
+                    let elgamal_keypair = ElGamalKeypair::new_from_signer(&*authority_signer, b"")
+                        .map_err(|err| format!("Could not derive ElGamal keypair: {}", err))?;
+                    Some((*elgamal_keypair.pubkey()).into())
+                } else {
+                    None
+                };
+
             command_create_token(
                 config,
                 decimals,
@@ -5486,14 +7035,19 @@ async fn process_command<'a>(
                 arg_matches.is_present("enable_close"),
                 arg_matches.is_present("enable_non_transferable"),
                 arg_matches.is_present("enable_permanent_delegate"),
+                arg_matches.is_present("enable_pause"),
                 memo,
                 metadata_address,
+                group_address,
                 rate_bps,
+                ui_multiplier,
                 default_account_state,
                 transfer_fee,
                 confidential_transfer_auto_approve,
+                confidential_transfer_fee_elgamal_pubkey,
                 transfer_hook_program_id,
                 arg_matches.is_present("enable_metadata"),
+                arg_matches.is_present("enable_group"),
                 bulk_signers,
             )
             .await
@@ -5516,6 +7070,26 @@ async fn process_command<'a>(
             )
             .await
         }
+        (CommandName::SetUiMultiplier, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let multiplier = value_t_or_exit!(arg_matches, "multiplier", f64);
+            let effective_timestamp = value_t!(arg_matches, "timestamp", i64).ok();
+            let (multiplier_authority_signer, multiplier_authority_pubkey) =
+                config.signer_or_default(arg_matches, "multiplier_authority", &mut wallet_manager);
+            let bulk_signers = vec![multiplier_authority_signer];
+
+            command_set_ui_multiplier(
+                config,
+                token_pubkey,
+                multiplier_authority_pubkey,
+                multiplier,
+                effective_timestamp,
+                bulk_signers,
+            )
+            .await
+        }
         (CommandName::SetTransferHookProgram, arg_matches) => {
             let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
@@ -5588,14 +7162,81 @@ async fn process_command<'a>(
             )
             .await
         }
-        (CommandName::CreateAccount, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+        (CommandName::InitializeGroup, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-
-            // No need to add a signer when creating an associated token account
-            let account = get_signer(arg_matches, "account_keypair", &mut wallet_manager).map(
-                |(signer, account)| {
+            let max_size = value_t_or_exit!(arg_matches, "max_size", u32);
+            let (mint_authority_signer, mint_authority) =
+                config.signer_or_default(arg_matches, "mint_authority", &mut wallet_manager);
+            let bulk_signers = vec![mint_authority_signer];
+            let update_authority =
+                config.pubkey_or_default(arg_matches, "update_authority", &mut wallet_manager)?;
+
+            command_initialize_group(
+                config,
+                token_pubkey,
+                mint_authority,
+                update_authority,
+                max_size,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::UpdateGroupMaxSize, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let new_max_size = value_t_or_exit!(arg_matches, "new_max_size", u32);
+            let (update_authority_signer, update_authority) =
+                config.signer_or_default(arg_matches, "update_authority", &mut wallet_manager);
+            let bulk_signers = vec![update_authority_signer];
+
+            command_update_group_max_size(
+                config,
+                token_pubkey,
+                update_authority,
+                new_max_size,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::InitializeMember, arg_matches) => {
+            let member_token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let group_token_pubkey =
+                pubkey_of_signer(arg_matches, "group_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (mint_authority_signer, mint_authority) =
+                config.signer_or_default(arg_matches, "mint_authority", &mut wallet_manager);
+            let (group_update_authority_signer, group_update_authority) = config.signer_or_default(
+                arg_matches,
+                "group_update_authority",
+                &mut wallet_manager,
+            );
+            let mut bulk_signers = vec![mint_authority_signer];
+            push_signer_with_dedup(group_update_authority_signer, &mut bulk_signers);
+
+            command_initialize_member(
+                config,
+                member_token_pubkey,
+                mint_authority,
+                group_token_pubkey,
+                group_update_authority,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::CreateAccount, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+
+            // No need to add a signer when creating an associated token account
+            let account = get_signer(arg_matches, "account_keypair", &mut wallet_manager).map(
+                |(signer, account)| {
                     push_signer_with_dedup(signer, &mut bulk_signers);
                     account
                 },
@@ -5613,23 +7254,58 @@ async fn process_command<'a>(
             .await
         }
         (CommandName::CreateMultisig, arg_matches) => {
-            let minimum_signers = value_of::<u8>(arg_matches, "minimum_signers").unwrap();
+            let minimum_signers = value_of::<u16>(arg_matches, "minimum_signers").unwrap();
             let multisig_members =
                 pubkeys_of_multiple_signers(arg_matches, "multisig_member", &mut wallet_manager)
                     .unwrap_or_else(print_error_and_exit)
                     .unwrap();
-            if minimum_signers as usize > multisig_members.len() {
-                eprintln!(
-                    "error: MINIMUM_SIGNERS cannot be greater than the number \
-                          of MULTISIG_MEMBERs passed"
-                );
-                exit(1);
-            }
+            let weights = values_of::<u8>(arg_matches, "weights");
+            let min_delay = value_of::<i64>(arg_matches, "min_delay");
 
             let (signer, _) = get_signer(arg_matches, "address_keypair", &mut wallet_manager)
                 .unwrap_or_else(new_throwaway_signer);
 
-            command_create_multisig(config, signer, minimum_signers, multisig_members).await
+            if weights.is_some() || min_delay.is_some() || multisig_members.len() > MAX_SIGNERS {
+                let weights = weights.unwrap_or_else(|| vec![1; multisig_members.len()]);
+                if weights.len() != multisig_members.len() {
+                    eprintln!("error: one WEIGHT must be passed for each MULTISIG_MEMBER");
+                    exit(1);
+                }
+                let total_weight = weights.iter().map(|weight| *weight as u16).sum::<u16>();
+                if minimum_signers > total_weight {
+                    eprintln!(
+                        "error: MINIMUM_SIGNERS cannot be greater than the total weight \
+                              of the MULTISIG_MEMBERs passed"
+                    );
+                    exit(1);
+                }
+                let min_delay = min_delay.unwrap_or_default();
+                if min_delay < 0 {
+                    eprintln!("error: --min-delay cannot be negative");
+                    exit(1);
+                }
+
+                command_create_weighted_multisig(
+                    config,
+                    signer,
+                    minimum_signers,
+                    multisig_members,
+                    weights,
+                    min_delay,
+                )
+                .await
+            } else {
+                if minimum_signers as usize > multisig_members.len() {
+                    eprintln!(
+                        "error: MINIMUM_SIGNERS cannot be greater than the number \
+                              of MULTISIG_MEMBERs passed"
+                    );
+                    exit(1);
+                }
+
+                command_create_multisig(config, signer, minimum_signers as u8, multisig_members)
+                    .await
+            }
         }
         (CommandName::Authorize, arg_matches) => {
             let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
@@ -5742,6 +7418,28 @@ async fn process_command<'a>(
             )
             .await
         }
+        (CommandName::Distribute, arg_matches) => {
+            distribute_process_command(
+                arg_matches,
+                config,
+                std::mem::take(&mut bulk_signers),
+                &mut wallet_manager,
+            )
+            .await
+        }
+        (CommandName::MigrateMint, arg_matches) => {
+            migrate_mint_process_command(arg_matches, config, &mut wallet_manager).await
+        }
+        (CommandName::Propose, arg_matches) => {
+            propose_process_command(arg_matches, config, &mut wallet_manager).await
+        }
+        (CommandName::Sign, arg_matches) => {
+            sign_process_command(arg_matches, config, &mut wallet_manager).await
+        }
+        (CommandName::Submit, arg_matches) => submit_process_command(arg_matches, config).await,
+        (CommandName::Watch, arg_matches) => {
+            watch_process_command(arg_matches, config, &mut wallet_manager).await
+        }
         (CommandName::Burn, arg_matches) => {
             let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
                 .unwrap()
@@ -6137,6 +7835,30 @@ async fn process_command<'a>(
             )
             .await
         }
+        (CommandName::Pause, arg_matches) => {
+            // Since account is required argument it will always be present
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (pause_authority_signer, pause_authority) =
+                config.signer_or_default(arg_matches, "pause_authority", &mut wallet_manager);
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(pause_authority_signer, &mut bulk_signers);
+            }
+            command_pause(config, token, pause_authority, bulk_signers, true).await
+        }
+        (CommandName::Resume, arg_matches) => {
+            // Since account is required argument it will always be present
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (pause_authority_signer, pause_authority) =
+                config.signer_or_default(arg_matches, "pause_authority", &mut wallet_manager);
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(pause_authority_signer, &mut bulk_signers);
+            }
+            command_pause(config, token, pause_authority, bulk_signers, false).await
+        }
         (CommandName::UpdateMetadataAddress, arg_matches) => {
             // Since account is required argument it will always be present
             let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
@@ -6165,6 +7887,21 @@ async fn process_command<'a>(
                 "withdraw_withheld_authority",
                 &mut wallet_manager,
             );
+
+            // Deriving the withdraw withheld authority's ElGamal keypair and the destination
+            // owner's AES key from their signers, as for the other confidential transfer commands.
+            let confidential_keys = if arg_matches.is_present("confidential") {
+                let (destination_owner_signer, _) =
+                    config.signer_or_default(arg_matches, "destination_owner", &mut wallet_manager);
+                let elgamal_keypair = ElGamalKeypair::new_from_signer(&*authority_signer, b"")
+                    .map_err(|err| format!("Could not derive ElGamal keypair: {}", err))?;
+                let aes_key = AeKey::new_from_signer(&*destination_owner_signer, b"")
+                    .map_err(|err| format!("Could not derive AES key: {}", err))?;
+                Some((elgamal_keypair, aes_key))
+            } else {
+                None
+            };
+
             if config.multisigner_pubkeys.is_empty() {
                 push_signer_with_dedup(authority_signer, &mut bulk_signers);
             }
@@ -6179,12 +7916,14 @@ async fn process_command<'a>(
                 .unwrap_or_default()
                 .map(|s| Pubkey::from_str(s).unwrap_or_else(print_error_and_exit))
                 .collect::<Vec<_>>();
+
             command_withdraw_withheld_tokens(
                 config,
                 destination_token_account,
                 source_accounts,
                 authority,
                 include_mint,
+                confidential_keys.as_ref(),
                 bulk_signers,
             )
             .await
@@ -6256,6 +7995,33 @@ async fn process_command<'a>(
             )
             .await
         }
+        (CommandName::RotateConfidentialTransferAuditor, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+
+            let previous_auditor_elgamal_keypair =
+                elgamal_keypair_of(arg_matches, "previous_auditor_keypair")?;
+            let new_auditor_elgamal_keypair =
+                elgamal_keypair_of(arg_matches, "new_auditor_keypair")?;
+
+            let (authority_signer, authority_pubkey) = config.signer_or_default(
+                arg_matches,
+                "confidential_transfer_authority",
+                &mut wallet_manager,
+            );
+            let bulk_signers = vec![authority_signer];
+
+            command_rotate_confidential_transfer_auditor(
+                config,
+                token_pubkey,
+                authority_pubkey,
+                &previous_auditor_elgamal_keypair,
+                &new_auditor_elgamal_keypair,
+                bulk_signers,
+            )
+            .await
+        }
         (CommandName::ConfigureConfidentialTransferAccount, arg_matches) => {
             let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
 
@@ -6421,6 +8187,22 @@ async fn process_command<'a>(
             )
             .await
         }
+        (CommandName::ConfidentialBalance, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
+
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+
+            let account = pubkey_of_signer(arg_matches, "address", &mut wallet_manager).unwrap();
+
+            // Deriving ElGamal and AES key from signer, as for the other confidential transfer
+            // commands.
+            let elgamal_keypair = ElGamalKeypair::new_from_signer(&*owner_signer, b"").unwrap();
+            let aes_key = AeKey::new_from_signer(&*owner_signer, b"").unwrap();
+
+            command_confidential_balance(config, token, owner, account, &elgamal_keypair, &aes_key)
+                .await
+        }
     }
 }
 
@@ -6433,6 +8215,28 @@ where
         command_output,
     })
 }
+
+/// Write a record of a long-running command through a temporary file, so that
+/// an interruption cannot leave it truncated
+fn write_json_file<T: Serialize>(path: &str, value: &T) -> Result<(), Error> {
+    let tmp_path = format!("{}.tmp", path);
+    fs::write(&tmp_path, serde_json::to_string_pretty(value)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Print the output of a command that partially failed, then fail with `error`
+fn print_output_and_fail(output: String, error: String) -> CommandResult {
+    println!("{}", output);
+    Err(error.into())
+}
+
+/// Commands that send many transactions reject `--sign-only` before sending
+/// any of them
+fn never_sign_only(command_name: &CommandName) -> ! {
+    unreachable!("{} is never sign-only", command_name)
+}
+
 enum TransactionReturnData {
     CliSignature(CliSignature),
     CliSignOnlyData(CliSignOnlyData),
@@ -6472,6 +8276,11 @@ async fn finish_tx<'a>(
                 signature: signature.to_string(),
             }))
         }
+        RpcClientResponse::VersionedTransaction(_) => {
+            // lookup tables must be fetched, so they are rejected when signing
+            // offline
+            Err("Versioned transactions cannot be signed offline".into())
+        }
         RpcClientResponse::Simulation(_) => {
             // Implement this once the CLI supports dry-running / simulation
             unreachable!()
@@ -6485,7 +8294,9 @@ mod tests {
         super::*,
         serial_test::serial,
         solana_sdk::{
-            bpf_loader_upgradeable, feature_set,
+            bpf_loader_upgradeable,
+            commitment_config::CommitmentConfig,
+            feature_set,
             hash::Hash,
             program_pack::Pack,
             signature::{write_keypair_file, Keypair, Signer},
@@ -6493,11 +8304,18 @@ mod tests {
             transaction::Transaction,
         },
         solana_test_validator::{TestValidator, TestValidatorGenesis, UpgradeableProgramInfo},
-        spl_token_2022::{extension::non_transferable::NonTransferable, state::Multisig},
-        spl_token_client::client::{
-            ProgramClient, ProgramOfflineClient, ProgramRpcClient, ProgramRpcClientSendTransaction,
+        spl_token_2022::{
+            extension::non_transferable::NonTransferable,
+            state::{Multisig, WeightedMultisig},
+        },
+        spl_token_client::{
+            client::{
+                ProgramClient, ProgramOfflineClient, ProgramRpcClient,
+                ProgramRpcClientSendTransaction,
+            },
+            token::ComputeUnitLimit,
         },
-        std::path::PathBuf,
+        std::{path::PathBuf, time::Duration},
         tempfile::NamedTempFile,
     };
 
@@ -6529,6 +8347,12 @@ mod tests {
                 program_path: PathBuf::from("../../target/deploy/spl_token_2022.so"),
                 upgrade_authority: Pubkey::new_unique(),
             },
+            UpgradeableProgramInfo {
+                program_id: spl_token_upgrade::id(),
+                loader: bpf_loader_upgradeable::id(),
+                program_path: PathBuf::from("../../target/deploy/spl_token_upgrade.so"),
+                upgrade_authority: Pubkey::new_unique(),
+            },
         ]);
         // TODO Remove this once the Range Proof cost goes under 200k compute units
         test_validator_genesis
@@ -6541,15 +8365,14 @@ mod tests {
         payer: &Keypair,
         program_id: &Pubkey,
     ) -> Config<'a> {
-        let websocket_url = test_validator.rpc_pubsub_url();
         let rpc_client = Arc::new(test_validator.get_async_rpc_client());
         let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
             ProgramRpcClient::new(rpc_client.clone(), ProgramRpcClientSendTransaction),
         );
         Config {
             rpc_client,
+            websocket_url: test_validator.rpc_pubsub_url(),
             program_client,
-            websocket_url,
             output_format: OutputFormat::JsonCompact,
             fee_payer: Some(Arc::new(clone_keypair(payer))),
             default_signer: Some(Arc::new(clone_keypair(payer))),
@@ -6561,6 +8384,9 @@ mod tests {
             multisigner_pubkeys: vec![],
             program_id: *program_id,
             restrict_to_program_id: true,
+            compute_unit_price: None,
+            compute_unit_limit: ComputeUnitLimit::Default,
+            address_lookup_tables: vec![],
         }
     }
 
@@ -6568,15 +8394,14 @@ mod tests {
         test_validator: &TestValidator,
         program_id: &Pubkey,
     ) -> Config<'a> {
-        let websocket_url = test_validator.rpc_pubsub_url();
         let rpc_client = Arc::new(test_validator.get_async_rpc_client());
         let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
             ProgramRpcClient::new(rpc_client.clone(), ProgramRpcClientSendTransaction),
         );
         Config {
             rpc_client,
+            websocket_url: test_validator.rpc_pubsub_url(),
             program_client,
-            websocket_url,
             output_format: OutputFormat::JsonCompact,
             fee_payer: None,
             default_signer: None,
@@ -6588,6 +8413,9 @@ mod tests {
             multisigner_pubkeys: vec![],
             program_id: *program_id,
             restrict_to_program_id: true,
+            compute_unit_price: None,
+            compute_unit_limit: ComputeUnitLimit::Default,
+            address_lookup_tables: vec![],
         }
     }
 
@@ -6656,6 +8484,10 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
+            None,
             None,
             None,
             None,
@@ -6664,6 +8496,7 @@ mod tests {
             None,
             None,
             false,
+            false,
             bulk_signers,
         )
         .await
@@ -6690,6 +8523,8 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
             None,
             None,
             Some(rate_bps),
@@ -6697,6 +8532,9 @@ mod tests {
             None,
             None,
             None,
+            None,
+            None,
+            false,
             false,
             bulk_signers,
         )
@@ -6810,14 +8648,13 @@ mod tests {
         let mut bulk_signers: Vec<Arc<dyn Signer>> = Vec::new();
         let mut multisigner_ids = Vec::new();
 
-        let config = Config::new_with_clients_and_ws_url(
+        let config = Config::new_with_clients(
             matches,
             &mut wallet_manager,
             &mut bulk_signers,
             &mut multisigner_ids,
             config.rpc_client.clone(),
             config.program_client.clone(),
-            config.websocket_url.clone(),
         )
         .await;
 
@@ -6914,6 +8751,106 @@ mod tests {
         assert_eq!(i16::from(extension.current_rate), new_rate);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn scaled_ui_amount() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let config =
+            test_config_with_default_signer(&test_validator, &payer, &spl_token_2022::id());
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::CreateToken.into(),
+                "--decimals",
+                &TEST_DECIMALS.to_string(),
+                "--ui-multiplier",
+                "5",
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        let token = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+        let account = config.rpc_client.get_account(&token).await.unwrap();
+        let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_account
+            .get_extension::<ScaledUiAmountConfig>()
+            .unwrap();
+        assert_eq!(f64::from(extension.multiplier), 5.0);
+        assert_eq!(
+            Option::<Pubkey>::from(extension.authority),
+            Some(payer.pubkey())
+        );
+
+        let account = create_associated_account(&config, &payer, &token, &payer.pubkey()).await;
+        mint_tokens(&config, &payer, token, 10.0, account).await;
+
+        // supply and balance are displayed with the multiplier
+        let result = process_test_command(
+            &config,
+            &payer,
+            &["spl-token", CommandName::Supply.into(), &token.to_string()],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["amount"], "10");
+        assert_eq!(value["uiAmountString"], "50");
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Balance.into(),
+                "--address",
+                &account.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["uiAmountString"], "50");
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Accounts.into(),
+                &token.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["accounts"][0]["tokenAmount"]["uiAmountString"], "50");
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::SetUiMultiplier.into(),
+                &token.to_string(),
+                "2",
+            ],
+        )
+        .await
+        .unwrap();
+        let account = config.rpc_client.get_account(&token).await.unwrap();
+        let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_account
+            .get_extension::<ScaledUiAmountConfig>()
+            .unwrap();
+        assert_eq!(f64::from(extension.multiplier), 2.0);
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &["spl-token", CommandName::Supply.into(), &token.to_string()],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["uiAmountString"], "20");
+    }
+
     #[tokio::test]
     #[serial]
     async fn supply() {
@@ -7140,6 +9077,37 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn watch() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            let config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let account = create_associated_account(&config, &payer, &token, &payer.pubkey()).await;
+
+            // the watch returns once it sees the mint
+            let watch = process_test_command(
+                &config,
+                &payer,
+                &[
+                    "spl-token",
+                    CommandName::Watch.into(),
+                    &token.to_string(),
+                    "--limit",
+                    "1",
+                ],
+            );
+            let mint = async {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                mint_tokens(&config, &payer, token, 100.0, account).await;
+            };
+            let (result, _) =
+                futures::join!(tokio::time::timeout(Duration::from_secs(60), watch), mint);
+            result.unwrap().unwrap();
+        }
+    }
+
     #[tokio::test]
     #[serial]
     async fn accounts() {
@@ -7334,10 +9302,183 @@ mod tests {
 
     #[tokio::test]
     #[serial]
-    async fn transfer_non_standard_recipient() {
+    async fn distribute() {
         let (test_validator, payer) = new_validator_for_test().await;
         for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
-            for other_program_id in VALID_TOKEN_PROGRAM_IDS
+            let config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let source = create_associated_account(&config, &payer, &token, &payer.pubkey()).await;
+            mint_tokens(&config, &payer, token, 100.0, source).await;
+
+            let recipients = [
+                Keypair::new().pubkey(),
+                Keypair::new().pubkey(),
+                Keypair::new().pubkey(),
+            ];
+            let dir = tempfile::tempdir().unwrap();
+            let csv_path = dir.path().join("recipients.csv");
+            std::fs::write(
+                &csv_path,
+                format!(
+                    "recipient,amount\n{},10\n{},20\n\n{},30\n",
+                    recipients[0], recipients[1], recipients[2]
+                ),
+            )
+            .unwrap();
+            let csv_path = csv_path.to_str().unwrap();
+            let args = [
+                "spl-token",
+                CommandName::Distribute.into(),
+                &token.to_string(),
+                csv_path,
+                "--batch-size",
+                "2",
+            ];
+
+            // recipients' accounts must exist without --fund-recipient
+            process_test_command(&config, &payer, &args)
+                .await
+                .unwrap_err();
+
+            let args = [&args[..], &["--fund-recipient"]].concat();
+            process_test_command(&config, &payer, &args).await.unwrap();
+            for (recipient, amount) in recipients.iter().zip([10, 20, 30]) {
+                let address =
+                    get_associated_token_address_with_program_id(recipient, &token, program_id);
+                let account = config.rpc_client.get_account(&address).await.unwrap();
+                let token_account =
+                    StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+                assert_eq!(token_account.base.amount, amount);
+            }
+
+            // resuming a finished distribution sends nothing
+            process_test_command(&config, &payer, &args).await.unwrap();
+            let account = config.rpc_client.get_account(&source).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 40);
+
+            // a batch that landed while its run was interrupted is not sent again
+            let state_path = format!("{}.state.json", csv_path);
+            let mut state: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&state_path).unwrap()).unwrap();
+            let transfer = state["completed"].as_array_mut().unwrap().pop().unwrap();
+            state["pending"] = serde_json::json!([{
+                "signature": transfer["signature"],
+                "blockhash": Hash::default().to_string(),
+                "transfers": [transfer],
+            }]);
+            std::fs::write(&state_path, state.to_string()).unwrap();
+            process_test_command(&config, &payer, &args).await.unwrap();
+            let account = config.rpc_client.get_account(&source).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 40);
+            let state: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&state_path).unwrap()).unwrap();
+            assert_eq!(state["completed"].as_array().unwrap().len(), 3);
+            assert!(state["pending"].as_array().unwrap().is_empty());
+
+            // the recipients file cannot change under a recorded distribution
+            std::fs::write(csv_path, format!("{},11\n", recipients[0])).unwrap();
+            process_test_command(&config, &payer, &args)
+                .await
+                .unwrap_err();
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn migrate_mint() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            let config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let payer_account =
+                create_associated_account(&config, &payer, &token, &payer.pubkey()).await;
+            mint_tokens(&config, &payer, token, 60.0, payer_account).await;
+
+            // the holder opts in by delegating their whole balance to the
+            // airdrop authority
+            let holder = Keypair::new();
+            let holder_account =
+                create_associated_account(&config, &payer, &token, &holder.pubkey()).await;
+            mint_tokens(&config, &payer, token, 40.0, holder_account).await;
+            token_client_from_config(&config, &token, None)
+                .unwrap()
+                .approve(
+                    &holder_account,
+                    &payer.pubkey(),
+                    &holder.pubkey(),
+                    40,
+                    &[&holder],
+                )
+                .await
+                .unwrap();
+
+            let dir = tempfile::tempdir().unwrap();
+            let report_path = dir.path().join("migration.json");
+            let report_path = report_path.to_str().unwrap();
+            let args = [
+                "spl-token",
+                CommandName::MigrateMint.into(),
+                &token.to_string(),
+                "--airdrop",
+                "--report",
+                report_path,
+            ];
+            process_test_command(&config, &payer, &args).await.unwrap();
+
+            let report: CliMigration =
+                serde_json::from_str(&std::fs::read_to_string(report_path).unwrap()).unwrap();
+            let new_token = Pubkey::from_str(report.new_token.as_ref().unwrap()).unwrap();
+            let escrow = Pubkey::from_str(report.escrow.as_ref().unwrap()).unwrap();
+            assert_eq!(report.total_amount, 100);
+            assert!(report.escrow_funded);
+            assert_eq!(report.holders.len(), 2);
+            for holder in &report.holders {
+                let expected_status = if holder.account == holder_account.to_string() {
+                    CliMigrationHolderStatus::Airdropped
+                } else {
+                    CliMigrationHolderStatus::Pending
+                };
+                assert!(holder.status == expected_status);
+            }
+
+            let balance = |address: Pubkey| {
+                let rpc_client = config.rpc_client.clone();
+                async move {
+                    let account = rpc_client.get_account(&address).await.unwrap();
+                    StateWithExtensionsOwned::<Account>::unpack(account.data)
+                        .unwrap()
+                        .base
+                        .amount
+                }
+            };
+            assert_eq!(balance(holder_account).await, 0);
+            let new_holder_account = get_associated_token_address_with_program_id(
+                &holder.pubkey(),
+                &new_token,
+                &spl_token_2022::id(),
+            );
+            assert_eq!(balance(new_holder_account).await, 40);
+            assert_eq!(balance(escrow).await, 60);
+
+            // resuming a migration keeps its new token and funds the escrow
+            // only once
+            process_test_command(&config, &payer, &args).await.unwrap();
+            let report: CliMigration =
+                serde_json::from_str(&std::fs::read_to_string(report_path).unwrap()).unwrap();
+            assert_eq!(report.new_token, Some(new_token.to_string()));
+            assert_eq!(balance(escrow).await, 60);
+            assert_eq!(balance(payer_account).await, 60);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn transfer_non_standard_recipient() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            for other_program_id in VALID_TOKEN_PROGRAM_IDS
                 .iter()
                 .filter(|id| *id != program_id)
             {
@@ -8078,6 +10219,10 @@ mod tests {
             true,
             false,
             false,
+            false,
+            None,
+            None,
+            None,
             None,
             None,
             None,
@@ -8086,6 +10231,7 @@ mod tests {
             None,
             None,
             false,
+            false,
             bulk_signers,
         )
         .await
@@ -8132,6 +10278,7 @@ mod tests {
             false,
             false,
             true,
+            false,
             None,
             None,
             None,
@@ -8139,6 +10286,10 @@ mod tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            false,
             false,
             bulk_signers,
         )
@@ -8210,6 +10361,10 @@ mod tests {
             false,
             false,
             true,
+            false,
+            None,
+            None,
+            None,
             None,
             None,
             None,
@@ -8218,6 +10373,7 @@ mod tests {
             None,
             None,
             false,
+            false,
             bulk_signers,
         )
         .await
@@ -8569,6 +10725,9 @@ mod tests {
             false,
             true,
             false,
+            false,
+            None,
+            None,
             None,
             None,
             None,
@@ -8576,6 +10735,8 @@ mod tests {
             None,
             None,
             None,
+            None,
+            false,
             false,
             bulk_signers,
         )
@@ -8629,6 +10790,9 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
             None,
             None,
             None,
@@ -8636,6 +10800,8 @@ mod tests {
             None,
             None,
             None,
+            None,
+            false,
             false,
             bulk_signers,
         )
@@ -8702,6 +10868,9 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
             None,
             None,
             None,
@@ -8709,6 +10878,8 @@ mod tests {
             Some((transfer_fee_basis_points, maximum_fee)),
             None,
             None,
+            None,
+            false,
             false,
             bulk_signers,
         )
@@ -8975,6 +11146,9 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
             None,
             None,
             None,
@@ -8982,6 +11156,8 @@ mod tests {
             None,
             Some(auto_approve),
             None,
+            None,
+            false,
             false,
             bulk_signers.clone(),
         )
@@ -9038,6 +11214,31 @@ mod tests {
             Some(auditor_pubkey),
         );
 
+        // rotate confidential transfer auditor
+        let new_auditor_keypair = ElGamalKeypair::new_rand();
+        let new_auditor_pubkey: ElGamalPubkey = (*new_auditor_keypair.pubkey()).into();
+
+        command_rotate_confidential_transfer_auditor(
+            &config,
+            token_pubkey,
+            confidential_transfer_mint_authority,
+            &new_auditor_keypair,
+            bulk_signers.clone(),
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+        let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = test_mint
+            .get_extension::<ConfidentialTransferMint>()
+            .unwrap();
+
+        assert_eq!(
+            Option::<ElGamalPubkey>::from(extension.auditor_elgamal_pubkey),
+            Some(new_auditor_pubkey),
+        );
+
         // create a confidential transfer account
         let token_account =
             create_associated_account(&config, &payer, &token_pubkey, &payer.pubkey()).await;
@@ -9202,6 +11403,22 @@ mod tests {
         .await
         .unwrap();
 
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ConfidentialBalance.into(),
+                "--address",
+                &destination_account.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["pendingBalance"], "100");
+        assert_eq!(value["availableBalance"], "0");
+        assert_eq!(value["pendingBalanceCreditCounter"], 1);
+
         // withdraw confidential tokens
         process_test_command(
             &config,
@@ -9216,6 +11433,21 @@ mod tests {
         .await
         .unwrap(); // apply pending balance first
 
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ConfidentialBalance.into(),
+                "--address",
+                &destination_account.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["pendingBalance"], "0");
+        assert_eq!(value["availableBalance"], "100");
+
         let withdraw_amount = 100.0;
 
         process_test_command(
@@ -9251,96 +11483,434 @@ mod tests {
 
     #[tokio::test]
     #[serial]
-    async fn multisig_transfer() {
+    async fn confidential_transfer_with_fee() {
         let (test_validator, payer) = new_validator_for_test().await;
-        let m = 3;
-        let n = 5u8;
-        // need to add "payer" to make the config provide the right signer
-        let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
-            std::iter::once(clone_keypair(&payer))
-                .chain(std::iter::repeat_with(Keypair::new).take((n - 2) as usize))
-                .map(|s| {
-                    let keypair_file = NamedTempFile::new().unwrap();
-                    write_keypair_file(&s, &keypair_file).unwrap();
-                    (s.pubkey(), keypair_file)
-                })
-                .unzip();
-        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
-            let config = test_config_with_default_signer(&test_validator, &payer, program_id);
-            let token = create_token(&config, &payer).await;
-            let multisig = Arc::new(Keypair::new());
-            let multisig_pubkey = multisig.pubkey();
-
-            // add the multisig as a member to itself, make it self-owned
-            let multisig_members = std::iter::once(multisig_pubkey)
-                .chain(multisig_members.iter().cloned())
-                .collect::<Vec<_>>();
-            let multisig_path = NamedTempFile::new().unwrap();
-            write_keypair_file(&multisig, &multisig_path).unwrap();
-            let multisig_paths = std::iter::once(&multisig_path)
-                .chain(multisig_paths.iter())
-                .collect::<Vec<_>>();
-
-            command_create_multisig(&config, multisig, m, multisig_members)
-                .await
-                .unwrap();
-
-            let account = config
-                .rpc_client
-                .get_account(&multisig_pubkey)
-                .await
-                .unwrap();
-            let multisig = Multisig::unpack(&account.data).unwrap();
-            assert_eq!(multisig.m, m);
-            assert_eq!(multisig.n, n);
+        let config =
+            test_config_with_default_signer(&test_validator, &payer, &spl_token_2022::id());
 
-            let source = create_associated_account(&config, &payer, &token, &multisig_pubkey).await;
-            let destination = create_auxiliary_account(&config, &payer, token).await;
-            let ui_amount = 100.0;
-            mint_tokens(&config, &payer, token, ui_amount, source).await;
+        // 1% fee, capped well above what the test transfers
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::CreateToken.into(),
+                "--transfer-fee",
+                "100",
+                "10000000000",
+                "--enable-confidential-transfers",
+                "auto",
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        let token_pubkey =
+            Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+        let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        assert!(mint_state
+            .get_extension::<ConfidentialTransferFeeConfig>()
+            .is_ok());
 
-            exec_test_cmd(
+        let source_account =
+            create_associated_account(&config, &payer, &token_pubkey, &payer.pubkey()).await;
+        let destination_account = create_auxiliary_account(&config, &payer, token_pubkey).await;
+        for account in [source_account, destination_account] {
+            process_test_command(
                 &config,
+                &payer,
                 &[
                     "spl-token",
-                    CommandName::Transfer.into(),
-                    &token.to_string(),
-                    "10",
-                    &destination.to_string(),
-                    "--multisig-signer",
-                    multisig_paths[0].path().to_str().unwrap(),
-                    "--multisig-signer",
-                    multisig_paths[1].path().to_str().unwrap(),
-                    "--multisig-signer",
-                    multisig_paths[2].path().to_str().unwrap(),
-                    "--from",
-                    &source.to_string(),
-                    "--owner",
-                    &multisig_pubkey.to_string(),
-                    "--fee-payer",
-                    multisig_paths[1].path().to_str().unwrap(),
+                    CommandName::ConfigureConfidentialTransferAccount.into(),
+                    "--address",
+                    &account.to_string(),
                 ],
             )
             .await
             .unwrap();
-
-            let account = config.rpc_client.get_account(&source).await.unwrap();
-            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
-            assert_eq!(token_account.base.amount, 90);
-            let account = config.rpc_client.get_account(&destination).await.unwrap();
-            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
-            assert_eq!(token_account.base.amount, 10);
         }
-    }
 
-    #[tokio::test]
-    #[serial]
-    async fn offline_multisig_transfer_with_nonce() {
-        let (test_validator, payer) = new_validator_for_test().await;
-        let m = 2;
-        let n = 3u8;
+        mint_tokens(&config, &payer, token_pubkey, 100.0, source_account).await;
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::DepositConfidentialTokens.into(),
+                &token_pubkey.to_string(),
+                "100",
+            ],
+        )
+        .await
+        .unwrap();
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ApplyPendingBalance.into(),
+                &token_pubkey.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
 
-        let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
+        // the wrong expected fee is caught before any proof is created
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                &token_pubkey.to_string(),
+                "100",
+                &destination_account.to_string(),
+                "--confidential",
+                "--expected-fee",
+                "2",
+            ],
+        )
+        .await;
+        assert!(result.is_err());
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                &token_pubkey.to_string(),
+                "100",
+                &destination_account.to_string(),
+                "--confidential",
+                "--expected-fee",
+                "1",
+            ],
+        )
+        .await
+        .unwrap();
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ApplyPendingBalance.into(),
+                "--address",
+                &destination_account.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ConfidentialBalance.into(),
+                "--address",
+                &destination_account.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["availableBalance"], "99");
+
+        // the withheld fee goes straight to the available balance
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::WithdrawWithheldTokens.into(),
+                &destination_account.to_string(),
+                &destination_account.to_string(),
+                "--confidential",
+            ],
+        )
+        .await
+        .unwrap();
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ConfidentialBalance.into(),
+                "--address",
+                &destination_account.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["pendingBalance"], "0");
+        assert_eq!(value["availableBalance"], "100");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn multisig_transfer() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let m = 3;
+        let n = 5u8;
+        // need to add "payer" to make the config provide the right signer
+        let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
+            std::iter::once(clone_keypair(&payer))
+                .chain(std::iter::repeat_with(Keypair::new).take((n - 2) as usize))
+                .map(|s| {
+                    let keypair_file = NamedTempFile::new().unwrap();
+                    write_keypair_file(&s, &keypair_file).unwrap();
+                    (s.pubkey(), keypair_file)
+                })
+                .unzip();
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            let config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let multisig = Arc::new(Keypair::new());
+            let multisig_pubkey = multisig.pubkey();
+
+            // add the multisig as a member to itself, make it self-owned
+            let multisig_members = std::iter::once(multisig_pubkey)
+                .chain(multisig_members.iter().cloned())
+                .collect::<Vec<_>>();
+            let multisig_path = NamedTempFile::new().unwrap();
+            write_keypair_file(&multisig, &multisig_path).unwrap();
+            let multisig_paths = std::iter::once(&multisig_path)
+                .chain(multisig_paths.iter())
+                .collect::<Vec<_>>();
+
+            command_create_multisig(&config, multisig, m, multisig_members)
+                .await
+                .unwrap();
+
+            let account = config
+                .rpc_client
+                .get_account(&multisig_pubkey)
+                .await
+                .unwrap();
+            let multisig = Multisig::unpack(&account.data).unwrap();
+            assert_eq!(multisig.m, m);
+            assert_eq!(multisig.n, n);
+
+            let source = create_associated_account(&config, &payer, &token, &multisig_pubkey).await;
+            let destination = create_auxiliary_account(&config, &payer, token).await;
+            let ui_amount = 100.0;
+            mint_tokens(&config, &payer, token, ui_amount, source).await;
+
+            exec_test_cmd(
+                &config,
+                &[
+                    "spl-token",
+                    CommandName::Transfer.into(),
+                    &token.to_string(),
+                    "10",
+                    &destination.to_string(),
+                    "--multisig-signer",
+                    multisig_paths[0].path().to_str().unwrap(),
+                    "--multisig-signer",
+                    multisig_paths[1].path().to_str().unwrap(),
+                    "--multisig-signer",
+                    multisig_paths[2].path().to_str().unwrap(),
+                    "--from",
+                    &source.to_string(),
+                    "--owner",
+                    &multisig_pubkey.to_string(),
+                    "--fee-payer",
+                    multisig_paths[1].path().to_str().unwrap(),
+                ],
+            )
+            .await
+            .unwrap();
+
+            let account = config.rpc_client.get_account(&source).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 90);
+            let account = config.rpc_client.get_account(&destination).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 10);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn compute_budget() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            let mut config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let source = create_associated_account(&config, &payer, &token, &payer.pubkey()).await;
+            let destination = create_auxiliary_account(&config, &payer, token).await;
+            mint_tokens(&config, &payer, token, 100.0, source).await;
+
+            config.compute_unit_price = Some(1_000);
+            for compute_unit_limit in [
+                ComputeUnitLimit::Static(50_000),
+                ComputeUnitLimit::Simulated,
+            ] {
+                config.compute_unit_limit = compute_unit_limit;
+                process_test_command(
+                    &config,
+                    &payer,
+                    &[
+                        "spl-token",
+                        CommandName::Transfer.into(),
+                        &token.to_string(),
+                        "10",
+                        &destination.to_string(),
+                    ],
+                )
+                .await
+                .unwrap();
+            }
+
+            let account = config.rpc_client.get_account(&destination).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 20);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn lookup_table() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            let mut config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let source = create_associated_account(&config, &payer, &token, &payer.pubkey()).await;
+            let destination = create_auxiliary_account(&config, &payer, token).await;
+            mint_tokens(&config, &payer, token, 100.0, source).await;
+
+            let token_client = token_client_from_config(&config, &token, None).unwrap();
+            let recent_slot = config
+                .rpc_client
+                .get_slot_with_commitment(CommitmentConfig::finalized())
+                .await
+                .unwrap();
+            let table_address = token_client
+                .create_address_lookup_table(
+                    &payer.pubkey(),
+                    recent_slot,
+                    &[token, source, destination, *program_id],
+                    &[&payer],
+                )
+                .await
+                .unwrap();
+            // the table can only be used once the slot it was extended in
+            // has passed
+            let extended_slot = config.rpc_client.get_slot().await.unwrap();
+            while config.rpc_client.get_slot().await.unwrap() <= extended_slot {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+
+            config.address_lookup_tables = vec![token_client
+                .get_address_lookup_table(&table_address)
+                .await
+                .unwrap()];
+            process_test_command(
+                &config,
+                &payer,
+                &[
+                    "spl-token",
+                    CommandName::Transfer.into(),
+                    &token.to_string(),
+                    "10",
+                    &destination.to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+
+            let account = config.rpc_client.get_account(&destination).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 10);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn weighted_multisig_transfer() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+        let token = create_token(&config, &payer).await;
+
+        let payer_path = NamedTempFile::new().unwrap();
+        write_keypair_file(&payer, &payer_path).unwrap();
+
+        // the payer alone carries the threshold weight
+        let multisig = Keypair::new();
+        let multisig_pubkey = multisig.pubkey();
+        let multisig_path = NamedTempFile::new().unwrap();
+        write_keypair_file(&multisig, &multisig_path).unwrap();
+        exec_test_cmd(
+            &config,
+            &[
+                "spl-token",
+                CommandName::CreateMultisig.into(),
+                "2",
+                &payer.pubkey().to_string(),
+                &Pubkey::new_unique().to_string(),
+                &Pubkey::new_unique().to_string(),
+                "--weights",
+                "2,1,1",
+                "--address-keypair",
+                multisig_path.path().to_str().unwrap(),
+                "--fee-payer",
+                payer_path.path().to_str().unwrap(),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config
+            .rpc_client
+            .get_account(&multisig_pubkey)
+            .await
+            .unwrap();
+        let multisig = WeightedMultisig::unpack(&account.data).unwrap();
+        assert_eq!(multisig.threshold, 2);
+        assert_eq!(multisig.n, 3);
+        assert_eq!(&multisig.weights[..3], &[2, 1, 1]);
+        assert_eq!(multisig.min_delay, 0);
+
+        let source = create_associated_account(&config, &payer, &token, &multisig_pubkey).await;
+        let destination = create_auxiliary_account(&config, &payer, token).await;
+        mint_tokens(&config, &payer, token, 100.0, source).await;
+
+        exec_test_cmd(
+            &config,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                &token.to_string(),
+                "10",
+                &destination.to_string(),
+                "--multisig-signer",
+                payer_path.path().to_str().unwrap(),
+                "--from",
+                &source.to_string(),
+                "--owner",
+                &multisig_pubkey.to_string(),
+                "--fee-payer",
+                payer_path.path().to_str().unwrap(),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&source).await.unwrap();
+        let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+        assert_eq!(token_account.base.amount, 90);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn offline_multisig_transfer_with_nonce() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let m = 2;
+        let n = 3u8;
+
+        let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
             std::iter::repeat_with(Keypair::new)
                 .take(n as usize)
                 .map(|s| {
@@ -9425,6 +11995,153 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn multisig_transfer_proposal_with_nonce() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let m = 2;
+        let n = 3u8;
+
+        let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
+            std::iter::repeat_with(Keypair::new)
+                .take(n as usize)
+                .map(|s| {
+                    let keypair_file = NamedTempFile::new().unwrap();
+                    write_keypair_file(&s, &keypair_file).unwrap();
+                    (s.pubkey(), keypair_file)
+                })
+                .unzip();
+        let payer_path = NamedTempFile::new().unwrap();
+        write_keypair_file(&payer, &payer_path).unwrap();
+        for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+            let config = test_config_with_default_signer(&test_validator, &payer, program_id);
+            let token = create_token(&config, &payer).await;
+            let nonce = create_nonce(&config, &payer).await;
+
+            let multisig = Arc::new(Keypair::new());
+            let multisig_pubkey = multisig.pubkey();
+            command_create_multisig(&config, multisig, m, multisig_members.clone())
+                .await
+                .unwrap();
+
+            let source = create_associated_account(&config, &payer, &token, &multisig_pubkey).await;
+            let destination = create_auxiliary_account(&config, &payer, token).await;
+            let ui_amount = 100.0;
+            mint_tokens(&config, &payer, token, ui_amount, source).await;
+
+            let dir = tempfile::tempdir().unwrap();
+            let bundle = dir.path().join("proposal.json");
+            let bundle = bundle.to_str().unwrap();
+
+            // the fee payer and nonce authority signs while proposing
+            let result = exec_test_cmd(
+                &config,
+                &[
+                    "spl-token",
+                    CommandName::Propose.into(),
+                    bundle,
+                    CommandName::Transfer.into(),
+                    &token.to_string(),
+                    "10",
+                    &destination.to_string(),
+                    "--nonce",
+                    &nonce.to_string(),
+                    "--nonce-authority",
+                    payer_path.path().to_str().unwrap(),
+                    "--multisig-signer",
+                    &multisig_members[0].to_string(),
+                    "--multisig-signer",
+                    &multisig_members[1].to_string(),
+                    "--from",
+                    &source.to_string(),
+                    "--owner",
+                    &multisig_pubkey.to_string(),
+                    "--fee-payer",
+                    payer_path.path().to_str().unwrap(),
+                ],
+            )
+            .await
+            .unwrap();
+            let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+            let proposal = &value["commandOutput"];
+            assert_eq!(proposal["nonceAccount"], nonce.to_string());
+            let instructions = proposal["instructions"].as_array().unwrap();
+            assert_eq!(instructions[0]["parsed"]["type"], "advanceNonce");
+            assert_eq!(instructions[1]["parsed"]["type"], "transferChecked");
+            assert_eq!(
+                instructions[1]["parsed"]["info"]["tokenAmount"]["uiAmountString"],
+                "10"
+            );
+            let signed = proposal["signers"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|signer| {
+                    (
+                        signer["pubkey"].as_str().unwrap().to_string(),
+                        signer["signed"].as_bool().unwrap(),
+                    )
+                })
+                .collect::<HashMap<_, _>>();
+            assert_eq!(signed.len(), 3);
+            assert!(signed[&payer.pubkey().to_string()]);
+            assert!(!signed[&multisig_members[0].to_string()]);
+            assert!(!signed[&multisig_members[1].to_string()]);
+
+            // the proposal can't be sent until the multisig members sign
+            let result =
+                exec_test_cmd(&config, &["spl-token", CommandName::Submit.into(), bundle]).await;
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("missing signatures"));
+
+            // a key that isn't a signer is rejected
+            let result = exec_test_cmd(
+                &config,
+                &[
+                    "spl-token",
+                    CommandName::Sign.into(),
+                    bundle,
+                    "--signer",
+                    multisig_paths[2].path().to_str().unwrap(),
+                ],
+            )
+            .await;
+            assert!(result.is_err());
+
+            for path in &multisig_paths[..2] {
+                exec_test_cmd(
+                    &config,
+                    &[
+                        "spl-token",
+                        CommandName::Sign.into(),
+                        bundle,
+                        "--signer",
+                        path.path().to_str().unwrap(),
+                    ],
+                )
+                .await
+                .unwrap();
+            }
+            exec_test_cmd(&config, &["spl-token", CommandName::Submit.into(), bundle])
+                .await
+                .unwrap();
+
+            let account = config.rpc_client.get_account(&source).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 90);
+            let account = config.rpc_client.get_account(&destination).await.unwrap();
+            let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+            assert_eq!(token_account.base.amount, 10);
+
+            // the nonce has advanced, so the proposal can't be sent again
+            let result =
+                exec_test_cmd(&config, &["spl-token", CommandName::Submit.into(), bundle]).await;
+            assert!(result.unwrap_err().to_string().contains("has advanced"));
+        }
+    }
+
     #[tokio::test]
     #[serial]
     async fn withdraw_excess_lamports_from_multisig() {
@@ -9760,6 +12477,74 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn pausable() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::CreateToken.into(),
+                "--program-id",
+                &program_id.to_string(),
+                "--enable-pause",
+            ],
+        )
+        .await;
+
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_state.get_extension::<PausableConfig>().unwrap();
+
+        assert_eq!(
+            extension.authority,
+            Some(payer.pubkey()).try_into().unwrap()
+        );
+        assert!(!bool::from(extension.paused));
+
+        process_test_command(
+            &config,
+            &payer,
+            &["spl-token", CommandName::Pause.into(), &mint.to_string()],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_state.get_extension::<PausableConfig>().unwrap();
+        assert!(bool::from(extension.paused));
+
+        // pausing twice fails
+        process_test_command(
+            &config,
+            &payer,
+            &["spl-token", CommandName::Pause.into(), &mint.to_string()],
+        )
+        .await
+        .unwrap_err();
+
+        process_test_command(
+            &config,
+            &payer,
+            &["spl-token", CommandName::Resume.into(), &mint.to_string()],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_state.get_extension::<PausableConfig>().unwrap();
+        assert!(!bool::from(extension.paused));
+    }
+
     #[tokio::test]
     #[serial]
     async fn transfer_hook() {
@@ -10049,4 +12834,133 @@ mod tests {
             Some(mint).try_into().unwrap()
         );
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn group() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::CreateToken.into(),
+                "--program-id",
+                &program_id.to_string(),
+                "--enable-group",
+            ],
+        )
+        .await;
+
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+
+        let extension = mint_state.get_extension::<GroupPointer>().unwrap();
+        assert_eq!(extension.group_address, Some(mint).try_into().unwrap());
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::InitializeGroup.into(),
+                &mint.to_string(),
+                "1",
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let group = mint_state.get_extension::<TokenGroup>().unwrap();
+        assert_eq!(group.mint, mint);
+        assert_eq!(
+            group.update_authority,
+            Some(payer.pubkey()).try_into().unwrap()
+        );
+        assert_eq!(u32::from(group.size), 0);
+        assert_eq!(u32::from(group.max_size), 1);
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::UpdateGroupMaxSize.into(),
+                &mint.to_string(),
+                "2",
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let group = mint_state.get_extension::<TokenGroup>().unwrap();
+        assert_eq!(u32::from(group.max_size), 2);
+
+        let member = create_token(&config, &payer).await;
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::InitializeMember.into(),
+                &member.to_string(),
+                &mint.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&member).await.unwrap();
+        let member_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let group_member = member_state.get_extension::<TokenGroupMember>().unwrap();
+        assert_eq!(group_member.mint, member);
+        assert_eq!(group_member.group, mint);
+        assert_eq!(u32::from(group_member.member_number), 1);
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Display.into(),
+                &member.to_string(),
+            ],
+        )
+        .await;
+        let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(
+            value["commandOutput"]["groupMember"]["group"],
+            mint.to_string()
+        );
+
+        // make the group immutable
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Authorize.into(),
+                &mint.to_string(),
+                "group",
+                "--disable",
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&mint).await.unwrap();
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let group = mint_state.get_extension::<TokenGroup>().unwrap();
+        assert_eq!(u32::from(group.size), 1);
+        assert_eq!(group.update_authority, None.try_into().unwrap());
+    }
 }
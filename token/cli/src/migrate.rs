@@ -0,0 +1,651 @@
+/// The `migrate-mint` subcommand
+use {
+    crate::{
+        config::Config,
+        finish_tx, format_output, get_signer, never_sign_only,
+        output::{CliMigration, CliMigrationHolder, CliMigrationHolderStatus},
+        print_output_and_fail, println_display, write_json_file, CommandName, CommandResult, Error,
+        TransactionReturnData,
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_account_decoder::UiAccountEncoding,
+    solana_clap_utils::{
+        input_parsers::pubkey_of_signer,
+        input_validators::{is_valid_pubkey, is_valid_signer},
+    },
+    solana_client::{
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_sdk::{
+        program_option::COption,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account_idempotent,
+    },
+    spl_token_2022::{extension::StateWithExtensionsOwned, state::Account},
+    spl_token_client::{
+        batch_sender::BatchSender,
+        client::{ProgramRpcClientSendTransaction, RpcClientResponse},
+        token::{ComputeUnitLimit, ExtensionInitializationParams, Token},
+    },
+    spl_token_upgrade::{get_token_upgrade_authority_address, instruction::exchange},
+    std::{fs, path::Path, rc::Rc, sync::Arc},
+};
+
+/// Number of exchanges sent between two saves of the report
+const AIRDROP_CHUNK_SIZE: usize = 50;
+
+pub(crate) trait MigrateMintSubCommand {
+    fn migrate_mint_subcommand(self) -> Self;
+}
+
+impl MigrateMintSubCommand for App<'_, '_> {
+    fn migrate_mint_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name(CommandName::MigrateMint.into())
+                .about("Migrate the holders of a token to a new Token-2022 mint through the token-upgrade program")
+                .after_help(
+                    "Holders are snapshotted into the report on the first run. The new mint \
+                    and the escrow of the token-upgrade program are then created, and the escrow \
+                    is funded with the snapshotted supply. Holders exchange their tokens with \
+                    `spl-token-upgrade exchange`, or opt in to the airdrop by approving the \
+                    airdrop authority as delegate for their whole balance. Run the command \
+                    again to resume an interrupted migration or to airdrop to new opt-ins.",
+                )
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token to migrate from"),
+                )
+                .arg(
+                    Arg::with_name("new_token_keypair")
+                        .long("new-token-keypair")
+                        .value_name("NEW_TOKEN_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the keypair of the new token. \
+                             This may be a keypair file or the ASK keyword. \
+                             [default: randomly generated keypair]"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("new_token")
+                        .long("new-token")
+                        .validator(is_valid_pubkey)
+                        .value_name("NEW_TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .conflicts_with_all(&[
+                            "new_token_keypair",
+                            "enable_freeze",
+                            "enable_close",
+                            "enable_permanent_delegate",
+                        ])
+                        .help(
+                            "Migrate to an existing Token-2022 mint instead of creating one, \
+                             for example one created by `create-token` with other extensions"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("mint_authority")
+                        .long("mint-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the mint authority of the new token, which funds the escrow. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("enable_freeze")
+                        .long("enable-freeze")
+                        .takes_value(false)
+                        .help("Enable the mint authority to freeze token accounts of the new token")
+                )
+                .arg(
+                    Arg::with_name("enable_close")
+                        .long("enable-close")
+                        .takes_value(false)
+                        .help("Enable the mint authority to close the new token")
+                )
+                .arg(
+                    Arg::with_name("enable_permanent_delegate")
+                        .long("enable-permanent-delegate")
+                        .takes_value(false)
+                        .help("Enable the mint authority to be permanent delegate for the new token")
+                )
+                .arg(
+                    Arg::with_name("airdrop")
+                        .long("airdrop")
+                        .takes_value(false)
+                        .help(
+                            "Exchange the tokens of holders who opted in, sending the new tokens \
+                             to their associated token accounts"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("airdrop_authority")
+                        .long("airdrop-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the delegate that holders approve to opt in to the airdrop. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("report")
+                        .long("report")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help(
+                            "File recording the progress of the migration, used to resume it \
+                             [default: TOKEN_MINT_ADDRESS with a `.migration.json` suffix]"
+                        ),
+                ),
+        )
+    }
+}
+
+pub(crate) async fn migrate_mint_process_command(
+    matches: &ArgMatches<'_>,
+    config: &Config<'_>,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let token = pubkey_of_signer(matches, "token", wallet_manager)
+        .unwrap()
+        .unwrap();
+    let new_token =
+        if let Some(new_token) = pubkey_of_signer(matches, "new_token", wallet_manager).unwrap() {
+            NewToken::Existing(new_token)
+        } else if let Some((signer, _)) = get_signer(matches, "new_token_keypair", wallet_manager) {
+            NewToken::Keypair(signer)
+        } else {
+            NewToken::Generated
+        };
+    let (mint_authority_signer, _) =
+        config.signer_or_default(matches, "mint_authority", wallet_manager);
+    let airdrop = matches.is_present("airdrop");
+    let (airdrop_authority_signer, airdrop_authority) =
+        config.signer_or_default(matches, "airdrop_authority", wallet_manager);
+    let report_path = matches
+        .value_of("report")
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{}.migration.json", token));
+
+    command_migrate_mint(
+        config,
+        token,
+        new_token,
+        mint_authority_signer,
+        NewMintOptions {
+            enable_freeze: matches.is_present("enable_freeze"),
+            enable_close: matches.is_present("enable_close"),
+            enable_permanent_delegate: matches.is_present("enable_permanent_delegate"),
+        },
+        airdrop.then_some(airdrop_authority_signer),
+        airdrop_authority,
+        &report_path,
+    )
+    .await
+}
+
+/// Where the new mint comes from
+enum NewToken {
+    Existing(Pubkey),
+    Keypair(Arc<dyn Signer>),
+    Generated,
+}
+
+/// Authorities given to a newly created mint
+struct NewMintOptions {
+    enable_freeze: bool,
+    enable_close: bool,
+    enable_permanent_delegate: bool,
+}
+
+fn read_report(path: &str, token: &Pubkey) -> Result<Option<CliMigration>, Error> {
+    if !Path::new(path).exists() {
+        return Ok(None);
+    }
+    let report: CliMigration = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|err| format!("Error: invalid migration report {}: {}", path, err))?;
+    if report.original_token != token.to_string() {
+        return Err(format!(
+            "Error: migration report {} records a migration of {}",
+            path, report.original_token
+        )
+        .into());
+    }
+    Ok(Some(report))
+}
+
+async fn signature_of(config: &Config<'_>, response: &RpcClientResponse) -> Result<String, Error> {
+    match finish_tx(config, response, false).await? {
+        TransactionReturnData::CliSignature(signature) => Ok(signature.signature),
+        TransactionReturnData::CliSignOnlyData(_) => never_sign_only(&CommandName::MigrateMint),
+    }
+}
+
+fn new_token_client(
+    config: &Config<'_>,
+    new_token: &Pubkey,
+    decimals: u8,
+) -> Result<Token<ProgramRpcClientSendTransaction>, Error> {
+    let token = Token::new(
+        config.program_client.clone(),
+        &spl_token_2022::id(),
+        new_token,
+        Some(decimals),
+        config.fee_payer()?.clone(),
+    )
+    .with_compute_unit_limit(config.compute_unit_limit);
+    Ok(
+        if let Some(compute_unit_price) = config.compute_unit_price {
+            token.with_compute_unit_price(compute_unit_price)
+        } else {
+            token
+        },
+    )
+}
+
+/// Read every account of the original token holding a balance
+async fn snapshot_holders(
+    config: &Config<'_>,
+    token: &Pubkey,
+    program_id: &Pubkey,
+    decimals: u8,
+) -> Result<(u64, Vec<CliMigrationHolder>), Error> {
+    let slot = config.rpc_client.get_slot().await?;
+    let accounts = config
+        .rpc_client
+        .get_program_accounts_with_config(
+            program_id,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::TokenAccountState,
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, token.as_ref())),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(config.rpc_client.commitment()),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+
+    let mut holders = accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            let account = StateWithExtensionsOwned::<Account>::unpack(account.data).ok()?;
+            (account.base.amount > 0).then(|| CliMigrationHolder {
+                account: address.to_string(),
+                owner: account.base.owner.to_string(),
+                amount: account.base.amount,
+                ui_amount: spl_token_2022::amount_to_ui_amount_string_trimmed(
+                    account.base.amount,
+                    decimals,
+                ),
+                status: CliMigrationHolderStatus::Pending,
+                signature: None,
+                error: None,
+            })
+        })
+        .collect::<Vec<_>>();
+    holders.sort_by(|a, b| a.account.cmp(&b.account));
+    Ok((slot, holders))
+}
+
+async fn create_new_mint(
+    config: &Config<'_>,
+    report: &mut CliMigration,
+    new_token_signer: Arc<dyn Signer>,
+    mint_authority: Pubkey,
+    new_mint_options: &NewMintOptions,
+) -> Result<Pubkey, Error> {
+    let new_token_pubkey = new_token_signer.pubkey();
+
+    let mut extensions = vec![];
+    if new_mint_options.enable_close {
+        extensions.push(ExtensionInitializationParams::MintCloseAuthority {
+            close_authority: Some(mint_authority),
+        });
+    }
+    if new_mint_options.enable_permanent_delegate {
+        extensions.push(ExtensionInitializationParams::PermanentDelegate {
+            delegate: mint_authority,
+        });
+    }
+    let freeze_authority = new_mint_options.enable_freeze.then_some(mint_authority);
+
+    println_display(
+        config,
+        format!(
+            "Creating token {} under program {}",
+            new_token_pubkey,
+            spl_token_2022::id()
+        ),
+    );
+    let token = new_token_client(config, &new_token_pubkey, report.decimals)?;
+    let response = token
+        .create_mint(
+            &mint_authority,
+            freeze_authority.as_ref(),
+            extensions,
+            &[new_token_signer],
+        )
+        .await?;
+    report.new_token_signature = Some(signature_of(config, &response).await?);
+    Ok(new_token_pubkey)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn command_migrate_mint(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    new_token: NewToken,
+    mint_authority_signer: Arc<dyn Signer>,
+    new_mint_options: NewMintOptions,
+    airdrop_authority_signer: Option<Arc<dyn Signer>>,
+    airdrop_authority: Pubkey,
+    report_path: &str,
+) -> CommandResult {
+    let mint_authority = mint_authority_signer.pubkey();
+    if config.sign_only || config.nonce_account.is_some() {
+        return Err("Error: migrations cannot be signed offline or use a nonce account".into());
+    }
+    let mint_info = config.get_mint_info(&token_pubkey, None).await?;
+
+    let mut report = match read_report(report_path, &token_pubkey)? {
+        Some(report) => {
+            let requested = match &new_token {
+                NewToken::Existing(address) => Some(*address),
+                NewToken::Keypair(signer) => Some(signer.pubkey()),
+                NewToken::Generated => None,
+            };
+            match (&report.new_token, requested) {
+                (Some(recorded), Some(requested)) if *recorded != requested.to_string() => {
+                    return Err(format!(
+                        "Error: migration report {} records a migration to {}",
+                        report_path, recorded
+                    )
+                    .into());
+                }
+                _ => report,
+            }
+        }
+        None => {
+            println_display(config, format!("Snapshotting holders of {}", token_pubkey));
+            let (snapshot_slot, holders) = snapshot_holders(
+                config,
+                &token_pubkey,
+                &mint_info.program_id,
+                mint_info.decimals,
+            )
+            .await?;
+            let total_amount = holders.iter().map(|holder| holder.amount).sum::<u64>();
+            let report = CliMigration {
+                report_file: report_path.to_string(),
+                original_token: token_pubkey.to_string(),
+                original_program_id: mint_info.program_id.to_string(),
+                decimals: mint_info.decimals,
+                snapshot_slot,
+                total_amount,
+                new_token: None,
+                new_token_signature: None,
+                escrow: None,
+                escrow_authority: None,
+                escrow_signature: None,
+                escrow_funded: false,
+                funding_signature: None,
+                airdrop_authority: airdrop_authority.to_string(),
+                holders,
+            };
+            write_json_file(report_path, &report)?;
+            report
+        }
+    };
+    report.report_file = report_path.to_string();
+    report.airdrop_authority = airdrop_authority.to_string();
+
+    // new mint
+    let new_token_pubkey = if let Some(new_token) = &report.new_token {
+        new_token.parse::<Pubkey>()?
+    } else {
+        let new_token_pubkey = match new_token {
+            NewToken::Existing(new_token_pubkey) => {
+                let new_mint_info = config.get_mint_info(&new_token_pubkey, None).await?;
+                if new_mint_info.program_id != spl_token_2022::id() {
+                    return Err(format!(
+                        "Error: new token {} is not a Token-2022 mint",
+                        new_token_pubkey
+                    )
+                    .into());
+                }
+                if new_mint_info.decimals != mint_info.decimals {
+                    return Err(format!(
+                        "Error: new token {} has {} decimals, but {} has {}",
+                        new_token_pubkey, new_mint_info.decimals, token_pubkey, mint_info.decimals
+                    )
+                    .into());
+                }
+                new_token_pubkey
+            }
+            NewToken::Keypair(new_token_signer) => {
+                create_new_mint(
+                    config,
+                    &mut report,
+                    new_token_signer,
+                    mint_authority,
+                    &new_mint_options,
+                )
+                .await?
+            }
+            NewToken::Generated => {
+                create_new_mint(
+                    config,
+                    &mut report,
+                    Arc::new(Keypair::new()),
+                    mint_authority,
+                    &new_mint_options,
+                )
+                .await?
+            }
+        };
+        report.new_token = Some(new_token_pubkey.to_string());
+        write_json_file(report_path, &report)?;
+        new_token_pubkey
+    };
+    let token = new_token_client(config, &new_token_pubkey, mint_info.decimals)?;
+
+    // escrow owned by the token-upgrade program
+    let escrow_authority = get_token_upgrade_authority_address(
+        &token_pubkey,
+        &new_token_pubkey,
+        &spl_token_upgrade::id(),
+    );
+    let escrow = token.get_associated_token_address(&escrow_authority);
+    report.escrow = Some(escrow.to_string());
+    report.escrow_authority = Some(escrow_authority.to_string());
+    if config
+        .rpc_client
+        .get_account_with_commitment(&escrow, config.rpc_client.commitment())
+        .await?
+        .value
+        .is_none()
+    {
+        println_display(
+            config,
+            format!(
+                "Creating escrow account {} owned by escrow authority {}",
+                escrow, escrow_authority
+            ),
+        );
+        let response = token
+            .create_associated_token_account(&escrow_authority)
+            .await?;
+        report.escrow_signature = Some(signature_of(config, &response).await?);
+        write_json_file(report_path, &report)?;
+    }
+
+    // the escrow is funded once, with everything the snapshotted holders can
+    // exchange
+    if !report.escrow_funded {
+        let escrow_balance = token.get_account_info(&escrow).await?.base.amount;
+        if escrow_balance < report.total_amount {
+            println_display(
+                config,
+                format!(
+                    "Funding escrow account {} with {}",
+                    escrow,
+                    spl_token_2022::amount_to_ui_amount_string_trimmed(
+                        report.total_amount - escrow_balance,
+                        mint_info.decimals
+                    )
+                ),
+            );
+            let response = token
+                .mint_to(
+                    &escrow,
+                    &mint_authority,
+                    report.total_amount - escrow_balance,
+                    &[mint_authority_signer],
+                )
+                .await?;
+            report.funding_signature = Some(signature_of(config, &response).await?);
+        }
+        report.escrow_funded = true;
+        write_json_file(report_path, &report)?;
+    }
+
+    // holders whose original account is now empty have exchanged on their
+    // own, and the others may have opted in since the last run
+    let original_program_id = mint_info.program_id;
+    let pending = report
+        .holders
+        .iter()
+        .enumerate()
+        .filter(|(_, holder)| holder.status == CliMigrationHolderStatus::Pending)
+        .map(|(i, holder)| Ok((i, holder.account.parse::<Pubkey>()?)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let mut opted_in = vec![];
+    for chunk in pending.chunks(100) {
+        let addresses = chunk
+            .iter()
+            .map(|(_, address)| *address)
+            .collect::<Vec<_>>();
+        let accounts = config.rpc_client.get_multiple_accounts(&addresses).await?;
+        for ((i, address), account) in chunk.iter().zip(accounts) {
+            let state = account
+                .and_then(|account| StateWithExtensionsOwned::<Account>::unpack(account.data).ok());
+            match state {
+                Some(state) if state.base.amount > 0 => {
+                    if state.base.delegate == COption::Some(airdrop_authority)
+                        && state.base.delegated_amount >= state.base.amount
+                    {
+                        opted_in.push((*i, *address, state.base.owner));
+                    }
+                }
+                _ => report.holders[*i].status = CliMigrationHolderStatus::Exchanged,
+            }
+        }
+    }
+    write_json_file(report_path, &report)?;
+
+    let mut failed = 0;
+    if let Some(airdrop_authority_signer) = airdrop_authority_signer {
+        if !opted_in.is_empty() {
+            println_display(
+                config,
+                format!("Airdropping to {} holders who opted in", opted_in.len()),
+            );
+            let payer = config.fee_payer()?;
+            let mut sender = BatchSender::new(config.program_client.clone(), payer.clone())
+                .with_signers(vec![airdrop_authority_signer]);
+            if let Some(compute_unit_price) = config.compute_unit_price {
+                sender = sender.with_compute_unit_price(compute_unit_price);
+            }
+            if let ComputeUnitLimit::Static(compute_unit_limit) = config.compute_unit_limit {
+                sender = sender.with_compute_unit_limit(compute_unit_limit);
+            }
+
+            for chunk in opted_in.chunks(AIRDROP_CHUNK_SIZE) {
+                let instruction_sets = chunk
+                    .iter()
+                    .map(|(_, address, owner)| {
+                        let destination = get_associated_token_address_with_program_id(
+                            owner,
+                            &new_token_pubkey,
+                            &spl_token_2022::id(),
+                        );
+                        vec![
+                            create_associated_token_account_idempotent(
+                                &payer.pubkey(),
+                                owner,
+                                &new_token_pubkey,
+                                &spl_token_2022::id(),
+                            ),
+                            exchange(
+                                &spl_token_upgrade::id(),
+                                address,
+                                &token_pubkey,
+                                &escrow,
+                                &destination,
+                                &new_token_pubkey,
+                                &original_program_id,
+                                &spl_token_2022::id(),
+                                &airdrop_authority,
+                                &[],
+                            ),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let batch_report = sender.send(&instruction_sets).await?;
+                for (j, (i, _, _)) in chunk.iter().enumerate() {
+                    let holder = &mut report.holders[*i];
+                    match batch_report.instruction_result(j) {
+                        Some(Ok(RpcClientResponse::Signature(signature))) => {
+                            holder.status = CliMigrationHolderStatus::Airdropped;
+                            holder.signature = Some(signature.to_string());
+                            holder.error = None;
+                        }
+                        Some(Err(err)) => {
+                            holder.error = Some(err.to_string());
+                            failed += 1;
+                        }
+                        _ => never_sign_only(&CommandName::MigrateMint),
+                    }
+                }
+                write_json_file(report_path, &report)?;
+            }
+        }
+    }
+
+    let output = format_output(report, &CommandName::MigrateMint, config);
+    if failed == 0 {
+        Ok(output)
+    } else {
+        print_output_and_fail(
+            output,
+            format!(
+                "Error: {} airdrops failed; run the command again to retry them",
+                failed
+            ),
+        )
+    }
+}
@@ -12,6 +12,11 @@ use solana_account_decoder::{
     },
 };
 use solana_cli_output::{display::writeln_name_value, OutputFormat, QuietDisplay, VerboseDisplay};
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::{
+    group_pointer::GroupPointer, scaled_ui_amount::ScaledUiAmountConfig,
+};
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
 use std::fmt::{self, Display};
 
 pub(crate) trait Output: Serialize + fmt::Display + QuietDisplay + VerboseDisplay {}
@@ -132,6 +137,33 @@ impl fmt::Display for CliTokenAmount {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliConfidentialBalance {
+    pub(crate) address: String,
+    pub(crate) mint: String,
+    pub(crate) pending_balance: String,
+    pub(crate) available_balance: String,
+    pub(crate) pending_balance_credit_counter: u64,
+}
+
+impl QuietDisplay for CliConfidentialBalance {}
+impl VerboseDisplay for CliConfidentialBalance {}
+
+impl fmt::Display for CliConfidentialBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln_name_value(f, "Address:", &self.address)?;
+        writeln_name_value(f, "Mint:", &self.mint)?;
+        writeln_name_value(f, "Pending balance:", &self.pending_balance)?;
+        writeln_name_value(f, "Available balance:", &self.available_balance)?;
+        writeln_name_value(
+            f,
+            "Pending balance credits:",
+            &self.pending_balance_credit_counter.to_string(),
+        )
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CliWalletAddress {
@@ -196,6 +228,8 @@ pub(crate) struct CliTokenAccount {
     pub(crate) account: UiTokenAccount,
     #[serde(skip_serializing)]
     pub(crate) has_permanent_delegate: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) vesting: Option<CliVesting>,
 }
 
 impl QuietDisplay for CliTokenAccount {}
@@ -250,6 +284,23 @@ impl fmt::Display for CliTokenAccount {
                 .unwrap_or(&String::new()),
         )?;
 
+        if let Some(vesting) = &self.vesting {
+            writeln!(f, "  {}", style("Vesting:").bold())?;
+            writeln_name_value(f, "    Total:", &vesting.total_amount)?;
+            writeln_name_value(f, "    Locked:", &vesting.locked_amount)?;
+            writeln_name_value(
+                f,
+                "    Start timestamp:",
+                &vesting.start_timestamp.to_string(),
+            )?;
+            writeln_name_value(
+                f,
+                "    Cliff timestamp:",
+                &vesting.cliff_timestamp.to_string(),
+            )?;
+            writeln_name_value(f, "    End timestamp:", &vesting.end_timestamp.to_string())?;
+        }
+
         if !self.account.extensions.is_empty() {
             writeln!(f, "{}", style("Extensions:").bold())?;
             for extension in &self.account.extensions {
@@ -291,6 +342,14 @@ pub(crate) struct CliMint {
     pub(crate) epoch: u64,
     #[serde(flatten)]
     pub(crate) mint: UiMint,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) scaled_ui_amount: Option<CliScaledUiAmount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) group_pointer: Option<CliGroupPointer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) group: Option<CliTokenGroup>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) group_member: Option<CliTokenGroupMember>,
 }
 
 impl QuietDisplay for CliMint {}
@@ -326,10 +385,587 @@ impl fmt::Display for CliMint {
             }
         }
 
+        if let Some(scaled_ui_amount) = &self.scaled_ui_amount {
+            writeln!(f, "  {}", style("Scaled UI amount:").bold())?;
+            writeln_name_value(
+                f,
+                "    Multiplier:",
+                &scaled_ui_amount.multiplier.to_string(),
+            )?;
+            if scaled_ui_amount.new_multiplier_effective_timestamp != 0 {
+                writeln_name_value(
+                    f,
+                    "    New multiplier:",
+                    &scaled_ui_amount.new_multiplier.to_string(),
+                )?;
+                writeln_name_value(
+                    f,
+                    "    New multiplier effective timestamp:",
+                    &scaled_ui_amount
+                        .new_multiplier_effective_timestamp
+                        .to_string(),
+                )?;
+            }
+            writeln_name_value(
+                f,
+                "    Multiplier authority:",
+                scaled_ui_amount
+                    .authority
+                    .as_ref()
+                    .unwrap_or(&String::new()),
+            )?;
+        }
+
+        if let Some(group_pointer) = &self.group_pointer {
+            writeln!(f, "  {}", style("Group Pointer:").bold())?;
+            writeln_name_value(
+                f,
+                "    Authority:",
+                group_pointer.authority.as_ref().unwrap_or(&String::new()),
+            )?;
+            writeln_name_value(
+                f,
+                "    Group address:",
+                group_pointer
+                    .group_address
+                    .as_ref()
+                    .unwrap_or(&String::new()),
+            )?;
+        }
+
+        if let Some(group) = &self.group {
+            writeln!(f, "  {}", style("Token Group:").bold())?;
+            writeln_name_value(
+                f,
+                "    Update Authority:",
+                group.update_authority.as_ref().unwrap_or(&String::new()),
+            )?;
+            writeln_name_value(f, "    Mint:", &group.mint)?;
+            writeln_name_value(f, "    Size:", &group.size.to_string())?;
+            writeln_name_value(f, "    Max Size:", &group.max_size.to_string())?;
+        }
+
+        if let Some(group_member) = &self.group_member {
+            writeln!(f, "  {}", style("Token Group Member:").bold())?;
+            writeln_name_value(f, "    Mint:", &group_member.mint)?;
+            writeln_name_value(f, "    Group:", &group_member.group)?;
+            writeln_name_value(
+                f,
+                "    Member Number:",
+                &group_member.member_number.to_string(),
+            )?;
+        }
+
         Ok(())
     }
 }
 
+/// The account decoder doesn't know about the scaled UI amount extension yet,
+/// so its config is carried separately
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliScaledUiAmount {
+    pub(crate) authority: Option<String>,
+    pub(crate) multiplier: f64,
+    pub(crate) new_multiplier_effective_timestamp: i64,
+    pub(crate) new_multiplier: f64,
+}
+
+impl From<&ScaledUiAmountConfig> for CliScaledUiAmount {
+    fn from(config: &ScaledUiAmountConfig) -> Self {
+        Self {
+            authority: Option::<Pubkey>::from(config.authority).map(|pubkey| pubkey.to_string()),
+            multiplier: config.multiplier.into(),
+            new_multiplier_effective_timestamp: config.new_multiplier_effective_timestamp.into(),
+            new_multiplier: config.new_multiplier.into(),
+        }
+    }
+}
+
+/// The account decoder doesn't know about the group extensions yet, so the
+/// pointer, group, and member configurations are carried separately
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliGroupPointer {
+    pub(crate) authority: Option<String>,
+    pub(crate) group_address: Option<String>,
+}
+
+impl From<&GroupPointer> for CliGroupPointer {
+    fn from(group_pointer: &GroupPointer) -> Self {
+        Self {
+            authority: Option::<Pubkey>::from(group_pointer.authority)
+                .map(|pubkey| pubkey.to_string()),
+            group_address: Option::<Pubkey>::from(group_pointer.group_address)
+                .map(|pubkey| pubkey.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliTokenGroup {
+    pub(crate) update_authority: Option<String>,
+    pub(crate) mint: String,
+    pub(crate) size: u32,
+    pub(crate) max_size: u32,
+}
+
+impl From<&TokenGroup> for CliTokenGroup {
+    fn from(group: &TokenGroup) -> Self {
+        Self {
+            update_authority: Option::<Pubkey>::from(group.update_authority)
+                .map(|pubkey| pubkey.to_string()),
+            mint: group.mint.to_string(),
+            size: group.size.into(),
+            max_size: group.max_size.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliTokenGroupMember {
+    pub(crate) mint: String,
+    pub(crate) group: String,
+    pub(crate) member_number: u32,
+}
+
+impl From<&TokenGroupMember> for CliTokenGroupMember {
+    fn from(member: &TokenGroupMember) -> Self {
+        Self {
+            mint: member.mint.to_string(),
+            group: member.group.to_string(),
+            member_number: member.member_number.into(),
+        }
+    }
+}
+
+/// The account decoder doesn't know about the vesting extension yet, so the
+/// schedule is carried separately, along with the amount still locked at the
+/// cluster's current time
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliVesting {
+    pub(crate) total_amount: String,
+    pub(crate) locked_amount: String,
+    pub(crate) start_timestamp: i64,
+    pub(crate) cliff_timestamp: i64,
+    pub(crate) end_timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliDistribution {
+    pub(crate) token: String,
+    pub(crate) sender: String,
+    pub(crate) state_file: String,
+    pub(crate) total_distributed: String,
+    pub(crate) transfers: Vec<CliDistributionTransfer>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliDistributionTransfer {
+    pub(crate) line: usize,
+    pub(crate) recipient: String,
+    pub(crate) amount: String,
+    pub(crate) status: CliDistributionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CliDistributionStatus {
+    PreviouslySent,
+    Sent,
+    Failed,
+}
+
+impl CliDistribution {
+    fn count(&self, status: CliDistributionStatus) -> usize {
+        self.transfers
+            .iter()
+            .filter(|transfer| transfer.status == status)
+            .count()
+    }
+
+    fn write_summary(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(w)?;
+        writeln!(w, "{}", style("Distribution").bold())?;
+        writeln_name_value(w, "  Token:", &self.token)?;
+        writeln_name_value(w, "  Sender:", &self.sender)?;
+        writeln_name_value(w, "  State file:", &self.state_file)?;
+        writeln_name_value(w, "  Recipients:", &self.transfers.len().to_string())?;
+        writeln_name_value(
+            w,
+            "  Previously sent:",
+            &self
+                .count(CliDistributionStatus::PreviouslySent)
+                .to_string(),
+        )?;
+        writeln_name_value(
+            w,
+            "  Sent:",
+            &self.count(CliDistributionStatus::Sent).to_string(),
+        )?;
+        writeln_name_value(
+            w,
+            "  Failed:",
+            &self.count(CliDistributionStatus::Failed).to_string(),
+        )?;
+        writeln_name_value(w, "  Total distributed:", &self.total_distributed)
+    }
+}
+
+impl QuietDisplay for CliDistribution {}
+impl VerboseDisplay for CliDistribution {
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_summary(w)?;
+        writeln!(w, "  {}", style("Transfers:").bold())?;
+        for transfer in &self.transfers {
+            let result = match transfer.status {
+                CliDistributionStatus::Failed => transfer.error.clone().unwrap_or_default(),
+                _ => transfer.signature.clone().unwrap_or_default(),
+            };
+            writeln!(
+                w,
+                "    {:>6}  {:<44}  {}  {}",
+                transfer.line, transfer.recipient, transfer.amount, result
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CliDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_summary(f)?;
+        if self.count(CliDistributionStatus::Failed) > 0 {
+            writeln!(f, "  {}", style("Failed transfers:").bold())?;
+            for transfer in &self.transfers {
+                if let Some(error) = &transfer.error {
+                    writeln!(
+                        f,
+                        "    {:>6}  {:<44}  {}  {}",
+                        transfer.line, transfer.recipient, transfer.amount, error
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliMigration {
+    pub(crate) report_file: String,
+    pub(crate) original_token: String,
+    pub(crate) original_program_id: String,
+    pub(crate) decimals: u8,
+    pub(crate) snapshot_slot: u64,
+    pub(crate) total_amount: u64,
+    pub(crate) new_token: Option<String>,
+    pub(crate) new_token_signature: Option<String>,
+    pub(crate) escrow: Option<String>,
+    pub(crate) escrow_authority: Option<String>,
+    pub(crate) escrow_signature: Option<String>,
+    pub(crate) escrow_funded: bool,
+    pub(crate) funding_signature: Option<String>,
+    pub(crate) airdrop_authority: String,
+    pub(crate) holders: Vec<CliMigrationHolder>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliMigrationHolder {
+    pub(crate) account: String,
+    pub(crate) owner: String,
+    pub(crate) amount: u64,
+    pub(crate) ui_amount: String,
+    pub(crate) status: CliMigrationHolderStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CliMigrationHolderStatus {
+    /// Holds original tokens, waiting to exchange or to opt in to the airdrop
+    Pending,
+    /// The original account was emptied outside of the airdrop
+    Exchanged,
+    /// Exchanged by the airdrop
+    Airdropped,
+}
+
+impl CliMigration {
+    fn count(&self, status: CliMigrationHolderStatus) -> usize {
+        self.holders
+            .iter()
+            .filter(|holder| holder.status == status)
+            .count()
+    }
+
+    fn write_summary(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        let not_done = "-".to_string();
+        writeln!(w)?;
+        writeln!(w, "{}", style("Migration").bold())?;
+        writeln_name_value(w, "  Original token:", &self.original_token)?;
+        writeln_name_value(
+            w,
+            "  New token:",
+            self.new_token.as_ref().unwrap_or(&not_done),
+        )?;
+        writeln_name_value(w, "  Escrow:", self.escrow.as_ref().unwrap_or(&not_done))?;
+        writeln_name_value(
+            w,
+            "  Escrow funded:",
+            &format!(
+                "{} ({})",
+                self.escrow_funded,
+                spl_token_2022::amount_to_ui_amount_string_trimmed(
+                    self.total_amount,
+                    self.decimals
+                )
+            ),
+        )?;
+        writeln_name_value(w, "  Airdrop authority:", &self.airdrop_authority)?;
+        writeln_name_value(w, "  Report file:", &self.report_file)?;
+        writeln_name_value(w, "  Snapshot slot:", &self.snapshot_slot.to_string())?;
+        writeln_name_value(w, "  Holders:", &self.holders.len().to_string())?;
+        writeln_name_value(
+            w,
+            "  Pending:",
+            &self.count(CliMigrationHolderStatus::Pending).to_string(),
+        )?;
+        writeln_name_value(
+            w,
+            "  Exchanged:",
+            &self.count(CliMigrationHolderStatus::Exchanged).to_string(),
+        )?;
+        writeln_name_value(
+            w,
+            "  Airdropped:",
+            &self.count(CliMigrationHolderStatus::Airdropped).to_string(),
+        )
+    }
+
+    fn write_holder(w: &mut dyn fmt::Write, holder: &CliMigrationHolder) -> fmt::Result {
+        let result = holder
+            .error
+            .as_ref()
+            .or(holder.signature.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        writeln!(
+            w,
+            "    {:<44}  {:<44}  {}  {}",
+            holder.account, holder.owner, holder.ui_amount, result
+        )
+    }
+}
+
+impl QuietDisplay for CliMigration {}
+impl VerboseDisplay for CliMigration {
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_summary(w)?;
+        for (title, status) in [
+            ("Pending:", CliMigrationHolderStatus::Pending),
+            ("Exchanged:", CliMigrationHolderStatus::Exchanged),
+            ("Airdropped:", CliMigrationHolderStatus::Airdropped),
+        ] {
+            writeln!(w, "  {}", style(title).bold())?;
+            for holder in &self.holders {
+                if holder.status == status {
+                    Self::write_holder(w, holder)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CliMigration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_summary(f)?;
+        if self.count(CliMigrationHolderStatus::Pending) > 0 {
+            writeln!(f, "  {}", style("Pending holders:").bold())?;
+            for holder in &self.holders {
+                if holder.status == CliMigrationHolderStatus::Pending {
+                    Self::write_holder(f, holder)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliProposal {
+    pub(crate) file: String,
+    pub(crate) command: String,
+    pub(crate) blockhash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) nonce_account: Option<String>,
+    pub(crate) instructions: Vec<CliProposalInstruction>,
+    pub(crate) signers: Vec<CliProposalSigner>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliProposalInstruction {
+    pub(crate) program_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) program: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) parsed: Option<serde_json::Value>,
+    /// Base64 data of instructions that couldn't be decoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) data: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliProposalSigner {
+    pub(crate) pubkey: String,
+    pub(crate) signed: bool,
+}
+
+impl QuietDisplay for CliProposal {}
+impl VerboseDisplay for CliProposal {}
+
+impl fmt::Display for CliProposal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "{}", style("Proposal").bold())?;
+        writeln_name_value(f, "  File:", &self.file)?;
+        writeln_name_value(f, "  Command:", &self.command)?;
+        writeln_name_value(f, "  Blockhash:", &self.blockhash)?;
+        if let Some(nonce_account) = &self.nonce_account {
+            writeln_name_value(f, "  Nonce account:", nonce_account)?;
+        }
+
+        writeln!(f, "  {}", style("Instructions:").bold())?;
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            write!(f, "    {}. ", i + 1)?;
+            match (&instruction.program, &instruction.parsed) {
+                (Some(program), Some(serde_json::Value::Object(parsed))) => {
+                    let instruction_type = parsed
+                        .get("type")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default();
+                    writeln!(f, "{}: {}", program, instruction_type)?;
+                    if let Some(serde_json::Value::Object(info)) = parsed.get("info") {
+                        for (name, value) in info {
+                            writeln_name_value(
+                                f,
+                                &format!("         {}:", name),
+                                &display_parsed_value(value),
+                            )?;
+                        }
+                    }
+                }
+                (Some(program), Some(parsed)) => {
+                    writeln!(f, "{}: {}", program, display_parsed_value(parsed))?;
+                }
+                _ => {
+                    writeln!(f, "{}", instruction.program_id)?;
+                    if let Some(data) = &instruction.data {
+                        writeln_name_value(f, "         Data:", data)?;
+                    }
+                }
+            }
+        }
+
+        let signed = self.signers.iter().filter(|signer| signer.signed).count();
+        writeln!(
+            f,
+            "  {}",
+            style(format!("Signatures ({}/{}):", signed, self.signers.len())).bold()
+        )?;
+        for signer in &self.signers {
+            writeln!(
+                f,
+                "    {:<44}  {}",
+                signer.pubkey,
+                if signer.signed { "signed" } else { "missing" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// token amounts are shown as their UI amount, and anything else that isn't a
+// plain value as compact JSON
+fn display_parsed_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(string) => string.clone(),
+        serde_json::Value::Object(object) => match object.get("uiAmountString") {
+            Some(serde_json::Value::String(ui_amount)) => ui_amount.clone(),
+            _ => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliWatchEvent {
+    pub(crate) slot: u64,
+    #[serde(flatten)]
+    pub(crate) account: CliTokenAccount,
+    pub(crate) balance_change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) withheld_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) withheld_change: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pending_balance_credit_counter: Option<u64>,
+}
+
+impl QuietDisplay for CliWatchEvent {}
+impl VerboseDisplay for CliWatchEvent {
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        write!(w, "{}", self)?;
+        writeln_name_value(w, "  Mint:", &self.account.account.mint)?;
+        writeln_name_value(w, "  Owner:", &self.account.account.owner)?;
+        writeln_name_value(w, "  Program:", &self.account.program_id)
+    }
+}
+
+impl fmt::Display for CliWatchEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[slot {}] {}  balance: {} ({})",
+            self.slot,
+            self.account.address,
+            self.account
+                .account
+                .token_amount
+                .real_number_string_trimmed(),
+            self.balance_change,
+        )?;
+        if let (Some(withheld_amount), Some(withheld_change)) =
+            (&self.withheld_amount, &self.withheld_change)
+        {
+            write!(f, "  withheld: {} ({})", withheld_amount, withheld_change)?;
+        }
+        if let Some(counter) = self.pending_balance_credit_counter {
+            write!(f, "  pending credits: {}", counter)?;
+        }
+        writeln!(f)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CliTokenAccounts {
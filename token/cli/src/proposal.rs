@@ -0,0 +1,531 @@
+/// The `propose`, `sign` and `submit` subcommands, which pass a transaction
+/// between the members of a multisig in a file
+use {
+    crate::{
+        app,
+        config::Config,
+        finish_tx, format_output, minimum_signers_help_string, multisig_member_help_string,
+        output::{CliProposal, CliProposalInstruction, CliProposalSigner},
+        process_command, write_json_file, BulkSigners, CommandName, CommandResult, Error,
+        TransactionReturnData, COMPUTE_UNIT_LIMIT_ARG,
+    },
+    async_trait::async_trait,
+    base64::{prelude::BASE64_STANDARD, Engine},
+    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    serde::{Deserialize, Serialize},
+    solana_clap_utils::{
+        compute_unit_price::COMPUTE_UNIT_PRICE_ARG,
+        fee_payer::FEE_PAYER_ARG,
+        input_parsers::{pubkey_of_signer, value_of},
+        input_validators::is_valid_signer,
+        keypair::signer_from_path,
+        nonce::NONCE_ARG,
+        offline::{BLOCKHASH_ARG, DUMP_TRANSACTION_MESSAGE, SIGN_ONLY_ARG},
+    },
+    solana_client::{nonblocking::rpc_client::RpcClient, nonce_utils},
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_sdk::{
+        account::Account,
+        hash::Hash,
+        message::AccountKeys,
+        pubkey::Pubkey,
+        signer::Signer,
+        transaction::{Transaction, VersionedTransaction},
+    },
+    solana_transaction_status::{
+        parse_instruction, Encodable, EncodedTransaction, UiTransactionEncoding,
+    },
+    spl_token_client::client::{
+        ProgramClient, ProgramClientResult, ProgramRpcClient, ProgramRpcClientSendTransaction,
+        RpcClientResponse,
+    },
+    std::{
+        fs,
+        future::Future,
+        path::Path,
+        pin::Pin,
+        rc::Rc,
+        str::FromStr,
+        sync::{Arc, Mutex},
+    },
+};
+
+pub(crate) trait ProposalSubCommands {
+    fn proposal_subcommands(self) -> Self;
+}
+
+impl ProposalSubCommands for App<'_, '_> {
+    fn proposal_subcommands(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name(CommandName::Propose.into())
+                .about("Write the transaction of a command to a file, to be signed by each of its signers")
+                .setting(AppSettings::TrailingVarArg)
+                .arg(bundle_arg())
+                .arg(
+                    Arg::with_name("command")
+                        .value_name("COMMAND")
+                        .takes_value(true)
+                        .index(2)
+                        .multiple(true)
+                        .allow_hyphen_values(true)
+                        .required(true)
+                        .help("The command to propose, with its arguments. Signers that are not \
+                            available may be given by address, and `--nonce` makes the proposal \
+                            outlast the blockhash"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Sign.into())
+                .about("Add signatures to a proposed transaction")
+                .arg(bundle_arg())
+                .arg(
+                    Arg::with_name("signers")
+                        .long("signer")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Keypair to sign with, such as a keypair file or a hardware \
+                            wallet URL. May be specified multiple times \
+                            [default: client keypair]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Submit.into())
+                .about("Send a proposed transaction once it has all of its signatures")
+                .arg(bundle_arg()),
+        )
+    }
+}
+
+fn bundle_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("bundle")
+        .value_name("PROPOSAL_FILE")
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("File holding the proposed transaction")
+}
+
+/// A proposed transaction, with the signatures collected so far
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProposalBundle {
+    command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nonce_account: Option<String>,
+    transaction: EncodedTransaction,
+}
+
+fn read_bundle(path: &str) -> Result<(ProposalBundle, Transaction), Error> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Error: unable to read {}: {}", path, err))?;
+    let bundle: ProposalBundle = serde_json::from_str(&contents)
+        .map_err(|err| format!("Error: invalid proposal file {}: {}", path, err))?;
+    let transaction = bundle
+        .transaction
+        .decode()
+        .and_then(VersionedTransaction::into_legacy_transaction)
+        .ok_or_else(|| format!("Error: {} does not contain a valid transaction", path))?;
+    Ok((bundle, transaction))
+}
+
+/// Client that reads accounts from the cluster, but keeps the transactions of
+/// a proposed command instead of sending them
+struct ProposalClient {
+    client: ProgramRpcClient<ProgramRpcClientSendTransaction>,
+    blockhash: Hash,
+    transactions: Mutex<Vec<Transaction>>,
+}
+
+#[async_trait]
+impl ProgramClient<ProgramRpcClientSendTransaction> for ProposalClient {
+    async fn get_minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> ProgramClientResult<u64> {
+        self.client
+            .get_minimum_balance_for_rent_exemption(data_len)
+            .await
+    }
+
+    async fn get_latest_blockhash(&self) -> ProgramClientResult<Hash> {
+        Ok(self.blockhash)
+    }
+
+    async fn send_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> ProgramClientResult<RpcClientResponse> {
+        self.transactions.lock().unwrap().push(transaction.clone());
+        Ok(RpcClientResponse::Transaction(transaction.clone()))
+    }
+
+    async fn get_account(&self, address: Pubkey) -> ProgramClientResult<Option<Account>> {
+        self.client.get_account(address).await
+    }
+
+    async fn simulate_transaction(
+        &self,
+        _transaction: &Transaction,
+    ) -> ProgramClientResult<RpcClientResponse> {
+        Err("Unable to simulate a proposed transaction".into())
+    }
+
+    async fn send_versioned_transaction(
+        &self,
+        _transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<RpcClientResponse> {
+        Err("Versioned transactions cannot be proposed".into())
+    }
+
+    async fn simulate_versioned_transaction(
+        &self,
+        _transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<RpcClientResponse> {
+        Err("Versioned transactions cannot be proposed".into())
+    }
+}
+
+// `process_command` dispatches to `propose`, so the proposed command's future
+// must be boxed to break the cycle
+fn process_proposed_command<'a>(
+    sub_command: &'a CommandName,
+    sub_matches: &'a ArgMatches<'a>,
+    config: &'a Config<'a>,
+    wallet_manager: Option<Rc<RemoteWalletManager>>,
+    bulk_signers: BulkSigners,
+) -> Pin<Box<dyn Future<Output = CommandResult> + 'a>> {
+    Box::pin(process_command(
+        sub_command,
+        sub_matches,
+        config,
+        wallet_manager,
+        bulk_signers,
+    ))
+}
+
+async fn nonce_blockhash(rpc_client: &RpcClient, nonce_account: &Pubkey) -> Result<Hash, Error> {
+    let account = nonce_utils::nonblocking::get_account_with_commitment(
+        rpc_client,
+        nonce_account,
+        rpc_client.commitment(),
+    )
+    .await?;
+    Ok(nonce_utils::nonblocking::data_from_account(&account)?.blockhash())
+}
+
+pub(crate) async fn propose_process_command(
+    matches: &ArgMatches<'_>,
+    config: &Config<'_>,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let bundle_path = matches.value_of("bundle").unwrap();
+    let command = matches
+        .values_of("command")
+        .unwrap()
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    if Path::new(bundle_path).exists() {
+        return Err(format!("Error: {} already exists", bundle_path).into());
+    }
+    if matches!(
+        CommandName::from_str(&command[0]),
+        Ok(CommandName::Propose | CommandName::Sign | CommandName::Submit)
+    ) {
+        return Err(format!("Error: `{}` cannot be proposed", command[0]).into());
+    }
+    for arg in [SIGN_ONLY_ARG, BLOCKHASH_ARG, DUMP_TRANSACTION_MESSAGE] {
+        if command.contains(&format!("--{}", arg.long)) {
+            return Err(format!("Error: --{} cannot be used in a proposal", arg.long).into());
+        }
+    }
+
+    let default_decimals = format!("{}", spl_token_2022::native_mint::DECIMALS);
+    let minimum_signers_help = minimum_signers_help_string();
+    let multisig_member_help = multisig_member_help_string();
+    let parse = |args: &[String]| {
+        app(
+            &default_decimals,
+            &minimum_signers_help,
+            &multisig_member_help,
+        )
+        .get_matches_from_safe(args)
+        .map_err(|err| format!("Error: unable to propose `{}`: {}", command.join(" "), err))
+    };
+    // signers that aren't available are given by address, which requires
+    // `--sign-only`, so the command must support it
+    let offline_args = |blockhash: Hash| {
+        vec![
+            format!("--{}", SIGN_ONLY_ARG.long),
+            format!("--{}", BLOCKHASH_ARG.long),
+            blockhash.to_string(),
+        ]
+    };
+
+    let mut args = std::iter::once("spl-token".to_string())
+        .chain(command.iter().cloned())
+        .collect::<Vec<_>>();
+    let first_pass = parse(&[args.clone(), offline_args(Hash::default())].concat())?;
+    let (_, first_pass) = first_pass.subcommand();
+    let first_pass = first_pass.unwrap();
+
+    let nonce_account = pubkey_of_signer(first_pass, NONCE_ARG.name, wallet_manager)
+        .map_err(|err| format!("Error: {}", err))?;
+    let blockhash = if let Some(nonce_account) = nonce_account {
+        nonce_blockhash(&config.rpc_client, &nonce_account).await?
+    } else {
+        config.rpc_client.get_latest_blockhash().await?
+    };
+
+    // global arguments given before `propose` apply to the proposed command
+    for (name, long) in [
+        ("config_file", "config"),
+        ("output_format", "output"),
+        (FEE_PAYER_ARG.name, FEE_PAYER_ARG.long),
+        (COMPUTE_UNIT_PRICE_ARG.name, COMPUTE_UNIT_PRICE_ARG.long),
+        (COMPUTE_UNIT_LIMIT_ARG.name, COMPUTE_UNIT_LIMIT_ARG.long),
+    ] {
+        if let (Some(value), false) = (matches.value_of(name), first_pass.is_present(name)) {
+            args.push(format!("--{}", long));
+            args.push(value.to_string());
+        }
+    }
+    // the program id isn't looked up when signing offline
+    if !first_pass.is_present("program_id") {
+        let program_id = if config.restrict_to_program_id {
+            Some(config.program_id)
+        } else if let Some(address) = value_of::<Pubkey>(first_pass, "token")
+            .or_else(|| value_of(first_pass, "account"))
+            .or_else(|| value_of(first_pass, "address"))
+        {
+            config
+                .rpc_client
+                .get_account(&address)
+                .await
+                .ok()
+                .map(|account| account.owner)
+        } else {
+            None
+        };
+        if let Some(program_id) = program_id {
+            args.push("--program-id".to_string());
+            args.push(program_id.to_string());
+        }
+    }
+    args.extend(offline_args(blockhash));
+
+    let proposal_matches = parse(&args)?;
+    let (sub_command, sub_matches) = proposal_matches.subcommand();
+    let sub_command = CommandName::from_str(sub_command).unwrap();
+    let sub_matches = sub_matches.unwrap();
+
+    let client = Arc::new(ProposalClient {
+        client: ProgramRpcClient::new(config.rpc_client.clone(), ProgramRpcClientSendTransaction),
+        blockhash,
+        transactions: Mutex::new(vec![]),
+    });
+    let mut proposal_wallet_manager = wallet_manager.clone();
+    let mut bulk_signers: BulkSigners = Vec::new();
+    let mut multisigner_ids = Vec::new();
+    let mut proposal_config = Config::new_with_clients(
+        sub_matches,
+        &mut proposal_wallet_manager,
+        &mut bulk_signers,
+        &mut multisigner_ids,
+        config.rpc_client.clone(),
+        client.clone(),
+    )
+    .await;
+    // the proposal client still reads the cluster, so the command can run
+    // its usual checks
+    proposal_config.sign_only = false;
+
+    process_proposed_command(
+        &sub_command,
+        sub_matches,
+        &proposal_config,
+        proposal_wallet_manager,
+        bulk_signers,
+    )
+    .await?;
+
+    let mut transactions = std::mem::take(&mut *client.transactions.lock().unwrap());
+    if transactions.len() != 1 {
+        return Err(format!(
+            "Error: `{}` needs {} transactions, but only single transaction commands can be proposed",
+            command.join(" "),
+            transactions.len()
+        )
+        .into());
+    }
+    let transaction = transactions.remove(0);
+
+    let bundle = ProposalBundle {
+        command: command.join(" "),
+        nonce_account: nonce_account.map(|address| address.to_string()),
+        transaction: transaction.encode(UiTransactionEncoding::Base64),
+    };
+    write_json_file(bundle_path, &bundle)?;
+
+    Ok(format_output(
+        proposal_output(bundle_path, &bundle, &transaction),
+        &CommandName::Propose,
+        config,
+    ))
+}
+
+pub(crate) async fn sign_process_command(
+    matches: &ArgMatches<'_>,
+    config: &Config<'_>,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let bundle_path = matches.value_of("bundle").unwrap();
+    let signers = if let Some(paths) = matches.values_of("signers") {
+        paths
+            .map(|path| {
+                signer_from_path(matches, path, "signer", wallet_manager)
+                    .map(Arc::from)
+                    .map_err(|err| format!("Error: {}", err))
+            })
+            .collect::<Result<Vec<Arc<dyn Signer>>, _>>()?
+    } else {
+        vec![config.default_signer()?]
+    };
+
+    let (mut bundle, mut transaction) = read_bundle(bundle_path)?;
+    let message_data = transaction.message_data();
+    let num_required_signatures = transaction.message.header.num_required_signatures as usize;
+    for signer in signers {
+        let pubkey = signer.pubkey();
+        let index = transaction.message.account_keys[..num_required_signatures]
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or_else(|| {
+                format!(
+                    "Error: {} is not a signer of the proposed transaction",
+                    pubkey
+                )
+            })?;
+        let signature = signer.try_sign_message(&message_data)?;
+        if !signature.verify(pubkey.as_ref(), &message_data) {
+            return Err(format!("Error: invalid signature from {}", pubkey).into());
+        }
+        transaction.signatures[index] = signature;
+    }
+
+    bundle.transaction = transaction.encode(UiTransactionEncoding::Base64);
+    write_json_file(bundle_path, &bundle)?;
+
+    Ok(format_output(
+        proposal_output(bundle_path, &bundle, &transaction),
+        &CommandName::Sign,
+        config,
+    ))
+}
+
+pub(crate) async fn submit_process_command(
+    matches: &ArgMatches<'_>,
+    config: &Config<'_>,
+) -> CommandResult {
+    let bundle_path = matches.value_of("bundle").unwrap();
+    let (bundle, transaction) = read_bundle(bundle_path)?;
+
+    let missing = transaction
+        .verify_with_results()
+        .into_iter()
+        .zip(&transaction.message.account_keys)
+        .filter(|(verified, _)| !verified)
+        .map(|(_, pubkey)| pubkey.to_string())
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Error: {} is missing signatures from {}",
+            bundle_path,
+            missing.join(", ")
+        )
+        .into());
+    }
+
+    let blockhash = transaction.message.recent_blockhash;
+    if let Some(nonce_account) = &bundle.nonce_account {
+        let nonce_account = Pubkey::from_str(nonce_account)?;
+        if nonce_blockhash(&config.rpc_client, &nonce_account).await? != blockhash {
+            return Err(format!(
+                "Error: nonce account {} has advanced since the transaction was proposed",
+                nonce_account
+            )
+            .into());
+        }
+    } else if !config
+        .rpc_client
+        .is_blockhash_valid(&blockhash, config.rpc_client.commitment())
+        .await?
+    {
+        return Err(format!(
+            "Error: the blockhash of {} has expired, propose the command again with `--nonce` \
+            to give signers more time",
+            bundle_path
+        )
+        .into());
+    }
+
+    let res = config.program_client.send_transaction(&transaction).await?;
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+/// Decode the instructions of a proposed transaction, and list who has
+/// signed it
+fn proposal_output(path: &str, bundle: &ProposalBundle, transaction: &Transaction) -> CliProposal {
+    let message = &transaction.message;
+    let account_keys = AccountKeys::new(&message.account_keys, None);
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            let program_id = &message.account_keys[instruction.program_id_index as usize];
+            match parse_instruction::parse(program_id, instruction, &account_keys, None) {
+                Ok(parsed) => CliProposalInstruction {
+                    program_id: parsed.program_id,
+                    program: Some(parsed.program),
+                    parsed: Some(parsed.parsed),
+                    data: None,
+                },
+                Err(_) => CliProposalInstruction {
+                    program_id: program_id.to_string(),
+                    program: None,
+                    parsed: None,
+                    data: Some(BASE64_STANDARD.encode(&instruction.data)),
+                },
+            }
+        })
+        .collect();
+    let signers = transaction
+        .verify_with_results()
+        .into_iter()
+        .zip(&message.account_keys)
+        .map(|(signed, pubkey)| CliProposalSigner {
+            pubkey: pubkey.to_string(),
+            signed,
+        })
+        .collect();
+
+    CliProposal {
+        file: path.to_string(),
+        command: bundle.command.clone(),
+        blockhash: message.recent_blockhash.to_string(),
+        nonce_account: bundle.nonce_account.clone(),
+        instructions,
+        signers,
+    }
+}
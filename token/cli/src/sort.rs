@@ -77,6 +77,7 @@ pub(crate) fn sort_and_parse_token_accounts(
                         account: ui_token_account,
                         is_associated,
                         has_permanent_delegate: false,
+                        vesting: None,
                     };
 
                     let entry = cli_accounts.entry(btree_key);
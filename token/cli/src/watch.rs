@@ -0,0 +1,280 @@
+/// The `watch` subcommand
+use {
+    crate::{
+        account_vesting, apply_mint_ui_multiplier,
+        config::Config,
+        has_permanent_delegate,
+        output::{CliTokenAccount, CliWatchEvent},
+        owner_address_arg, CommandName, CommandResult, Error, VALID_TOKEN_PROGRAM_IDS,
+    },
+    clap::{value_t, App, Arg, ArgMatches, SubCommand},
+    futures::stream::{self, StreamExt},
+    solana_account_decoder::{
+        parse_token::{parse_token, token_amount_to_ui_amount, TokenAccountType},
+        UiAccountEncoding,
+    },
+    solana_clap_utils::{
+        input_parsers::pubkey_of_signer,
+        input_validators::{is_parsable, is_valid_pubkey},
+    },
+    solana_client::{
+        nonblocking::pubsub_client::PubsubClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_sdk::{account::Account as RawAccount, pubkey::Pubkey},
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+    spl_token_2022::{
+        extension::{
+            confidential_transfer::ConfidentialTransferAccount, transfer_fee::TransferFeeAmount,
+            BaseStateWithExtensions, StateWithExtensionsOwned,
+        },
+        state::{Account, Mint},
+    },
+    std::{collections::HashMap, rc::Rc},
+};
+
+pub(crate) trait WatchSubCommand {
+    fn watch_subcommand(self) -> Self;
+}
+
+impl WatchSubCommand for App<'_, '_> {
+    fn watch_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name(CommandName::Watch.into())
+                .about("Print changes to the balances of token accounts as they happen")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .help(
+                            "Watch the accounts of this token \
+                            [default: all of the owner's accounts]",
+                        ),
+                )
+                .arg(owner_address_arg().help(
+                    "Watch the accounts of this owner. Defaults to the client keypair \
+                    address, unless a token is given.",
+                ))
+                .arg(
+                    Arg::with_name("limit")
+                        .long("limit")
+                        .validator(is_parsable::<usize>)
+                        .value_name("N")
+                        .takes_value(true)
+                        .help("Stop after printing this many changes"),
+                ),
+        )
+    }
+}
+
+pub(crate) async fn watch_process_command(
+    matches: &ArgMatches<'_>,
+    config: &Config<'_>,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let token = pubkey_of_signer(matches, "token", wallet_manager).unwrap();
+    let owner = if token.is_some() {
+        pubkey_of_signer(matches, "owner", wallet_manager).unwrap()
+    } else {
+        Some(config.pubkey_or_default(matches, "owner", wallet_manager)?)
+    };
+    let limit = value_t!(matches, "limit", usize).ok();
+
+    command_watch(config, token, owner, limit).await
+}
+
+/// The values of an account that changes are reported for
+#[derive(Clone, Copy, Default, PartialEq)]
+struct WatchedValues {
+    amount: u64,
+    withheld_amount: Option<u64>,
+    pending_balance_credit_counter: Option<u64>,
+}
+
+impl WatchedValues {
+    fn from_account(account: &StateWithExtensionsOwned<Account>) -> Self {
+        Self {
+            amount: account.base.amount,
+            withheld_amount: account
+                .get_extension::<TransferFeeAmount>()
+                .ok()
+                .map(|extension| extension.withheld_amount.into()),
+            pending_balance_credit_counter: account
+                .get_extension::<ConfidentialTransferAccount>()
+                .ok()
+                .map(|extension| extension.pending_balance_credit_counter.into()),
+        }
+    }
+}
+
+/// The difference between two amounts as a signed UI amount, scaled like the
+/// balance of the account
+async fn signed_ui_amount(
+    config: &Config<'_>,
+    mint_state: &StateWithExtensionsOwned<Mint>,
+    previous: u64,
+    current: u64,
+) -> Result<String, Error> {
+    let (sign, difference) = if current >= previous {
+        ("+", current - previous)
+    } else {
+        ("-", previous - current)
+    };
+    let mut ui_amount = token_amount_to_ui_amount(difference, mint_state.base.decimals);
+    apply_mint_ui_multiplier(config, mint_state, [&mut ui_amount]).await?;
+    Ok(format!(
+        "{}{}",
+        sign,
+        ui_amount.real_number_string_trimmed()
+    ))
+}
+
+async fn command_watch(
+    config: &Config<'_>,
+    token: Option<Pubkey>,
+    owner: Option<Pubkey>,
+    limit: Option<usize>,
+) -> CommandResult {
+    let program_ids = if config.restrict_to_program_id {
+        vec![config.program_id]
+    } else {
+        VALID_TOKEN_PROGRAM_IDS.to_vec()
+    };
+
+    let mut filters = vec![RpcFilterType::TokenAccountState];
+    if let Some(token) = token {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            token.as_ref(),
+        )));
+    }
+    if let Some(owner) = owner {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            32,
+            owner.as_ref(),
+        )));
+    }
+    let program_accounts_config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(config.rpc_client.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    // subscribe before reading the current state, so no change can be missed
+    let pubsub_client = PubsubClient::new(&config.websocket_url)
+        .await
+        .map_err(|err| {
+            format!(
+                "Error: unable to connect to {}: {}",
+                config.websocket_url, err
+            )
+        })?;
+    let mut subscriptions = vec![];
+    for program_id in &program_ids {
+        let (subscription, _unsubscribe) = pubsub_client
+            .program_subscribe(program_id, Some(program_accounts_config.clone()))
+            .await?;
+        subscriptions.push(subscription);
+    }
+    let mut notifications = stream::select_all(subscriptions);
+
+    let mut watched = HashMap::new();
+    for program_id in &program_ids {
+        let accounts = config
+            .rpc_client
+            .get_program_accounts_with_config(program_id, program_accounts_config.clone())
+            .await?;
+        for (address, account) in accounts {
+            if let Ok(account) = StateWithExtensionsOwned::<Account>::unpack(account.data) {
+                watched.insert(address, WatchedValues::from_account(&account));
+            }
+        }
+    }
+
+    let mut count = 0;
+    while limit != Some(count) {
+        let Some(notification) = notifications.next().await else {
+            return Err("Error: the subscription was closed by the cluster".into());
+        };
+        let address: Pubkey = notification.value.pubkey.parse()?;
+        let Some(account) = notification.value.account.decode::<RawAccount>() else {
+            continue;
+        };
+        let Ok(state) = StateWithExtensionsOwned::<Account>::unpack(account.data.clone()) else {
+            continue;
+        };
+        let current = WatchedValues::from_account(&state);
+        // a new account is reported as a change from zero
+        let previous = watched.insert(address, current).unwrap_or_default();
+        if previous == current {
+            continue;
+        }
+
+        let mint_account = config.get_account_checked(&state.base.mint).await?;
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", state.base.mint))?;
+        let TokenAccountType::Account(mut ui_account) =
+            parse_token(&account.data, Some(mint_state.base.decimals))?
+        else {
+            continue;
+        };
+        apply_mint_ui_multiplier(config, &mint_state, [&mut ui_account.token_amount]).await?;
+        let is_associated = get_associated_token_address_with_program_id(
+            &state.base.owner,
+            &state.base.mint,
+            &account.owner,
+        ) == address;
+        let vesting = account_vesting(config, &account.data, mint_state.base.decimals).await?;
+
+        let withheld_amount = match current.withheld_amount {
+            Some(withheld_amount) => {
+                let mut ui_amount =
+                    token_amount_to_ui_amount(withheld_amount, mint_state.base.decimals);
+                apply_mint_ui_multiplier(config, &mint_state, [&mut ui_amount]).await?;
+                Some(ui_amount.real_number_string_trimmed())
+            }
+            None => None,
+        };
+        let withheld_change = match current.withheld_amount {
+            Some(withheld_amount) => Some(
+                signed_ui_amount(
+                    config,
+                    &mint_state,
+                    previous.withheld_amount.unwrap_or_default(),
+                    withheld_amount,
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
+        let event = CliWatchEvent {
+            slot: notification.context.slot,
+            account: CliTokenAccount {
+                address: address.to_string(),
+                program_id: account.owner.to_string(),
+                is_associated,
+                account: ui_account,
+                has_permanent_delegate: has_permanent_delegate(&mint_state),
+                vesting,
+            },
+            balance_change: signed_ui_amount(config, &mint_state, previous.amount, current.amount)
+                .await?,
+            withheld_amount,
+            withheld_change,
+            pending_balance_credit_counter: current.pending_balance_credit_counter,
+        };
+        println!("{}", config.output_format.formatted_string(&event));
+        count += 1;
+    }
+
+    Ok(String::new())
+}
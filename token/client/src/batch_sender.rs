@@ -0,0 +1,388 @@
+//! Packs independent instructions into transactions and sends them in
+//! parallel
+
+use {
+    crate::client::{
+        ProgramClient, ProgramClientError, ProgramClientResult, SendTransaction,
+        SimulateTransaction,
+    },
+    futures::stream::{self, StreamExt},
+    solana_program_test::BanksClientError,
+    solana_rpc_client_api::{
+        client_error::{Error as ClientError, ErrorKind as ClientErrorKind},
+        request::RpcError,
+    },
+    solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        hash::Hash,
+        instruction::Instruction,
+        message::Message,
+        packet::PACKET_DATA_SIZE,
+        signature::SIGNATURE_BYTES,
+        signer::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+    std::sync::Arc,
+};
+
+/// Default number of transactions in flight at once
+pub const DEFAULT_MAX_PARALLELISM: usize = 8;
+
+/// Default number of times a transaction is re-signed and re-sent after its
+/// blockhash expired
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+
+/// Outcome of a single packed transaction
+#[derive(Debug)]
+pub struct BatchTransactionOutcome<O> {
+    /// Indices of the instruction sets packed into the transaction
+    pub instruction_indices: Vec<usize>,
+    /// Number of times the transaction was sent
+    pub attempts: usize,
+    /// Result of the final attempt
+    pub result: ProgramClientResult<O>,
+}
+
+/// Outcomes of all transactions sent for a batch
+#[derive(Debug)]
+pub struct BatchReport<O> {
+    /// Transaction outcomes, in the order the transactions were packed
+    pub transactions: Vec<BatchTransactionOutcome<O>>,
+    // position in `transactions` for every instruction set
+    instruction_transactions: Vec<usize>,
+}
+
+impl<O> BatchReport<O> {
+    /// Result of the transaction that carried the given instruction set
+    pub fn instruction_result(&self, index: usize) -> Option<&ProgramClientResult<O>> {
+        self.instruction_transactions
+            .get(index)
+            .map(|position| &self.transactions[*position].result)
+    }
+
+    /// Indices of the instruction sets whose transaction failed
+    pub fn failed_instructions(&self) -> Vec<usize> {
+        let mut failed = self
+            .transactions
+            .iter()
+            .filter(|outcome| outcome.result.is_err())
+            .flat_map(|outcome| outcome.instruction_indices.iter().copied())
+            .collect::<Vec<_>>();
+        failed.sort_unstable();
+        failed
+    }
+
+    /// Whether every transaction succeeded
+    pub fn is_success(&self) -> bool {
+        self.transactions
+            .iter()
+            .all(|outcome| outcome.result.is_ok())
+    }
+}
+
+/// Sends many independent instruction sets with as few transactions as
+/// possible.
+///
+/// Each instruction set is kept whole, and consecutive sets are packed into a
+/// transaction until it would exceed the packet size limit. Transactions are
+/// sent concurrently, up to `max_parallelism` at a time, and re-signed with a
+/// fresh blockhash if theirs expired before landing.
+pub struct BatchSender<T> {
+    client: Arc<dyn ProgramClient<T>>,
+    payer: Arc<dyn Signer>,
+    signers: Vec<Arc<dyn Signer>>,
+    max_parallelism: usize,
+    max_retries: usize,
+    compute_unit_price: Option<u64>,
+    compute_unit_limit: Option<u32>,
+}
+
+impl<T> BatchSender<T>
+where
+    T: SendTransaction + SimulateTransaction,
+{
+    pub fn new(client: Arc<dyn ProgramClient<T>>, payer: Arc<dyn Signer>) -> Self {
+        BatchSender {
+            client,
+            payer,
+            signers: vec![],
+            max_parallelism: DEFAULT_MAX_PARALLELISM,
+            max_retries: DEFAULT_MAX_RETRIES,
+            compute_unit_price: None,
+            compute_unit_limit: None,
+        }
+    }
+
+    /// Signers made available to every transaction. Each transaction is only
+    /// signed by those it requires.
+    pub fn with_signers(mut self, signers: Vec<Arc<dyn Signer>>) -> Self {
+        self.signers = signers;
+        self
+    }
+
+    pub fn with_max_parallelism(mut self, max_parallelism: usize) -> Self {
+        self.max_parallelism = max_parallelism.max(1);
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_compute_unit_price(mut self, compute_unit_price: u64) -> Self {
+        self.compute_unit_price = Some(compute_unit_price);
+        self
+    }
+
+    /// Compute unit limit requested by every transaction, regardless of how
+    /// many instruction sets were packed into it
+    pub fn with_compute_unit_limit(mut self, compute_unit_limit: u32) -> Self {
+        self.compute_unit_limit = Some(compute_unit_limit);
+        self
+    }
+
+    fn compute_budget_instructions(&self) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        if let Some(compute_unit_price) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                compute_unit_price,
+            ));
+        }
+        if let Some(compute_unit_limit) = self.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                compute_unit_limit,
+            ));
+        }
+        instructions
+    }
+
+    fn fits(&self, instructions: &[Instruction]) -> bool {
+        transaction_size(&Message::new(instructions, Some(&self.payer.pubkey())))
+            <= PACKET_DATA_SIZE
+    }
+
+    /// Pack the instruction sets into transactions, returning the indices of
+    /// the sets and the instructions for each. A set that doesn't fit in a
+    /// transaction on its own gets one to itself, to be reported as failed.
+    pub fn pack(
+        &self,
+        instruction_sets: &[Vec<Instruction>],
+    ) -> Vec<(Vec<usize>, Vec<Instruction>)> {
+        let compute_budget_instructions = self.compute_budget_instructions();
+        let mut packed = vec![];
+        let mut indices = vec![];
+        let mut instructions = compute_budget_instructions.clone();
+
+        for (index, instruction_set) in instruction_sets.iter().enumerate() {
+            let mut candidate = instructions.clone();
+            candidate.extend_from_slice(instruction_set);
+            if indices.is_empty() || self.fits(&candidate) {
+                indices.push(index);
+                instructions = candidate;
+            } else {
+                packed.push((indices, instructions));
+                indices = vec![index];
+                instructions = compute_budget_instructions.clone();
+                instructions.extend_from_slice(instruction_set);
+            }
+        }
+        if !indices.is_empty() {
+            packed.push((indices, instructions));
+        }
+        packed
+    }
+
+    fn sign(
+        &self,
+        instructions: &[Instruction],
+        blockhash: Hash,
+    ) -> ProgramClientResult<Transaction> {
+        let message = Message::new(instructions, Some(&self.payer.pubkey()));
+        let required_signers =
+            &message.account_keys[..message.header.num_required_signatures as usize];
+        let mut signers = vec![self.payer.clone()];
+        for signer in &self.signers {
+            let pubkey = signer.pubkey();
+            if required_signers.contains(&pubkey)
+                && !signers.iter().any(|existing| existing.pubkey() == pubkey)
+            {
+                signers.push(signer.clone());
+            }
+        }
+
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.try_partial_sign(&signers, blockhash)?;
+        Ok(transaction)
+    }
+
+    async fn send_packed(
+        &self,
+        instructions: &[Instruction],
+        blockhash: Hash,
+    ) -> (usize, ProgramClientResult<T::Output>) {
+        if !self.fits(instructions) {
+            return (
+                0,
+                Err("Instructions do not fit in a single transaction".into()),
+            );
+        }
+
+        let mut blockhash = blockhash;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let result = match self.sign(instructions, blockhash) {
+                Ok(transaction) => self.client.send_transaction(&transaction).await,
+                Err(error) => Err(error),
+            };
+            match result {
+                Err(error) if attempts <= self.max_retries && is_blockhash_expired(&error) => {
+                    match self.client.get_latest_blockhash().await {
+                        Ok(new_blockhash) => blockhash = new_blockhash,
+                        Err(error) => return (attempts, Err(error)),
+                    }
+                }
+                result => return (attempts, result),
+            }
+        }
+    }
+
+    /// Send every instruction set, reporting the outcome of each transaction.
+    ///
+    /// Failures don't stop the rest of the batch; check the report for the
+    /// instruction sets that need to be retried.
+    pub async fn send(
+        &self,
+        instruction_sets: &[Vec<Instruction>],
+    ) -> ProgramClientResult<BatchReport<T::Output>> {
+        let packed = self.pack(instruction_sets);
+        let blockhash = self.client.get_latest_blockhash().await?;
+
+        let mut outcomes = stream::iter(packed.into_iter().enumerate())
+            .map(
+                |(position, (instruction_indices, instructions))| async move {
+                    let (attempts, result) = self.send_packed(&instructions, blockhash).await;
+                    (
+                        position,
+                        BatchTransactionOutcome {
+                            instruction_indices,
+                            attempts,
+                            result,
+                        },
+                    )
+                },
+            )
+            .buffer_unordered(self.max_parallelism)
+            .collect::<Vec<_>>()
+            .await;
+        outcomes.sort_by_key(|(position, _)| *position);
+
+        let mut instruction_transactions = vec![0; instruction_sets.len()];
+        let transactions = outcomes
+            .into_iter()
+            .map(|(position, outcome)| {
+                for index in &outcome.instruction_indices {
+                    instruction_transactions[*index] = position;
+                }
+                outcome
+            })
+            .collect();
+
+        Ok(BatchReport {
+            transactions,
+            instruction_transactions,
+        })
+    }
+}
+
+/// Serialized size of a signed transaction for the message. The signature
+/// count is encoded as a short vec, which only takes one byte below 128.
+fn transaction_size(message: &Message) -> usize {
+    let num_signatures = message.header.num_required_signatures as usize;
+    1 + num_signatures * SIGNATURE_BYTES + message.serialize().len()
+}
+
+fn is_blockhash_expired(error: &ProgramClientError) -> bool {
+    if let Some(error) = error.downcast_ref::<ClientError>() {
+        match &error.kind {
+            // the rpc client gives up confirming once the blockhash expires
+            ClientErrorKind::RpcError(RpcError::ForUser(message)) => {
+                message.starts_with("unable to confirm transaction")
+            }
+            kind => kind.get_transaction_error() == Some(TransactionError::BlockhashNotFound),
+        }
+    } else if let Some(error) = error.downcast_ref::<BanksClientError>() {
+        matches!(
+            error,
+            BanksClientError::TransactionError(TransactionError::BlockhashNotFound)
+                | BanksClientError::SimulationError {
+                    err: TransactionError::BlockhashNotFound,
+                    ..
+                }
+        )
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::client::{ProgramOfflineClient, ProgramRpcClientSendTransaction},
+        solana_sdk::signature::Keypair,
+    };
+
+    fn memo(len: usize) -> Vec<Instruction> {
+        vec![spl_memo::build_memo(&vec![b'a'; len], &[])]
+    }
+
+    fn sender() -> BatchSender<ProgramRpcClientSendTransaction> {
+        BatchSender::new(
+            Arc::new(ProgramOfflineClient::new(
+                Hash::default(),
+                ProgramRpcClientSendTransaction,
+            )),
+            Arc::new(Keypair::new()),
+        )
+    }
+
+    #[test]
+    fn pack_fills_transactions() {
+        let sender = sender();
+        let instruction_sets = vec![memo(400), memo(400), memo(400), memo(10)];
+        let packed = sender.pack(&instruction_sets);
+        assert_eq!(
+            packed
+                .iter()
+                .map(|(indices, _)| indices.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![2, 3]]
+        );
+        for (_, instructions) in &packed {
+            assert!(sender.fits(instructions));
+        }
+    }
+
+    #[test]
+    fn pack_keeps_oversized_set_alone() {
+        let sender = sender().with_compute_unit_price(1);
+        let instruction_sets = vec![memo(10), memo(2_000), memo(10)];
+        let packed = sender.pack(&instruction_sets);
+        assert_eq!(
+            packed
+                .iter()
+                .map(|(indices, _)| indices.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![0], vec![1], vec![2]]
+        );
+        // compute budget instructions lead every transaction
+        for (_, instructions) in &packed {
+            assert_eq!(
+                instructions[0],
+                ComputeBudgetInstruction::set_compute_unit_price(1)
+            );
+        }
+    }
+}
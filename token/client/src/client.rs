@@ -5,8 +5,11 @@ use {
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
     solana_rpc_client_api::response::RpcSimulateTransactionResult,
     solana_sdk::{
-        account::Account, hash::Hash, pubkey::Pubkey, signature::Signature,
-        transaction::Transaction,
+        account::Account,
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::{Transaction, VersionedTransaction},
     },
     std::{fmt, future::Future, pin::Pin, sync::Arc},
 };
@@ -20,7 +23,12 @@ pub trait SendTransaction {
 
 /// Basic trait for simulating transactions in a validator.
 pub trait SimulateTransaction {
-    type SimulationOutput;
+    type SimulationOutput: SimulationResult;
+}
+
+/// Trait for the output of a simulation
+pub trait SimulationResult {
+    fn get_compute_units_consumed(&self) -> ProgramClientResult<u64>;
 }
 
 /// Extends basic `SendTransaction` trait with function `send` where client is `&mut BanksClient`.
@@ -31,6 +39,12 @@ pub trait SendTransactionBanksClient: SendTransaction {
         client: &'a mut BanksClient,
         transaction: Transaction,
     ) -> BoxFuture<'a, ProgramClientResult<Self::Output>>;
+
+    fn send_versioned<'a>(
+        &self,
+        client: &'a mut BanksClient,
+        transaction: VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::Output>>;
 }
 
 /// Extends basic `SimulateTransaction` trait with function `simulation` where client is `&mut BanksClient`.
@@ -41,6 +55,12 @@ pub trait SimulateTransactionBanksClient: SimulateTransaction {
         client: &'a mut BanksClient,
         transaction: Transaction,
     ) -> BoxFuture<'a, ProgramClientResult<Self::SimulationOutput>>;
+
+    fn simulate_versioned<'a>(
+        &self,
+        client: &'a mut BanksClient,
+        transaction: VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::SimulationOutput>>;
 }
 
 /// Send transaction to validator using `BanksClient::process_transaction`.
@@ -64,6 +84,28 @@ impl SendTransactionBanksClient for ProgramBanksClientProcessTransaction {
                 .map_err(Into::into)
         })
     }
+
+    fn send_versioned<'a>(
+        &self,
+        client: &'a mut BanksClient,
+        transaction: VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::Output>> {
+        Box::pin(async move {
+            client
+                .process_transaction(transaction)
+                .await
+                .map_err(Into::into)
+        })
+    }
+}
+
+impl SimulationResult for BanksTransactionResultWithSimulation {
+    fn get_compute_units_consumed(&self) -> ProgramClientResult<u64> {
+        self.simulation_details
+            .as_ref()
+            .map(|details| details.units_consumed)
+            .ok_or_else(|| "No simulation results found".into())
+    }
 }
 
 impl SimulateTransaction for ProgramBanksClientProcessTransaction {
@@ -83,6 +125,19 @@ impl SimulateTransactionBanksClient for ProgramBanksClientProcessTransaction {
                 .map_err(Into::into)
         })
     }
+
+    fn simulate_versioned<'a>(
+        &self,
+        client: &'a mut BanksClient,
+        transaction: VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::SimulationOutput>> {
+        Box::pin(async move {
+            client
+                .simulate_transaction(transaction)
+                .await
+                .map_err(Into::into)
+        })
+    }
 }
 
 /// Extends basic `SendTransaction` trait with function `send` where client is `&RpcClient`.
@@ -93,6 +148,12 @@ pub trait SendTransactionRpc: SendTransaction {
         client: &'a RpcClient,
         transaction: &'a Transaction,
     ) -> BoxFuture<'a, ProgramClientResult<Self::Output>>;
+
+    fn send_versioned<'a>(
+        &self,
+        client: &'a RpcClient,
+        transaction: &'a VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::Output>>;
 }
 
 /// Extends basic `SimulateTransaction` trait with function `simulate` where client is `&RpcClient`.
@@ -103,6 +164,12 @@ pub trait SimulateTransactionRpc: SimulateTransaction {
         client: &'a RpcClient,
         transaction: &'a Transaction,
     ) -> BoxFuture<'a, ProgramClientResult<Self::SimulationOutput>>;
+
+    fn simulate_versioned<'a>(
+        &self,
+        client: &'a RpcClient,
+        transaction: &'a VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::SimulationOutput>>;
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -112,6 +179,7 @@ pub struct ProgramRpcClientSendTransaction;
 pub enum RpcClientResponse {
     Signature(Signature),
     Transaction(Transaction),
+    VersionedTransaction(VersionedTransaction),
     Simulation(RpcSimulateTransactionResult),
 }
 
@@ -137,6 +205,43 @@ impl SendTransactionRpc for ProgramRpcClientSendTransaction {
                 .map_err(Into::into)
         })
     }
+
+    fn send_versioned<'a>(
+        &self,
+        client: &'a RpcClient,
+        transaction: &'a VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::Output>> {
+        Box::pin(async move {
+            if transaction
+                .signatures
+                .iter()
+                .any(|signature| *signature == Signature::default())
+            {
+                return Err("Cannot send transaction: not fully signed".into());
+            }
+
+            client
+                .send_and_confirm_transaction(transaction)
+                .await
+                .map(RpcClientResponse::Signature)
+                .map_err(Into::into)
+        })
+    }
+}
+
+impl SimulationResult for RpcClientResponse {
+    fn get_compute_units_consumed(&self) -> ProgramClientResult<u64> {
+        match self {
+            // offline clients return the unsent transaction instead of
+            // simulating it
+            Self::Signature(_) | Self::Transaction(_) | Self::VersionedTransaction(_) => {
+                Err("Not a simulation result".into())
+            }
+            Self::Simulation(simulation_result) => simulation_result
+                .units_consumed
+                .ok_or_else(|| "No simulation results found".into()),
+        }
+    }
 }
 
 impl SimulateTransaction for ProgramRpcClientSendTransaction {
@@ -157,6 +262,20 @@ impl SimulateTransactionRpc for ProgramRpcClientSendTransaction {
                 .map_err(Into::into)
         })
     }
+
+    fn simulate_versioned<'a>(
+        &self,
+        client: &'a RpcClient,
+        transaction: &'a VersionedTransaction,
+    ) -> BoxFuture<'a, ProgramClientResult<Self::SimulationOutput>> {
+        Box::pin(async move {
+            client
+                .simulate_transaction(transaction)
+                .await
+                .map(|r| RpcClientResponse::Simulation(r.value))
+                .map_err(Into::into)
+        })
+    }
 }
 
 pub type ProgramClientError = Box<dyn std::error::Error + Send + Sync>;
@@ -183,6 +302,16 @@ where
         &self,
         transaction: &Transaction,
     ) -> ProgramClientResult<ST::SimulationOutput>;
+
+    async fn send_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::Output>;
+
+    async fn simulate_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::SimulationOutput>;
 }
 
 enum ProgramBanksClientContext {
@@ -276,6 +405,28 @@ where
         .await
     }
 
+    async fn send_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::Output> {
+        self.run_in_lock(|client| {
+            let transaction = transaction.clone();
+            self.send.send_versioned(client, transaction)
+        })
+        .await
+    }
+
+    async fn simulate_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::SimulationOutput> {
+        self.run_in_lock(|client| {
+            let transaction = transaction.clone();
+            self.send.simulate_versioned(client, transaction)
+        })
+        .await
+    }
+
     async fn get_account(&self, address: Pubkey) -> ProgramClientResult<Option<Account>> {
         self.run_in_lock(|client| {
             Box::pin(async move { client.get_account(address).await.map_err(Into::into) })
@@ -332,6 +483,22 @@ where
         self.send.simulate(&self.client, transaction).await
     }
 
+    async fn send_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::Output> {
+        self.send.send_versioned(&self.client, transaction).await
+    }
+
+    async fn simulate_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::SimulationOutput> {
+        self.send
+            .simulate_versioned(&self.client, transaction)
+            .await
+    }
+
     async fn get_account(&self, address: Pubkey) -> ProgramClientResult<Option<Account>> {
         Ok(self
             .client
@@ -392,6 +559,20 @@ where
         Ok(RpcClientResponse::Transaction(transaction.clone()))
     }
 
+    async fn send_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::Output> {
+        Ok(RpcClientResponse::VersionedTransaction(transaction.clone()))
+    }
+
+    async fn simulate_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::SimulationOutput> {
+        Ok(RpcClientResponse::VersionedTransaction(transaction.clone()))
+    }
+
     async fn get_account(&self, _address: Pubkey) -> ProgramClientResult<Option<Account>> {
         Err("Unable to fetch account in offline mode".into())
     }
@@ -1,5 +1,8 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod batch_sender;
 pub mod client;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
 pub mod output;
 pub mod token;
 
@@ -0,0 +1,1340 @@
+//! In-memory program client for tests
+//!
+//! `ProgramMockClient` keeps accounts in a map and executes transactions
+//! natively through the token, token-2022, associated token account and memo
+//! processors. A minimal runtime shim provides what those programs need: part
+//! of the system program, cross-program invocations, sysvars, return data and
+//! logs. Like the runtime, it rejects changes to read-only accounts and
+//! writes by programs that do not own the account. Compute is not metered, and
+//! instructions for any other program fail.
+
+use {
+    crate::client::{
+        ProgramClient, ProgramClientError, ProgramClientResult, RpcClientResponse, SendTransaction,
+        SimulateTransaction,
+    },
+    async_trait::async_trait,
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_rpc_client_api::client_error::{Error as ClientError, ErrorKind as ClientErrorKind},
+    solana_sdk::{
+        account::{create_account_for_test, Account},
+        account_info::AccountInfo,
+        address_lookup_table::{
+            self,
+            state::{AddressLookupTable, LookupTableMeta},
+        },
+        clock::{Clock, Epoch},
+        compute_budget,
+        entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+        epoch_schedule::EpochSchedule,
+        hash::Hash,
+        instruction::{CompiledInstruction, Instruction, InstructionError},
+        message::{
+            v0::{LoadedAddresses, LoadedMessage},
+            VersionedMessage,
+        },
+        program_error::{PrintProgramError, ProgramError},
+        program_stubs::{set_syscall_stubs, SyscallStubs},
+        program_utils::limited_deserialize,
+        pubkey::Pubkey,
+        rent::Rent,
+        sanitize::Sanitize,
+        signature::Signature,
+        system_instruction::{SystemError, SystemInstruction},
+        system_program, sysvar,
+        transaction::{Transaction, TransactionError, VersionedTransaction},
+    },
+    std::{
+        borrow::Cow,
+        cell::RefCell,
+        collections::{HashMap, VecDeque},
+        fmt,
+        panic::{self, AssertUnwindSafe},
+        sync::{Mutex, PoisonError, RwLock},
+    },
+};
+
+/// Compute units reported by simulations for every executed instruction,
+/// including cross-program invocations
+pub const MOCK_COMPUTE_UNITS_PER_INSTRUCTION: u64 = 5_000;
+
+/// Fee charged for every signature of a sent transaction
+pub const MOCK_LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// A failure injected into the next sent transactions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockFailure {
+    /// The transaction fails with the error, without being executed
+    Transaction(TransactionError),
+    /// The request fails before reaching the cluster
+    Rpc(String),
+}
+
+impl MockFailure {
+    fn into_client_error(self) -> ProgramClientError {
+        match self {
+            Self::Transaction(error) => ClientError::from(error).into(),
+            Self::Rpc(message) => ClientError::from(ClientErrorKind::Custom(message)).into(),
+        }
+    }
+}
+
+type FailureFilter = Box<dyn Fn(&[Pubkey]) -> Option<MockFailure> + Send + Sync>;
+
+struct MockState {
+    accounts: HashMap<Pubkey, Account>,
+    blockhash: Hash,
+    rent: Rent,
+    clock: Clock,
+    failures: VecDeque<MockFailure>,
+    failure_filter: Option<FailureFilter>,
+    logs: HashMap<Signature, Vec<String>>,
+}
+
+/// Program client executing transactions against accounts held in memory.
+pub struct ProgramMockClient<ST> {
+    state: Mutex<MockState>,
+    _send: ST,
+}
+
+impl<ST> fmt::Debug for ProgramMockClient<ST> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgramMockClient").finish()
+    }
+}
+
+impl<ST> ProgramMockClient<ST> {
+    pub fn new(send: ST) -> Self {
+        let rent = Rent::default();
+        let clock = Clock::default();
+        let accounts = HashMap::from([
+            (sysvar::rent::id(), create_account_for_test(&rent)),
+            (sysvar::clock::id(), create_account_for_test(&clock)),
+        ]);
+        Self {
+            state: Mutex::new(MockState {
+                accounts,
+                blockhash: Hash::new_unique(),
+                rent,
+                clock,
+                failures: VecDeque::new(),
+                failure_filter: None,
+                logs: HashMap::new(),
+            }),
+            _send: send,
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn set_account(&self, address: Pubkey, account: Account) {
+        self.state().accounts.insert(address, account);
+    }
+
+    pub fn remove_account(&self, address: &Pubkey) {
+        self.state().accounts.remove(address);
+    }
+
+    /// Add lamports to an account, creating it if needed
+    pub fn airdrop(&self, address: &Pubkey, lamports: u64) {
+        self.state()
+            .accounts
+            .entry(*address)
+            .or_insert_with(|| Account::new(0, 0, &system_program::id()))
+            .lamports += lamports;
+    }
+
+    /// Store an active address lookup table holding the addresses, returning
+    /// its address
+    pub fn add_address_lookup_table(&self, addresses: &[Pubkey]) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let table = AddressLookupTable {
+            meta: LookupTableMeta::default(),
+            addresses: Cow::Borrowed(addresses),
+        };
+        let data = table.serialize_for_tests().unwrap();
+        let mut state = self.state();
+        let lamports = state.rent.minimum_balance(data.len());
+        state.accounts.insert(
+            address,
+            Account {
+                lamports,
+                data,
+                owner: address_lookup_table::program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        address
+    }
+
+    pub fn set_clock(&self, clock: Clock) {
+        let mut state = self.state();
+        state
+            .accounts
+            .insert(sysvar::clock::id(), create_account_for_test(&clock));
+        state.clock = clock;
+    }
+
+    /// Replace the latest blockhash, so that transactions signed with any
+    /// earlier one fail with `BlockhashNotFound`
+    pub fn expire_blockhash(&self) -> Hash {
+        let blockhash = Hash::new_unique();
+        self.state().blockhash = blockhash;
+        blockhash
+    }
+
+    /// Fail the next sent transaction. Failures are used in the order they
+    /// were pushed, one per transaction.
+    pub fn push_failure(&self, failure: MockFailure) {
+        self.state().failures.push_back(failure);
+    }
+
+    /// Fail every sent transaction for which the filter returns a failure.
+    /// The filter is given the account keys of the transaction.
+    pub fn set_failure_filter<F>(&self, filter: F)
+    where
+        F: Fn(&[Pubkey]) -> Option<MockFailure> + Send + Sync + 'static,
+    {
+        self.state().failure_filter = Some(Box::new(filter));
+    }
+
+    pub fn clear_failures(&self) {
+        let mut state = self.state();
+        state.failures.clear();
+        state.failure_filter = None;
+    }
+
+    /// Logs of a sent transaction, including failed ones
+    pub fn transaction_logs(&self, signature: &Signature) -> Option<Vec<String>> {
+        self.state().logs.get(signature).cloned()
+    }
+
+    fn process(
+        &self,
+        transaction: impl FnOnce(&MockState) -> Result<MockTransaction, TransactionError>,
+        simulate: bool,
+    ) -> ProgramClientResult<RpcClientResponse> {
+        let mut state = self.state();
+        let transaction = transaction(&state);
+        if !simulate {
+            let failure = state.failures.pop_front().or_else(|| {
+                let keys = transaction
+                    .as_ref()
+                    .map(|transaction| transaction.account_keys.as_slice())
+                    .unwrap_or_default();
+                state
+                    .failure_filter
+                    .as_ref()
+                    .and_then(|filter| filter(keys))
+            });
+            if let Some(failure) = failure {
+                return Err(failure.into_client_error());
+            }
+        }
+
+        let (signature, execution) = match transaction {
+            Ok(transaction) => (
+                transaction.signature,
+                state.execute(&transaction, !simulate),
+            ),
+            Err(error) => (Signature::default(), Execution::failed(error)),
+        };
+        if simulate {
+            // built the way the RPC client parses it
+            let simulation = serde_json::from_value(serde_json::json!({
+                "err": execution.result.err(),
+                "logs": execution.logs,
+                "unitsConsumed": execution.units_consumed,
+            }))?;
+            return Ok(RpcClientResponse::Simulation(simulation));
+        }
+
+        state.logs.insert(signature, execution.logs);
+        execution
+            .result
+            .map(|()| RpcClientResponse::Signature(signature))
+            .map_err(|error| ClientError::from(error).into())
+    }
+}
+
+#[async_trait]
+impl<ST> ProgramClient<ST> for ProgramMockClient<ST>
+where
+    ST: SendTransaction<Output = RpcClientResponse>
+        + SimulateTransaction<SimulationOutput = RpcClientResponse>
+        + Send
+        + Sync,
+{
+    async fn get_minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> ProgramClientResult<u64> {
+        Ok(self.state().rent.minimum_balance(data_len))
+    }
+
+    async fn get_latest_blockhash(&self) -> ProgramClientResult<Hash> {
+        Ok(self.state().blockhash)
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> ProgramClientResult<ST::Output> {
+        self.process(|_| MockTransaction::from_legacy(transaction), false)
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> ProgramClientResult<ST::SimulationOutput> {
+        self.process(|_| MockTransaction::from_legacy(transaction), true)
+    }
+
+    async fn send_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::Output> {
+        self.process(
+            |state| MockTransaction::from_versioned(transaction, &state.accounts),
+            false,
+        )
+    }
+
+    async fn simulate_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ProgramClientResult<ST::SimulationOutput> {
+        self.process(
+            |state| MockTransaction::from_versioned(transaction, &state.accounts),
+            true,
+        )
+    }
+
+    async fn get_account(&self, address: Pubkey) -> ProgramClientResult<Option<Account>> {
+        Ok(self
+            .state()
+            .accounts
+            .get(&address)
+            .filter(|account| account.lamports > 0)
+            .cloned())
+    }
+}
+
+/// A sanitized transaction, with its lookup table addresses resolved
+struct MockTransaction {
+    signature: Signature,
+    account_keys: Vec<Pubkey>,
+    is_signer: Vec<bool>,
+    is_writable: Vec<bool>,
+    instructions: Vec<CompiledInstruction>,
+    recent_blockhash: Hash,
+    num_signatures: usize,
+    is_verified: bool,
+}
+
+impl MockTransaction {
+    fn from_legacy(transaction: &Transaction) -> Result<Self, TransactionError> {
+        transaction.sanitize()?;
+        let message = &transaction.message;
+        let num_keys = message.account_keys.len();
+        Ok(Self {
+            signature: transaction.signatures.first().copied().unwrap_or_default(),
+            account_keys: message.account_keys.clone(),
+            is_signer: (0..num_keys).map(|i| message.is_signer(i)).collect(),
+            is_writable: (0..num_keys).map(|i| message.is_writable(i)).collect(),
+            instructions: message.instructions.clone(),
+            recent_blockhash: message.recent_blockhash,
+            num_signatures: message.header.num_required_signatures as usize,
+            is_verified: transaction.verify().is_ok(),
+        })
+    }
+
+    fn from_versioned(
+        transaction: &VersionedTransaction,
+        accounts: &HashMap<Pubkey, Account>,
+    ) -> Result<Self, TransactionError> {
+        transaction.sanitize()?;
+        let message = match &transaction.message {
+            VersionedMessage::Legacy(message) => {
+                return Self::from_legacy(&Transaction {
+                    signatures: transaction.signatures.clone(),
+                    message: message.clone(),
+                })
+            }
+            VersionedMessage::V0(message) => message,
+        };
+
+        let mut loaded_addresses = LoadedAddresses::default();
+        for lookup in &message.address_table_lookups {
+            let account = accounts
+                .get(&lookup.account_key)
+                .ok_or(TransactionError::AddressLookupTableNotFound)?;
+            if account.owner != address_lookup_table::program::id() {
+                return Err(TransactionError::InvalidAddressLookupTableOwner);
+            }
+            let table = AddressLookupTable::deserialize(&account.data)
+                .map_err(|_| TransactionError::InvalidAddressLookupTableData)?;
+            let lookup_addresses = |indexes: &[u8]| {
+                indexes
+                    .iter()
+                    .map(|index| {
+                        table
+                            .addresses
+                            .get(*index as usize)
+                            .copied()
+                            .ok_or(TransactionError::InvalidAddressLookupTableIndex)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            loaded_addresses
+                .writable
+                .extend(lookup_addresses(&lookup.writable_indexes)?);
+            loaded_addresses
+                .readonly
+                .extend(lookup_addresses(&lookup.readonly_indexes)?);
+        }
+
+        let message = LoadedMessage::new(message.clone(), loaded_addresses);
+        let account_keys = message.account_keys().iter().copied().collect::<Vec<_>>();
+        let num_keys = account_keys.len();
+        Ok(Self {
+            signature: transaction.signatures.first().copied().unwrap_or_default(),
+            account_keys,
+            is_signer: (0..num_keys).map(|i| message.is_signer(i)).collect(),
+            is_writable: (0..num_keys).map(|i| message.is_writable(i)).collect(),
+            instructions: message.message.instructions.clone(),
+            recent_blockhash: message.message.recent_blockhash,
+            num_signatures: message.message.header.num_required_signatures as usize,
+            is_verified: transaction.verify_with_results().into_iter().all(|ok| ok),
+        })
+    }
+}
+
+struct Execution {
+    result: Result<(), TransactionError>,
+    logs: Vec<String>,
+    units_consumed: u64,
+}
+
+impl Execution {
+    fn failed(error: TransactionError) -> Self {
+        Self {
+            result: Err(error),
+            logs: vec![],
+            units_consumed: 0,
+        }
+    }
+}
+
+impl MockState {
+    /// Execute the transaction, committing the changes and charging the fee
+    /// when sending
+    fn execute(&mut self, transaction: &MockTransaction, send: bool) -> Execution {
+        if transaction.recent_blockhash != self.blockhash {
+            return Execution::failed(TransactionError::BlockhashNotFound);
+        }
+        if send && !transaction.is_verified {
+            return Execution::failed(TransactionError::SignatureFailure);
+        }
+
+        let fee_payer = transaction.account_keys[0];
+        let fee = MOCK_LAMPORTS_PER_SIGNATURE * transaction.num_signatures as u64;
+        let Some(fee_payer_account) = self.accounts.get(&fee_payer).cloned() else {
+            return Execution::failed(TransactionError::AccountNotFound);
+        };
+        if fee_payer_account.lamports < fee {
+            return Execution::failed(TransactionError::InsufficientFundsForFee);
+        }
+
+        let mut loaded = transaction
+            .account_keys
+            .iter()
+            .map(|key| {
+                LoadedAccount::new(*key, self.accounts.get(key).cloned().unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        loaded[0].lamports -= fee;
+
+        let context = MockContext {
+            rent: self.rent.clone(),
+            clock: self.clock.clone(),
+            program_ids: vec![],
+            logs: vec![],
+            return_data: None,
+            units_consumed: 0,
+            pre_accounts: vec![],
+            runtime_error: None,
+        };
+        let (result, context) = with_context(context, || {
+            let account_infos = loaded
+                .iter_mut()
+                .enumerate()
+                .map(|(i, account)| {
+                    account.account_info(transaction.is_signer[i], transaction.is_writable[i])
+                })
+                .collect::<Vec<_>>();
+            for (index, instruction) in transaction.instructions.iter().enumerate() {
+                let program_id = transaction.account_keys[instruction.program_id_index as usize];
+                let accounts = instruction
+                    .accounts
+                    .iter()
+                    .map(|i| account_infos[*i as usize].clone())
+                    .collect::<Vec<_>>();
+                with_current(|context| context.return_data = None);
+                let result = process_instruction(&program_id, &accounts, &instruction.data);
+                // a failed runtime check aborts the transaction, even if the
+                // calling program ignored the error
+                if let Some(error) = with_current(|context| context.runtime_error.take()).flatten()
+                {
+                    return Err(TransactionError::InstructionError(index as u8, error));
+                }
+                result.map_err(|error| {
+                    TransactionError::InstructionError(
+                        index as u8,
+                        InstructionError::from(u64::from(error)),
+                    )
+                })?;
+            }
+            Ok(())
+        });
+        let mut result = result.unwrap_or(Err(TransactionError::InstructionError(
+            0,
+            InstructionError::ProgramFailedToComplete,
+        )));
+
+        let changed = loaded
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| transaction.is_writable[*i])
+            .map(|(i, account)| (i, account.key, account.to_account()))
+            .filter(|(_, key, account)| self.accounts.get(key) != Some(account))
+            .collect::<Vec<_>>();
+        if result.is_ok() {
+            if let Some((i, _, _)) = changed.iter().find(|(_, _, account)| {
+                account.lamports > 0 && !self.rent.is_exempt(account.lamports, account.data.len())
+            }) {
+                result = Err(TransactionError::InsufficientFundsForRent {
+                    account_index: *i as u8,
+                });
+            }
+        }
+
+        if send {
+            if result.is_ok() {
+                for (_, key, account) in changed {
+                    if account.lamports == 0 {
+                        self.accounts.remove(&key);
+                    } else {
+                        self.accounts.insert(key, account);
+                    }
+                }
+            } else {
+                let mut fee_payer_account = fee_payer_account;
+                fee_payer_account.lamports -= fee;
+                self.accounts.insert(fee_payer, fee_payer_account);
+            }
+        }
+
+        Execution {
+            result,
+            logs: context.logs,
+            units_consumed: context.units_consumed,
+        }
+    }
+}
+
+/// An account laid out the way the runtime serializes it for programs, since
+/// `AccountInfo::realloc` finds the original data length in front of the key
+/// and the current data length in front of the data
+#[repr(C)]
+struct LoadedAccount {
+    original_data_len: u32,
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    executable: bool,
+    rent_epoch: Epoch,
+    // data length, followed by the data and room for it to grow
+    data: Vec<u64>,
+}
+
+impl LoadedAccount {
+    fn new(key: Pubkey, account: Account) -> Self {
+        let len = account.data.len();
+        let mut data = vec![0; 1 + (len + MAX_PERMITTED_DATA_INCREASE).div_ceil(8)];
+        data[0] = len as u64;
+        bytemuck::cast_slice_mut::<u64, u8>(&mut data)[8..8 + len].copy_from_slice(&account.data);
+        Self {
+            original_data_len: len as u32,
+            key,
+            owner: account.owner,
+            lamports: account.lamports,
+            executable: account.executable || is_supported_program(&key),
+            rent_epoch: account.rent_epoch,
+            data,
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.data[0] as usize
+    }
+
+    fn account_info(&mut self, is_signer: bool, is_writable: bool) -> AccountInfo<'_> {
+        let len = self.data_len();
+        let data = &mut bytemuck::cast_slice_mut::<u64, u8>(&mut self.data)[8..8 + len];
+        AccountInfo::new(
+            &self.key,
+            is_signer,
+            is_writable,
+            &mut self.lamports,
+            data,
+            &self.owner,
+            self.executable,
+            self.rent_epoch,
+        )
+    }
+
+    fn to_account(&self) -> Account {
+        let len = self.data_len();
+        Account {
+            lamports: self.lamports,
+            data: bytemuck::cast_slice::<u64, u8>(&self.data)[8..8 + len].to_vec(),
+            owner: self.owner,
+            executable: self.executable && !is_supported_program(&self.key),
+            rent_epoch: self.rent_epoch,
+        }
+    }
+}
+
+/// Runtime state of the transaction being executed on this thread
+struct MockContext {
+    rent: Rent,
+    clock: Clock,
+    program_ids: Vec<Pubkey>,
+    logs: Vec<String>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    units_consumed: u64,
+    /// Accounts as each program on the stack found them
+    pre_accounts: Vec<Vec<PreAccount>>,
+    /// Error of a runtime check, which has no `ProgramError` equivalent
+    runtime_error: Option<InstructionError>,
+}
+
+/// An account before a program runs, to check the changes the program makes
+struct PreAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_writable: bool,
+}
+
+impl PreAccount {
+    fn new(accounts: &[AccountInfo]) -> Vec<Self> {
+        let mut pre_accounts: Vec<Self> = vec![];
+        for account in accounts {
+            match pre_accounts.iter_mut().find(|pre| pre.key == *account.key) {
+                Some(pre) => pre.is_writable |= account.is_writable,
+                None => pre_accounts.push(Self {
+                    key: *account.key,
+                    owner: *account.owner,
+                    lamports: account.lamports(),
+                    data: account.data.borrow().to_vec(),
+                    is_writable: account.is_writable,
+                }),
+            }
+        }
+        pre_accounts
+    }
+
+    fn update(&mut self, account: &AccountInfo) {
+        self.owner = *account.owner;
+        self.lamports = account.lamports();
+        self.data = account.data.borrow().to_vec();
+    }
+
+    /// Check the account after `program_id` returned, the way the runtime does
+    fn verify(&self, program_id: &Pubkey, account: &AccountInfo) -> Result<(), InstructionError> {
+        let is_owned = self.owner == *program_id;
+        let lamports = account.lamports();
+        let data = account.data.borrow();
+        // only the owner can assign a writable account, once its data is zeroed
+        if *account.owner != self.owner
+            && !(self.is_writable && is_owned && data.iter().all(|byte| *byte == 0))
+        {
+            return Err(InstructionError::ModifiedProgramId);
+        }
+        if lamports < self.lamports && !is_owned {
+            return Err(InstructionError::ExternalAccountLamportSpend);
+        }
+        if lamports != self.lamports && !self.is_writable {
+            return Err(InstructionError::ReadonlyLamportChange);
+        }
+        if data.len() != self.data.len() && !(self.is_writable && is_owned) {
+            return Err(InstructionError::AccountDataSizeChanged);
+        }
+        if data[..] != self.data[..] {
+            if !self.is_writable {
+                return Err(InstructionError::ReadonlyDataModified);
+            }
+            if !is_owned {
+                return Err(InstructionError::ExternalAccountDataModified);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MockContext {
+    /// Record a failed runtime check, which fails the transaction whatever the
+    /// calling program does with the returned error
+    fn fail_runtime_check(&mut self, error: InstructionError) -> ProgramError {
+        self.runtime_error.get_or_insert(error);
+        ProgramError::InvalidArgument
+    }
+}
+
+/// Check the changes `program_id` made to the accounts it was given
+fn verify_accounts(
+    program_id: &Pubkey,
+    pre_accounts: &[PreAccount],
+    accounts: &[AccountInfo],
+) -> Result<(), InstructionError> {
+    pre_accounts.iter().try_for_each(|pre| {
+        match accounts.iter().find(|account| *account.key == pre.key) {
+            Some(account) => pre.verify(program_id, account),
+            None => Ok(()),
+        }
+    })
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Option<MockContext>> = const { RefCell::new(None) };
+}
+
+// syscall stubs are global, so executions are serialized and other threads
+// fall back to the stubs installed before
+static EXECUTION: Mutex<()> = Mutex::new(());
+static FALLBACK: RwLock<Option<Box<dyn SyscallStubs>>> = RwLock::new(None);
+
+fn with_current<R>(f: impl FnOnce(&mut MockContext) -> R) -> Option<R> {
+    CONTEXT.with(|context| context.borrow_mut().as_mut().map(f))
+}
+
+fn with_context<R>(
+    context: MockContext,
+    f: impl FnOnce() -> R,
+) -> (std::thread::Result<R>, MockContext) {
+    let _execution = EXECUTION.lock().unwrap_or_else(PoisonError::into_inner);
+    let previous = set_syscall_stubs(Box::new(MockSyscallStubs));
+    *FALLBACK.write().unwrap_or_else(PoisonError::into_inner) = Some(previous);
+    CONTEXT.with(|current| *current.borrow_mut() = Some(context));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+    let context = CONTEXT.with(|current| current.borrow_mut().take()).unwrap();
+    if let Some(previous) = FALLBACK
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+    {
+        set_syscall_stubs(previous);
+    }
+    (result, context)
+}
+
+struct DefaultSyscallStubs;
+impl SyscallStubs for DefaultSyscallStubs {}
+
+fn fallback<R>(f: impl FnOnce(&dyn SyscallStubs) -> R) -> R {
+    let stubs = FALLBACK.read().unwrap_or_else(PoisonError::into_inner);
+    match stubs.as_deref() {
+        Some(stubs) => f(stubs),
+        None => f(&DefaultSyscallStubs),
+    }
+}
+
+fn is_supported_program(program_id: &Pubkey) -> bool {
+    [
+        spl_token_2022::id(),
+        spl_token::id(),
+        spl_associated_token_account::id(),
+        spl_memo::id(),
+        system_program::id(),
+        compute_budget::id(),
+    ]
+    .contains(program_id)
+}
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    with_current(|context| {
+        context.program_ids.push(*program_id);
+        context.pre_accounts.push(PreAccount::new(accounts));
+        context.units_consumed += MOCK_COMPUTE_UNITS_PER_INSTRUCTION;
+        context.logs.push(format!(
+            "Program {} invoke [{}]",
+            program_id,
+            context.program_ids.len()
+        ));
+    });
+
+    let mut result = if *program_id == spl_token_2022::id() {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+            .inspect_err(PrintProgramError::print::<spl_token_2022::error::TokenError>)
+    } else if *program_id == spl_token::id() {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+            .inspect_err(PrintProgramError::print::<spl_token::error::TokenError>)
+    } else if *program_id == spl_associated_token_account::id() {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+    } else if *program_id == spl_memo::id() {
+        spl_memo::processor::process_instruction(program_id, accounts, data)
+    } else if *program_id == system_program::id() {
+        process_system_instruction(accounts, data)
+    } else if *program_id == compute_budget::id() {
+        Ok(())
+    } else {
+        with_current(|context| {
+            context.logs.push(format!(
+                "Program {} is not supported by the mock client",
+                program_id
+            ))
+        });
+        Err(ProgramError::IncorrectProgramId)
+    };
+
+    with_current(|context| {
+        context.program_ids.pop();
+        let pre_accounts = context.pre_accounts.pop().unwrap_or_default();
+        let runtime_error = result
+            .is_ok()
+            .then(|| verify_accounts(program_id, &pre_accounts, accounts).err())
+            .flatten();
+        context.logs.push(match (&result, &runtime_error) {
+            (_, Some(error)) => format!("Program {} failed: {}", program_id, error),
+            (Ok(()), None) => format!("Program {} success", program_id),
+            (Err(error), None) => format!("Program {} failed: {}", program_id, error),
+        });
+        if let Some(error) = runtime_error {
+            result = Err(context.fail_runtime_check(error));
+        }
+    });
+    result
+}
+
+fn system_error(error: SystemError) -> ProgramError {
+    ProgramError::Custom(error as u32)
+}
+
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !from.data_is_empty() || *from.owner != system_program::id() {
+        return Err(system_error(SystemError::InvalidAccountDataLength));
+    }
+    let from_lamports = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or_else(|| system_error(SystemError::ResultWithNegativeLamports))?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !account.data_is_empty() || *account.owner != system_program::id() {
+        return Err(system_error(SystemError::AccountAlreadyInUse));
+    }
+    account.realloc(space as usize, true)
+}
+
+fn assign(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    account.assign(owner);
+    Ok(())
+}
+
+/// The system instructions needed to create and fund accounts
+fn process_system_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction: SystemInstruction =
+        limited_deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let (from, to) = (account(0)?, account(1)?);
+            if to.lamports() > 0 {
+                return Err(system_error(SystemError::AccountAlreadyInUse));
+            }
+            allocate(to, space)?;
+            assign(to, &owner)?;
+            transfer_lamports(from, to, lamports)
+        }
+        SystemInstruction::Transfer { lamports } => {
+            transfer_lamports(account(0)?, account(1)?, lamports)
+        }
+        SystemInstruction::Allocate { space } => allocate(account(0)?, space),
+        SystemInstruction::Assign { owner } => assign(account(0)?, &owner),
+        _ => {
+            with_current(|context| {
+                context
+                    .logs
+                    .push("System instruction not supported by the mock client".to_string())
+            });
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
+
+struct MockSyscallStubs;
+
+impl SyscallStubs for MockSyscallStubs {
+    fn sol_log(&self, message: &str) {
+        if with_current(|context| context.logs.push(format!("Program log: {}", message))).is_none()
+        {
+            fallback(|stubs| stubs.sol_log(message));
+        }
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let logged = with_current(|context| {
+            let fields = fields
+                .iter()
+                .map(|field| BASE64_STANDARD.encode(field))
+                .collect::<Vec<_>>();
+            context
+                .logs
+                .push(format!("Program data: {}", fields.join(" ")));
+        });
+        if logged.is_none() {
+            fallback(|stubs| stubs.sol_log_data(fields));
+        }
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let Some(caller) = with_current(|context| context.program_ids.last().copied()).flatten()
+        else {
+            return fallback(|stubs| {
+                stubs.sol_invoke_signed(instruction, account_infos, signers_seeds)
+            });
+        };
+        let program_signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut accounts = vec![];
+        for meta in &instruction.accounts {
+            let account_info = account_infos
+                .iter()
+                .find(|account_info| *account_info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_signer && !account_info.is_signer && !program_signers.contains(&meta.pubkey)
+            {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !account_info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+            let mut account_info = account_info.clone();
+            account_info.is_signer = meta.is_signer;
+            account_info.is_writable = meta.is_writable;
+            accounts.push(account_info);
+        }
+
+        // the caller's changes so far are checked before the callee runs, and
+        // its later changes from the state the callee left
+        let verified = with_current(|context| {
+            let pre_accounts = context.pre_accounts.last().map(Vec::as_slice);
+            verify_accounts(&caller, pre_accounts.unwrap_or_default(), &accounts)
+                .map_err(|error| context.fail_runtime_check(error))
+        });
+        if let Some(Err(error)) = verified {
+            return Err(error);
+        }
+        let result = process_instruction(&instruction.program_id, &accounts, &instruction.data);
+        with_current(|context| {
+            for pre in context.pre_accounts.last_mut().into_iter().flatten() {
+                if let Some(account) = accounts.iter().find(|account| *account.key == pre.key) {
+                    pre.update(account);
+                }
+            }
+        });
+        result
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        with_current(|context| {
+            unsafe { *(var_addr as *mut Clock) = context.clock.clone() };
+            SUCCESS
+        })
+        .unwrap_or_else(|| fallback(|stubs| stubs.sol_get_clock_sysvar(var_addr)))
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        with_current(|_| {
+            unsafe { *(var_addr as *mut EpochSchedule) = EpochSchedule::default() };
+            SUCCESS
+        })
+        .unwrap_or_else(|| fallback(|stubs| stubs.sol_get_epoch_schedule_sysvar(var_addr)))
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        with_current(|context| {
+            unsafe { *(var_addr as *mut Rent) = context.rent.clone() };
+            SUCCESS
+        })
+        .unwrap_or_else(|| fallback(|stubs| stubs.sol_get_rent_sysvar(var_addr)))
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        with_current(|context| context.return_data.clone())
+            .unwrap_or_else(|| fallback(|stubs| stubs.sol_get_return_data()))
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let set = with_current(|context| {
+            context.return_data = context
+                .program_ids
+                .last()
+                .filter(|_| !data.is_empty())
+                .map(|program_id| (*program_id, data.to_vec()));
+        });
+        if set.is_none() {
+            fallback(|stubs| stubs.sol_set_return_data(data));
+        }
+    }
+
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        with_current(|_| None)
+            .unwrap_or_else(|| fallback(|stubs| stubs.sol_get_processed_sibling_instruction(index)))
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        with_current(|context| context.program_ids.len() as u64)
+            .unwrap_or_else(|| fallback(|stubs| stubs.sol_get_stack_height()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            batch_sender::BatchSender,
+            client::ProgramRpcClientSendTransaction,
+            token::{Token, TokenError},
+        },
+        solana_program_test::tokio,
+        solana_sdk::{
+            native_token::LAMPORTS_PER_SOL,
+            signature::{Keypair, Signer},
+            system_instruction,
+        },
+        std::sync::Arc,
+    };
+
+    type MockClient = ProgramMockClient<ProgramRpcClientSendTransaction>;
+
+    fn setup() -> (Arc<MockClient>, Arc<Keypair>) {
+        let client = Arc::new(ProgramMockClient::new(ProgramRpcClientSendTransaction));
+        let payer = Arc::new(Keypair::new());
+        client.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL);
+        (client, payer)
+    }
+
+    async fn setup_token(
+        client: &Arc<MockClient>,
+        payer: &Arc<Keypair>,
+        program_id: &Pubkey,
+    ) -> (Token<ProgramRpcClientSendTransaction>, Pubkey) {
+        let mint = Keypair::new();
+        let token = Token::new(
+            client.clone(),
+            program_id,
+            &mint.pubkey(),
+            Some(6),
+            payer.clone(),
+        );
+        token
+            .create_mint(&payer.pubkey(), None, vec![], &[&mint])
+            .await
+            .unwrap();
+        let owner = Pubkey::new_unique();
+        token.create_associated_token_account(&owner).await.unwrap();
+        let account = token.get_associated_token_address(&owner);
+        (token, account)
+    }
+
+    fn transaction_error(error: &ProgramClientError) -> Option<TransactionError> {
+        error
+            .downcast_ref::<ClientError>()
+            .and_then(|error| error.get_transaction_error())
+    }
+
+    fn token_transaction_error(error: &TokenError) -> Option<TransactionError> {
+        match error {
+            TokenError::Client(error) => transaction_error(error),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn token_lifecycle() {
+        for program_id in [spl_token::id(), spl_token_2022::id()] {
+            let (client, payer) = setup();
+            let (token, account) = setup_token(&client, &payer, &program_id).await;
+            token
+                .mint_to(&account, &payer.pubkey(), 100, &[payer.as_ref()])
+                .await
+                .unwrap();
+
+            let account = token.get_account_info(&account).await.unwrap();
+            assert_eq!(account.base.amount, 100);
+            assert_eq!(token.get_mint_info().await.unwrap().base.supply, 100);
+        }
+    }
+
+    #[tokio::test]
+    async fn injected_failures() {
+        let (client, payer) = setup();
+        let (token, account) = setup_token(&client, &payer, &spl_token_2022::id()).await;
+
+        client.push_failure(MockFailure::Transaction(TransactionError::AccountInUse));
+        let error = token
+            .mint_to(&account, &payer.pubkey(), 100, &[payer.as_ref()])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            token_transaction_error(&error),
+            Some(TransactionError::AccountInUse)
+        );
+        let info = token.get_account_info(&account).await.unwrap();
+        assert_eq!(info.base.amount, 0);
+
+        client.set_failure_filter(move |keys| {
+            keys.contains(&account)
+                .then(|| MockFailure::Rpc("connection reset".to_string()))
+        });
+        token
+            .mint_to(&account, &payer.pubkey(), 100, &[payer.as_ref()])
+            .await
+            .unwrap_err();
+
+        client.clear_failures();
+        token
+            .mint_to(&account, &payer.pubkey(), 100, &[payer.as_ref()])
+            .await
+            .unwrap();
+        let info = token.get_account_info(&account).await.unwrap();
+        assert_eq!(info.base.amount, 100);
+    }
+
+    #[tokio::test]
+    async fn instruction_error_charges_fee() {
+        let (client, payer) = setup();
+        let (token, account) = setup_token(&client, &payer, &spl_token_2022::id()).await;
+        let before = client.get_account(payer.pubkey()).await.unwrap().unwrap();
+
+        let wrong_authority = Keypair::new();
+        let error = token
+            .mint_to(
+                &account,
+                &wrong_authority.pubkey(),
+                100,
+                &[&wrong_authority],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            token_transaction_error(&error),
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(spl_token_2022::error::TokenError::OwnerMismatch as u32)
+            ))
+        );
+
+        let after = client.get_account(payer.pubkey()).await.unwrap().unwrap();
+        assert_eq!(
+            before.lamports - after.lamports,
+            2 * MOCK_LAMPORTS_PER_SIGNATURE
+        );
+        let info = token.get_account_info(&account).await.unwrap();
+        assert_eq!(info.base.amount, 0);
+    }
+
+    #[tokio::test]
+    async fn batch_sender_retries_expired_blockhash() {
+        let (client, payer) = setup();
+        let sender = BatchSender::new(client.clone(), payer.clone());
+
+        client.push_failure(MockFailure::Transaction(
+            TransactionError::BlockhashNotFound,
+        ));
+        let report = sender
+            .send(&[vec![spl_memo::build_memo(b"retried", &[])]])
+            .await
+            .unwrap();
+        assert!(report.is_success());
+        assert_eq!(report.transactions[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn stale_blockhash() {
+        let (client, payer) = setup();
+        let blockhash = client.get_latest_blockhash().await.unwrap();
+        client.expire_blockhash();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[spl_memo::build_memo(b"stale", &[])],
+            Some(&payer.pubkey()),
+            &[payer.as_ref()],
+            blockhash,
+        );
+        let error = client.send_transaction(&transaction).await.unwrap_err();
+        assert_eq!(
+            transaction_error(&error),
+            Some(TransactionError::BlockhashNotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn simulation_logs() {
+        let (client, payer) = setup();
+        let (token, account) = setup_token(&client, &payer, &spl_token_2022::id()).await;
+        let blockhash = client.get_latest_blockhash().await.unwrap();
+
+        let mint_to = |authority: &Pubkey| {
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::id(),
+                token.get_address(),
+                &account,
+                authority,
+                &[],
+                100,
+            )
+            .unwrap()
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[mint_to(&payer.pubkey())],
+            Some(&payer.pubkey()),
+            &[payer.as_ref()],
+            blockhash,
+        );
+        let RpcClientResponse::Simulation(simulation) =
+            client.simulate_transaction(&transaction).await.unwrap()
+        else {
+            panic!("expected a simulation");
+        };
+        assert_eq!(simulation.err, None);
+        let logs = simulation.logs.unwrap();
+        assert_eq!(
+            logs[0],
+            format!("Program {} invoke [1]", spl_token_2022::id())
+        );
+        assert!(logs.contains(&"Program log: Instruction: MintTo".to_string()));
+        assert_eq!(
+            simulation.units_consumed,
+            Some(MOCK_COMPUTE_UNITS_PER_INSTRUCTION)
+        );
+
+        let wrong_authority = Keypair::new();
+        let transaction = Transaction::new_signed_with_payer(
+            &[mint_to(&wrong_authority.pubkey())],
+            Some(&payer.pubkey()),
+            &[payer.as_ref(), &wrong_authority],
+            blockhash,
+        );
+        let RpcClientResponse::Simulation(simulation) =
+            client.simulate_transaction(&transaction).await.unwrap()
+        else {
+            panic!("expected a simulation");
+        };
+        assert!(matches!(
+            simulation.err,
+            Some(TransactionError::InstructionError(0, _))
+        ));
+
+        // simulations never commit
+        let info = token.get_account_info(&account).await.unwrap();
+        assert_eq!(info.base.amount, 0);
+    }
+
+    #[tokio::test]
+    async fn runtime_checks() {
+        let (client, payer) = setup();
+        let blockhash = client.get_latest_blockhash().await.unwrap();
+
+        // read-only accounts cannot change
+        let recipient = Pubkey::new_unique();
+        let mut transfer = system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000);
+        transfer.accounts[1].is_writable = false;
+        let transaction = Transaction::new_signed_with_payer(
+            &[transfer],
+            Some(&payer.pubkey()),
+            &[payer.as_ref()],
+            blockhash,
+        );
+        let error = client.send_transaction(&transaction).await.unwrap_err();
+        assert_eq!(
+            transaction_error(&error),
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::ReadonlyLamportChange
+            ))
+        );
+        assert!(client.get_account(recipient).await.unwrap().is_none());
+
+        // only the owner of an account can assign it
+        let account = Keypair::new();
+        client.set_account(
+            account.pubkey(),
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: vec![1; 8],
+                owner: spl_token_2022::id(),
+                ..Account::default()
+            },
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::assign(
+                &account.pubkey(),
+                &Pubkey::new_unique(),
+            )],
+            Some(&payer.pubkey()),
+            &[payer.as_ref(), &account],
+            blockhash,
+        );
+        let error = client.send_transaction(&transaction).await.unwrap_err();
+        assert_eq!(
+            transaction_error(&error),
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::ModifiedProgramId
+            ))
+        );
+        let after = client.get_account(account.pubkey()).await.unwrap().unwrap();
+        assert_eq!(after.owner, spl_token_2022::id());
+    }
+}
@@ -10,6 +10,10 @@ impl fmt::Display for RpcClientResponse {
                 writeln!(f, "Transaction:")?;
                 writeln_transaction(f, &transaction.clone().into(), None, "  ", None, None)
             }
+            RpcClientResponse::VersionedTransaction(transaction) => {
+                writeln!(f, "Transaction:")?;
+                writeln_transaction(f, transaction, None, "  ", None, None)
+            }
             RpcClientResponse::Simulation(result) => {
                 writeln!(f, "Simulation:")?;
                 // maybe implement another formatter on simulation result?
@@ -25,11 +29,12 @@ mod tests {
         super::*,
         solana_sdk::{
             hash::Hash,
+            message::{v0, VersionedMessage},
             pubkey::Pubkey,
             signature::{Signature, Signer, SIGNATURE_BYTES},
             signer::keypair::Keypair,
             system_instruction,
-            transaction::Transaction,
+            transaction::{Transaction, VersionedTransaction},
         },
     };
 
@@ -56,4 +61,24 @@ mod tests {
         let transaction = RpcClientResponse::Transaction(transaction);
         println!("{}", transaction);
     }
+
+    #[test]
+    fn display_versioned_transaction() {
+        let payer = Keypair::new();
+        let message = v0::Message::try_compile(
+            &payer.pubkey(),
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                10,
+            )],
+            &[],
+            Hash::default(),
+        )
+        .unwrap();
+        let transaction =
+            VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer]).unwrap();
+        let transaction = RpcClientResponse::VersionedTransaction(transaction);
+        println!("{}", transaction);
+    }
 }
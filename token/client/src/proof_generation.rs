@@ -1,5 +1,6 @@
 //! Helper functions to generate split zero-knowledge proofs for confidential transfers in the
-//! Confidential Transfer Extension.
+//! Confidential Transfer Extension and for confidential mints and burns in the Confidential
+//! Mint-Burn Extension, as well as re-encryption proofs for auditor key rotation.
 //!
 //! The logic in this submodule should belong to the `solana-zk-token-sdk` and will be removed with
 //! an upgrade to the Solana program.
@@ -24,8 +25,10 @@ use {
                     combine_lo_hi_commitments, combine_lo_hi_openings, FeeEncryption,
                     FeeParameters, TransferAmountCiphertext,
                 },
-                BatchedGroupedCiphertext2HandlesValidityProofData, BatchedRangeProofU256Data,
-                CiphertextCommitmentEqualityProofData, FeeSigmaProofData,
+                BatchedGroupedCiphertext2HandlesValidityProofData, BatchedRangeProofU128Data,
+                BatchedRangeProofU256Data, BatchedRangeProofU64Data,
+                CiphertextCiphertextEqualityProofData, CiphertextCommitmentEqualityProofData,
+                FeeSigmaProofData,
             },
             zk_token_elgamal::ops::subtract_with_lo_hi,
         },
@@ -323,6 +326,245 @@ pub fn transfer_with_fee_split_proof_data(
     ))
 }
 
+/// The main logic to create the two split proof data for a confidential mint.
+pub fn mint_split_proof_data(
+    mint_amount: u64,
+    destination_elgamal_pubkey: &ElGamalPubkey,
+    supply_elgamal_pubkey: &ElGamalPubkey,
+) -> Result<
+    (
+        BatchedGroupedCiphertext2HandlesValidityProofData,
+        BatchedRangeProofU64Data,
+    ),
+    TokenError,
+> {
+    // Split the mint amount into the low and high bit components.
+    let (mint_amount_lo, mint_amount_hi) = verify_and_split_deposit_amount(mint_amount)?;
+
+    // Encrypt the `lo` and `hi` mint amounts under the destination and supply ElGamal public keys
+    let mint_amount_opening_lo = PedersenOpening::new_rand();
+    let mint_amount_opening_hi = PedersenOpening::new_rand();
+    let mint_amount_ciphertext_lo = GroupedElGamal::encrypt_with(
+        [destination_elgamal_pubkey, supply_elgamal_pubkey],
+        mint_amount_lo,
+        &mint_amount_opening_lo,
+    );
+    let mint_amount_ciphertext_hi = GroupedElGamal::encrypt_with(
+        [destination_elgamal_pubkey, supply_elgamal_pubkey],
+        mint_amount_hi,
+        &mint_amount_opening_hi,
+    );
+
+    // generate ciphertext validity data
+    let ciphertext_validity_proof_data = BatchedGroupedCiphertext2HandlesValidityProofData::new(
+        destination_elgamal_pubkey,
+        supply_elgamal_pubkey,
+        &mint_amount_ciphertext_lo,
+        &mint_amount_ciphertext_hi,
+        mint_amount_lo,
+        mint_amount_hi,
+        &mint_amount_opening_lo,
+        &mint_amount_opening_hi,
+    )
+    .map_err(|_| TokenError::ProofGeneration)?;
+
+    // generate range proof data
+    const MINT_AMOUNT_LO_BIT_LENGTH: usize = 16;
+    const MINT_AMOUNT_HI_BIT_LENGTH: usize = 32;
+    const PADDING_BIT_LENGTH: usize = 16;
+
+    let (padding_commitment, padding_opening) = Pedersen::new(0_u64);
+
+    let range_proof_data = BatchedRangeProofU64Data::new(
+        vec![
+            &mint_amount_ciphertext_lo.commitment,
+            &mint_amount_ciphertext_hi.commitment,
+            &padding_commitment,
+        ],
+        vec![mint_amount_lo, mint_amount_hi, 0],
+        vec![
+            MINT_AMOUNT_LO_BIT_LENGTH,
+            MINT_AMOUNT_HI_BIT_LENGTH,
+            PADDING_BIT_LENGTH,
+        ],
+        vec![
+            &mint_amount_opening_lo,
+            &mint_amount_opening_hi,
+            &padding_opening,
+        ],
+    )
+    .map_err(|_| TokenError::ProofGeneration)?;
+
+    Ok((ciphertext_validity_proof_data, range_proof_data))
+}
+
+/// The main logic to create the three split proof data for a confidential burn.
+pub fn burn_split_proof_data(
+    current_available_balance: &ElGamalCiphertext,
+    current_decryptable_available_balance: &AeCiphertext,
+    burn_amount: u64,
+    source_elgamal_keypair: &ElGamalKeypair,
+    aes_key: &AeKey,
+    supply_elgamal_pubkey: &ElGamalPubkey,
+) -> Result<
+    (
+        CiphertextCommitmentEqualityProofData,
+        BatchedGroupedCiphertext2HandlesValidityProofData,
+        BatchedRangeProofU128Data,
+    ),
+    TokenError,
+> {
+    // Split the burn amount into the low and high bit components.
+    let (burn_amount_lo, burn_amount_hi) = verify_and_split_deposit_amount(burn_amount)?;
+
+    // Encrypt the `lo` and `hi` burn amounts under the source and supply ElGamal public keys
+    let burn_amount_opening_lo = PedersenOpening::new_rand();
+    let burn_amount_opening_hi = PedersenOpening::new_rand();
+    let burn_amount_ciphertext_lo = GroupedElGamal::encrypt_with(
+        [source_elgamal_keypair.pubkey(), supply_elgamal_pubkey],
+        burn_amount_lo,
+        &burn_amount_opening_lo,
+    );
+    let burn_amount_ciphertext_hi = GroupedElGamal::encrypt_with(
+        [source_elgamal_keypair.pubkey(), supply_elgamal_pubkey],
+        burn_amount_hi,
+        &burn_amount_opening_hi,
+    );
+
+    // Decrypt the current available balance at the source
+    let current_decrypted_available_balance = current_decryptable_available_balance
+        .decrypt(aes_key)
+        .ok_or(TokenError::AccountDecryption)?;
+
+    // Compute the remaining balance at the source
+    let new_decrypted_available_balance = current_decrypted_available_balance
+        .checked_sub(burn_amount)
+        .ok_or(TokenError::InsufficientFunds)?;
+
+    // Create a new Pedersen commitment for the remaining balance at the source
+    let (new_available_balance_commitment, new_source_opening) =
+        Pedersen::new(new_decrypted_available_balance);
+
+    // Compute the remaining balance at the source as ElGamal ciphertexts
+    let burn_amount_source_ciphertext_lo = ElGamalCiphertext {
+        commitment: burn_amount_ciphertext_lo.commitment,
+        handle: burn_amount_ciphertext_lo.handles[0],
+    };
+    let burn_amount_source_ciphertext_hi = ElGamalCiphertext {
+        commitment: burn_amount_ciphertext_hi.commitment,
+        handle: burn_amount_ciphertext_hi.handles[0],
+    };
+
+    let current_available_balance = (*current_available_balance).into();
+    let new_available_balance_ciphertext = subtract_with_lo_hi(
+        &current_available_balance,
+        &burn_amount_source_ciphertext_lo.into(),
+        &burn_amount_source_ciphertext_hi.into(),
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+    let new_available_balance_ciphertext: ElGamalCiphertext = new_available_balance_ciphertext
+        .try_into()
+        .map_err(|_| TokenError::MalformedCiphertext)?;
+
+    // generate equality proof data
+    let equality_proof_data = CiphertextCommitmentEqualityProofData::new(
+        source_elgamal_keypair,
+        &new_available_balance_ciphertext,
+        &new_available_balance_commitment,
+        &new_source_opening,
+        new_decrypted_available_balance,
+    )
+    .map_err(|_| TokenError::ProofGeneration)?;
+
+    // generate ciphertext validity data
+    let ciphertext_validity_proof_data = BatchedGroupedCiphertext2HandlesValidityProofData::new(
+        source_elgamal_keypair.pubkey(),
+        supply_elgamal_pubkey,
+        &burn_amount_ciphertext_lo,
+        &burn_amount_ciphertext_hi,
+        burn_amount_lo,
+        burn_amount_hi,
+        &burn_amount_opening_lo,
+        &burn_amount_opening_hi,
+    )
+    .map_err(|_| TokenError::ProofGeneration)?;
+
+    // generate range proof data
+    const REMAINING_BALANCE_BIT_LENGTH: usize = 64;
+    const BURN_AMOUNT_LO_BIT_LENGTH: usize = 16;
+    const BURN_AMOUNT_HI_BIT_LENGTH: usize = 32;
+    const PADDING_BIT_LENGTH: usize = 16;
+
+    let (padding_commitment, padding_opening) = Pedersen::new(0_u64);
+
+    let range_proof_data = BatchedRangeProofU128Data::new(
+        vec![
+            &new_available_balance_commitment,
+            &burn_amount_ciphertext_lo.commitment,
+            &burn_amount_ciphertext_hi.commitment,
+            &padding_commitment,
+        ],
+        vec![
+            new_decrypted_available_balance,
+            burn_amount_lo,
+            burn_amount_hi,
+            0,
+        ],
+        vec![
+            REMAINING_BALANCE_BIT_LENGTH,
+            BURN_AMOUNT_LO_BIT_LENGTH,
+            BURN_AMOUNT_HI_BIT_LENGTH,
+            PADDING_BIT_LENGTH,
+        ],
+        vec![
+            &new_source_opening,
+            &burn_amount_opening_lo,
+            &burn_amount_opening_hi,
+            &padding_opening,
+        ],
+    )
+    .map_err(|_| TokenError::ProofGeneration)?;
+
+    Ok((
+        equality_proof_data,
+        ciphertext_validity_proof_data,
+        range_proof_data,
+    ))
+}
+
+/// Re-encrypt a ciphertext under a previous auditor ElGamal public key to a new auditor ElGamal
+/// public key when the auditor of a mint is rotated.
+///
+/// The function returns the re-encrypted ciphertext along with a ciphertext-ciphertext equality
+/// proof data, which the new auditor can verify to be convinced that both ciphertexts encrypt the
+/// same amount. Auditor ciphertexts in a transfer encrypt the low and high bits of the transfer
+/// amount, which are at most 32 bits each.
+pub fn auditor_reencryption_proof_data(
+    previous_auditor_elgamal_keypair: &ElGamalKeypair,
+    new_auditor_elgamal_pubkey: &ElGamalPubkey,
+    auditor_ciphertext: &ElGamalCiphertext,
+) -> Result<(ElGamalCiphertext, CiphertextCiphertextEqualityProofData), TokenError> {
+    let amount = auditor_ciphertext
+        .decrypt_u32(previous_auditor_elgamal_keypair.secret())
+        .ok_or(TokenError::AccountDecryption)?;
+
+    let new_auditor_opening = PedersenOpening::new_rand();
+    let new_auditor_ciphertext =
+        new_auditor_elgamal_pubkey.encrypt_with(amount, &new_auditor_opening);
+
+    let equality_proof_data = CiphertextCiphertextEqualityProofData::new(
+        previous_auditor_elgamal_keypair,
+        new_auditor_elgamal_pubkey,
+        auditor_ciphertext,
+        &new_auditor_ciphertext,
+        &new_auditor_opening,
+        amount,
+    )
+    .map_err(|_| TokenError::ProofGeneration)?;
+
+    Ok((new_auditor_ciphertext, equality_proof_data))
+}
+
 /// Calculate transfer fee and the "delta" value. The function returns the raw fee, which could be
 /// greater than the maximum fee amount of a fee parameter.
 ///
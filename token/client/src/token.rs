@@ -1,22 +1,34 @@
 use {
     crate::{
-        client::{ProgramClient, ProgramClientError, SendTransaction, SimulateTransaction},
-        proof_generation::transfer_with_fee_split_proof_data,
+        client::{
+            ProgramClient, ProgramClientError, SendTransaction, SimulateTransaction,
+            SimulationResult,
+        },
+        proof_generation::{auditor_reencryption_proof_data, transfer_with_fee_split_proof_data},
     },
+    bytemuck::Pod,
     futures::{future::join_all, try_join},
     futures_util::TryFutureExt,
     solana_program_test::tokio::time,
     solana_sdk::{
         account::Account as BaseAccount,
+        address_lookup_table::{
+            instruction::{create_lookup_table, extend_lookup_table},
+            state::AddressLookupTable,
+            AddressLookupTableAccount,
+        },
+        clock::Slot,
+        compute_budget::ComputeBudgetInstruction,
         hash::Hash,
         instruction::{AccountMeta, Instruction},
-        message::Message,
+        message::{v0, Message, VersionedMessage},
         program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
+        signature::Signature,
         signer::{signers::Signers, Signer, SignerError},
         system_instruction,
-        transaction::Transaction,
+        transaction::{Transaction, VersionedTransaction},
     },
     spl_associated_token_account::{
         get_associated_token_address_with_program_id, instruction::create_associated_token_account,
@@ -24,6 +36,11 @@ use {
     },
     spl_token_2022::{
         extension::{
+            confidential_mint_burn::{
+                self,
+                instruction::{BurnSplitContextStateAccounts, MintSplitContextStateAccounts},
+                ConfidentialMintBurn,
+            },
             confidential_transfer::{
                 self,
                 account_info::{
@@ -41,14 +58,14 @@ use {
                 ConfidentialTransferFeeConfig,
             },
             cpi_guard, default_account_state, group_pointer, interest_bearing_mint, memo_transfer,
-            metadata_pointer, transfer_fee, transfer_hook, BaseStateWithExtensions, ExtensionType,
-            StateWithExtensionsOwned,
+            metadata_pointer, pausable, scaled_ui_amount, transfer_fee, transfer_hook, vesting,
+            BaseStateWithExtensions, Extension, ExtensionType, StateWithExtensionsOwned,
         },
         instruction, offchain,
         proof::ProofLocation,
         solana_zk_token_sdk::{
             encryption::{
-                auth_encryption::AeKey,
+                auth_encryption::{AeCiphertext, AeKey},
                 elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey, ElGamalSecretKey},
             },
             instruction::*,
@@ -57,8 +74,9 @@ use {
             zk_token_proof_program,
             zk_token_proof_state::ProofContextState,
         },
-        state::{Account, AccountState, Mint, Multisig},
+        state::{Account, AccountState, Mint, Multisig, MultisigProposal, WeightedMultisig},
     },
+    spl_token_group_interface::state::{TokenGroup, TokenGroupMember},
     spl_token_metadata_interface::state::{Field, TokenMetadata},
     std::{
         fmt, io,
@@ -173,6 +191,21 @@ pub enum ExtensionInitializationParams {
         authority: Option<Pubkey>,
         group_address: Option<Pubkey>,
     },
+    PausableConfig {
+        authority: Pubkey,
+    },
+    ScaledUiAmountConfig {
+        authority: Option<Pubkey>,
+        multiplier: f64,
+    },
+    ConfidentialMintBurn {
+        supply_elgamal_pubkey: PodElGamalPubkey,
+        decryptable_supply: DecryptableBalance,
+    },
+    TransferHookMintBurn {
+        invoke_on_mint: bool,
+        invoke_on_burn: bool,
+    },
 }
 impl ExtensionInitializationParams {
     /// Get the extension type associated with the init params
@@ -191,6 +224,10 @@ impl ExtensionInitializationParams {
                 ExtensionType::ConfidentialTransferFeeConfig
             }
             Self::GroupPointer { .. } => ExtensionType::GroupPointer,
+            Self::PausableConfig { .. } => ExtensionType::Pausable,
+            Self::ScaledUiAmountConfig { .. } => ExtensionType::ScaledUiAmount,
+            Self::ConfidentialMintBurn { .. } => ExtensionType::ConfidentialMintBurn,
+            Self::TransferHookMintBurn { .. } => ExtensionType::TransferHookMintBurn,
         }
     }
     /// Generate an appropriate initialization instruction for the given mint
@@ -291,6 +328,36 @@ impl ExtensionInitializationParams {
                 authority,
                 group_address,
             ),
+            Self::PausableConfig { authority } => {
+                pausable::instruction::initialize(token_program_id, mint, &authority)
+            }
+            Self::ScaledUiAmountConfig {
+                authority,
+                multiplier,
+            } => scaled_ui_amount::instruction::initialize(
+                token_program_id,
+                mint,
+                authority,
+                multiplier,
+            ),
+            Self::ConfidentialMintBurn {
+                supply_elgamal_pubkey,
+                decryptable_supply,
+            } => confidential_mint_burn::instruction::initialize_mint(
+                token_program_id,
+                mint,
+                supply_elgamal_pubkey,
+                decryptable_supply,
+            ),
+            Self::TransferHookMintBurn {
+                invoke_on_mint,
+                invoke_on_burn,
+            } => transfer_hook::instruction::initialize_mint_burn_hooks(
+                token_program_id,
+                mint,
+                invoke_on_mint,
+                invoke_on_burn,
+            ),
         }
     }
 }
@@ -311,6 +378,41 @@ impl TokenMemo {
     }
 }
 
+/// Compute unit limit to request for each transaction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ComputeUnitLimit {
+    /// Do not request a limit, leaving the runtime default
+    #[default]
+    Default,
+    /// Simulate each transaction and request the units it consumed, plus a
+    /// margin
+    Simulated,
+    /// Request a fixed limit for every transaction
+    Static(u32),
+}
+
+/// Maximum compute unit limit of a transaction, used while simulating
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Margin added to the simulated compute units, in percent, since the state
+/// the transaction lands on may differ from the simulated one
+const COMPUTE_UNIT_LIMIT_MARGIN_PERCENT: u64 = 10;
+
+/// Compute unit limit to request for a transaction that consumed
+/// `units_consumed` in a simulation
+fn compute_unit_limit_with_margin(units_consumed: u64) -> u32 {
+    let margin = units_consumed
+        .saturating_mul(COMPUTE_UNIT_LIMIT_MARGIN_PERCENT)
+        .div_ceil(100);
+    u32::try_from(units_consumed.saturating_add(margin))
+        .unwrap_or(u32::MAX)
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
+/// Number of addresses added to a lookup table per transaction, small enough
+/// for the extend instruction to fit in a legacy transaction
+const LOOKUP_TABLE_EXTEND_CHUNK_SIZE: usize = 20;
+
 pub struct Token<T> {
     client: Arc<dyn ProgramClient<T>>,
     pubkey: Pubkey, /*token mint*/
@@ -322,6 +424,9 @@ pub struct Token<T> {
     nonce_blockhash: Option<Hash>,
     memo: Arc<RwLock<Option<TokenMemo>>>,
     transfer_hook_accounts: Option<Vec<AccountMeta>>,
+    compute_unit_price: Option<u64>,
+    compute_unit_limit: ComputeUnitLimit,
+    address_lookup_tables: Vec<AddressLookupTableAccount>,
 }
 
 impl<T> fmt::Debug for Token<T> {
@@ -339,6 +444,16 @@ impl<T> fmt::Debug for Token<T> {
             .field("nonce_blockhash", &self.nonce_blockhash)
             .field("memo", &self.memo.read().unwrap())
             .field("transfer_hook_accounts", &self.transfer_hook_accounts)
+            .field("compute_unit_price", &self.compute_unit_price)
+            .field("compute_unit_limit", &self.compute_unit_limit)
+            .field(
+                "address_lookup_tables",
+                &self
+                    .address_lookup_tables
+                    .iter()
+                    .map(|table| table.key)
+                    .collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
@@ -385,6 +500,9 @@ where
             nonce_blockhash: None,
             memo: Arc::new(RwLock::new(None)),
             transfer_hook_accounts: None,
+            compute_unit_price: None,
+            compute_unit_limit: ComputeUnitLimit::Default,
+            address_lookup_tables: vec![],
         }
     }
 
@@ -434,6 +552,27 @@ where
         self
     }
 
+    /// Set the price of each compute unit, in micro-lamports
+    pub fn with_compute_unit_price(mut self, compute_unit_price: u64) -> Self {
+        self.compute_unit_price = Some(compute_unit_price);
+        self
+    }
+
+    /// Set how the compute unit limit of each transaction is requested
+    pub fn with_compute_unit_limit(mut self, compute_unit_limit: ComputeUnitLimit) -> Self {
+        self.compute_unit_limit = compute_unit_limit;
+        self
+    }
+
+    /// Send v0 transactions, compiled against the given address lookup tables
+    pub fn with_address_lookup_tables(
+        mut self,
+        address_lookup_tables: Vec<AddressLookupTableAccount>,
+    ) -> Self {
+        self.address_lookup_tables = address_lookup_tables;
+        self
+    }
+
     pub fn with_memo<M: AsRef<str>>(&self, memo: M, signers: Vec<Pubkey>) -> &Self {
         let mut w_memo = self.memo.write().unwrap();
         *w_memo = Some(TokenMemo {
@@ -488,14 +627,100 @@ where
         }
     }
 
-    async fn construct_tx<S: Signers>(
+    /// Request the units consumed by a simulation of the transaction, plus a
+    /// margin, as its compute unit limit
+    async fn add_compute_unit_limit_from_simulation(
+        &self,
+        instructions: &mut Vec<Instruction>,
+        blockhash: &Hash,
+    ) -> TokenResult<()> {
+        // simulate with the maximum limit, so the simulation cannot run out
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+            MAX_COMPUTE_UNIT_LIMIT,
+        ));
+        let simulation = if self.address_lookup_tables.is_empty() {
+            let transaction = Transaction::new_unsigned(self.new_message(instructions, blockhash));
+            self.client.simulate_transaction(&transaction).await
+        } else {
+            let message = self.new_versioned_message(instructions, blockhash)?;
+            let transaction = VersionedTransaction {
+                signatures: vec![
+                    Signature::default();
+                    message.header().num_required_signatures as usize
+                ],
+                message,
+            };
+            self.client
+                .simulate_versioned_transaction(&transaction)
+                .await
+        };
+        let units_consumed = simulation
+            .map_err(TokenError::Client)?
+            .get_compute_units_consumed()
+            .map_err(TokenError::Client)?;
+        *instructions.last_mut().unwrap() = ComputeBudgetInstruction::set_compute_unit_limit(
+            compute_unit_limit_with_margin(units_consumed),
+        );
+        Ok(())
+    }
+
+    fn new_message(&self, instructions: &[Instruction], blockhash: &Hash) -> Message {
+        let payer_key = self.payer.pubkey();
+        if let (Some(nonce_account), Some(nonce_authority)) =
+            (self.nonce_account, &self.nonce_authority)
+        {
+            let mut message = Message::new_with_nonce(
+                instructions.to_vec(),
+                Some(&payer_key),
+                &nonce_account,
+                &nonce_authority.pubkey(),
+            );
+            message.recent_blockhash = *blockhash;
+            message
+        } else {
+            Message::new_with_blockhash(instructions, Some(&payer_key), blockhash)
+        }
+    }
+
+    fn new_versioned_message(
+        &self,
+        instructions: &[Instruction],
+        blockhash: &Hash,
+    ) -> TokenResult<VersionedMessage> {
+        let mut all_instructions = vec![];
+        if let (Some(nonce_account), Some(nonce_authority)) =
+            (self.nonce_account, &self.nonce_authority)
+        {
+            all_instructions.push(system_instruction::advance_nonce_account(
+                &nonce_account,
+                &nonce_authority.pubkey(),
+            ));
+        }
+        all_instructions.extend_from_slice(instructions);
+        let message = v0::Message::try_compile(
+            &self.payer.pubkey(),
+            &all_instructions,
+            &self.address_lookup_tables,
+            *blockhash,
+        )
+        .map_err(|error| TokenError::Client(error.into()))?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// Gather the instructions of a transaction, with the blockhash it should
+    /// be signed with
+    async fn construct_instructions<S: Signers>(
         &self,
         token_instructions: &[Instruction],
         signing_keypairs: &S,
-    ) -> TokenResult<Transaction> {
+    ) -> TokenResult<(Vec<Instruction>, Hash)> {
         let mut instructions = vec![];
-        let payer_key = self.payer.pubkey();
-        let fee_payer = Some(&payer_key);
+
+        if let Some(compute_unit_price) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                compute_unit_price,
+            ));
+        }
 
         {
             let mut w_memo = self.memo.write().unwrap();
@@ -515,33 +740,44 @@ where
 
         instructions.extend_from_slice(token_instructions);
 
-        let (message, blockhash) =
-            if let (Some(nonce_account), Some(nonce_authority), Some(nonce_blockhash)) = (
-                self.nonce_account,
-                &self.nonce_authority,
-                self.nonce_blockhash,
-            ) {
-                let mut message = Message::new_with_nonce(
-                    token_instructions.to_vec(),
-                    fee_payer,
-                    &nonce_account,
-                    &nonce_authority.pubkey(),
-                );
-                message.recent_blockhash = nonce_blockhash;
-                (message, nonce_blockhash)
-            } else {
-                let latest_blockhash = self
-                    .client
-                    .get_latest_blockhash()
-                    .await
-                    .map_err(TokenError::Client)?;
-                (
-                    Message::new_with_blockhash(&instructions, fee_payer, &latest_blockhash),
-                    latest_blockhash,
-                )
-            };
+        let blockhash = if let Some(nonce_blockhash) = self.nonce_blockhash {
+            nonce_blockhash
+        } else {
+            self.client
+                .get_latest_blockhash()
+                .await
+                .map_err(TokenError::Client)?
+        };
+
+        // the simulation must include every other instruction, so the limit
+        // is added last
+        match self.compute_unit_limit {
+            ComputeUnitLimit::Default => {}
+            ComputeUnitLimit::Simulated => {
+                self.add_compute_unit_limit_from_simulation(&mut instructions, &blockhash)
+                    .await?;
+            }
+            ComputeUnitLimit::Static(compute_unit_limit) => {
+                instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                    compute_unit_limit,
+                ));
+            }
+        }
+
+        Ok((instructions, blockhash))
+    }
 
-        let mut transaction = Transaction::new_unsigned(message);
+    async fn construct_tx<S: Signers>(
+        &self,
+        token_instructions: &[Instruction],
+        signing_keypairs: &S,
+    ) -> TokenResult<Transaction> {
+        let (instructions, blockhash) = self
+            .construct_instructions(token_instructions, signing_keypairs)
+            .await?;
+
+        let mut transaction =
+            Transaction::new_unsigned(self.new_message(&instructions, &blockhash));
 
         transaction
             .try_partial_sign(&vec![self.payer.clone()], blockhash)
@@ -558,11 +794,75 @@ where
         Ok(transaction)
     }
 
+    async fn construct_versioned_tx<S: Signers>(
+        &self,
+        token_instructions: &[Instruction],
+        signing_keypairs: &S,
+    ) -> TokenResult<VersionedTransaction> {
+        let (instructions, blockhash) = self
+            .construct_instructions(token_instructions, signing_keypairs)
+            .await?;
+        let message = self.new_versioned_message(&instructions, &blockhash)?;
+
+        // partially sign, like `Transaction::try_partial_sign`, so that
+        // offline signers can add the missing signatures later
+        let message_data = message.serialize();
+        let num_required_signatures = message.header().num_required_signatures as usize;
+        let signer_keys = &message.static_account_keys()[..num_required_signatures];
+        let mut signatures = vec![Signature::default(); num_required_signatures];
+        let mut pubkeys = vec![self.payer.pubkey()];
+        let mut signer_signatures = vec![self
+            .payer
+            .try_sign_message(&message_data)
+            .map_err(|error| TokenError::Client(error.into()))?];
+        if let Some(nonce_authority) = &self.nonce_authority {
+            pubkeys.push(nonce_authority.pubkey());
+            signer_signatures.push(
+                nonce_authority
+                    .try_sign_message(&message_data)
+                    .map_err(|error| TokenError::Client(error.into()))?,
+            );
+        }
+        pubkeys.extend(
+            signing_keypairs
+                .try_pubkeys()
+                .map_err(|error| TokenError::Client(error.into()))?,
+        );
+        signer_signatures.extend(
+            signing_keypairs
+                .try_sign_message(&message_data)
+                .map_err(|error| TokenError::Client(error.into()))?,
+        );
+        for (pubkey, signature) in pubkeys.into_iter().zip(signer_signatures) {
+            let position = signer_keys
+                .iter()
+                .position(|key| *key == pubkey)
+                .ok_or_else(|| TokenError::Client(SignerError::KeypairPubkeyMismatch.into()))?;
+            signatures[position] = signature;
+        }
+
+        Ok(VersionedTransaction {
+            signatures,
+            message,
+        })
+    }
+
     pub async fn simulate_ixs<S: Signers>(
         &self,
         token_instructions: &[Instruction],
         signing_keypairs: &S,
     ) -> TokenResult<T::SimulationOutput> {
+        if !self.address_lookup_tables.is_empty() {
+            let transaction = self
+                .construct_versioned_tx(token_instructions, signing_keypairs)
+                .await?;
+            return self
+                .client
+                .simulate_versioned_transaction(&transaction)
+                .await
+                .map_err(TokenError::Client);
+        }
+
         let transaction = self
             .construct_tx(token_instructions, signing_keypairs)
             .await?;
@@ -573,11 +873,40 @@ where
             .map_err(TokenError::Client)
     }
 
+    /// Build and sign a transaction without sending it, so that its signature
+    /// is known before it is submitted
+    pub async fn sign_ixs<S: Signers>(
+        &self,
+        token_instructions: &[Instruction],
+        signing_keypairs: &S,
+    ) -> TokenResult<VersionedTransaction> {
+        if !self.address_lookup_tables.is_empty() {
+            return self
+                .construct_versioned_tx(token_instructions, signing_keypairs)
+                .await;
+        }
+
+        self.construct_tx(token_instructions, signing_keypairs)
+            .await
+            .map(VersionedTransaction::from)
+    }
+
     pub async fn process_ixs<S: Signers>(
         &self,
         token_instructions: &[Instruction],
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        if !self.address_lookup_tables.is_empty() {
+            let transaction = self
+                .construct_versioned_tx(token_instructions, signing_keypairs)
+                .await?;
+            return self
+                .client
+                .send_versioned_transaction(&transaction)
+                .await
+                .map_err(TokenError::Client);
+        }
+
         let transaction = self
             .construct_tx(token_instructions, signing_keypairs)
             .await?;
@@ -588,6 +917,63 @@ where
             .map_err(TokenError::Client)
     }
 
+    /// Fetch an address lookup table, to be passed to
+    /// `with_address_lookup_tables`
+    pub async fn get_address_lookup_table(
+        &self,
+        address: &Pubkey,
+    ) -> TokenResult<AddressLookupTableAccount> {
+        let account = self.get_account(*address).await?;
+        let table = AddressLookupTable::deserialize(&account.data)
+            .map_err(|error| TokenError::Client(error.into()))?;
+        Ok(AddressLookupTableAccount {
+            key: *address,
+            addresses: table.addresses.to_vec(),
+        })
+    }
+
+    /// Create an address lookup table holding the given addresses, returning
+    /// its address. The table address is derived from `recent_slot`, which
+    /// must be a recent rooted slot, and the table can be used from the slot
+    /// after it was last extended.
+    pub async fn create_address_lookup_table<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        recent_slot: Slot,
+        addresses: &[Pubkey],
+        signing_keypairs: &S,
+    ) -> TokenResult<Pubkey> {
+        let payer = self.payer.pubkey();
+        let (create_instruction, table_address) =
+            create_lookup_table(*authority, payer, recent_slot);
+
+        let mut chunks = addresses.chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE);
+        let mut instructions = vec![create_instruction];
+        if let Some(chunk) = chunks.next() {
+            instructions.push(extend_lookup_table(
+                table_address,
+                *authority,
+                Some(payer),
+                chunk.to_vec(),
+            ));
+        }
+        self.process_ixs(&instructions, signing_keypairs).await?;
+        for chunk in chunks {
+            self.process_ixs(
+                &[extend_lookup_table(
+                    table_address,
+                    *authority,
+                    Some(payer),
+                    chunk.to_vec(),
+                )],
+                signing_keypairs,
+            )
+            .await?;
+        }
+
+        Ok(table_address)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_mint<'a, S: Signers>(
         &self,
@@ -679,6 +1065,136 @@ where
         self.process_ixs(&instructions, &[account]).await
     }
 
+    /// Create weighted multisig, with an optional delay for proposals
+    pub async fn create_weighted_multisig(
+        &self,
+        account: &dyn Signer,
+        multisig_members: &[&Pubkey],
+        weights: &[u8],
+        threshold: u16,
+        min_delay: i64,
+    ) -> TokenResult<T::Output> {
+        let instructions = vec![
+            system_instruction::create_account(
+                &self.payer.pubkey(),
+                &account.pubkey(),
+                self.client
+                    .get_minimum_balance_for_rent_exemption(WeightedMultisig::LEN)
+                    .await
+                    .map_err(TokenError::Client)?,
+                WeightedMultisig::LEN as u64,
+                &self.program_id,
+            ),
+            instruction::initialize_weighted_multisig(
+                &self.program_id,
+                &account.pubkey(),
+                multisig_members,
+                weights,
+                threshold,
+                min_delay,
+            )?,
+        ];
+
+        self.process_ixs(&instructions, &[account]).await
+    }
+
+    /// Propose an instruction to a weighted multisig
+    pub async fn create_multisig_proposal(
+        &self,
+        proposal: &dyn Signer,
+        multisig: &Pubkey,
+        proposer: &dyn Signer,
+        proposed_instruction: &Instruction,
+    ) -> TokenResult<T::Output> {
+        let instructions = vec![
+            system_instruction::create_account(
+                &self.payer.pubkey(),
+                &proposal.pubkey(),
+                self.client
+                    .get_minimum_balance_for_rent_exemption(MultisigProposal::LEN)
+                    .await
+                    .map_err(TokenError::Client)?,
+                MultisigProposal::LEN as u64,
+                &self.program_id,
+            ),
+            instruction::create_multisig_proposal(
+                &self.program_id,
+                &proposal.pubkey(),
+                multisig,
+                &proposer.pubkey(),
+                proposed_instruction,
+            )?,
+        ];
+
+        self.process_ixs(&instructions, &[proposal, proposer]).await
+    }
+
+    /// Approve a weighted multisig proposal with the given signers
+    pub async fn approve_multisig_proposal<S: Signers>(
+        &self,
+        proposal: &Pubkey,
+        multisig: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let signers = signing_pubkeys.iter().collect::<Vec<_>>();
+
+        self.process_ixs(
+            &[instruction::approve_multisig_proposal(
+                &self.program_id,
+                proposal,
+                multisig,
+                &signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Execute an approved weighted multisig proposal
+    pub async fn execute_multisig_proposal(
+        &self,
+        proposal: &Pubkey,
+        multisig: &Pubkey,
+        proposed_instruction: &Instruction,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs::<[&dyn Signer; 0]>(
+            &[instruction::execute_multisig_proposal(
+                &self.program_id,
+                proposal,
+                multisig,
+                proposed_instruction,
+            )?],
+            &[],
+        )
+        .await
+    }
+
+    /// Cancel a weighted multisig proposal with the given signers, sending its
+    /// rent to the destination
+    pub async fn cancel_multisig_proposal<S: Signers>(
+        &self,
+        proposal: &Pubkey,
+        multisig: &Pubkey,
+        destination: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let signers = signing_pubkeys.iter().collect::<Vec<_>>();
+
+        self.process_ixs(
+            &[instruction::cancel_multisig_proposal(
+                &self.program_id,
+                proposal,
+                multisig,
+                destination,
+                &signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Get the address for the associated token account.
     pub fn get_associated_token_address(&self, owner: &Pubkey) -> Pubkey {
         get_associated_token_address_with_program_id(owner, &self.pubkey, &self.program_id)
@@ -859,8 +1375,8 @@ where
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
-        let instructions = if let Some(decimals) = self.decimals {
-            [instruction::mint_to_checked(
+        let mut instruction = if let Some(decimals) = self.decimals {
+            instruction::mint_to_checked(
                 &self.program_id,
                 &self.pubkey,
                 destination,
@@ -868,53 +1384,189 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?]
+            )?
         } else {
-            [instruction::mint_to(
+            instruction::mint_to(
                 &self.program_id,
                 &self.pubkey,
                 destination,
                 authority,
                 &multisig_signers,
                 amount,
-            )?]
+            )?
         };
+        // offline signing skips resolution, same as for transfers
+        if self.transfer_hook_accounts.is_none() {
+            offchain::resolve_extra_mint_to_account_metas(
+                &mut instruction,
+                |address| {
+                    self.client
+                        .get_account(address)
+                        .map_ok(|opt| opt.map(|acc| acc.data))
+                },
+                self.get_address(),
+            )
+            .await
+            .map_err(|_| TokenError::AccountNotFound)?;
+        }
 
-        self.process_ixs(&instructions, signing_keypairs).await
+        self.process_ixs(&[instruction], signing_keypairs).await
     }
 
-    /// Transfer tokens to another account
+    /// Mint new tokens that are locked under a vesting schedule, reallocating
+    /// the destination for the schedule at the payer's expense if needed
     #[allow(clippy::too_many_arguments)]
-    pub async fn transfer<S: Signers>(
+    pub async fn mint_to_with_vesting<S: Signers>(
         &self,
-        source: &Pubkey,
         destination: &Pubkey,
         authority: &Pubkey,
         amount: u64,
+        start_timestamp: i64,
+        cliff_timestamp: i64,
+        end_timestamp: i64,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+        let decimals = self.decimals.ok_or(TokenError::MissingDecimals)?;
 
-        let mut instruction = if let Some(decimals) = self.decimals {
-            instruction::transfer_checked(
-                &self.program_id,
-                source,
-                &self.pubkey,
-                destination,
-                authority,
-                &multisig_signers,
-                amount,
-                decimals,
-            )?
-        } else {
-            #[allow(deprecated)]
-            instruction::transfer(
-                &self.program_id,
-                source,
-                destination,
+        let mut instruction = vesting::instruction::mint_to(
+            &self.program_id,
+            &self.pubkey,
+            destination,
+            authority,
+            &self.payer.pubkey(),
+            &multisig_signers,
+            amount,
+            decimals,
+            start_timestamp,
+            cliff_timestamp,
+            end_timestamp,
+        )?;
+        if self.transfer_hook_accounts.is_none() {
+            offchain::resolve_extra_mint_to_account_metas(
+                &mut instruction,
+                |address| {
+                    self.client
+                        .get_account(address)
+                        .map_ok(|opt| opt.map(|acc| acc.data))
+                },
+                self.get_address(),
+            )
+            .await
+            .map_err(|_| TokenError::AccountNotFound)?;
+        }
+
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    /// Transfer tokens to another account
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer<S: Signers>(
+        &self,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let instruction = self
+            .transfer_instruction(source, destination, authority, &multisig_signers, amount)
+            .await?;
+
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    /// Transfer tokens to the associated accounts of several owners in a single
+    /// transaction, creating the associated accounts if `fund_recipients` is set
+    pub async fn transfer_to_associated_accounts<S: Signers>(
+        &self,
+        source: &Pubkey,
+        authority: &Pubkey,
+        recipients: &[(Pubkey, u64)],
+        fund_recipients: bool,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let instructions = self
+            .transfer_to_associated_accounts_instructions(
+                source,
                 authority,
-                &multisig_signers,
+                recipients,
+                fund_recipients,
+                &signing_pubkeys,
+            )
+            .await?;
+
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Instructions for `transfer_to_associated_accounts`
+    pub async fn transfer_to_associated_accounts_instructions(
+        &self,
+        source: &Pubkey,
+        authority: &Pubkey,
+        recipients: &[(Pubkey, u64)],
+        fund_recipients: bool,
+        signing_pubkeys: &[Pubkey],
+    ) -> TokenResult<Vec<Instruction>> {
+        let multisig_signers = self.get_multisig_signers(authority, signing_pubkeys);
+
+        let mut instructions = vec![];
+        for (owner, amount) in recipients {
+            if fund_recipients {
+                instructions.push(create_associated_token_account_idempotent(
+                    &self.payer.pubkey(),
+                    owner,
+                    &self.pubkey,
+                    &self.program_id,
+                ));
+            }
+            instructions.push(
+                self.transfer_instruction(
+                    source,
+                    &self.get_associated_token_address(owner),
+                    authority,
+                    &multisig_signers,
+                    *amount,
+                )
+                .await?,
+            );
+        }
+
+        Ok(instructions)
+    }
+
+    async fn transfer_instruction(
+        &self,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        multisig_signers: &[&Pubkey],
+        amount: u64,
+    ) -> TokenResult<Instruction> {
+        let mut instruction = if let Some(decimals) = self.decimals {
+            instruction::transfer_checked(
+                &self.program_id,
+                source,
+                &self.pubkey,
+                destination,
+                authority,
+                multisig_signers,
+                amount,
+                decimals,
+            )?
+        } else {
+            #[allow(deprecated)]
+            instruction::transfer(
+                &self.program_id,
+                source,
+                destination,
+                authority,
+                multisig_signers,
                 amount,
             )?
         };
@@ -934,7 +1586,7 @@ where
             .map_err(|_| TokenError::AccountNotFound)?;
         };
 
-        self.process_ixs(&[instruction], signing_keypairs).await
+        Ok(instruction)
     }
 
     /// Transfer tokens to an associated account, creating it if it does not exist
@@ -1047,8 +1699,8 @@ where
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
-        let instructions = if let Some(decimals) = self.decimals {
-            [instruction::burn_checked(
+        let mut instruction = if let Some(decimals) = self.decimals {
+            instruction::burn_checked(
                 &self.program_id,
                 source,
                 &self.pubkey,
@@ -1056,19 +1708,33 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?]
+            )?
         } else {
-            [instruction::burn(
+            instruction::burn(
                 &self.program_id,
                 source,
                 &self.pubkey,
                 authority,
                 &multisig_signers,
                 amount,
-            )?]
+            )?
         };
+        // offline signing skips resolution, same as for transfers
+        if self.transfer_hook_accounts.is_none() {
+            offchain::resolve_extra_burn_account_metas(
+                &mut instruction,
+                |address| {
+                    self.client
+                        .get_account(address)
+                        .map_ok(|opt| opt.map(|acc| acc.data))
+                },
+                self.get_address(),
+            )
+            .await
+            .map_err(|_| TokenError::AccountNotFound)?;
+        }
 
-        self.process_ixs(&instructions, signing_keypairs).await
+        self.process_ixs(&[instruction], signing_keypairs).await
     }
 
     /// Approve a delegate to spend tokens
@@ -1396,6 +2062,57 @@ where
         .await
     }
 
+    /// Exempt a token account from the mint transfer fee, or charge it a
+    /// lower fee
+    pub async fn set_transfer_fee_override<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_fee::instruction::set_transfer_fee_override(
+                &self.program_id,
+                account,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                transfer_fee_basis_points,
+                maximum_fee,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Disable the transfer fee override on a token account
+    pub async fn disable_transfer_fee_override<S: Signers>(
+        &self,
+        account: &Pubkey,
+        authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_fee::instruction::disable_transfer_fee_override(
+                &self.program_id,
+                account,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Set default account state on mint
     pub async fn set_default_account_state<S: Signers>(
         &self,
@@ -1514,6 +2231,47 @@ where
         .await
     }
 
+    /// Remove disabled extensions from a token account and shrink it, sending
+    /// the freed rent lamports to the destination
+    pub async fn remove_extensions<S: Signers>(
+        &self,
+        account: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        extension_types: &[ExtensionType],
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[instruction::remove_extensions(
+                &self.program_id,
+                account,
+                destination,
+                authority,
+                &multisig_signers,
+                extension_types,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Execute several token instructions in order with a single `Batch`
+    /// instruction
+    pub async fn batch<S: Signers>(
+        &self,
+        instructions: &[Instruction],
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[instruction::batch(&self.program_id, instructions)?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Require memos on transfers into this account
     pub async fn enable_required_transfer_memos<S: Signers>(
         &self,
@@ -1625,6 +2383,31 @@ where
         .await
     }
 
+    /// Update the UI amount multiplier, taking effect at the given timestamp
+    pub async fn update_multiplier<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        new_multiplier: f64,
+        new_multiplier_effective_timestamp: i64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[scaled_ui_amount::instruction::update_multiplier(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_multiplier,
+                new_multiplier_effective_timestamp,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Update transfer hook program id
     pub async fn update_transfer_hook_program_id<S: Signers>(
         &self,
@@ -1648,6 +2431,31 @@ where
         .await
     }
 
+    /// Update whether mint and burn invoke the transfer hook program
+    pub async fn update_transfer_hook_mint_burn<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        invoke_on_mint: bool,
+        invoke_on_burn: bool,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_hook::instruction::update_mint_burn_hooks(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                invoke_on_mint,
+                invoke_on_burn,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Update metadata pointer address
     pub async fn update_metadata_address<S: Signers>(
         &self,
@@ -1694,6 +2502,48 @@ where
         .await
     }
 
+    /// Pause minting, transferring and burning for the mint
+    pub async fn pause<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[pausable::instruction::pause(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Resume minting, transferring and burning for the mint
+    pub async fn resume<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[pausable::instruction::resume(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Update confidential transfer mint
     pub async fn confidential_transfer_update_mint<S: Signers>(
         &self,
@@ -1706,14 +2556,87 @@ where
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[confidential_transfer::instruction::update_mint(
+            &[confidential_transfer::instruction::update_mint(
+                &self.program_id,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                auto_approve_new_account,
+                auditor_elgamal_pubkey,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Rotates the auditor ElGamal public key of a mint. The new auditor ElGamal keypair is
+    /// required to prove knowledge of the new auditor secret key and the previous auditor ElGamal
+    /// keypair is required to hand the auditor role over to the new auditor.
+    pub async fn confidential_transfer_rotate_auditor_elgamal_pubkey<S: Signers>(
+        &self,
+        authority: &Pubkey,
+        context_state_account: Option<&Pubkey>,
+        reencryption_context_state_account: Option<&Pubkey>,
+        previous_auditor_elgamal_keypair: &ElGamalKeypair,
+        new_auditor_elgamal_keypair: &ElGamalKeypair,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let proof_data = if context_state_account.is_some() {
+            None
+        } else {
+            Some(
+                confidential_transfer::instruction::PubkeyValidityData::new(
+                    new_auditor_elgamal_keypair,
+                )
+                .map_err(|_| TokenError::ProofGeneration)?,
+            )
+        };
+
+        // The previous auditor re-encrypts a fresh ciphertext under the new auditor public key,
+        // which proves that the handover is authorized by the holder of the previous secret key.
+        let reencryption_proof_data = if reencryption_context_state_account.is_some() {
+            None
+        } else {
+            let (_, equality_proof_data) = auditor_reencryption_proof_data(
+                previous_auditor_elgamal_keypair,
+                new_auditor_elgamal_keypair.pubkey(),
+                &previous_auditor_elgamal_keypair.pubkey().encrypt(0_u64),
+            )
+            .map_err(|_| TokenError::ProofGeneration)?;
+            Some(equality_proof_data)
+        };
+
+        let proof_location = if let Some(proof_data_temp) = proof_data.as_ref() {
+            ProofLocation::InstructionOffset(1.try_into().unwrap(), proof_data_temp)
+        } else {
+            let context_state_account = context_state_account.unwrap();
+            ProofLocation::ContextStateAccount(context_state_account)
+        };
+
+        let reencryption_proof_location =
+            if let Some(proof_data_temp) = reencryption_proof_data.as_ref() {
+                let proof_instruction_offset: i8 = if proof_data.is_some() { 2 } else { 1 };
+                ProofLocation::InstructionOffset(
+                    proof_instruction_offset.try_into().unwrap(),
+                    proof_data_temp,
+                )
+            } else {
+                let context_state_account = reencryption_context_state_account.unwrap();
+                ProofLocation::ContextStateAccount(context_state_account)
+            };
+
+        self.process_ixs(
+            &confidential_transfer::instruction::rotate_auditor_elgamal_pubkey(
                 &self.program_id,
                 &self.pubkey,
                 authority,
                 &multisig_signers,
-                auto_approve_new_account,
-                auditor_elgamal_pubkey,
-            )?],
+                proof_location,
+                reencryption_proof_location,
+            )?,
             signing_keypairs,
         )
         .await
@@ -3402,6 +4325,164 @@ where
         .await
     }
 
+    /// Create a context state account holding a verified zero-knowledge proof for a confidential
+    /// mint or burn.
+    pub async fn confidential_mint_burn_create_proof_context_state<ZK, U, S>(
+        &self,
+        context_state_account: &Pubkey,
+        context_state_authority: &Pubkey,
+        instruction_type: ProofInstruction,
+        proof_data: &ZK,
+        context_state_signer: &S,
+    ) -> TokenResult<T::Output>
+    where
+        ZK: Pod + ZkProofData<U>,
+        U: Pod,
+        S: Signer,
+    {
+        let space = size_of::<ProofContextState<U>>();
+        let rent = self
+            .client
+            .get_minimum_balance_for_rent_exemption(space)
+            .await
+            .map_err(TokenError::Client)?;
+        let context_state_info = ContextStateInfo {
+            context_state_account,
+            context_state_authority,
+        };
+        self.process_ixs(
+            &[system_instruction::create_account(
+                &self.payer.pubkey(),
+                context_state_account,
+                rent,
+                space as u64,
+                &zk_token_proof_program::id(),
+            )],
+            &[context_state_signer],
+        )
+        .await?;
+
+        // Range proofs are close to the transaction size limit, so the proof is verified in a
+        // separate transaction
+        self.process_ixs(
+            &[instruction_type.encode_verify_proof(Some(context_state_info), proof_data)],
+            &[] as &[&dyn Signer; 0],
+        )
+        .await
+    }
+
+    /// Mint tokens confidentially to the pending balance of a token account.
+    ///
+    /// This function assumes that proof context states have already been created.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confidential_mint<S: Signers>(
+        &self,
+        destination_account: &Pubkey,
+        mint_authority: &Pubkey,
+        context_state_accounts: MintSplitContextStateAccounts<'_>,
+        amount: u64,
+        supply_aes_key: &AeKey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(mint_authority, &signing_pubkeys);
+
+        let mint = self.get_mint_info().await?;
+        let confidential_mint_burn = mint.get_extension::<ConfidentialMintBurn>()?;
+        let current_supply = AeCiphertext::try_from(confidential_mint_burn.decryptable_supply)
+            .ok()
+            .and_then(|decryptable_supply| decryptable_supply.decrypt(supply_aes_key))
+            .ok_or(TokenError::AccountDecryption)?;
+        let new_supply = current_supply
+            .checked_add(amount)
+            .ok_or(TokenError::Program(ProgramError::ArithmeticOverflow))?;
+
+        self.process_ixs(
+            &[confidential_mint_burn::instruction::confidential_mint(
+                &self.program_id,
+                destination_account,
+                &self.pubkey,
+                supply_aes_key.encrypt(new_supply).into(),
+                mint_authority,
+                &multisig_signers,
+                context_state_accounts,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Burn tokens confidentially from the available balance of a token account.
+    ///
+    /// This function assumes that proof context states have already been created.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confidential_burn<S: Signers>(
+        &self,
+        source_account: &Pubkey,
+        source_authority: &Pubkey,
+        context_state_accounts: BurnSplitContextStateAccounts<'_>,
+        amount: u64,
+        account_info: Option<TransferAccountInfo>,
+        source_aes_key: &AeKey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(source_authority, &signing_pubkeys);
+
+        let account_info = if let Some(account_info) = account_info {
+            account_info
+        } else {
+            let account = self.get_account_info(source_account).await?;
+            let confidential_transfer_account =
+                account.get_extension::<ConfidentialTransferAccount>()?;
+            TransferAccountInfo::new(confidential_transfer_account)
+        };
+
+        let new_decryptable_available_balance = account_info
+            .new_decryptable_available_balance(amount, source_aes_key)
+            .map_err(|_| TokenError::AccountDecryption)?;
+
+        self.process_ixs(
+            &[confidential_mint_burn::instruction::confidential_burn(
+                &self.program_id,
+                source_account,
+                &self.pubkey,
+                new_decryptable_available_balance.into(),
+                source_authority,
+                &multisig_signers,
+                context_state_accounts,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update the decryptable supply of a confidential mint-burn mint
+    pub async fn confidential_mint_burn_update_decryptable_supply<S: Signers>(
+        &self,
+        mint_authority: &Pubkey,
+        new_supply: u64,
+        supply_aes_key: &AeKey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(mint_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[
+                confidential_mint_burn::instruction::update_decryptable_supply(
+                    &self.program_id,
+                    &self.pubkey,
+                    mint_authority,
+                    &multisig_signers,
+                    supply_aes_key.encrypt(new_supply),
+                )?,
+            ],
+            signing_keypairs,
+        )
+        .await
+    }
+
     pub async fn withdraw_excess_lamports<S: Signers>(
         &self,
         source: &Pubkey,
@@ -3626,4 +4707,195 @@ where
         )
         .await
     }
+
+    async fn get_additional_rent_for_fixed_len_extension<V: Extension + Pod>(
+        &self,
+    ) -> TokenResult<u64> {
+        let account = self.get_account(self.pubkey).await?;
+        let account_lamports = account.lamports;
+        let mint_state = self.unpack_mint_info(account)?;
+        if mint_state.get_extension::<V>().is_ok() {
+            Ok(0)
+        } else {
+            let new_account_len = mint_state.try_get_new_account_len::<V>()?;
+            let new_rent_exempt_minimum = self
+                .client
+                .get_minimum_balance_for_rent_exemption(new_account_len)
+                .await
+                .map_err(TokenError::Client)?;
+            Ok(new_rent_exempt_minimum.saturating_sub(account_lamports))
+        }
+    }
+
+    /// Initialize token-group on a mint
+    pub async fn token_group_initialize<S: Signers>(
+        &self,
+        mint_authority: &Pubkey,
+        update_authority: &Pubkey,
+        max_size: u32,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[spl_token_group_interface::instruction::initialize_group(
+                &self.program_id,
+                &self.pubkey,
+                &self.pubkey,
+                mint_authority,
+                Some(*update_authority),
+                max_size,
+            )],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Initialize token-group on a mint. Includes a transfer for any
+    /// additional rent-exempt SOL required.
+    pub async fn token_group_initialize_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Pubkey,
+        mint_authority: &Pubkey,
+        update_authority: &Pubkey,
+        max_size: u32,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension::<TokenGroup>()
+            .await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(spl_token_group_interface::instruction::initialize_group(
+            &self.program_id,
+            &self.pubkey,
+            &self.pubkey,
+            mint_authority,
+            Some(*update_authority),
+            max_size,
+        ));
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Update a token-group max size on a mint
+    pub async fn token_group_update_max_size<S: Signers>(
+        &self,
+        update_authority: &Pubkey,
+        new_max_size: u32,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[
+                spl_token_group_interface::instruction::update_group_max_size(
+                    &self.program_id,
+                    &self.pubkey,
+                    update_authority,
+                    new_max_size,
+                ),
+            ],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update the token-group authority on a mint
+    pub async fn token_group_update_authority<S: Signers>(
+        &self,
+        current_authority: &Pubkey,
+        new_authority: Option<Pubkey>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[
+                spl_token_group_interface::instruction::update_group_authority(
+                    &self.program_id,
+                    &self.pubkey,
+                    current_authority,
+                    new_authority,
+                ),
+            ],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Initialize a token-group member on a mint
+    pub async fn token_group_initialize_member<S: Signers>(
+        &self,
+        mint_authority: &Pubkey,
+        group_mint: &Pubkey,
+        group_update_authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[spl_token_group_interface::instruction::initialize_member(
+                &self.program_id,
+                &self.pubkey,
+                &self.pubkey,
+                mint_authority,
+                group_mint,
+                group_update_authority,
+            )],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Initialize a token-group member on a mint. Includes a transfer for any
+    /// additional rent-exempt SOL required.
+    pub async fn token_group_initialize_member_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Pubkey,
+        mint_authority: &Pubkey,
+        group_mint: &Pubkey,
+        group_update_authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension::<TokenGroupMember>()
+            .await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(spl_token_group_interface::instruction::initialize_member(
+            &self.program_id,
+            &self.pubkey,
+            &self.pubkey,
+            mint_authority,
+            group_mint,
+            group_update_authority,
+        ));
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_unit_limit_margin() {
+        assert_eq!(compute_unit_limit_with_margin(0), 0);
+        assert_eq!(compute_unit_limit_with_margin(10_000), 11_000);
+        // the margin is rounded up
+        assert_eq!(compute_unit_limit_with_margin(10_001), 11_002);
+        // the limit never exceeds the maximum limit of a transaction
+        assert_eq!(
+            compute_unit_limit_with_margin(1_300_000),
+            MAX_COMPUTE_UNIT_LIMIT
+        );
+        assert_eq!(
+            compute_unit_limit_with_margin(u64::MAX),
+            MAX_COMPUTE_UNIT_LIMIT
+        );
+    }
 }
@@ -2,5 +2,6 @@ export enum AccountType {
     Uninitialized,
     Mint,
     Account,
+    WeightedMultisig,
 }
 export const ACCOUNT_TYPE_SIZE = 1;
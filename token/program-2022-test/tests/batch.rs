@@ -0,0 +1,230 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::Signer,
+        signer::keypair::Keypair,
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+    spl_token_2022::{error::TokenError, instruction},
+    spl_token_client::token::TokenError as TokenClientError,
+};
+
+#[tokio::test]
+async fn success_batch() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        decimals,
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    // mint, transfer and burn in one instruction
+    token
+        .batch(
+            &[
+                instruction::mint_to_checked(
+                    &spl_token_2022::id(),
+                    token.get_address(),
+                    &alice_account,
+                    &mint_authority.pubkey(),
+                    &[],
+                    100,
+                    decimals,
+                )
+                .unwrap(),
+                instruction::transfer_checked(
+                    &spl_token_2022::id(),
+                    &alice_account,
+                    token.get_address(),
+                    &bob_account,
+                    &alice.pubkey(),
+                    &[],
+                    40,
+                    decimals,
+                )
+                .unwrap(),
+                instruction::burn_checked(
+                    &spl_token_2022::id(),
+                    &bob_account,
+                    token.get_address(),
+                    &bob.pubkey(),
+                    &[],
+                    10,
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[&mint_authority, &alice, &bob],
+        )
+        .await
+        .unwrap();
+
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 60);
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 30);
+    let mint = token.get_mint_info().await.unwrap();
+    assert_eq!(mint.base.supply, 90);
+}
+
+#[tokio::test]
+async fn fail_batch() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        decimals,
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    let mint_to = instruction::mint_to_checked(
+        &spl_token_2022::id(),
+        token.get_address(),
+        &alice_account,
+        &mint_authority.pubkey(),
+        &[],
+        100,
+        decimals,
+    )
+    .unwrap();
+    let transfer = instruction::transfer_checked(
+        &spl_token_2022::id(),
+        &alice_account,
+        token.get_address(),
+        &bob_account,
+        &alice.pubkey(),
+        &[],
+        101,
+        decimals,
+    )
+    .unwrap();
+
+    // a failing instruction fails the whole batch
+    let error = token
+        .batch(&[mint_to.clone(), transfer], &[&mint_authority, &alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InsufficientFunds as u32)
+            )
+        )))
+    );
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 0);
+
+    // not enough accounts for the last instruction
+    let mut batch =
+        instruction::batch(&spl_token_2022::id(), &[mint_to.clone(), mint_to.clone()]).unwrap();
+    batch.accounts.pop();
+    let error = token
+        .process_ixs(&[batch], &[&mint_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+        )))
+    );
+
+    // instructions for other programs cannot be batched
+    let error = instruction::batch(
+        &spl_token_2022::id(),
+        &[Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![],
+        )],
+    )
+    .unwrap_err();
+    assert_eq!(error, ProgramError::IncorrectProgramId);
+
+    // instruction data must fit in a single length byte
+    let error = instruction::batch(
+        &spl_token_2022::id(),
+        &[Instruction::new_with_bytes(
+            spl_token_2022::id(),
+            &[0; 256],
+            vec![],
+        )],
+    )
+    .unwrap_err();
+    assert_eq!(error, ProgramError::InvalidInstructionData);
+
+    // batches cannot be nested
+    let nested_batch = instruction::batch(&spl_token_2022::id(), &[mint_to.clone()]).unwrap();
+    let error = instruction::batch(&spl_token_2022::id(), &[nested_batch.clone()]).unwrap_err();
+    assert_eq!(error, ProgramError::InvalidInstructionData);
+
+    let mut data = vec![
+        46,
+        nested_batch.accounts.len() as u8,
+        nested_batch.data.len() as u8,
+    ];
+    data.extend_from_slice(&nested_batch.data);
+    let error = token
+        .process_ixs(
+            &[Instruction::new_with_bytes(
+                spl_token_2022::id(),
+                &data,
+                nested_batch.accounts,
+            )],
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InvalidInstruction as u32)
+            )
+        )))
+    );
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{keypair_clone, TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{pubkey::Pubkey, signature::Signer},
+    spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account_idempotent,
+    },
+    spl_token_2022::instruction,
+    spl_token_client::{
+        batch_sender::BatchSender,
+        client::{ProgramBanksClient, ProgramBanksClientProcessTransaction, ProgramClient},
+    },
+    std::sync::Arc,
+};
+
+#[tokio::test]
+async fn send_batch() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        ..
+    } = context.token_context.take().unwrap();
+    let payer = keypair_clone(&context.context.lock().await.payer);
+    let client: Arc<dyn ProgramClient<ProgramBanksClientProcessTransaction>> =
+        Arc::new(ProgramBanksClient::new_from_context(
+            Arc::clone(&context.context),
+            ProgramBanksClientProcessTransaction,
+        ));
+    let sender = BatchSender::new(client, Arc::new(keypair_clone(&payer)))
+        .with_signers(vec![Arc::new(keypair_clone(&mint_authority))])
+        .with_max_parallelism(4);
+
+    let owners = (0..20).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let instruction_sets = owners
+        .iter()
+        .enumerate()
+        .map(|(i, owner)| {
+            let account = get_associated_token_address_with_program_id(
+                owner,
+                token.get_address(),
+                &spl_token_2022::id(),
+            );
+            vec![
+                create_associated_token_account_idempotent(
+                    &payer.pubkey(),
+                    owner,
+                    token.get_address(),
+                    &spl_token_2022::id(),
+                ),
+                instruction::mint_to(
+                    &spl_token_2022::id(),
+                    token.get_address(),
+                    &account,
+                    &mint_authority.pubkey(),
+                    &[],
+                    i as u64 + 1,
+                )
+                .unwrap(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    // several sets fit in each transaction
+    let packed = sender.pack(&instruction_sets);
+    assert!(packed.len() > 1);
+    assert!(packed.len() < instruction_sets.len());
+
+    let report = sender.send(&instruction_sets).await.unwrap();
+    assert!(report.is_success());
+    assert_eq!(report.transactions.len(), packed.len());
+    assert!(report.failed_instructions().is_empty());
+    for (i, owner) in owners.iter().enumerate() {
+        assert!(report.instruction_result(i).unwrap().is_ok());
+        let account = get_associated_token_address_with_program_id(
+            owner,
+            token.get_address(),
+            &spl_token_2022::id(),
+        );
+        let account = token.get_account_info(&account).await.unwrap();
+        assert_eq!(account.base.amount, i as u64 + 1);
+    }
+
+    // a failed set is reported along with the rest of its transaction, and
+    // doesn't stop the others
+    let missing_account = Pubkey::new_unique();
+    let existing_account = get_associated_token_address_with_program_id(
+        &owners[0],
+        token.get_address(),
+        &spl_token_2022::id(),
+    );
+    let mint_to = |account: &Pubkey| {
+        vec![instruction::mint_to(
+            &spl_token_2022::id(),
+            token.get_address(),
+            account,
+            &mint_authority.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap()]
+    };
+    // the large memo needs a transaction to itself, and more compute
+    let sender = sender.with_compute_unit_limit(1_400_000);
+    let instruction_sets = vec![
+        mint_to(&missing_account),
+        vec![spl_memo::build_memo(&[b'a'; 1_000], &[])],
+        mint_to(&existing_account),
+    ];
+    let report = sender.send(&instruction_sets).await.unwrap();
+    assert!(!report.is_success());
+    assert_eq!(report.transactions.len(), 3);
+    assert_eq!(report.failed_instructions(), vec![0]);
+    assert!(report.instruction_result(2).unwrap().is_ok());
+    let account = token.get_account_info(&existing_account).await.unwrap();
+    assert_eq!(account.base.amount, 2);
+}
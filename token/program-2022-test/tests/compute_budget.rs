@@ -0,0 +1,126 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{keypair_clone, TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_client::{
+        client::{ProgramBanksClient, ProgramBanksClientProcessTransaction, ProgramClient},
+        token::{ComputeUnitLimit, Token, TokenError as TokenClientError},
+    },
+    std::sync::Arc,
+};
+
+async fn token_with_compute_budget(
+    context: &TestContext,
+    token: &Token<ProgramBanksClientProcessTransaction>,
+    compute_unit_price: u64,
+    compute_unit_limit: ComputeUnitLimit,
+) -> Token<ProgramBanksClientProcessTransaction> {
+    let payer = keypair_clone(&context.context.lock().await.payer);
+    let client: Arc<dyn ProgramClient<ProgramBanksClientProcessTransaction>> =
+        Arc::new(ProgramBanksClient::new_from_context(
+            Arc::clone(&context.context),
+            ProgramBanksClientProcessTransaction,
+        ));
+    Token::new(
+        client,
+        &spl_token_2022::id(),
+        token.get_address(),
+        Some(9),
+        Arc::new(payer),
+    )
+    .with_compute_unit_price(compute_unit_price)
+    .with_compute_unit_limit(compute_unit_limit)
+}
+
+#[tokio::test]
+async fn success_compute_budget() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    for compute_unit_limit in [
+        ComputeUnitLimit::Simulated,
+        ComputeUnitLimit::Static(50_000),
+    ] {
+        let token =
+            token_with_compute_budget(&context, &token, 1_000_000, compute_unit_limit).await;
+        token
+            .mint_to(
+                &alice_account,
+                &mint_authority.pubkey(),
+                10,
+                &[&mint_authority],
+            )
+            .await
+            .unwrap();
+        token
+            .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+            .await
+            .unwrap();
+    }
+
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 20);
+}
+
+#[tokio::test]
+async fn fail_compute_unit_limit_exceeded() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    // the limit is too low for even the compute unit price instruction
+    let token = token_with_compute_budget(&context, &token, 1, ComputeUnitLimit::Static(1)).await;
+    let error = token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::ComputationalBudgetExceeded)
+        )))
+    );
+}
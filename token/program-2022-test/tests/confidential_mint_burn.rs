@@ -0,0 +1,601 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::Signer,
+        signer::keypair::Keypair,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            confidential_mint_burn::{
+                self,
+                instruction::{BurnSplitContextStateAccounts, MintSplitContextStateAccounts},
+                ConfidentialMintBurn,
+            },
+            confidential_transfer::ConfidentialTransferAccount,
+            BaseStateWithExtensions, ExtensionType,
+        },
+        instruction,
+        solana_zk_token_sdk::{
+            encryption::{auth_encryption::*, elgamal::*},
+            zk_token_proof_instruction::*,
+        },
+    },
+    spl_token_client::{
+        client::{SendTransaction, SimulateTransaction},
+        proof_generation::{burn_split_proof_data, mint_split_proof_data},
+        token::{ExtensionInitializationParams, Token, TokenError as TokenClientError},
+    },
+    std::convert::TryInto,
+};
+
+struct ConfidentialTokenAccountMeta {
+    token_account: Pubkey,
+    elgamal_keypair: ElGamalKeypair,
+    aes_key: AeKey,
+}
+
+impl ConfidentialTokenAccountMeta {
+    async fn new<T>(token: &Token<T>, owner: &Keypair) -> Self
+    where
+        T: SendTransaction + SimulateTransaction,
+    {
+        let token_account_keypair = Keypair::new();
+        token
+            .create_auxiliary_token_account_with_extension_space(
+                &token_account_keypair,
+                &owner.pubkey(),
+                vec![ExtensionType::ConfidentialTransferAccount],
+            )
+            .await
+            .unwrap();
+        let token_account = token_account_keypair.pubkey();
+
+        let elgamal_keypair =
+            ElGamalKeypair::new_from_signer(owner, &token_account.to_bytes()).unwrap();
+        let aes_key = AeKey::new_from_signer(owner, &token_account.to_bytes()).unwrap();
+
+        token
+            .confidential_transfer_configure_token_account(
+                &token_account,
+                &owner.pubkey(),
+                None,
+                None,
+                &elgamal_keypair,
+                &aes_key,
+                &[owner],
+            )
+            .await
+            .unwrap();
+
+        Self {
+            token_account,
+            elgamal_keypair,
+            aes_key,
+        }
+    }
+}
+
+async fn setup_confidential_mint_burn(
+    supply_elgamal_keypair: &ElGamalKeypair,
+    supply_aes_key: &AeKey,
+) -> TestContext {
+    let authority = Keypair::new();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(authority.pubkey()),
+                auto_approve_new_accounts: true,
+                auditor_elgamal_pubkey: None,
+            },
+            ExtensionInitializationParams::ConfidentialMintBurn {
+                supply_elgamal_pubkey: (*supply_elgamal_keypair.pubkey()).into(),
+                decryptable_supply: supply_aes_key.encrypt(0).into(),
+            },
+        ])
+        .await
+        .unwrap();
+    context
+}
+
+async fn check_supply<T>(
+    token: &Token<T>,
+    supply_elgamal_keypair: &ElGamalKeypair,
+    supply_aes_key: &AeKey,
+    expected: u64,
+) where
+    T: SendTransaction + SimulateTransaction,
+{
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialMintBurn>().unwrap();
+    assert_eq!(
+        extension
+            .confidential_supply
+            .decrypt(supply_elgamal_keypair.secret())
+            .unwrap(),
+        expected,
+    );
+    assert_eq!(
+        supply_aes_key
+            .decrypt(&extension.decryptable_supply.try_into().unwrap())
+            .unwrap(),
+        expected,
+    );
+    // the public supply is never touched by confidential mints and burns
+    assert_eq!(state.base.supply, 0);
+}
+
+async fn confidential_mint<T>(
+    token: &Token<T>,
+    mint_authority: &Keypair,
+    destination_meta: &ConfidentialTokenAccountMeta,
+    supply_elgamal_keypair: &ElGamalKeypair,
+    supply_aes_key: &AeKey,
+    amount: u64,
+) -> Result<(), TokenClientError>
+where
+    T: SendTransaction + SimulateTransaction,
+{
+    let (ciphertext_validity_proof_data, range_proof_data) = mint_split_proof_data(
+        amount,
+        destination_meta.elgamal_keypair.pubkey(),
+        supply_elgamal_keypair.pubkey(),
+    )
+    .unwrap();
+
+    let context_state_authority = Keypair::new();
+    let ciphertext_validity_proof_context_state_account = Keypair::new();
+    let range_proof_context_state_account = Keypair::new();
+
+    token
+        .confidential_mint_burn_create_proof_context_state(
+            &ciphertext_validity_proof_context_state_account.pubkey(),
+            &context_state_authority.pubkey(),
+            ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity,
+            &ciphertext_validity_proof_data,
+            &ciphertext_validity_proof_context_state_account,
+        )
+        .await
+        .unwrap();
+    token
+        .confidential_mint_burn_create_proof_context_state(
+            &range_proof_context_state_account.pubkey(),
+            &context_state_authority.pubkey(),
+            ProofInstruction::VerifyBatchedRangeProofU64,
+            &range_proof_data,
+            &range_proof_context_state_account,
+        )
+        .await
+        .unwrap();
+
+    token
+        .confidential_mint(
+            &destination_meta.token_account,
+            &mint_authority.pubkey(),
+            MintSplitContextStateAccounts {
+                ciphertext_validity_proof: &ciphertext_validity_proof_context_state_account
+                    .pubkey(),
+                range_proof: &range_proof_context_state_account.pubkey(),
+                authority: &context_state_authority.pubkey(),
+            },
+            amount,
+            supply_aes_key,
+            &[mint_authority],
+        )
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn confidential_mint_burn() {
+    let supply_elgamal_keypair = ElGamalKeypair::new_rand();
+    let supply_aes_key = AeKey::new_rand();
+    let context = setup_confidential_mint_burn(&supply_elgamal_keypair, &supply_aes_key).await;
+
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        ..
+    } = context.token_context.unwrap();
+    let alice_meta = ConfidentialTokenAccountMeta::new(&token, &alice).await;
+
+    check_supply(&token, &supply_elgamal_keypair, &supply_aes_key, 0).await;
+
+    // mint confidentially into the pending balance
+    confidential_mint(
+        &token,
+        &mint_authority,
+        &alice_meta,
+        &supply_elgamal_keypair,
+        &supply_aes_key,
+        65_537,
+    )
+    .await
+    .unwrap();
+
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    assert_eq!(state.base.amount, 0);
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert_eq!(
+        extension
+            .pending_balance_lo
+            .decrypt(alice_meta.elgamal_keypair.secret())
+            .unwrap(),
+        1,
+    );
+    assert_eq!(
+        extension
+            .pending_balance_hi
+            .decrypt(alice_meta.elgamal_keypair.secret())
+            .unwrap(),
+        1,
+    );
+    check_supply(&token, &supply_elgamal_keypair, &supply_aes_key, 65_537).await;
+
+    // only the mint authority can mint
+    let err = confidential_mint(
+        &token,
+        &alice,
+        &alice_meta,
+        &supply_elgamal_keypair,
+        &supply_aes_key,
+        1,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .confidential_transfer_apply_pending_balance(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            None,
+            alice_meta.elgamal_keypair.secret(),
+            &alice_meta.aes_key,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // burn part of the available balance
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    let (equality_proof_data, ciphertext_validity_proof_data, range_proof_data) =
+        burn_split_proof_data(
+            &extension.available_balance.try_into().unwrap(),
+            &extension.decryptable_available_balance.try_into().unwrap(),
+            37,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            supply_elgamal_keypair.pubkey(),
+        )
+        .unwrap();
+
+    let context_state_authority = Keypair::new();
+    let equality_proof_context_state_account = Keypair::new();
+    let ciphertext_validity_proof_context_state_account = Keypair::new();
+    let range_proof_context_state_account = Keypair::new();
+
+    token
+        .confidential_mint_burn_create_proof_context_state(
+            &equality_proof_context_state_account.pubkey(),
+            &context_state_authority.pubkey(),
+            ProofInstruction::VerifyCiphertextCommitmentEquality,
+            &equality_proof_data,
+            &equality_proof_context_state_account,
+        )
+        .await
+        .unwrap();
+    token
+        .confidential_mint_burn_create_proof_context_state(
+            &ciphertext_validity_proof_context_state_account.pubkey(),
+            &context_state_authority.pubkey(),
+            ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity,
+            &ciphertext_validity_proof_data,
+            &ciphertext_validity_proof_context_state_account,
+        )
+        .await
+        .unwrap();
+    token
+        .confidential_mint_burn_create_proof_context_state(
+            &range_proof_context_state_account.pubkey(),
+            &context_state_authority.pubkey(),
+            ProofInstruction::VerifyBatchedRangeProofU128,
+            &range_proof_data,
+            &range_proof_context_state_account,
+        )
+        .await
+        .unwrap();
+
+    token
+        .confidential_burn(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            BurnSplitContextStateAccounts {
+                equality_proof: &equality_proof_context_state_account.pubkey(),
+                ciphertext_validity_proof: &ciphertext_validity_proof_context_state_account
+                    .pubkey(),
+                range_proof: &range_proof_context_state_account.pubkey(),
+                authority: &context_state_authority.pubkey(),
+            },
+            37,
+            None,
+            &alice_meta.aes_key,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert_eq!(
+        extension
+            .available_balance
+            .decrypt(alice_meta.elgamal_keypair.secret())
+            .unwrap(),
+        65_500,
+    );
+    assert_eq!(
+        alice_meta
+            .aes_key
+            .decrypt(&extension.decryptable_available_balance.try_into().unwrap())
+            .unwrap(),
+        65_500,
+    );
+
+    // the confidential supply is updated by the burn, and the decryptable supply is updated
+    // separately by the mint authority
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialMintBurn>().unwrap();
+    assert_eq!(
+        extension
+            .confidential_supply
+            .decrypt(supply_elgamal_keypair.secret())
+            .unwrap(),
+        65_500,
+    );
+    token
+        .confidential_mint_burn_update_decryptable_supply(
+            &mint_authority.pubkey(),
+            65_500,
+            &supply_aes_key,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    check_supply(&token, &supply_elgamal_keypair, &supply_aes_key, 65_500).await;
+}
+
+#[tokio::test]
+async fn confidential_mint_burn_deposit_withdraw_fail() {
+    let supply_elgamal_keypair = ElGamalKeypair::new_rand();
+    let supply_aes_key = AeKey::new_rand();
+    let context = setup_confidential_mint_burn(&supply_elgamal_keypair, &supply_aes_key).await;
+
+    let TokenContext {
+        token,
+        alice,
+        decimals,
+        ..
+    } = context.token_context.unwrap();
+    let alice_meta = ConfidentialTokenAccountMeta::new(&token, &alice).await;
+
+    // public tokens cannot be converted into confidential ones, or the other way around
+    let err = token
+        .confidential_transfer_deposit(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            10,
+            decimals,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::IllegalMintBurnConversion as u32)
+            )
+        )))
+    );
+
+    let err = token
+        .confidential_transfer_withdraw(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            None,
+            0,
+            decimals,
+            None,
+            &alice_meta.elgamal_keypair,
+            &alice_meta.aes_key,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::IllegalMintBurnConversion as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn confidential_mint_burn_public_mint_burn_fail() {
+    let supply_elgamal_keypair = ElGamalKeypair::new_rand();
+    let supply_aes_key = AeKey::new_rand();
+    let context = setup_confidential_mint_burn(&supply_elgamal_keypair, &supply_aes_key).await;
+
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        ..
+    } = context.token_context.unwrap();
+    let alice_meta = ConfidentialTokenAccountMeta::new(&token, &alice).await;
+    let illegal_conversion = TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::IllegalMintBurnConversion as u32),
+        ),
+    )));
+
+    // public mints and burns would reveal the amounts, so only confidential ones are
+    // allowed, checked or not
+    let err = token
+        .mint_to(
+            &alice_meta.token_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, illegal_conversion);
+    let err = token
+        .process_ixs(
+            &[instruction::mint_to(
+                &spl_token_2022::id(),
+                token.get_address(),
+                &alice_meta.token_account,
+                &mint_authority.pubkey(),
+                &[],
+                10,
+            )
+            .unwrap()],
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, illegal_conversion);
+
+    let err = token
+        .burn(&alice_meta.token_account, &alice.pubkey(), 0, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(err, illegal_conversion);
+    let err = token
+        .process_ixs(
+            &[instruction::burn(
+                &spl_token_2022::id(),
+                &alice_meta.token_account,
+                token.get_address(),
+                &alice.pubkey(),
+                &[],
+                0,
+            )
+            .unwrap()],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, illegal_conversion);
+
+    check_supply(&token, &supply_elgamal_keypair, &supply_aes_key, 0).await;
+}
+
+#[tokio::test]
+async fn confidential_mint_burn_requires_confidential_transfer() {
+    let supply_elgamal_keypair = ElGamalKeypair::new_rand();
+    let supply_aes_key = AeKey::new_rand();
+
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![ExtensionInitializationParams::ConfidentialMintBurn {
+            supply_elgamal_pubkey: (*supply_elgamal_keypair.pubkey()).into(),
+            decryptable_supply: supply_aes_key.encrypt(0).into(),
+        }])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InvalidExtensionCombination as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn confidential_mint_burn_update_decryptable_supply_authority() {
+    let supply_elgamal_keypair = ElGamalKeypair::new_rand();
+    let supply_aes_key = AeKey::new_rand();
+    let context = setup_confidential_mint_burn(&supply_elgamal_keypair, &supply_aes_key).await;
+
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+
+    let mut ctx = context.context.lock().await;
+    let instruction = confidential_mint_burn::instruction::update_decryptable_supply(
+        &spl_token_2022::id(),
+        token.get_address(),
+        &alice.pubkey(),
+        &[],
+        supply_aes_key.encrypt(42),
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &alice],
+        ctx.last_blockhash,
+    );
+    let err = ctx
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+    drop(ctx);
+
+    // the decryptable supply is untouched
+    check_supply(&token, &supply_elgamal_keypair, &supply_aes_key, 0).await;
+}
@@ -22,10 +22,12 @@ use {
                 instruction::{
                     TransferSplitContextStateAccounts, TransferWithFeeSplitContextStateAccounts,
                 },
-                ConfidentialTransferAccount, MAXIMUM_DEPOSIT_TRANSFER_AMOUNT,
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+                MAXIMUM_DEPOSIT_TRANSFER_AMOUNT,
             },
             BaseStateWithExtensions, ExtensionType,
         },
+        proof::ProofLocation,
         solana_zk_token_sdk::{
             encryption::{auth_encryption::*, elgamal::*},
             zk_token_elgamal::pod::{self, Zeroable},
@@ -36,7 +38,7 @@ use {
     },
     spl_token_client::{
         client::{SendTransaction, SimulateTransaction},
-        proof_generation::transfer_with_fee_split_proof_data,
+        proof_generation::{auditor_reencryption_proof_data, transfer_with_fee_split_proof_data},
         token::{ExtensionInitializationParams, Token, TokenError as TokenClientError},
     },
     std::{convert::TryInto, mem::size_of},
@@ -3056,3 +3058,362 @@ async fn confidential_transfer_transfer_with_fee_and_split_proof_context_in_para
         )
         .await;
 }
+
+#[tokio::test]
+async fn confidential_transfer_rotate_auditor_elgamal_pubkey() {
+    let authority = Keypair::new();
+    let auto_approve_new_accounts = true;
+    let auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let auditor_elgamal_pubkey = (*auditor_elgamal_keypair.pubkey()).into();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(authority.pubkey()),
+                auto_approve_new_accounts,
+                auditor_elgamal_pubkey: Some(auditor_elgamal_pubkey),
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+
+    let new_auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let new_auditor_elgamal_pubkey = (*new_auditor_elgamal_keypair.pubkey()).into();
+
+    // only the confidential transfer mint authority can rotate the auditor
+    let err = token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &alice.pubkey(),
+            None,
+            None,
+            &auditor_elgamal_keypair,
+            &new_auditor_elgamal_keypair,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32),
+            )
+        )))
+    );
+
+    // only the current auditor can hand the auditor role over
+    let err = token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &authority.pubkey(),
+            None,
+            None,
+            &ElGamalKeypair::new_rand(),
+            &new_auditor_elgamal_keypair,
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    TokenError::ConfidentialTransferElGamalPubkeyMismatch as u32
+                ),
+            )
+        )))
+    );
+
+    // the current auditor must hand the auditor role over to the new auditor that proves
+    // knowledge of its secret key
+    let pubkey_validity_proof_data = PubkeyValidityData::new(&new_auditor_elgamal_keypair).unwrap();
+    let (_, reencryption_proof_data) = auditor_reencryption_proof_data(
+        &auditor_elgamal_keypair,
+        ElGamalKeypair::new_rand().pubkey(),
+        &auditor_elgamal_keypair.pubkey().encrypt(0_u64),
+    )
+    .unwrap();
+    let err = token
+        .process_ixs(
+            &confidential_transfer::instruction::rotate_auditor_elgamal_pubkey(
+                &spl_token_2022::id(),
+                token.get_address(),
+                &authority.pubkey(),
+                &[],
+                ProofLocation::InstructionOffset(
+                    1.try_into().unwrap(),
+                    &pubkey_validity_proof_data,
+                ),
+                ProofLocation::InstructionOffset(2.try_into().unwrap(), &reencryption_proof_data),
+            )
+            .unwrap(),
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    TokenError::ConfidentialTransferElGamalPubkeyMismatch as u32
+                ),
+            )
+        )))
+    );
+
+    token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &authority.pubkey(),
+            None,
+            None,
+            &auditor_elgamal_keypair,
+            &new_auditor_elgamal_keypair,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialTransferMint>().unwrap();
+    assert_eq!(
+        extension.auditor_elgamal_pubkey,
+        Some(new_auditor_elgamal_pubkey).try_into().unwrap(),
+    );
+
+    // the previous auditor can no longer hand the auditor role over
+    let err = token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &authority.pubkey(),
+            None,
+            None,
+            &auditor_elgamal_keypair,
+            &ElGamalKeypair::new_rand(),
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    TokenError::ConfidentialTransferElGamalPubkeyMismatch as u32
+                ),
+            )
+        )))
+    );
+
+    // rotate the auditor again with pre-verified proofs
+    let next_auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let next_auditor_elgamal_pubkey = (*next_auditor_elgamal_keypair.pubkey()).into();
+    let context_state_account = Keypair::new();
+    let reencryption_context_state_account = Keypair::new();
+    {
+        let mut ctx = context.context.lock().await;
+        let rent = ctx.banks_client.get_rent().await.unwrap();
+        let space = size_of::<ProofContextState<PubkeyValidityProofContext>>();
+        let reencryption_space =
+            size_of::<ProofContextState<CiphertextCiphertextEqualityProofContext>>();
+        let proof_data = PubkeyValidityData::new(&next_auditor_elgamal_keypair).unwrap();
+        let (_, reencryption_proof_data) = auditor_reencryption_proof_data(
+            &new_auditor_elgamal_keypair,
+            next_auditor_elgamal_keypair.pubkey(),
+            &new_auditor_elgamal_keypair.pubkey().encrypt(0_u64),
+        )
+        .unwrap();
+        let instructions = vec![
+            system_instruction::create_account(
+                &ctx.payer.pubkey(),
+                &context_state_account.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &zk_token_proof_program::id(),
+            ),
+            verify_pubkey_validity(
+                Some(ContextStateInfo {
+                    context_state_account: &context_state_account.pubkey(),
+                    context_state_authority: &authority.pubkey(),
+                }),
+                &proof_data,
+            ),
+            system_instruction::create_account(
+                &ctx.payer.pubkey(),
+                &reencryption_context_state_account.pubkey(),
+                rent.minimum_balance(reencryption_space),
+                reencryption_space as u64,
+                &zk_token_proof_program::id(),
+            ),
+            verify_ciphertext_ciphertext_equality(
+                Some(ContextStateInfo {
+                    context_state_account: &reencryption_context_state_account.pubkey(),
+                    context_state_authority: &authority.pubkey(),
+                }),
+                &reencryption_proof_data,
+            ),
+        ];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&ctx.payer.pubkey()),
+            &[
+                &ctx.payer,
+                &context_state_account,
+                &reencryption_context_state_account,
+            ],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &authority.pubkey(),
+            Some(&context_state_account.pubkey()),
+            Some(&reencryption_context_state_account.pubkey()),
+            &new_auditor_elgamal_keypair,
+            &next_auditor_elgamal_keypair,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialTransferMint>().unwrap();
+    assert_eq!(
+        extension.auditor_elgamal_pubkey,
+        Some(next_auditor_elgamal_pubkey).try_into().unwrap(),
+    );
+
+    // ciphertexts under the previous auditor can be handed over to the new auditor
+    let auditor_ciphertext = auditor_elgamal_keypair.pubkey().encrypt(42_u64);
+    let (new_auditor_ciphertext, equality_proof_data) = auditor_reencryption_proof_data(
+        &auditor_elgamal_keypair,
+        new_auditor_elgamal_keypair.pubkey(),
+        &auditor_ciphertext,
+    )
+    .unwrap();
+    equality_proof_data.verify_proof().unwrap();
+    assert_eq!(
+        new_auditor_ciphertext.decrypt_u32(new_auditor_elgamal_keypair.secret()),
+        Some(42),
+    );
+}
+
+#[tokio::test]
+async fn confidential_transfer_rotate_auditor_elgamal_pubkey_without_auditor() {
+    let authority = Keypair::new();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(authority.pubkey()),
+                auto_approve_new_accounts: true,
+                auditor_elgamal_pubkey: None,
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext { token, .. } = context.token_context.unwrap();
+
+    // without a current auditor there is no one to hand the auditor role over, so the first
+    // auditor must be set with `UpdateMint`
+    let err = token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &authority.pubkey(),
+            None,
+            None,
+            &ElGamalKeypair::new_rand(),
+            &ElGamalKeypair::new_rand(),
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    TokenError::ConfidentialTransferElGamalPubkeyMismatch as u32
+                ),
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn confidential_transfer_rotate_auditor_elgamal_pubkey_with_multisig() {
+    let multisig = Keypair::new();
+    let signer_1 = Keypair::new();
+    let signer_2 = Keypair::new();
+    let auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let auditor_elgamal_pubkey = (*auditor_elgamal_keypair.pubkey()).into();
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: Some(multisig.pubkey()),
+                auto_approve_new_accounts: true,
+                auditor_elgamal_pubkey: Some(auditor_elgamal_pubkey),
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext { token, .. } = context.token_context.unwrap();
+    token
+        .create_multisig(&multisig, &[&signer_1.pubkey(), &signer_2.pubkey()], 2)
+        .await
+        .unwrap();
+
+    let new_auditor_elgamal_keypair = ElGamalKeypair::new_rand();
+    let new_auditor_elgamal_pubkey = (*new_auditor_elgamal_keypair.pubkey()).into();
+
+    // the multisig threshold must be met
+    let err = token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &multisig.pubkey(),
+            None,
+            None,
+            &auditor_elgamal_keypair,
+            &new_auditor_elgamal_keypair,
+            &[&signer_1],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+        )))
+    );
+
+    token
+        .confidential_transfer_rotate_auditor_elgamal_pubkey(
+            &multisig.pubkey(),
+            None,
+            None,
+            &auditor_elgamal_keypair,
+            &new_auditor_elgamal_keypair,
+            &[&signer_1, &signer_2],
+        )
+        .await
+        .unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialTransferMint>().unwrap();
+    assert_eq!(
+        extension.auditor_elgamal_pubkey,
+        Some(new_auditor_elgamal_pubkey).try_into().unwrap(),
+    );
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{keypair_clone, TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{signature::Signer, signer::keypair::Keypair},
+    spl_token_client::{
+        client::{
+            ProgramBanksClient, ProgramBanksClientProcessTransaction, ProgramClient,
+            ProgramOfflineClient, ProgramRpcClientSendTransaction, RpcClientResponse,
+        },
+        token::{ComputeUnitLimit, Token},
+    },
+    std::sync::Arc,
+};
+
+#[tokio::test]
+async fn success_versioned_transaction() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    // the table must be derived from a slot in the slot hashes, and only
+    // becomes usable in a later one
+    let slot = context
+        .context
+        .lock()
+        .await
+        .banks_client
+        .get_root_slot()
+        .await
+        .unwrap();
+    context.context.lock().await.warp_to_slot(slot + 2).unwrap();
+    let table_authority = Keypair::new();
+    let table_address = token
+        .create_address_lookup_table(
+            &table_authority.pubkey(),
+            slot + 1,
+            &[
+                *token.get_address(),
+                alice_account,
+                bob_account,
+                spl_token_2022::id(),
+            ],
+            &[&table_authority],
+        )
+        .await
+        .unwrap();
+    context.context.lock().await.warp_to_slot(slot + 4).unwrap();
+
+    let table = token
+        .get_address_lookup_table(&table_address)
+        .await
+        .unwrap();
+    assert_eq!(table.key, table_address);
+    assert_eq!(table.addresses.len(), 4);
+
+    let payer = keypair_clone(&context.context.lock().await.payer);
+    let client: Arc<dyn ProgramClient<ProgramBanksClientProcessTransaction>> =
+        Arc::new(ProgramBanksClient::new_from_context(
+            Arc::clone(&context.context),
+            ProgramBanksClientProcessTransaction,
+        ));
+    let versioned_token = Token::new(
+        client,
+        &spl_token_2022::id(),
+        token.get_address(),
+        Some(9),
+        Arc::new(keypair_clone(&payer)),
+    )
+    .with_address_lookup_tables(vec![table.clone()])
+    .with_compute_unit_limit(ComputeUnitLimit::Simulated);
+
+    versioned_token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    versioned_token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 4, &[&alice])
+        .await
+        .unwrap();
+    let account = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(account.base.amount, 6);
+    let account = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(account.base.amount, 4);
+
+    // offline signing returns the partially signed v0 transaction
+    let offline_token = Token::new(
+        Arc::new(ProgramOfflineClient::new(
+            context.context.lock().await.last_blockhash,
+            ProgramRpcClientSendTransaction,
+        )),
+        &spl_token_2022::id(),
+        token.get_address(),
+        Some(9),
+        Arc::new(payer),
+    )
+    .with_address_lookup_tables(vec![table])
+    .with_transfer_hook_accounts(vec![]);
+    let response = offline_token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    let RpcClientResponse::VersionedTransaction(transaction) = response else {
+        panic!("expected a versioned transaction, got {:?}", response);
+    };
+    assert_eq!(
+        transaction.message.address_table_lookups().unwrap()[0].account_key,
+        table_address
+    );
+    assert!(transaction.verify_with_results().into_iter().all(|ok| ok));
+}
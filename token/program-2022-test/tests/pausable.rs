@@ -0,0 +1,266 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            pausable::{PausableAccount, PausableConfig},
+            BaseStateWithExtensions,
+        },
+        instruction::AuthorityType,
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    std::convert::TryInto,
+};
+
+fn paused_error(index: u8) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(
+            index,
+            InstructionError::Custom(TokenError::MintPaused as u32),
+        ),
+    )))
+}
+
+#[tokio::test]
+async fn success_init() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: authority.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    assert!(state.base.is_initialized);
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert_eq!(
+        extension.authority,
+        Some(authority.pubkey()).try_into().unwrap()
+    );
+    assert!(!bool::from(extension.paused));
+
+    // new accounts are marked as belonging to a pausable mint
+    let account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&account, &alice.pubkey())
+        .await
+        .unwrap();
+    let state = token.get_account_info(&account.pubkey()).await.unwrap();
+    assert!(state.get_extension::<PausableAccount>().is_ok());
+}
+
+#[tokio::test]
+async fn pause_and_resume() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: authority.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        token,
+        token_unchecked,
+        mint_authority,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            100,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail, wrong authority
+    let wrong = Keypair::new();
+    let err = token.pause(&wrong.pubkey(), &[&wrong]).await.unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .pause(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert!(bool::from(extension.paused));
+
+    // everything that moves tokens fails
+    let err = token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, paused_error(0));
+    let err = token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(err, paused_error(0));
+    let err = token
+        .burn(&alice_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(err, paused_error(0));
+
+    // an unchecked transfer can't tell whether the mint is paused
+    let err = token_unchecked
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintRequiredForTransfer as u32)
+            )
+        )))
+    );
+
+    token
+        .resume(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert!(!bool::from(extension.paused));
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap();
+    token
+        .burn(&alice_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 90);
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 10);
+}
+
+#[tokio::test]
+async fn set_authority() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: authority.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let token = context.token_context.take().unwrap().token;
+    let new_authority = Keypair::new();
+
+    token
+        .set_authority(
+            token.get_address(),
+            &authority.pubkey(),
+            Some(&new_authority.pubkey()),
+            AuthorityType::Pause,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert_eq!(
+        extension.authority,
+        Some(new_authority.pubkey()).try_into().unwrap(),
+    );
+
+    // the old authority can't pause anymore
+    let err = token
+        .pause(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+    token
+        .pause(&new_authority.pubkey(), &[&new_authority])
+        .await
+        .unwrap();
+
+    // set to none, which leaves the mint paused for good
+    token
+        .set_authority(
+            token.get_address(),
+            &new_authority.pubkey(),
+            None,
+            AuthorityType::Pause,
+            &[&new_authority],
+        )
+        .await
+        .unwrap();
+    let err = token
+        .resume(&new_authority.pubkey(), &[&new_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::NoAuthorityExists as u32)
+            )
+        )))
+    );
+}
@@ -14,7 +14,12 @@ use {
         transaction::{Transaction, TransactionError},
         transport::TransportError,
     },
-    spl_token_2022::{error::TokenError, extension::ExtensionType, state::Account},
+    spl_token_2022::{
+        error::TokenError,
+        extension::{cpi_guard::CpiGuard, BaseStateWithExtensions, ExtensionType},
+        solana_program::program_pack::Pack,
+        state::Account,
+    },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
     std::convert::TryInto,
     test_case::test_case,
@@ -286,3 +291,275 @@ async fn reallocate_updates_native_rent_exemption(
         assert!(pre_rent_exempt_reserve < post_rent_exempt_reserve);
     }
 }
+
+#[tokio::test]
+async fn remove_extensions() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        ..
+    } = context.token_context.unwrap();
+    let context = context.context.clone();
+    let (destination, rent) = {
+        let mut context = context.lock().await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+        (context.payer.pubkey(), rent)
+    };
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    token
+        .reallocate(
+            &alice_account,
+            &alice.pubkey(),
+            &[ExtensionType::MemoTransfer, ExtensionType::CpiGuard],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    token
+        .enable_required_transfer_memos(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    token
+        .enable_cpi_guard(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+
+    // fails on mint extension type
+    let error = token
+        .remove_extensions(
+            &alice_account,
+            &destination,
+            &alice.pubkey(),
+            &[ExtensionType::MintCloseAuthority],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InvalidState as u32)
+            )
+        )))
+    );
+
+    // fails on invalid authority
+    let error = token
+        .remove_extensions(
+            &alice_account,
+            &destination,
+            &mint_authority.pubkey(),
+            &[ExtensionType::MemoTransfer],
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // fails on protected and enabled extensions
+    for extension_type in [
+        ExtensionType::ImmutableOwner,
+        ExtensionType::TransferHookAccount,
+        ExtensionType::NonTransferableAccount,
+        ExtensionType::MemoTransfer,
+        ExtensionType::CpiGuard,
+    ] {
+        let error = token
+            .remove_extensions(
+                &alice_account,
+                &destination,
+                &alice.pubkey(),
+                &[extension_type],
+                &[&alice],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            TokenClientError::Client(Box::new(TransportError::TransactionError(
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(TokenError::ExtensionNotRemovable as u32)
+                )
+            )))
+        );
+    }
+
+    // succeeds once disabled, shrinking the account and refunding rent
+    token
+        .disable_required_transfer_memos(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    let pre_destination_lamports = token.get_account(destination).await.unwrap().lamports;
+    let pre_account_lamports = token.get_account(alice_account).await.unwrap().lamports;
+    token
+        .remove_extensions(
+            &alice_account,
+            &destination,
+            &alice.pubkey(),
+            &[ExtensionType::MemoTransfer],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let account = token.get_account(alice_account).await.unwrap();
+    assert_eq!(
+        account.data.len(),
+        ExtensionType::try_calculate_account_len::<Account>(&[ExtensionType::CpiGuard]).unwrap()
+    );
+    assert_eq!(account.lamports, rent.minimum_balance(account.data.len()));
+    let destination_lamports = token.get_account(destination).await.unwrap().lamports;
+    // the payer also pays the transaction fee
+    assert!(destination_lamports > pre_destination_lamports);
+    assert!(account.lamports < pre_account_lamports);
+    let account_info = token.get_account_info(&alice_account).await.unwrap();
+    assert!(bool::from(
+        account_info.get_extension::<CpiGuard>().unwrap().lock_cpi
+    ));
+
+    // removing an extension twice fails
+    token.get_new_latest_blockhash().await.unwrap();
+    let error = token
+        .remove_extensions(
+            &alice_account,
+            &destination,
+            &alice.pubkey(),
+            &[ExtensionType::MemoTransfer],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        )))
+    );
+
+    // removing the last extension shrinks to the base account size
+    token
+        .disable_cpi_guard(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    token
+        .remove_extensions(
+            &alice_account,
+            &destination,
+            &alice.pubkey(),
+            &[ExtensionType::CpiGuard],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let account = token.get_account(alice_account).await.unwrap();
+    assert_eq!(account.data.len(), Account::LEN);
+    assert_eq!(account.lamports, rent.minimum_balance(Account::LEN));
+
+    // the account is still usable
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    let account_info = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(account_info.base.amount, 10);
+    assert_eq!(account_info.get_extension_types().unwrap(), vec![]);
+}
+
+#[tokio::test]
+async fn remove_extensions_updates_native_rent_exemption() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_native_mint().await.unwrap();
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+    let context = context.context.clone();
+    let destination = context.lock().await.payer.pubkey();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    token
+        .reallocate(
+            &alice_account,
+            &alice.pubkey(),
+            &[ExtensionType::CpiGuard],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    {
+        let mut context = context.lock().await;
+        let instructions = vec![system_instruction::transfer(
+            &context.payer.pubkey(),
+            &alice_account,
+            1_000_000_000,
+        )];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+    token.sync_native(&alice_account).await.unwrap();
+    let pre_amount = token
+        .get_account_info(&alice_account)
+        .await
+        .unwrap()
+        .base
+        .amount;
+
+    // space reserved for an extension that was never initialized is released
+    token
+        .remove_extensions(
+            &alice_account,
+            &destination,
+            &alice.pubkey(),
+            &[],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let account = token.get_account(alice_account).await.unwrap();
+    assert_eq!(account.data.len(), Account::LEN);
+    let expected_rent_exempt_reserve = {
+        let mut context = context.lock().await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+        rent.minimum_balance(Account::LEN)
+    };
+    let token_account = token.get_account_info(&alice_account).await.unwrap();
+    // token amount is unchanged, only the rent exempt reserve shrinks
+    assert_eq!(token_account.base.amount, pre_amount);
+    assert_eq!(
+        token_account.base.is_native.unwrap(),
+        expected_rent_exempt_reserve
+    );
+    assert_eq!(account.lamports, pre_amount + expected_rent_exempt_reserve);
+}
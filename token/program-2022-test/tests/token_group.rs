@@ -0,0 +1,418 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{keypair_clone, TestContext, TokenContext},
+    solana_program_test::{processor, tokio, ProgramTest},
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_pod::{optional_keys::OptionalNonZeroPubkey, primitives::PodU32},
+    spl_token_2022::{error::TokenError, extension::BaseStateWithExtensions, processor::Processor},
+    spl_token_client::{
+        client::{ProgramBanksClient, ProgramBanksClientProcessTransaction, ProgramClient},
+        token::{ExtensionInitializationParams, Token, TokenError as TokenClientError},
+    },
+    spl_token_group_interface::{
+        error::TokenGroupError,
+        state::{TokenGroup, TokenGroupMember},
+    },
+    std::{convert::TryInto, sync::Arc},
+};
+
+fn setup_program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    program_test.add_program(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    program_test
+}
+
+async fn setup(
+    mint: Keypair,
+    extension_init_params: Vec<ExtensionInitializationParams>,
+) -> TestContext {
+    let program_test = setup_program_test();
+
+    let context = program_test.start_with_context().await;
+    let context = Arc::new(tokio::sync::Mutex::new(context));
+    let mut context = TestContext {
+        context,
+        token_context: None,
+    };
+    context
+        .init_token_with_mint_keypair_and_freeze_authority(mint, extension_init_params, None)
+        .await
+        .unwrap();
+    context
+}
+
+fn group_pointer(mint: &Keypair, authority: &Pubkey) -> ExtensionInitializationParams {
+    ExtensionInitializationParams::GroupPointer {
+        authority: Some(*authority),
+        group_address: Some(mint.pubkey()),
+    }
+}
+
+async fn create_member_mint(
+    context: &TestContext,
+    mint_authority: &Pubkey,
+) -> Token<ProgramBanksClientProcessTransaction> {
+    let payer = keypair_clone(&context.context.lock().await.payer);
+    let client: Arc<dyn ProgramClient<ProgramBanksClientProcessTransaction>> =
+        Arc::new(ProgramBanksClient::new_from_context(
+            Arc::clone(&context.context),
+            ProgramBanksClientProcessTransaction,
+        ));
+    let mint = Keypair::new();
+    let token = Token::new(
+        client,
+        &spl_token_2022::id(),
+        &mint.pubkey(),
+        Some(0),
+        Arc::new(payer),
+    );
+    token
+        .create_mint(mint_authority, None, vec![], &[&mint])
+        .await
+        .unwrap();
+    token
+}
+
+#[tokio::test]
+async fn success_initialize_group() {
+    let authority = Pubkey::new_unique();
+    let mint_keypair = Keypair::new();
+    let group_pointer = group_pointer(&mint_keypair, &authority);
+    let mut test_context = setup(mint_keypair, vec![group_pointer]).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let TokenContext {
+        token,
+        mint_authority,
+        ..
+    } = test_context.token_context.take().unwrap();
+
+    let update_authority = Pubkey::new_unique();
+
+    // fails without more lamports for new rent-exemption
+    let error = token
+        .token_group_initialize(
+            &mint_authority.pubkey(),
+            &update_authority,
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InsufficientFundsForRent { account_index: 2 }
+        )))
+    );
+
+    // fail wrong signer
+    let not_mint_authority = Keypair::new();
+    let error = token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &not_mint_authority.pubkey(),
+            &update_authority,
+            10,
+            &[&not_mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenGroupError::IncorrectMintAuthority as u32)
+            )
+        )))
+    );
+
+    token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            &update_authority,
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // check that the data is correct
+    let mint_info = token.get_mint_info().await.unwrap();
+    let group = mint_info.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(
+        *group,
+        TokenGroup {
+            update_authority: Some(update_authority).try_into().unwrap(),
+            mint: *token.get_address(),
+            size: PodU32::from(0),
+            max_size: PodU32::from(10),
+        }
+    );
+
+    // fail double-init
+    let error = token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            &update_authority,
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::ExtensionAlreadyInitialized as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn fail_initialize_group_without_pointer() {
+    let mut test_context = setup(Keypair::new(), vec![]).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let TokenContext {
+        token,
+        mint_authority,
+        ..
+    } = test_context.token_context.take().unwrap();
+
+    let error = token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            &Pubkey::new_unique(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::InvalidExtensionCombination as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn success_update_group_max_size_and_authority() {
+    let authority = Pubkey::new_unique();
+    let mint_keypair = Keypair::new();
+    let group_pointer = group_pointer(&mint_keypair, &authority);
+    let mut test_context = setup(mint_keypair, vec![group_pointer]).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let TokenContext {
+        token,
+        mint_authority,
+        ..
+    } = test_context.token_context.take().unwrap();
+
+    let update_authority = Keypair::new();
+    token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            &update_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail wrong update authority
+    let wrong_authority = Keypair::new();
+    let error = token
+        .token_group_update_max_size(&wrong_authority.pubkey(), 20, &[&wrong_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenGroupError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+
+    token
+        .token_group_update_max_size(&update_authority.pubkey(), 20, &[&update_authority])
+        .await
+        .unwrap();
+    let mint_info = token.get_mint_info().await.unwrap();
+    let group = mint_info.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(u32::from(group.max_size), 20);
+
+    // transfer the authority, then make the group immutable
+    let new_authority = Keypair::new();
+    token
+        .token_group_update_authority(
+            &update_authority.pubkey(),
+            Some(new_authority.pubkey()),
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .token_group_update_authority(&new_authority.pubkey(), None, &[&new_authority])
+        .await
+        .unwrap();
+    let mint_info = token.get_mint_info().await.unwrap();
+    let group = mint_info.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(group.update_authority, OptionalNonZeroPubkey::default());
+
+    let error = token
+        .token_group_update_max_size(&new_authority.pubkey(), 30, &[&new_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenGroupError::ImmutableGroup as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn success_initialize_member() {
+    let authority = Pubkey::new_unique();
+    let mint_keypair = Keypair::new();
+    let group_pointer = group_pointer(&mint_keypair, &authority);
+    let mut test_context = setup(mint_keypair, vec![group_pointer]).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let TokenContext {
+        token,
+        mint_authority,
+        ..
+    } = test_context.token_context.take().unwrap();
+    let group_address = *token.get_address();
+
+    let update_authority = Keypair::new();
+    token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            &update_authority.pubkey(),
+            1,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail a group cannot be its own member
+    let error = token
+        .token_group_initialize_member_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            &group_address,
+            &update_authority.pubkey(),
+            &[&mint_authority, &update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenGroupError::MemberAccountIsGroupAccount as u32)
+            )
+        )))
+    );
+
+    let member_mint_authority = Keypair::new();
+    let member_token = create_member_mint(&test_context, &member_mint_authority.pubkey()).await;
+
+    // fail wrong group update authority
+    let wrong_authority = Keypair::new();
+    let error = member_token
+        .token_group_initialize_member_with_rent_transfer(
+            &payer_pubkey,
+            &member_mint_authority.pubkey(),
+            &group_address,
+            &wrong_authority.pubkey(),
+            &[&member_mint_authority, &wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenGroupError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+
+    member_token
+        .token_group_initialize_member_with_rent_transfer(
+            &payer_pubkey,
+            &member_mint_authority.pubkey(),
+            &group_address,
+            &update_authority.pubkey(),
+            &[&member_mint_authority, &update_authority],
+        )
+        .await
+        .unwrap();
+
+    let mint_info = member_token.get_mint_info().await.unwrap();
+    let member = mint_info.get_extension::<TokenGroupMember>().unwrap();
+    assert_eq!(
+        *member,
+        TokenGroupMember {
+            mint: *member_token.get_address(),
+            group: group_address,
+            member_number: PodU32::from(1),
+        }
+    );
+    let mint_info = token.get_mint_info().await.unwrap();
+    let group = mint_info.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(u32::from(group.size), 1);
+
+    // fail the group is full
+    let other_member_mint_authority = Keypair::new();
+    let other_member_token =
+        create_member_mint(&test_context, &other_member_mint_authority.pubkey()).await;
+    let error = other_member_token
+        .token_group_initialize_member_with_rent_transfer(
+            &payer_pubkey,
+            &other_member_mint_authority.pubkey(),
+            &group_address,
+            &update_authority.pubkey(),
+            &[&other_member_mint_authority, &update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenGroupError::SizeExceedsMaxSize as u32)
+            )
+        )))
+    );
+}
@@ -377,3 +377,83 @@ async fn transfer_with_fee_on_mint_without_fee_configured() {
         )))
     );
 }
+
+#[tokio::test]
+async fn transfer_to_associated_accounts() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            100,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // bob's associated account exists already, carol's does not
+    token
+        .create_associated_token_account(&bob.pubkey())
+        .await
+        .unwrap();
+    let carol = Pubkey::new_unique();
+    let recipients = [(bob.pubkey(), 10), (carol, 20)];
+
+    // without funding, the transfer to carol fails
+
+    let error = token
+        .transfer_to_associated_accounts(
+            &alice_account,
+            &alice.pubkey(),
+            &recipients,
+            false,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(1, InstructionError::IncorrectProgramId)
+        )))
+    );
+
+    token
+        .transfer_to_associated_accounts(
+            &alice_account,
+            &alice.pubkey(),
+            &recipients,
+            true,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 70);
+    let bob_state = token
+        .get_account_info(&token.get_associated_token_address(&bob.pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(bob_state.base.amount, 10);
+    let carol_state = token
+        .get_account_info(&token.get_associated_token_address(&carol))
+        .await
+        .unwrap();
+    assert_eq!(carol_state.base.amount, 20);
+}
@@ -12,9 +12,10 @@ use {
         error::TokenError,
         extension::{
             transfer_fee::{
-                TransferFee, TransferFeeAmount, TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                TransferFee, TransferFeeAmount, TransferFeeConfig, TransferFeeOverride,
+                MAX_FEE_BASIS_POINTS,
             },
-            BaseStateWithExtensions,
+            BaseStateWithExtensions, ExtensionType,
         },
         instruction,
     },
@@ -80,6 +81,7 @@ struct TokenWithAccounts {
     token: Token<ProgramBanksClientProcessTransaction>,
     token_unchecked: Token<ProgramBanksClientProcessTransaction>,
     transfer_fee_config: TransferFeeConfig,
+    transfer_fee_config_authority: Keypair,
     withdraw_withheld_authority: Keypair,
     freeze_authority: Keypair,
     alice: Keypair,
@@ -148,6 +150,7 @@ async fn create_mint_with_accounts(alice_amount: u64) -> TokenWithAccounts {
         token,
         token_unchecked,
         transfer_fee_config,
+        transfer_fee_config_authority,
         withdraw_withheld_authority,
         freeze_authority: freeze_authority.unwrap(),
         alice,
@@ -1713,3 +1716,217 @@ async fn fail_close_with_withheld() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn transfer_fee_override() {
+    let maximum_fee = TEST_MAXIMUM_FEE;
+    let alice_amount = maximum_fee * 100;
+    let TokenWithAccounts {
+        token,
+        transfer_fee_config,
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        alice,
+        alice_account,
+        bob_account,
+        ..
+    } = create_mint_with_accounts(alice_amount).await;
+    let transfer_amount = maximum_fee;
+    let mint_fee = transfer_fee_config
+        .calculate_epoch_fee(0, transfer_amount)
+        .unwrap();
+
+    // account must be reallocated before the override can be set
+    let error = token
+        .set_transfer_fee_override(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            0,
+            0,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        )))
+    );
+    token
+        .reallocate(
+            &alice_account,
+            &alice.pubkey(),
+            &[ExtensionType::TransferFeeOverride],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // only the transfer fee config authority may set the override
+    let error = token
+        .set_transfer_fee_override(&alice_account, &alice.pubkey(), 0, 0, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // basis points above the maximum
+    let error = token
+        .set_transfer_fee_override(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            MAX_FEE_BASIS_POINTS + 1,
+            0,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::TransferFeeExceedsMaximum as u32)
+            )
+        )))
+    );
+
+    // exempt alice
+    token
+        .set_transfer_fee_override(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            0,
+            0,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = alice_state.get_extension::<TransferFeeOverride>().unwrap();
+    assert!(bool::from(extension.enabled));
+    assert_eq!(u16::from(extension.transfer_fee_basis_points), 0);
+    assert_eq!(u64::from(extension.maximum_fee), 0);
+
+    // the mint fee no longer applies
+    let error = token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            mint_fee,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::FeeMismatch as u32)
+            )
+        )))
+    );
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            0,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, transfer_amount);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, 0.into());
+
+    // an override never raises the fee above the mint fee
+    token
+        .set_transfer_fee_override(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            MAX_FEE_BASIS_POINTS,
+            u64::MAX,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            mint_fee,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // disabling restores the mint fee
+    token
+        .set_transfer_fee_override(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            0,
+            0,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .disable_transfer_fee_override(
+            &alice_account,
+            &transfer_fee_config_authority.pubkey(),
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = alice_state.get_extension::<TransferFeeOverride>().unwrap();
+    assert!(!bool::from(extension.enabled));
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            mint_fee,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // withheld fees are still collected on the destination
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, transfer_amount * 3 - mint_fee * 2);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, (mint_fee * 2).into());
+    token
+        .withdraw_withheld_tokens_from_accounts(
+            &alice_account,
+            &withdraw_withheld_authority.pubkey(),
+            &[&bob_account],
+            &[&withdraw_withheld_authority],
+        )
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(
+        alice_state.base.amount,
+        alice_amount - transfer_amount * 3 + mint_fee * 2
+    );
+}
@@ -17,17 +17,21 @@ use {
         transaction::TransactionError,
         transport::TransportError,
     },
+    spl_tlv_account_resolution::state::ExtraAccountMetaList,
     spl_token_2022::{
         error::TokenError,
         extension::{
-            transfer_hook::{TransferHook, TransferHookAccount},
+            transfer_hook::{TransferHook, TransferHookAccount, TransferHookMintBurn},
             BaseStateWithExtensions,
         },
         instruction, offchain, onchain,
         processor::Processor,
     },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
-    spl_transfer_hook_interface::get_extra_account_metas_address,
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address,
+        instruction::{ExecuteInstruction, MintExecuteInstruction, TransferHookInstruction},
+    },
     std::{convert::TryInto, sync::Arc},
 };
 
@@ -40,6 +44,28 @@ pub fn process_instruction_fail(
     Err(ProgramError::InvalidInstructionData)
 }
 
+const MINT_HOOK_EXTRA_ACCOUNT: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Test program to check that extra accounts are resolved for mint hooks, and
+/// that burn hooks without a configured list get no extra accounts
+pub fn process_instruction_mint_burn_extras(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    match TransferHookInstruction::unpack(input)? {
+        TransferHookInstruction::MintExecute { .. }
+            if accounts.len() != 5 || *accounts[4].key != MINT_HOOK_EXTRA_ACCOUNT =>
+        {
+            Err(ProgramError::NotEnoughAccountKeys)
+        }
+        TransferHookInstruction::BurnExecute { .. } if accounts.len() != 4 => {
+            Err(ProgramError::InvalidArgument)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Test program to check signer / write downgrade for repeated accounts, conforms
 /// to transfer-hook-interface
 pub fn process_instruction_downgrade(
@@ -739,3 +765,278 @@ async fn success_transfers_using_onchain_helper() {
         .await
         .unwrap();
 }
+
+async fn setup_with_mint_burn_hooks(
+    program_test: ProgramTest,
+    mint: Keypair,
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    invoke_on_mint: bool,
+    invoke_on_burn: bool,
+) -> TestContext {
+    let context = program_test.start_with_context().await;
+    let context = Arc::new(tokio::sync::Mutex::new(context));
+    let mut context = TestContext {
+        context,
+        token_context: None,
+    };
+    context
+        .init_token_with_mint_keypair_and_freeze_authority(
+            mint,
+            vec![
+                ExtensionInitializationParams::TransferHook {
+                    authority: Some(*authority),
+                    program_id: Some(*program_id),
+                },
+                ExtensionInitializationParams::TransferHookMintBurn {
+                    invoke_on_mint,
+                    invoke_on_burn,
+                },
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+    context
+}
+
+#[tokio::test]
+async fn success_mint_and_burn_hooks() {
+    let authority = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+    let mint = Keypair::new();
+    let mut program_test = setup_program_test(&program_id);
+    add_validation_account(&mut program_test, &mint.pubkey(), &program_id);
+    let token_context =
+        setup_with_mint_burn_hooks(program_test, mint, &program_id, &authority, true, true)
+            .await
+            .token_context
+            .take()
+            .unwrap();
+
+    let state = token_context.token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TransferHookMintBurn>().unwrap();
+    assert!(bool::from(extension.invoke_on_mint));
+    assert!(bool::from(extension.invoke_on_burn));
+
+    let amount = 10;
+    let (alice_account, _) =
+        setup_accounts(&token_context, Keypair::new(), Keypair::new(), amount).await;
+    token_context
+        .token
+        .burn(
+            &alice_account,
+            &token_context.alice.pubkey(),
+            1,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+
+    let account = token_context
+        .token
+        .get_account_info(&alice_account)
+        .await
+        .unwrap();
+    assert_eq!(account.base.amount, amount - 1);
+    let state = token_context.token.get_mint_info().await.unwrap();
+    assert_eq!(state.base.supply, amount - 1);
+
+    // the example hook checked the flags, which are cleared afterwards
+    let extension = state.get_extension::<TransferHookMintBurn>().unwrap();
+    assert!(!bool::from(extension.minting));
+    assert!(!bool::from(extension.burning));
+}
+
+#[tokio::test]
+async fn success_mint_hook_resolves_extra_accounts() {
+    let authority = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+    let mint = Keypair::new();
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    program_test.add_program(
+        "my_transfer_hook",
+        program_id,
+        processor!(process_instruction_mint_burn_extras),
+    );
+
+    // transfers get no extra accounts, mints get one, burns have no entry
+    let mint_extra_account_metas =
+        [AccountMeta::new_readonly(MINT_HOOK_EXTRA_ACCOUNT, false).into()];
+    let mut data = vec![
+        0;
+        ExtraAccountMetaList::size_of(0).unwrap()
+            + ExtraAccountMetaList::size_of(mint_extra_account_metas.len()).unwrap()
+    ];
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &[]).unwrap();
+    ExtraAccountMetaList::init::<MintExecuteInstruction>(&mut data, &mint_extra_account_metas)
+        .unwrap();
+    program_test.add_account(
+        get_extra_account_metas_address(&mint.pubkey(), &program_id),
+        Account {
+            lamports: 1_000_000_000, // a lot, just to be safe
+            data,
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+    let token_context =
+        setup_with_mint_burn_hooks(program_test, mint, &program_id, &authority, true, true)
+            .await
+            .token_context
+            .take()
+            .unwrap();
+
+    let amount = 10;
+    let (alice_account, _) =
+        setup_accounts(&token_context, Keypair::new(), Keypair::new(), amount).await;
+    token_context
+        .token
+        .burn(
+            &alice_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn fail_burn_hook_program() {
+    let authority = Keypair::new();
+    let program_id = Pubkey::new_unique();
+    let mint = Keypair::new();
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    program_test.add_program(
+        "my_transfer_hook",
+        program_id,
+        processor!(process_instruction_fail),
+    );
+    program_test.add_account(
+        get_extra_account_metas_address(&mint.pubkey(), &program_id),
+        Account {
+            lamports: 1_000_000_000, // a lot, just to be safe
+            data: spl_transfer_hook_example::state::example_data(&[]).unwrap(),
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+    let token_context = setup_with_mint_burn_hooks(
+        program_test,
+        mint,
+        &program_id,
+        &authority.pubkey(),
+        false,
+        true,
+    )
+    .await
+    .token_context
+    .take()
+    .unwrap();
+
+    // minting does not call the hook program
+    let amount = 10;
+    let (alice_account, _) =
+        setup_accounts(&token_context, Keypair::new(), Keypair::new(), amount).await;
+
+    let err = token_context
+        .token
+        .burn(
+            &alice_account,
+            &token_context.alice.pubkey(),
+            1,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        )))
+    );
+
+    // fail, wrong signature
+    let wrong = Keypair::new();
+    let err = token_context
+        .token
+        .update_transfer_hook_mint_burn(&wrong.pubkey(), false, false, &[&wrong])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // opt out of burn hooks, burning works again
+    token_context
+        .token
+        .update_transfer_hook_mint_burn(&authority.pubkey(), false, false, &[&authority])
+        .await
+        .unwrap();
+    token_context
+        .token
+        .burn(
+            &alice_account,
+            &token_context.alice.pubkey(),
+            1,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn fail_mint_burn_hooks_without_transfer_hook() {
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    let context = program_test.start_with_context().await;
+    let context = Arc::new(tokio::sync::Mutex::new(context));
+    let mut context = TestContext {
+        context,
+        token_context: None,
+    };
+    let err = context
+        .init_token_with_mint_keypair_and_freeze_authority(
+            Keypair::new(),
+            vec![ExtensionInitializationParams::TransferHookMintBurn {
+                invoke_on_mint: true,
+                invoke_on_burn: true,
+            }],
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InvalidExtensionCombination as u32)
+            )
+        )))
+    );
+}
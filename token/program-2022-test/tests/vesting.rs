@@ -0,0 +1,359 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::{tokio, ProgramTestContext},
+    solana_sdk::{
+        clock::Clock, instruction::InstructionError, pubkey::Pubkey, rent::Rent, signature::Signer,
+        signer::keypair::Keypair, transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{vesting::VestingSchedule, BaseStateWithExtensions, ExtensionType},
+        state::Account,
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    std::sync::Arc,
+    tokio::sync::Mutex,
+};
+
+const START: i64 = 1_000;
+const CLIFF: i64 = 1_500;
+const END: i64 = 2_000;
+const VESTED_AMOUNT: u64 = 1_000;
+
+fn token_error(error: TokenError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32)),
+    )))
+}
+
+async fn set_clock(context: &Arc<Mutex<ProgramTestContext>>, unix_timestamp: i64) {
+    let mut context = context.lock().await;
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn mint_to_with_vesting() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        ..
+    } = context.token_context.take().unwrap();
+    let context = context.context;
+    set_clock(&context, START).await;
+
+    // account without space for the extension, reallocated at the payer's expense
+    let no_space_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&no_space_account, &alice.pubkey())
+        .await
+        .unwrap();
+    token
+        .mint_to_with_vesting(
+            &no_space_account.pubkey(),
+            &mint_authority.pubkey(),
+            VESTED_AMOUNT,
+            START,
+            CLIFF,
+            END,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    let state = token
+        .get_account_info(&no_space_account.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(state.base.amount, VESTED_AMOUNT);
+    let schedule = state.get_extension::<VestingSchedule>().unwrap();
+    assert_eq!(u64::from(schedule.total_amount), VESTED_AMOUNT);
+    let account = {
+        let mut context = context.lock().await;
+        context
+            .banks_client
+            .get_account(no_space_account.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+    };
+    let account_len =
+        ExtensionType::try_calculate_account_len::<Account>(&[ExtensionType::Vesting]).unwrap();
+    assert_eq!(account.data.len(), account_len);
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(account_len)
+    );
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account_with_extension_space(
+            &alice_account,
+            &alice.pubkey(),
+            vec![ExtensionType::Vesting],
+        )
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    // invalid schedules
+    for (start, cliff, end) in [
+        (CLIFF, START, END),
+        (START, END, CLIFF),
+        (START, START, START),
+        (0, 1, START),
+    ] {
+        let error = token
+            .mint_to_with_vesting(
+                &alice_account,
+                &mint_authority.pubkey(),
+                VESTED_AMOUNT,
+                start,
+                cliff,
+                end,
+                &[&mint_authority],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error, token_error(TokenError::InvalidVestingSchedule));
+    }
+
+    // only the mint authority
+    let error = token
+        .mint_to_with_vesting(
+            &alice_account,
+            &alice.pubkey(),
+            VESTED_AMOUNT,
+            START,
+            CLIFF,
+            END,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::OwnerMismatch));
+
+    token
+        .mint_to_with_vesting(
+            &alice_account,
+            &mint_authority.pubkey(),
+            VESTED_AMOUNT,
+            START,
+            CLIFF,
+            END,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.amount, VESTED_AMOUNT);
+    let schedule = state.get_extension::<VestingSchedule>().unwrap();
+    assert_eq!(u64::from(schedule.total_amount), VESTED_AMOUNT);
+    assert_eq!(i64::from(schedule.start_timestamp), START);
+    assert_eq!(i64::from(schedule.cliff_timestamp), CLIFF);
+    assert_eq!(i64::from(schedule.end_timestamp), END);
+    let mint = token.get_mint_info().await.unwrap();
+    assert_eq!(mint.base.supply, VESTED_AMOUNT * 2);
+
+    // no new grant while the schedule is active
+    let error = token
+        .mint_to_with_vesting(
+            &alice_account,
+            &mint_authority.pubkey(),
+            VESTED_AMOUNT,
+            START,
+            CLIFF,
+            END + 1,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::InvalidVestingSchedule));
+
+    // once fully vested, a new grant replaces the schedule
+    set_clock(&context, END).await;
+    token
+        .mint_to_with_vesting(
+            &alice_account,
+            &mint_authority.pubkey(),
+            VESTED_AMOUNT,
+            END,
+            END,
+            END * 2,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.amount, VESTED_AMOUNT * 2);
+    let schedule = state.get_extension::<VestingSchedule>().unwrap();
+    assert_eq!(i64::from(schedule.start_timestamp), END);
+    assert_eq!(i64::from(schedule.end_timestamp), END * 2);
+}
+
+#[tokio::test]
+async fn locked_balance_enforced() {
+    let permanent_delegate = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PermanentDelegate {
+            delegate: permanent_delegate.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        token,
+        alice,
+        bob,
+        mint_authority,
+        ..
+    } = context.token_context.take().unwrap();
+    let context = context.context;
+    set_clock(&context, START).await;
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account_with_extension_space(
+            &alice_account,
+            &alice.pubkey(),
+            vec![ExtensionType::Vesting],
+        )
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    // 100 free tokens, plus the vesting grant
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            100,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .mint_to_with_vesting(
+            &alice_account,
+            &mint_authority.pubkey(),
+            VESTED_AMOUNT,
+            START,
+            CLIFF,
+            END,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // before the cliff, only the free tokens can move
+    let error = token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            101,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::TokensLocked));
+    let error = token
+        .burn(&alice_account, &alice.pubkey(), 101, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::TokensLocked));
+    let delegate = Pubkey::new_unique();
+    let error = token
+        .approve(&alice_account, &delegate, &alice.pubkey(), 101, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::TokensLocked));
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 50, &[&alice])
+        .await
+        .unwrap();
+    token
+        .burn(&alice_account, &alice.pubkey(), 50, &[&alice])
+        .await
+        .unwrap();
+
+    // at the cliff, half of the grant is unlocked
+    set_clock(&context, CLIFF).await;
+    let delegate = Keypair::new();
+    token
+        .approve(
+            &alice_account,
+            &delegate.pubkey(),
+            &alice.pubkey(),
+            VESTED_AMOUNT / 2,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+    let error = token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &delegate.pubkey(),
+            VESTED_AMOUNT / 2,
+            &[&delegate],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::TokensLocked));
+    token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &delegate.pubkey(),
+            VESTED_AMOUNT / 2 - 1,
+            &[&delegate],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.amount, VESTED_AMOUNT / 2);
+
+    // the permanent delegate can still claw back locked tokens
+    token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &permanent_delegate.pubkey(),
+            VESTED_AMOUNT / 4,
+            &[&permanent_delegate],
+        )
+        .await
+        .unwrap();
+
+    // after the end, everything is unlocked
+    set_clock(&context, END).await;
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    token
+        .burn(
+            &alice_account,
+            &alice.pubkey(),
+            state.base.amount,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.amount, 0);
+}
@@ -0,0 +1,428 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::{tokio, ProgramTestContext},
+    solana_sdk::{
+        clock::Clock, instruction::InstructionError, program_pack::Pack, pubkey::Pubkey,
+        signature::Signer, signer::keypair::Keypair, transaction::TransactionError,
+        transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        instruction::{self, AuthorityType},
+        state::{MultisigProposal, ProposalState, WeightedMultisig},
+    },
+    spl_token_client::token::TokenError as TokenClientError,
+    std::sync::Arc,
+    tokio::sync::Mutex,
+};
+
+const MIN_DELAY: i64 = 3_600;
+
+fn client_error(error: InstructionError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(0, error),
+    )))
+}
+
+fn token_error(error: TokenError) -> TokenClientError {
+    client_error(InstructionError::Custom(error as u32))
+}
+
+async fn set_clock(context: &Arc<Mutex<ProgramTestContext>>, unix_timestamp: i64) {
+    let mut context = context.lock().await;
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn success_weighted_multisig() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.unwrap();
+
+    // a heavy signer can mint alone, light signers need each other
+    let heavy = Keypair::new();
+    let light_1 = Keypair::new();
+    let light_2 = Keypair::new();
+    let multisig = Keypair::new();
+    token
+        .create_weighted_multisig(
+            &multisig,
+            &[&heavy.pubkey(), &light_1.pubkey(), &light_2.pubkey()],
+            &[2, 1, 1],
+            2,
+            0,
+        )
+        .await
+        .unwrap();
+    let multisig = multisig.pubkey();
+    let multisig_state =
+        WeightedMultisig::unpack(&token.get_account(multisig).await.unwrap().data).unwrap();
+    assert_eq!(multisig_state.threshold, 2);
+    assert_eq!(multisig_state.n, 3);
+    assert_eq!(&multisig_state.weights[..3], &[2, 1, 1]);
+
+    token
+        .set_authority(
+            token.get_address(),
+            &mint_authority.pubkey(),
+            Some(&multisig),
+            AuthorityType::MintTokens,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    token
+        .mint_to(&alice_account, &multisig, 1, &[&heavy])
+        .await
+        .unwrap();
+    token
+        .mint_to(&alice_account, &multisig, 2, &[&light_1, &light_2])
+        .await
+        .unwrap();
+    let error = token
+        .mint_to(&alice_account, &multisig, 4, &[&light_1])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        client_error(InstructionError::MissingRequiredSignature)
+    );
+
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 3);
+}
+
+#[tokio::test]
+async fn success_time_locked_proposal() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        decimals,
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.take().unwrap();
+    let context = context.context;
+    set_clock(&context, 1_000).await;
+
+    let proposer = Keypair::new();
+    let approver = Keypair::new();
+    let multisig = Keypair::new();
+    token
+        .create_weighted_multisig(
+            &multisig,
+            &[&proposer.pubkey(), &approver.pubkey()],
+            &[1, 1],
+            2,
+            MIN_DELAY,
+        )
+        .await
+        .unwrap();
+    let multisig = multisig.pubkey();
+    token
+        .set_authority(
+            token.get_address(),
+            &mint_authority.pubkey(),
+            Some(&multisig),
+            AuthorityType::MintTokens,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    // minting directly is not allowed, even with every signer
+    let error = token
+        .mint_to(&alice_account, &multisig, 1, &[&proposer, &approver])
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::MultisigProposalRequired));
+    let error = token
+        .set_authority(
+            token.get_address(),
+            &multisig,
+            None,
+            AuthorityType::MintTokens,
+            &[&proposer, &approver],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::MultisigProposalRequired));
+
+    // propose to mint through the proposal
+    let proposal = Keypair::new();
+    let proposed_instruction = instruction::mint_to_checked(
+        &spl_token_2022::id(),
+        token.get_address(),
+        &alice_account,
+        &multisig,
+        &[&proposal.pubkey()],
+        100,
+        decimals,
+    )
+    .unwrap();
+    token
+        .create_multisig_proposal(&proposal, &multisig, &proposer, &proposed_instruction)
+        .await
+        .unwrap();
+    let proposal = proposal.pubkey();
+
+    // not approved yet
+    let error = token
+        .execute_multisig_proposal(&proposal, &multisig, &proposed_instruction)
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::MultisigProposalNotReady));
+
+    token
+        .approve_multisig_proposal(&proposal, &multisig, &[&approver])
+        .await
+        .unwrap();
+    let proposal_state =
+        MultisigProposal::unpack(&token.get_account(proposal).await.unwrap().data).unwrap();
+    assert_eq!(proposal_state.state, ProposalState::Pending);
+    assert_eq!(proposal_state.approvals, 0b11);
+    assert_eq!(proposal_state.approved_at, 1_000);
+
+    // delay has not elapsed
+    token.get_new_latest_blockhash().await.unwrap();
+    let error = token
+        .execute_multisig_proposal(&proposal, &multisig, &proposed_instruction)
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::MultisigProposalNotReady));
+
+    set_clock(&context, 1_000 + MIN_DELAY).await;
+    token.get_new_latest_blockhash().await.unwrap();
+    token
+        .execute_multisig_proposal(&proposal, &multisig, &proposed_instruction)
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 100);
+    let proposal_state =
+        MultisigProposal::unpack(&token.get_account(proposal).await.unwrap().data).unwrap();
+    assert_eq!(proposal_state.state, ProposalState::Executed);
+
+    // proposals can only be executed once
+    token.get_new_latest_blockhash().await.unwrap();
+    let error = token
+        .execute_multisig_proposal(&proposal, &multisig, &proposed_instruction)
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::InvalidState));
+}
+
+#[tokio::test]
+async fn fail_proposal_mismatch() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        decimals,
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    let signer = Keypair::new();
+    let multisig = Keypair::new();
+    token
+        .create_weighted_multisig(&multisig, &[&signer.pubkey()], &[1], 1, 0)
+        .await
+        .unwrap();
+    let multisig = multisig.pubkey();
+    token
+        .set_authority(
+            token.get_address(),
+            &mint_authority.pubkey(),
+            Some(&multisig),
+            AuthorityType::MintTokens,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    let proposal = Keypair::new();
+    let mint_to = |destination: &Pubkey| {
+        instruction::mint_to_checked(
+            &spl_token_2022::id(),
+            token.get_address(),
+            destination,
+            &multisig,
+            &[&proposal.pubkey()],
+            100,
+            decimals,
+        )
+        .unwrap()
+    };
+    token
+        .create_multisig_proposal(&proposal, &multisig, &signer, &mint_to(&alice_account))
+        .await
+        .unwrap();
+
+    // the executed instruction must be the proposed one
+    let error = token
+        .execute_multisig_proposal(&proposal.pubkey(), &multisig, &mint_to(&bob_account))
+        .await
+        .unwrap_err();
+    assert_eq!(error, token_error(TokenError::MultisigProposalMismatch));
+
+    // only multisig signers can propose
+    let error = token
+        .create_multisig_proposal(
+            &Keypair::new(),
+            &multisig,
+            &Keypair::new(),
+            &mint_to(&alice_account),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn success_cancel_proposal() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        decimals,
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let proposer = Keypair::new();
+    let approver = Keypair::new();
+    let multisig = Keypair::new();
+    token
+        .create_weighted_multisig(
+            &multisig,
+            &[&proposer.pubkey(), &approver.pubkey()],
+            &[1, 1],
+            2,
+            MIN_DELAY,
+        )
+        .await
+        .unwrap();
+    let multisig = multisig.pubkey();
+    token
+        .set_authority(
+            token.get_address(),
+            &mint_authority.pubkey(),
+            Some(&multisig),
+            AuthorityType::MintTokens,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    let proposal = Keypair::new();
+    let proposed_instruction = instruction::mint_to_checked(
+        &spl_token_2022::id(),
+        token.get_address(),
+        &alice_account,
+        &multisig,
+        &[&proposal.pubkey()],
+        100,
+        decimals,
+    )
+    .unwrap();
+    token
+        .create_multisig_proposal(&proposal, &multisig, &proposer, &proposed_instruction)
+        .await
+        .unwrap();
+    let proposal = proposal.pubkey();
+    let proposal_lamports = token.get_account(proposal).await.unwrap().lamports;
+
+    // cancelling needs the multisig threshold
+    let destination = Pubkey::new_unique();
+    let error = token
+        .cancel_multisig_proposal(&proposal, &multisig, &destination, &[&proposer])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        client_error(InstructionError::MissingRequiredSignature)
+    );
+    let error = token
+        .cancel_multisig_proposal(&proposal, &multisig, &destination, &[&Keypair::new()])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        client_error(InstructionError::MissingRequiredSignature)
+    );
+
+    token
+        .cancel_multisig_proposal(&proposal, &multisig, &destination, &[&proposer, &approver])
+        .await
+        .unwrap();
+    assert!(token.get_account(proposal).await.is_err());
+    assert_eq!(
+        token.get_account(destination).await.unwrap().lamports,
+        proposal_lamports
+    );
+
+    // a cancelled proposal can no longer be approved or executed
+    let error = token
+        .approve_multisig_proposal(&proposal, &multisig, &[&approver])
+        .await
+        .unwrap_err();
+    assert_eq!(error, client_error(InstructionError::IncorrectProgramId));
+}
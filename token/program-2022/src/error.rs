@@ -232,6 +232,36 @@ pub enum TokenError {
     /// Ciphertext arithmetic failed
     #[error("Ciphertext arithmetic failed")]
     CiphertextArithmeticFailed,
+    /// Minting, transferring and burning are paused on this mint
+    #[error("Minting, transferring and burning are paused on this mint")]
+    MintPaused,
+    /// Invalid scale for scaled ui amount
+    #[error("Invalid scale for scaled ui amount")]
+    InvalidScale,
+    /// Public mints, burns, deposits and withdrawals are not allowed on
+    /// confidential mint-burn mints
+    #[error("Public mints, burns, deposits and withdrawals are not allowed on confidential mint-burn mints")]
+    IllegalMintBurnConversion,
+    /// Extension is required or still enabled, so it cannot be removed
+    #[error("Extension is required or still enabled, so it cannot be removed")]
+    ExtensionNotRemovable,
+
+    // 65
+    /// Amount exceeds the unlocked balance of the account
+    #[error("Amount exceeds the unlocked balance of the account")]
+    TokensLocked,
+    /// Invalid vesting schedule
+    #[error("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    /// Authority is a time-locked multisig, so an approved proposal is required
+    #[error("Authority is a time-locked multisig, so an approved proposal is required")]
+    MultisigProposalRequired,
+    /// Multisig proposal is not approved, or its delay has not elapsed
+    #[error("Multisig proposal is not approved, or its delay has not elapsed")]
+    MultisigProposalNotReady,
+    /// Multisig proposal does not match the provided multisig or instruction
+    #[error("Multisig proposal does not match the provided multisig or instruction")]
+    MultisigProposalMismatch,
 }
 impl From<TokenError> for ProgramError {
     fn from(e: TokenError) -> Self {
@@ -407,6 +437,33 @@ impl PrintProgramError for TokenError {
             TokenError::CiphertextArithmeticFailed => {
                 msg!("Ciphertext arithmetic failed")
             }
+            TokenError::MintPaused => {
+                msg!("Minting, transferring and burning are paused on this mint")
+            }
+            TokenError::InvalidScale => {
+                msg!("Invalid scale for scaled ui amount")
+            }
+            TokenError::IllegalMintBurnConversion => {
+                msg!("Public mints, burns, deposits and withdrawals are not allowed on confidential mint-burn mints")
+            }
+            TokenError::ExtensionNotRemovable => {
+                msg!("Extension is required or still enabled, so it cannot be removed")
+            }
+            TokenError::TokensLocked => {
+                msg!("Amount exceeds the unlocked balance of the account")
+            }
+            TokenError::InvalidVestingSchedule => {
+                msg!("Invalid vesting schedule")
+            }
+            TokenError::MultisigProposalRequired => {
+                msg!("Authority is a time-locked multisig, so an approved proposal is required")
+            }
+            TokenError::MultisigProposalNotReady => {
+                msg!("Multisig proposal is not approved, or its delay has not elapsed")
+            }
+            TokenError::MultisigProposalMismatch => {
+                msg!("Multisig proposal does not match the provided multisig or instruction")
+            }
         }
     }
 }
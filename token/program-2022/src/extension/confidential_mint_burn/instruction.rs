@@ -0,0 +1,327 @@
+#[cfg(not(target_os = "solana"))]
+use solana_zk_token_sdk::encryption::auth_encryption::AeCiphertext;
+use {
+    crate::{
+        check_program_account,
+        extension::confidential_transfer::DecryptableBalance,
+        instruction::{encode_instruction, TokenInstruction},
+    },
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
+};
+
+#[cfg(feature = "serde-traits")]
+use {
+    crate::serialization::{aeciphertext_fromstr, elgamalpubkey_fromstr},
+    serde::{Deserialize, Serialize},
+};
+
+/// Confidential Mint-Burn extension instructions
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum ConfidentialMintBurnInstruction {
+    /// Initializes confidential mints and burns for a mint.
+    ///
+    /// The `ConfidentialMintBurnInstruction::InitializeMint` instruction requires no signers
+    /// and MUST be included within the same Transaction as `TokenInstruction::InitializeMint`.
+    /// Otherwise another party can initialize the configuration.
+    ///
+    /// The instruction fails if the `TokenInstruction::InitializeMint` instruction has already
+    /// executed for the mint.
+    ///
+    /// The mint must also include the `ConfidentialTransferMint` extension.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The SPL Token mint.
+    ///
+    /// Data expected by this instruction:
+    ///   `InitializeMintData`
+    ///
+    InitializeMint,
+
+    /// Updates the decryptable supply of the mint, for example after the confidential supply was
+    /// changed by a burn.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The SPL Token mint.
+    ///   1. `[signer]` The mint authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The SPL Token mint.
+    ///   1. `[]` The multisig mint authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `UpdateDecryptableSupplyData`
+    ///
+    UpdateDecryptableSupply,
+
+    /// Mints tokens confidentially to the pending balance of a token account.
+    ///
+    /// The amount is never revealed: it is encrypted under the ElGamal public key of the
+    /// destination account and under the supply ElGamal public key. The instruction requires the
+    /// following zero-knowledge proofs, each provided in a context state account:
+    ///   * `BatchedGroupedCiphertext2HandlesValidity` for the low and high bits of the amount,
+    ///     with the destination and supply ElGamal public keys, in that order
+    ///   * `BatchedRangeProofU64` for the low (16 bits) and high (32 bits) amount commitments,
+    ///     padded with a 16 bit commitment
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The destination SPL Token account.
+    ///   1. `[writable]` The SPL Token mint.
+    ///   2. `[]` Context state account containing the ciphertext validity proof.
+    ///   3. `[]` Context state account containing the range proof.
+    ///   4. `[signer]` The mint authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The destination SPL Token account.
+    ///   1. `[writable]` The SPL Token mint.
+    ///   2. `[]` Context state account containing the ciphertext validity proof.
+    ///   3. `[]` Context state account containing the range proof.
+    ///   4. `[]` The multisig mint authority.
+    ///   5. ..5+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `MintInstructionData`
+    ///
+    Mint,
+
+    /// Burns tokens confidentially from the available balance of a token account.
+    ///
+    /// The instruction requires the following zero-knowledge proofs, each provided in a context
+    /// state account:
+    ///   * `CiphertextCommitmentEquality` for the new available balance of the source account
+    ///   * `BatchedGroupedCiphertext2HandlesValidity` for the low and high bits of the amount,
+    ///     with the source and supply ElGamal public keys, in that order
+    ///   * `BatchedRangeProofU128` for the new available balance (64 bits) and the low (16 bits)
+    ///     and high (32 bits) amount commitments, padded with a 16 bit commitment
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[writable]` The source SPL Token account.
+    ///   1. `[writable]` The SPL Token mint.
+    ///   2. `[]` Context state account containing the equality proof.
+    ///   3. `[]` Context state account containing the ciphertext validity proof.
+    ///   4. `[]` Context state account containing the range proof.
+    ///   5. `[signer]` The source account owner.
+    ///
+    ///   * Multisignature owner
+    ///   0. `[writable]` The source SPL Token account.
+    ///   1. `[writable]` The SPL Token mint.
+    ///   2. `[]` Context state account containing the equality proof.
+    ///   3. `[]` Context state account containing the ciphertext validity proof.
+    ///   4. `[]` Context state account containing the range proof.
+    ///   5. `[]` The multisig source account owner.
+    ///   6. ..6+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `BurnInstructionData`
+    ///
+    Burn,
+}
+
+/// Data expected by `ConfidentialMintBurnInstruction::InitializeMint`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct InitializeMintData {
+    /// The ElGamal public key used to encrypt the confidential supply
+    #[cfg_attr(feature = "serde-traits", serde(with = "elgamalpubkey_fromstr"))]
+    pub supply_elgamal_pubkey: ElGamalPubkey,
+    /// The initial decryptable supply (always 0)
+    #[cfg_attr(feature = "serde-traits", serde(with = "aeciphertext_fromstr"))]
+    pub decryptable_supply: DecryptableBalance,
+}
+
+/// Data expected by `ConfidentialMintBurnInstruction::UpdateDecryptableSupply`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct UpdateDecryptableSupplyData {
+    /// The new decryptable supply
+    #[cfg_attr(feature = "serde-traits", serde(with = "aeciphertext_fromstr"))]
+    pub new_decryptable_supply: DecryptableBalance,
+}
+
+/// Data expected by `ConfidentialMintBurnInstruction::Mint`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MintInstructionData {
+    /// The new decryptable supply if the mint succeeds
+    #[cfg_attr(feature = "serde-traits", serde(with = "aeciphertext_fromstr"))]
+    pub new_decryptable_supply: DecryptableBalance,
+}
+
+/// Data expected by `ConfidentialMintBurnInstruction::Burn`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct BurnInstructionData {
+    /// The new decryptable balance of the source account if the burn succeeds
+    #[cfg_attr(feature = "serde-traits", serde(with = "aeciphertext_fromstr"))]
+    pub new_decryptable_available_balance: DecryptableBalance,
+}
+
+/// Type for split mint instruction proof context state account addresses intended to be used as
+/// parameters to functions.
+#[derive(Clone, Copy)]
+pub struct MintSplitContextStateAccounts<'a> {
+    /// The context state account address for a ciphertext validity proof needed for a mint.
+    pub ciphertext_validity_proof: &'a Pubkey,
+    /// The context state account address for a range proof needed for a mint.
+    pub range_proof: &'a Pubkey,
+    /// The context state accounts authority
+    pub authority: &'a Pubkey,
+}
+
+/// Type for split burn instruction proof context state account addresses intended to be used as
+/// parameters to functions.
+#[derive(Clone, Copy)]
+pub struct BurnSplitContextStateAccounts<'a> {
+    /// The context state account address for an equality proof needed for a burn.
+    pub equality_proof: &'a Pubkey,
+    /// The context state account address for a ciphertext validity proof needed for a burn.
+    pub ciphertext_validity_proof: &'a Pubkey,
+    /// The context state account address for a range proof needed for a burn.
+    pub range_proof: &'a Pubkey,
+    /// The context state accounts authority
+    pub authority: &'a Pubkey,
+}
+
+/// Create a `InitializeMint` instruction
+pub fn initialize_mint(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    supply_elgamal_pubkey: ElGamalPubkey,
+    decryptable_supply: DecryptableBalance,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialMintBurnExtension,
+        ConfidentialMintBurnInstruction::InitializeMint,
+        &InitializeMintData {
+            supply_elgamal_pubkey,
+            decryptable_supply,
+        },
+    ))
+}
+
+/// Create a `UpdateDecryptableSupply` instruction
+#[cfg(not(target_os = "solana"))]
+pub fn update_decryptable_supply(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    new_decryptable_supply: AeCiphertext,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, multisig_signers.is_empty()),
+    ];
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialMintBurnExtension,
+        ConfidentialMintBurnInstruction::UpdateDecryptableSupply,
+        &UpdateDecryptableSupplyData {
+            new_decryptable_supply: new_decryptable_supply.into(),
+        },
+    ))
+}
+
+/// Create a `Mint` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+pub fn confidential_mint(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    new_decryptable_supply: DecryptableBalance,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    context_accounts: MintSplitContextStateAccounts,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*context_accounts.ciphertext_validity_proof, false),
+        AccountMeta::new_readonly(*context_accounts.range_proof, false),
+        AccountMeta::new_readonly(*authority, multisig_signers.is_empty()),
+    ];
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialMintBurnExtension,
+        ConfidentialMintBurnInstruction::Mint,
+        &MintInstructionData {
+            new_decryptable_supply,
+        },
+    ))
+}
+
+/// Create a `Burn` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+pub fn confidential_burn(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    new_decryptable_available_balance: DecryptableBalance,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    context_accounts: BurnSplitContextStateAccounts,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*context_accounts.equality_proof, false),
+        AccountMeta::new_readonly(*context_accounts.ciphertext_validity_proof, false),
+        AccountMeta::new_readonly(*context_accounts.range_proof, false),
+        AccountMeta::new_readonly(*authority, multisig_signers.is_empty()),
+    ];
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialMintBurnExtension,
+        ConfidentialMintBurnInstruction::Burn,
+        &BurnInstructionData {
+            new_decryptable_available_balance,
+        },
+    ))
+}
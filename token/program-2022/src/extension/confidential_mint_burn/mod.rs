@@ -0,0 +1,39 @@
+use {
+    crate::extension::{
+        confidential_transfer::{DecryptableBalance, EncryptedBalance},
+        Extension, ExtensionType,
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
+};
+
+/// Confidential Mint-Burn Extension instructions
+pub mod instruction;
+
+/// Confidential Mint-Burn Extension processor
+pub mod processor;
+
+/// Helper functions to verify zero-knowledge proofs in the Confidential Mint-Burn Extension
+pub mod verify_proof;
+
+/// Confidential mint-burn mint configuration
+///
+/// Tokens minted confidentially are credited to the pending balance of a confidential transfer
+/// account, and the amount is added to a supply that is only known to the holder of the supply
+/// ElGamal secret key.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ConfidentialMintBurn {
+    /// The confidential supply of the mint (encrypted by `supply_elgamal_pubkey`)
+    pub confidential_supply: EncryptedBalance,
+
+    /// The decryptable confidential supply of the mint
+    pub decryptable_supply: DecryptableBalance,
+
+    /// The ElGamal public key used to encrypt the confidential supply
+    pub supply_elgamal_pubkey: ElGamalPubkey,
+}
+
+impl Extension for ConfidentialMintBurn {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialMintBurn;
+}
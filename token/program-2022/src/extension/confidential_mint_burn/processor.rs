@@ -0,0 +1,326 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            confidential_mint_burn::{instruction::*, ConfidentialMintBurn},
+            confidential_transfer::{ConfidentialTransferAccount, DecryptableBalance},
+            StateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        processor::Processor,
+        state::{Account, Mint},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_option::COption,
+        pubkey::Pubkey,
+    },
+    solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
+};
+// Remove feature once zk ops syscalls are enabled on all networks
+#[cfg(not(feature = "zk-ops"))]
+use solana_program::program_error::ProgramError;
+#[cfg(feature = "zk-ops")]
+use {
+    crate::extension::{
+        confidential_mint_burn::verify_proof::*,
+        memo_transfer::{check_previous_sibling_instruction_is_memo, memo_required},
+        pausable::check_not_paused,
+    },
+    solana_zk_token_sdk::zk_token_elgamal::ops as syscall,
+};
+
+/// Processes an [InitializeMint] instruction.
+fn process_initialize_mint(
+    accounts: &[AccountInfo],
+    supply_elgamal_pubkey: &ElGamalPubkey,
+    decryptable_supply: &DecryptableBalance,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(mint_data)?;
+    let confidential_mint_burn = mint.init_extension::<ConfidentialMintBurn>(true)?;
+
+    confidential_mint_burn.supply_elgamal_pubkey = *supply_elgamal_pubkey;
+    confidential_mint_burn.decryptable_supply = *decryptable_supply;
+
+    Ok(())
+}
+
+/// Validates the mint authority of a mint, which is required to mint confidentially and to update
+/// the decryptable supply.
+fn validate_mint_authority(
+    program_id: &Pubkey,
+    mint: &StateWithExtensionsMut<Mint>,
+    authority_info: &AccountInfo,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    match mint.base.mint_authority {
+        COption::Some(mint_authority) => Processor::validate_owner(
+            program_id,
+            &mint_authority,
+            authority_info,
+            authority_info.data_len(),
+            signers,
+        ),
+        COption::None => Err(TokenError::FixedSupply.into()),
+    }
+}
+
+/// Processes an [UpdateDecryptableSupply] instruction.
+fn process_update_decryptable_supply(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_decryptable_supply: &DecryptableBalance,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+
+    validate_mint_authority(
+        program_id,
+        &mint,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let confidential_mint_burn = mint.get_extension_mut::<ConfidentialMintBurn>()?;
+    confidential_mint_burn.decryptable_supply = *new_decryptable_supply;
+
+    Ok(())
+}
+
+/// Processes a [Mint] instruction.
+#[cfg(feature = "zk-ops")]
+fn process_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_decryptable_supply: &DecryptableBalance,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // zero-knowledge proofs certify that the mint amount is encrypted under the destination and
+    // supply public keys, and that it is a valid 48-bit number
+    let proof_context = verify_mint_proof(account_info_iter)?;
+
+    let authority_info = next_account_info(account_info_iter)?;
+    Processor::check_multisig_time_lock(program_id, authority_info, account_info_iter.as_slice())?;
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+
+    validate_mint_authority(
+        program_id,
+        &mint,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+    check_not_paused(&mint)?;
+
+    check_program_account(token_account_info.owner)?;
+    let token_account_data = &mut token_account_info.data.borrow_mut();
+    let mut token_account = StateWithExtensionsMut::<Account>::unpack(token_account_data)?;
+
+    if token_account.base.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+
+    if token_account.base.mint != *mint_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    if memo_required(&token_account) {
+        check_previous_sibling_instruction_is_memo()?;
+    }
+
+    let confidential_transfer_account =
+        token_account.get_extension_mut::<ConfidentialTransferAccount>()?;
+    confidential_transfer_account.valid_as_destination()?;
+
+    // Check that the destination encryption public key is consistent with what was actually used
+    // to generate the zkp.
+    if proof_context.destination_pubkey != confidential_transfer_account.elgamal_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    confidential_transfer_account.pending_balance_lo = syscall::add(
+        &confidential_transfer_account.pending_balance_lo,
+        &account_ciphertext(&proof_context.ciphertext_lo),
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+    confidential_transfer_account.pending_balance_hi = syscall::add(
+        &confidential_transfer_account.pending_balance_hi,
+        &account_ciphertext(&proof_context.ciphertext_hi),
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+
+    confidential_transfer_account.increment_pending_balance_credit_counter()?;
+
+    let confidential_mint_burn = mint.get_extension_mut::<ConfidentialMintBurn>()?;
+
+    // Check that the supply encryption public key is consistent with what was actually used to
+    // generate the zkp.
+    if proof_context.supply_pubkey != confidential_mint_burn.supply_elgamal_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    confidential_mint_burn.confidential_supply = syscall::add_with_lo_hi(
+        &confidential_mint_burn.confidential_supply,
+        &supply_ciphertext(&proof_context.ciphertext_lo),
+        &supply_ciphertext(&proof_context.ciphertext_hi),
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+    confidential_mint_burn.decryptable_supply = *new_decryptable_supply;
+
+    Ok(())
+}
+
+/// Processes a [Burn] instruction.
+#[cfg(feature = "zk-ops")]
+fn process_burn(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_decryptable_available_balance: &DecryptableBalance,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // zero-knowledge proofs certify that the burn amount is encrypted under the source and supply
+    // public keys, and that the source has enough available balance to burn the amount
+    let proof_context = verify_burn_proof(account_info_iter)?;
+
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+    check_not_paused(&mint)?;
+
+    check_program_account(token_account_info.owner)?;
+    let token_account_data = &mut token_account_info.data.borrow_mut();
+    let mut token_account = StateWithExtensionsMut::<Account>::unpack(token_account_data)?;
+
+    Processor::validate_owner(
+        program_id,
+        &token_account.base.owner,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    if token_account.base.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+
+    if token_account.base.mint != *mint_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    let confidential_transfer_account =
+        token_account.get_extension_mut::<ConfidentialTransferAccount>()?;
+    confidential_transfer_account.valid_as_source()?;
+
+    // Check that the source encryption public key is consistent with what was actually used to
+    // generate the zkp.
+    if proof_context.source_pubkey != confidential_transfer_account.elgamal_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    let new_available_balance = syscall::subtract_with_lo_hi(
+        &confidential_transfer_account.available_balance,
+        &account_ciphertext(&proof_context.ciphertext_lo),
+        &account_ciphertext(&proof_context.ciphertext_hi),
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+
+    // Check that the computed available balance is consistent with what was actually used to
+    // generate the zkp on the client side.
+    if new_available_balance != proof_context.new_source_ciphertext {
+        return Err(TokenError::ConfidentialTransferBalanceMismatch.into());
+    }
+
+    confidential_transfer_account.available_balance = new_available_balance;
+    confidential_transfer_account.decryptable_available_balance =
+        *new_decryptable_available_balance;
+
+    let confidential_mint_burn = mint.get_extension_mut::<ConfidentialMintBurn>()?;
+
+    // Check that the supply encryption public key is consistent with what was actually used to
+    // generate the zkp.
+    if proof_context.supply_pubkey != confidential_mint_burn.supply_elgamal_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    confidential_mint_burn.confidential_supply = syscall::subtract_with_lo_hi(
+        &confidential_mint_burn.confidential_supply,
+        &supply_ciphertext(&proof_context.ciphertext_lo),
+        &supply_ciphertext(&proof_context.ciphertext_hi),
+    )
+    .ok_or(TokenError::CiphertextArithmeticFailed)?;
+
+    Ok(())
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match decode_instruction_type(input)? {
+        ConfidentialMintBurnInstruction::InitializeMint => {
+            msg!("ConfidentialMintBurnInstruction::InitializeMint");
+            let data = decode_instruction_data::<InitializeMintData>(input)?;
+            process_initialize_mint(
+                accounts,
+                &data.supply_elgamal_pubkey,
+                &data.decryptable_supply,
+            )
+        }
+        ConfidentialMintBurnInstruction::UpdateDecryptableSupply => {
+            msg!("ConfidentialMintBurnInstruction::UpdateDecryptableSupply");
+            let data = decode_instruction_data::<UpdateDecryptableSupplyData>(input)?;
+            process_update_decryptable_supply(program_id, accounts, &data.new_decryptable_supply)
+        }
+        ConfidentialMintBurnInstruction::Mint => {
+            msg!("ConfidentialMintBurnInstruction::Mint");
+            #[cfg(feature = "zk-ops")]
+            {
+                let data = decode_instruction_data::<MintInstructionData>(input)?;
+                process_mint(program_id, accounts, &data.new_decryptable_supply)
+            }
+            #[cfg(not(feature = "zk-ops"))]
+            Err(ProgramError::InvalidInstructionData)
+        }
+        ConfidentialMintBurnInstruction::Burn => {
+            msg!("ConfidentialMintBurnInstruction::Burn");
+            #[cfg(feature = "zk-ops")]
+            {
+                let data = decode_instruction_data::<BurnInstructionData>(input)?;
+                process_burn(
+                    program_id,
+                    accounts,
+                    &data.new_decryptable_available_balance,
+                )
+            }
+            #[cfg(not(feature = "zk-ops"))]
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
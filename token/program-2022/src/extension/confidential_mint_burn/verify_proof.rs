@@ -0,0 +1,245 @@
+use {
+    crate::{
+        check_zk_token_proof_program_account,
+        extension::confidential_transfer::{
+            ciphertext_extraction::extract_commitment_from_grouped_ciphertext,
+            verify_proof::{verify_ciphertext_validity_proof, verify_equality_proof},
+            EncryptedBalance,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        program_error::ProgramError,
+    },
+    solana_zk_token_sdk::{
+        instruction::{
+            BatchedGroupedCiphertext2HandlesValidityProofContext, BatchedRangeProofContext,
+            CiphertextCommitmentEqualityProofContext, ProofType,
+        },
+        zk_token_elgamal::pod::{
+            ElGamalCiphertext, ElGamalPubkey, GroupedElGamalCiphertext2Handles, PedersenCommitment,
+        },
+        zk_token_proof_state::ProofContextState,
+    },
+    spl_pod::bytemuck::pod_from_bytes,
+    std::slice::Iter,
+};
+
+/// Bit length of the low bits of a mint or burn amount
+const AMOUNT_LO_BIT_LENGTH: u8 = 16;
+/// Bit length of the high bits of a mint or burn amount
+const AMOUNT_HI_BIT_LENGTH: u8 = 32;
+/// Bit length of the padding commitment of a batched range proof
+const PADDING_BIT_LENGTH: u8 = 16;
+/// Bit length of the remaining balance after a burn
+const REMAINING_BALANCE_BIT_LENGTH: u8 = 64;
+
+/// The proof context information needed to process a [Mint] instruction.
+pub struct MintProofContextInfo {
+    /// ElGamal public key of the destination account
+    pub destination_pubkey: ElGamalPubkey,
+    /// ElGamal public key of the mint supply
+    pub supply_pubkey: ElGamalPubkey,
+    /// Ciphertext containing the low 16 bits of the mint amount
+    pub ciphertext_lo: GroupedElGamalCiphertext2Handles,
+    /// Ciphertext containing the high 32 bits of the mint amount
+    pub ciphertext_hi: GroupedElGamalCiphertext2Handles,
+}
+
+/// The proof context information needed to process a [Burn] instruction.
+pub struct BurnProofContextInfo {
+    /// ElGamal public key of the source account
+    pub source_pubkey: ElGamalPubkey,
+    /// ElGamal public key of the mint supply
+    pub supply_pubkey: ElGamalPubkey,
+    /// Ciphertext containing the low 16 bits of the burn amount
+    pub ciphertext_lo: GroupedElGamalCiphertext2Handles,
+    /// Ciphertext containing the high 32 bits of the burn amount
+    pub ciphertext_hi: GroupedElGamalCiphertext2Handles,
+    /// The new source available balance ciphertext
+    pub new_source_ciphertext: EncryptedBalance,
+}
+
+/// Extract the ElGamal ciphertext under the first public key of a grouped ciphertext with 2
+/// handles, which is the destination of a mint or the source of a burn.
+///
+/// A grouped ciphertext with 2 handles consists of the following 32-byte components that are
+/// serialized in order:
+///   1. The `commitment` component that encodes the amount.
+///   2. The `decryption handle` component with respect to the first public key.
+///   3. The `decryption handle` component with respect to the second public key.
+#[cfg(feature = "zk-ops")]
+pub(crate) fn account_ciphertext(
+    grouped_ciphertext: &GroupedElGamalCiphertext2Handles,
+) -> EncryptedBalance {
+    let grouped_ciphertext_bytes = bytemuck::bytes_of(grouped_ciphertext);
+
+    let mut ciphertext_bytes = [0u8; 64];
+    ciphertext_bytes[..32].copy_from_slice(&grouped_ciphertext_bytes[..32]);
+    ciphertext_bytes[32..].copy_from_slice(&grouped_ciphertext_bytes[32..64]);
+
+    ElGamalCiphertext(ciphertext_bytes)
+}
+
+/// Extract the ElGamal ciphertext under the supply public key, the second public key of a grouped
+/// ciphertext with 2 handles.
+#[cfg(feature = "zk-ops")]
+pub(crate) fn supply_ciphertext(
+    grouped_ciphertext: &GroupedElGamalCiphertext2Handles,
+) -> EncryptedBalance {
+    let grouped_ciphertext_bytes = bytemuck::bytes_of(grouped_ciphertext);
+
+    let mut ciphertext_bytes = [0u8; 64];
+    ciphertext_bytes[..32].copy_from_slice(&grouped_ciphertext_bytes[..32]);
+    ciphertext_bytes[32..].copy_from_slice(&grouped_ciphertext_bytes[64..96]);
+
+    ElGamalCiphertext(ciphertext_bytes)
+}
+
+/// Verify zero-knowledge proofs needed for a [Mint] instruction and return the corresponding
+/// proof context information.
+pub fn verify_mint_proof(
+    account_info_iter: &mut Iter<'_, AccountInfo<'_>>,
+) -> Result<MintProofContextInfo, ProgramError> {
+    let ciphertext_validity_proof_context =
+        verify_ciphertext_validity_proof(next_account_info(account_info_iter)?)?;
+    let range_proof_context = verify_range_proof(
+        next_account_info(account_info_iter)?,
+        ProofType::BatchedRangeProofU64,
+    )?;
+
+    let BatchedGroupedCiphertext2HandlesValidityProofContext {
+        destination_pubkey,
+        auditor_pubkey: supply_pubkey,
+        grouped_ciphertext_lo,
+        grouped_ciphertext_hi,
+    } = ciphertext_validity_proof_context;
+
+    // The range proof must be created for the commitments of the low and high bits of the mint
+    // amount, padded with a dummy commitment that can be any commitment.
+    verify_range_proof_commitments(
+        &range_proof_context,
+        &[
+            extract_commitment_from_grouped_ciphertext(&grouped_ciphertext_lo),
+            extract_commitment_from_grouped_ciphertext(&grouped_ciphertext_hi),
+        ],
+        &[
+            AMOUNT_LO_BIT_LENGTH,
+            AMOUNT_HI_BIT_LENGTH,
+            PADDING_BIT_LENGTH,
+        ],
+    )?;
+
+    Ok(MintProofContextInfo {
+        destination_pubkey,
+        supply_pubkey,
+        ciphertext_lo: grouped_ciphertext_lo,
+        ciphertext_hi: grouped_ciphertext_hi,
+    })
+}
+
+/// Verify zero-knowledge proofs needed for a [Burn] instruction and return the corresponding
+/// proof context information.
+pub fn verify_burn_proof(
+    account_info_iter: &mut Iter<'_, AccountInfo<'_>>,
+) -> Result<BurnProofContextInfo, ProgramError> {
+    let equality_proof_context = verify_equality_proof(next_account_info(account_info_iter)?)?;
+    let ciphertext_validity_proof_context =
+        verify_ciphertext_validity_proof(next_account_info(account_info_iter)?)?;
+    let range_proof_context = verify_range_proof(
+        next_account_info(account_info_iter)?,
+        ProofType::BatchedRangeProofU128,
+    )?;
+
+    let CiphertextCommitmentEqualityProofContext {
+        pubkey: equality_proof_pubkey,
+        ciphertext: new_source_ciphertext,
+        commitment: new_source_commitment,
+    } = equality_proof_context;
+
+    let BatchedGroupedCiphertext2HandlesValidityProofContext {
+        destination_pubkey: source_pubkey,
+        auditor_pubkey: supply_pubkey,
+        grouped_ciphertext_lo,
+        grouped_ciphertext_hi,
+    } = ciphertext_validity_proof_context;
+
+    // The equality and the ciphertext validity proofs must both be generated for the source
+    if equality_proof_pubkey != source_pubkey {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // The range proof must be created for the commitment of the remaining balance and the
+    // commitments of the low and high bits of the burn amount, padded with a dummy commitment
+    // that can be any commitment.
+    verify_range_proof_commitments(
+        &range_proof_context,
+        &[
+            new_source_commitment,
+            extract_commitment_from_grouped_ciphertext(&grouped_ciphertext_lo),
+            extract_commitment_from_grouped_ciphertext(&grouped_ciphertext_hi),
+        ],
+        &[
+            REMAINING_BALANCE_BIT_LENGTH,
+            AMOUNT_LO_BIT_LENGTH,
+            AMOUNT_HI_BIT_LENGTH,
+            PADDING_BIT_LENGTH,
+        ],
+    )?;
+
+    Ok(BurnProofContextInfo {
+        source_pubkey,
+        supply_pubkey,
+        ciphertext_lo: grouped_ciphertext_lo,
+        ciphertext_hi: grouped_ciphertext_hi,
+        new_source_ciphertext,
+    })
+}
+
+/// Verify and process a batched range proof of the expected type for [Mint] and [Burn]
+/// instructions.
+fn verify_range_proof(
+    account_info: &AccountInfo<'_>,
+    expected_proof_type: ProofType,
+) -> Result<BatchedRangeProofContext, ProgramError> {
+    check_zk_token_proof_program_account(account_info.owner)?;
+    let context_state_account_data = account_info.data.borrow();
+    let range_proof_context_state =
+        pod_from_bytes::<ProofContextState<BatchedRangeProofContext>>(&context_state_account_data)?;
+
+    if range_proof_context_state.proof_type != expected_proof_type.into() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(range_proof_context_state.proof_context)
+}
+
+/// Check that a range proof was created for the expected commitments and bit lengths.
+fn verify_range_proof_commitments(
+    range_proof_context: &BatchedRangeProofContext,
+    expected_commitments: &[PedersenCommitment],
+    expected_bit_lengths: &[u8],
+) -> Result<(), ProgramError> {
+    let BatchedRangeProofContext {
+        commitments: range_proof_commitments,
+        bit_lengths: range_proof_bit_lengths,
+    } = range_proof_context;
+
+    if !range_proof_commitments
+        .iter()
+        .zip(expected_commitments.iter())
+        .all(|(proof_commitment, expected_commitment)| proof_commitment == expected_commitment)
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if !range_proof_bit_lengths
+        .iter()
+        .zip(expected_bit_lengths.iter())
+        .all(|(proof_len, expected_len)| proof_len == expected_len)
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
@@ -112,7 +112,19 @@ impl ApplyPendingBalanceAccountInfo {
             .ok_or(TokenError::AccountDecryption)
     }
 
-    fn decrypted_available_balance(&self, aes_key: &AeKey) -> Result<u64, TokenError> {
+    /// Decrypt the pending balance of the account.
+    pub fn decrypted_pending_balance(
+        &self,
+        elgamal_secret_key: &ElGamalSecretKey,
+    ) -> Result<u64, TokenError> {
+        let decrypted_pending_balance_lo = self.decrypted_pending_balance_lo(elgamal_secret_key)?;
+        let decrypted_pending_balance_hi = self.decrypted_pending_balance_hi(elgamal_secret_key)?;
+        combine_balances(decrypted_pending_balance_lo, decrypted_pending_balance_hi)
+            .ok_or(TokenError::AccountDecryption)
+    }
+
+    /// Decrypt the available balance of the account.
+    pub fn decrypted_available_balance(&self, aes_key: &AeKey) -> Result<u64, TokenError> {
         let decryptable_available_balance = self
             .decryptable_available_balance
             .try_into()
@@ -128,11 +140,7 @@ impl ApplyPendingBalanceAccountInfo {
         elgamal_secret_key: &ElGamalSecretKey,
         aes_key: &AeKey,
     ) -> Result<AeCiphertext, TokenError> {
-        let decrypted_pending_balance_lo = self.decrypted_pending_balance_lo(elgamal_secret_key)?;
-        let decrypted_pending_balance_hi = self.decrypted_pending_balance_hi(elgamal_secret_key)?;
-        let pending_balance =
-            combine_balances(decrypted_pending_balance_lo, decrypted_pending_balance_hi)
-                .ok_or(TokenError::AccountDecryption)?;
+        let pending_balance = self.decrypted_pending_balance(elgamal_secret_key)?;
         let current_available_balance = self.decrypted_available_balance(aes_key)?;
         let new_decrypted_available_balance = current_available_balance
             .checked_add(pending_balance)
@@ -401,6 +401,52 @@ pub enum ConfidentialTransferInstruction {
     ///   `TransferWithSplitProofsInstructionData`
     ///
     TransferWithSplitProofs,
+
+    /// Rotates the auditor ElGamal public key of a mint.
+    ///
+    /// Unlike `UpdateMint`, this instruction hands the auditor role over from the current auditor
+    /// to the new auditor. The new auditor must prove knowledge of the secret key corresponding to
+    /// the new ElGamal public key, which guarantees that the new auditor is able to decrypt the
+    /// transfer amounts of future confidential transfers. The current auditor must re-encrypt a
+    /// ciphertext under the new public key and prove that both ciphertexts encrypt the same value,
+    /// which can only be done with the secret key of the current auditor. The mint must therefore
+    /// already have an auditor; the first auditor of a mint is set with `UpdateMint`.
+    ///
+    /// Ciphertexts that were produced under the previous auditor public key are not modified. The
+    /// previous auditor can hand them over to the new auditor off-chain in the same way.
+    ///
+    /// In order for this instruction to be successfully processed, it must be accompanied by the
+    /// `VerifyPubkeyValidityProof` and `VerifyCiphertextCiphertextEquality` instructions of the
+    /// `zk_token_proof` program in the same transaction or the addresses of context state accounts
+    /// for the proofs must be provided.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The SPL Token mint.
+    ///   1. `[]` Instructions sysvar if `VerifyPubkeyValidityProof` is included in the same
+    ///      transaction or context state account if `VerifyPubkeyValidityProof` is pre-verified
+    ///      into a context state account.
+    ///   2. `[]` Instructions sysvar if `VerifyCiphertextCiphertextEquality` is included in the
+    ///      same transaction or context state account if `VerifyCiphertextCiphertextEquality` is
+    ///      pre-verified into a context state account.
+    ///   3. `[signer]` Confidential transfer mint authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The SPL Token mint.
+    ///   1. `[]` Instructions sysvar if `VerifyPubkeyValidityProof` is included in the same
+    ///      transaction or context state account if `VerifyPubkeyValidityProof` is pre-verified
+    ///      into a context state account.
+    ///   2. `[]` Instructions sysvar if `VerifyCiphertextCiphertextEquality` is included in the
+    ///      same transaction or context state account if `VerifyCiphertextCiphertextEquality` is
+    ///      pre-verified into a context state account.
+    ///   3. `[]` The multisig confidential transfer mint authority.
+    ///   4. ..4+M `[signer]` M signer accounts for the SPL Token Multisig account.
+    ///
+    /// Data expected by this instruction:
+    ///   `RotateAuditorElGamalPubkeyInstructionData`
+    ///
+    RotateAuditorElGamalPubkey,
 }
 
 /// Data expected by `ConfidentialTransferInstruction::InitializeMint`
@@ -432,6 +478,22 @@ pub struct UpdateMintData {
     pub auditor_elgamal_pubkey: OptionalNonZeroElGamalPubkey,
 }
 
+/// Data expected by `ConfidentialTransferInstruction::RotateAuditorElGamalPubkey`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct RotateAuditorElGamalPubkeyInstructionData {
+    /// Relative location of the `ProofInstruction::VerifyPubkeyValidity` instruction to the
+    /// `RotateAuditorElGamalPubkey` instruction in the transaction. If the offset is `0`, then use
+    /// a context state account for the proof.
+    pub proof_instruction_offset: i8,
+    /// Relative location of the `ProofInstruction::VerifyCiphertextCiphertextEquality` instruction
+    /// to the `RotateAuditorElGamalPubkey` instruction in the transaction. If the offset is `0`,
+    /// then use a context state account for the proof.
+    pub reencryption_proof_instruction_offset: i8,
+}
+
 /// Data expected by `ConfidentialTransferInstruction::ConfigureAccount`
 #[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
@@ -648,6 +710,114 @@ pub fn update_mint(
     ))
 }
 
+/// Create a `RotateAuditorElGamalPubkey` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+#[cfg(not(target_os = "solana"))]
+pub fn inner_rotate_auditor_elgamal_pubkey(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data_location: ProofLocation<PubkeyValidityData>,
+    reencryption_proof_data_location: ProofLocation<CiphertextCiphertextEqualityProofData>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+
+    let proof_instruction_offset = match proof_data_location {
+        ProofLocation::InstructionOffset(proof_instruction_offset, _) => {
+            accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+            proof_instruction_offset.into()
+        }
+        ProofLocation::ContextStateAccount(context_state_account) => {
+            accounts.push(AccountMeta::new_readonly(*context_state_account, false));
+            0
+        }
+    };
+
+    let reencryption_proof_instruction_offset = match reencryption_proof_data_location {
+        ProofLocation::InstructionOffset(proof_instruction_offset, _) => {
+            accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+            proof_instruction_offset.into()
+        }
+        ProofLocation::ContextStateAccount(context_state_account) => {
+            accounts.push(AccountMeta::new_readonly(*context_state_account, false));
+            0
+        }
+    };
+
+    accounts.push(AccountMeta::new_readonly(
+        *authority,
+        multisig_signers.is_empty(),
+    ));
+
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialTransferExtension,
+        ConfidentialTransferInstruction::RotateAuditorElGamalPubkey,
+        &RotateAuditorElGamalPubkeyInstructionData {
+            proof_instruction_offset,
+            reencryption_proof_instruction_offset,
+        },
+    ))
+}
+
+/// Create a `RotateAuditorElGamalPubkey` instruction
+#[cfg(not(target_os = "solana"))]
+pub fn rotate_auditor_elgamal_pubkey(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data_location: ProofLocation<PubkeyValidityData>,
+    reencryption_proof_data_location: ProofLocation<CiphertextCiphertextEqualityProofData>,
+) -> Result<Vec<Instruction>, ProgramError> {
+    let mut instructions = vec![inner_rotate_auditor_elgamal_pubkey(
+        token_program_id,
+        mint,
+        authority,
+        multisig_signers,
+        proof_data_location,
+        reencryption_proof_data_location,
+    )?];
+
+    // This constructor appends the proof instructions right after the `RotateAuditorElGamalPubkey`
+    // instruction in order, so an instruction-based pubkey validity proof must be at offset 1 and
+    // an instruction-based re-encryption proof must directly follow it. To use arbitrary proof
+    // instruction offsets, use the `inner_rotate_auditor_elgamal_pubkey` constructor.
+    let mut expected_proof_instruction_offset: i8 = 1;
+
+    if let ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) =
+        proof_data_location
+    {
+        let proof_instruction_offset: i8 = proof_instruction_offset.into();
+        if proof_instruction_offset != expected_proof_instruction_offset {
+            return Err(TokenError::InvalidProofInstructionOffset.into());
+        }
+        instructions.push(verify_pubkey_validity(None, proof_data));
+        expected_proof_instruction_offset += 1;
+    };
+
+    if let ProofLocation::InstructionOffset(proof_instruction_offset, proof_data) =
+        reencryption_proof_data_location
+    {
+        let proof_instruction_offset: i8 = proof_instruction_offset.into();
+        if proof_instruction_offset != expected_proof_instruction_offset {
+            return Err(TokenError::InvalidProofInstructionOffset.into());
+        }
+        instructions.push(verify_ciphertext_ciphertext_equality(None, proof_data));
+    };
+
+    Ok(instructions)
+}
+
 /// Create a `ConfigureAccount` instruction
 ///
 /// This instruction is suitable for use with a cross-program `invoke`
@@ -29,7 +29,10 @@ use {
 // Remove feature once zk ops syscalls are enabled on all networks
 #[cfg(feature = "zk-ops")]
 use {
-    crate::extension::non_transferable::NonTransferable,
+    crate::extension::{
+        confidential_mint_burn::ConfidentialMintBurn, non_transferable::NonTransferable,
+        pausable::check_not_paused, vesting::check_unlocked_amount,
+    },
     solana_zk_token_sdk::zk_token_elgamal::ops as syscall,
 };
 
@@ -87,6 +90,60 @@ fn process_update_mint(
     Ok(())
 }
 
+/// Processes a [RotateAuditorElGamalPubkey] instruction.
+fn process_rotate_auditor_elgamal_pubkey(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proof_instruction_offset: i64,
+    reencryption_proof_instruction_offset: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+
+    // zero-knowledge proofs certify that the new auditor knows the secret key of the new auditor
+    // ElGamal public key and that the previous auditor re-encrypted a ciphertext under it
+    let (pubkey_validity_proof_context, reencryption_proof_context) =
+        verify_rotate_auditor_elgamal_pubkey_proof(
+            account_info_iter,
+            proof_instruction_offset,
+            reencryption_proof_instruction_offset,
+        )?;
+
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+    let confidential_transfer_mint = mint.get_extension_mut::<ConfidentialTransferMint>()?;
+    let maybe_confidential_transfer_mint_authority: Option<Pubkey> =
+        confidential_transfer_mint.authority.into();
+    let confidential_transfer_mint_authority =
+        maybe_confidential_transfer_mint_authority.ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &confidential_transfer_mint_authority,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    // The re-encryption proof can only be generated with the secret key of the current auditor,
+    // so the first auditor of a mint must be set with `UpdateMint` instead.
+    let current_auditor_elgamal_pubkey: Option<ElGamalPubkey> =
+        confidential_transfer_mint.auditor_elgamal_pubkey.into();
+    if current_auditor_elgamal_pubkey != Some(reencryption_proof_context.source_pubkey)
+        || reencryption_proof_context.destination_pubkey != pubkey_validity_proof_context.pubkey
+    {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    confidential_transfer_mint.auditor_elgamal_pubkey =
+        Some(pubkey_validity_proof_context.pubkey).try_into()?;
+    Ok(())
+}
+
 /// Processes a [ConfigureAccount] instruction.
 fn process_configure_account(
     program_id: &Pubkey,
@@ -261,6 +318,13 @@ fn process_deposit(
     if mint.get_extension::<NonTransferable>().is_ok() {
         return Err(TokenError::NonTransferable.into());
     }
+    check_not_paused(&mint)?;
+
+    // Moving tokens in and out of the confidential balance would make the encrypted supply drift
+    // from the sum of all confidential balances
+    if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+        return Err(TokenError::IllegalMintBurnConversion.into());
+    }
 
     check_program_account(token_account_info.owner)?;
     let token_account_data = &mut token_account_info.data.borrow_mut();
@@ -285,6 +349,8 @@ fn process_deposit(
     // Wrapped SOL deposits are not supported because lamports cannot be vanished.
     assert!(!token_account.base.is_native());
 
+    check_unlocked_amount(&token_account, token_account.base.amount, amount)?;
+
     token_account.base.amount = token_account
         .base
         .amount
@@ -360,6 +426,13 @@ fn process_withdraw(
     if mint.get_extension::<NonTransferable>().is_ok() {
         return Err(TokenError::NonTransferable.into());
     }
+    check_not_paused(&mint)?;
+
+    // Moving tokens in and out of the confidential balance would make the encrypted supply drift
+    // from the sum of all confidential balances
+    if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+        return Err(TokenError::IllegalMintBurnConversion.into());
+    }
 
     check_program_account(token_account_info.owner)?;
     let token_account_data = &mut token_account_info.data.borrow_mut();
@@ -442,6 +515,7 @@ fn process_transfer(
     if mint.get_extension::<NonTransferable>().is_ok() {
         return Err(TokenError::NonTransferable.into());
     }
+    check_not_paused(&mint)?;
     let confidential_transfer_mint = mint.get_extension::<ConfidentialTransferMint>()?;
 
     // A `Transfer` instruction must be accompanied by a zero-knowledge proof instruction that
@@ -1163,5 +1237,15 @@ pub(crate) fn process_instruction(
             #[cfg(not(feature = "zk-ops"))]
             Err(ProgramError::InvalidInstructionData)
         }
+        ConfidentialTransferInstruction::RotateAuditorElGamalPubkey => {
+            msg!("ConfidentialTransferInstruction::RotateAuditorElGamalPubkey");
+            let data = decode_instruction_data::<RotateAuditorElGamalPubkeyInstructionData>(input)?;
+            process_rotate_auditor_elgamal_pubkey(
+                program_id,
+                accounts,
+                data.proof_instruction_offset as i64,
+                data.reencryption_proof_instruction_offset as i64,
+            )
+        }
     }
 }
@@ -52,6 +52,65 @@ pub fn verify_configure_account_proof(
     }
 }
 
+/// Verify zero-knowledge proofs needed for a [RotateAuditorElGamalPubkey] instruction and return
+/// the corresponding proof contexts.
+///
+/// The new auditor proves knowledge of its secret key with the same pubkey validity proof that is
+/// required to configure a token account. The previous auditor hands over to the new auditor with
+/// a ciphertext-ciphertext equality proof, which can only be generated with the secret key of the
+/// previous auditor.
+pub fn verify_rotate_auditor_elgamal_pubkey_proof(
+    account_info_iter: &mut Iter<'_, AccountInfo<'_>>,
+    proof_instruction_offset: i64,
+    reencryption_proof_instruction_offset: i64,
+) -> Result<
+    (
+        PubkeyValidityProofContext,
+        CiphertextCiphertextEqualityProofContext,
+    ),
+    ProgramError,
+> {
+    let pubkey_validity_proof_context =
+        verify_configure_account_proof(account_info_iter, proof_instruction_offset)?;
+    let reencryption_proof_context = verify_ciphertext_ciphertext_equality_proof(
+        next_account_info(account_info_iter)?,
+        reencryption_proof_instruction_offset,
+    )?;
+
+    Ok((pubkey_validity_proof_context, reencryption_proof_context))
+}
+
+/// Verify a ciphertext-ciphertext equality proof and return the corresponding proof context.
+pub fn verify_ciphertext_ciphertext_equality_proof(
+    account_info: &AccountInfo<'_>,
+    proof_instruction_offset: i64,
+) -> Result<CiphertextCiphertextEqualityProofContext, ProgramError> {
+    if proof_instruction_offset == 0 {
+        // interpret `account_info` as a context state account
+        check_zk_token_proof_program_account(account_info.owner)?;
+        let context_state_account_data = account_info.data.borrow();
+        let context_state = pod_from_bytes::<
+            ProofContextState<CiphertextCiphertextEqualityProofContext>,
+        >(&context_state_account_data)?;
+
+        if context_state.proof_type != ProofType::CiphertextCiphertextEquality.into() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(context_state.proof_context)
+    } else {
+        // interpret `account_info` as a sysvar
+        let zkp_instruction = get_instruction_relative(proof_instruction_offset, account_info)?;
+        Ok(*decode_proof_instruction_context::<
+            CiphertextCiphertextEqualityProofData,
+            CiphertextCiphertextEqualityProofContext,
+        >(
+            ProofInstruction::VerifyCiphertextCiphertextEquality,
+            &zkp_instruction,
+        )?)
+    }
+}
+
 /// Verify zero-knowledge proof needed for a [EmptyAccount] instruction and return the
 /// corresponding proof context.
 pub fn verify_empty_account_proof(
@@ -509,7 +568,7 @@ pub fn verify_transfer_with_fee_proof(
 }
 
 /// Verify and process equality proof for [Transfer] and [TransferWithFee] instructions.
-fn verify_equality_proof(
+pub(crate) fn verify_equality_proof(
     account_info: &AccountInfo<'_>,
 ) -> Result<CiphertextCommitmentEqualityProofContext, ProgramError> {
     check_zk_token_proof_program_account(account_info.owner)?;
@@ -526,7 +585,7 @@ fn verify_equality_proof(
 }
 
 /// Verify and process ciphertext validity proof for [Transfer] and [TransferWithFee] instructions.
-fn verify_ciphertext_validity_proof(
+pub(crate) fn verify_ciphertext_validity_proof(
     account_info: &AccountInfo<'_>,
 ) -> Result<BatchedGroupedCiphertext2HandlesValidityProofContext, ProgramError> {
     check_zk_token_proof_program_account(account_info.owner)?;
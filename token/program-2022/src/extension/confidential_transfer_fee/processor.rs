@@ -1,14 +1,10 @@
 use {
     crate::{
-        check_program_account, check_zk_token_proof_program_account,
+        check_program_account,
         error::TokenError,
         extension::{
             confidential_transfer::{
-                instruction::{
-                    CiphertextCiphertextEqualityProofContext,
-                    CiphertextCiphertextEqualityProofData, ProofContextState, ProofInstruction,
-                    ProofType,
-                },
+                verify_proof::verify_ciphertext_ciphertext_equality_proof,
                 ConfidentialTransferAccount, DecryptableBalance,
             },
             confidential_transfer_fee::{
@@ -25,7 +21,6 @@ use {
         },
         instruction::{decode_instruction_data, decode_instruction_type},
         processor::Processor,
-        proof::decode_proof_instruction_context,
         solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
         state::{Account, Mint},
     },
@@ -36,9 +31,8 @@ use {
         msg,
         program_error::ProgramError,
         pubkey::Pubkey,
-        sysvar::instructions::get_instruction_relative,
     },
-    spl_pod::{bytemuck::pod_from_bytes, optional_keys::OptionalNonZeroPubkey},
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
 };
 
 // Remove feature once zk ops syscalls are enabled on all networks
@@ -168,38 +162,6 @@ fn process_withdraw_withheld_tokens_from_mint(
     Ok(())
 }
 
-/// Verify zero-knowledge proof needed for a [WithdrawWithheldTokensFromMint] instruction or a
-/// `[WithdrawWithheldTokensFromAccounts]` and return the corresponding proof context.
-fn verify_ciphertext_ciphertext_equality_proof(
-    account_info: &AccountInfo<'_>,
-    proof_instruction_offset: i64,
-) -> Result<CiphertextCiphertextEqualityProofContext, ProgramError> {
-    if proof_instruction_offset == 0 {
-        // interpret `account_info` as a context state account
-        check_zk_token_proof_program_account(account_info.owner)?;
-        let context_state_account_data = account_info.data.borrow();
-        let context_state = pod_from_bytes::<
-            ProofContextState<CiphertextCiphertextEqualityProofContext>,
-        >(&context_state_account_data)?;
-
-        if context_state.proof_type != ProofType::CiphertextCiphertextEquality.into() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        Ok(context_state.proof_context)
-    } else {
-        // interpret `account_info` as a sysvar
-        let zkp_instruction = get_instruction_relative(proof_instruction_offset, account_info)?;
-        Ok(*decode_proof_instruction_context::<
-            CiphertextCiphertextEqualityProofData,
-            CiphertextCiphertextEqualityProofContext,
-        >(
-            ProofInstruction::VerifyCiphertextCiphertextEquality,
-            &zkp_instruction,
-        )?)
-    }
-}
-
 /// Processes a [WithdrawWithheldTokensFromAccounts] instruction.
 #[cfg(feature = "zk-ops")]
 fn process_withdraw_withheld_tokens_from_accounts(
@@ -4,6 +4,7 @@ use {
     crate::{
         error::TokenError,
         extension::{
+            confidential_mint_burn::ConfidentialMintBurn,
             confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
             confidential_transfer_fee::{
                 ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
@@ -17,9 +18,12 @@ use {
             metadata_pointer::MetadataPointer,
             mint_close_authority::MintCloseAuthority,
             non_transferable::{NonTransferable, NonTransferableAccount},
+            pausable::{PausableAccount, PausableConfig},
             permanent_delegate::PermanentDelegate,
-            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
-            transfer_hook::{TransferHook, TransferHookAccount},
+            scaled_ui_amount::ScaledUiAmountConfig,
+            transfer_fee::{TransferFeeAmount, TransferFeeConfig, TransferFeeOverride},
+            transfer_hook::{TransferHook, TransferHookAccount, TransferHookMintBurn},
+            vesting::VestingSchedule,
         },
         state::{Account, Mint, Multisig},
     },
@@ -34,6 +38,7 @@ use {
         bytemuck::{pod_from_bytes, pod_from_bytes_mut, pod_get_packed_len},
         primitives::PodU16,
     },
+    spl_token_group_interface::state::{TokenGroup, TokenGroupMember},
     spl_type_length_value::variable_len_pack::VariableLenPack,
     std::{
         cmp::Ordering,
@@ -45,6 +50,8 @@ use {
 #[cfg(feature = "serde-traits")]
 use serde::{Deserialize, Serialize};
 
+/// Confidential Mint-Burn extension
+pub mod confidential_mint_burn;
 /// Confidential Transfer extension
 pub mod confidential_transfer;
 /// Confidential Transfer Fee extension
@@ -67,16 +74,24 @@ pub mod metadata_pointer;
 pub mod mint_close_authority;
 /// Non Transferable extension
 pub mod non_transferable;
+/// Pausable extension
+pub mod pausable;
 /// Permanent Delegate extension
 pub mod permanent_delegate;
 /// Utility to reallocate token accounts
 pub mod reallocate;
+/// Scaled UI Amount extension
+pub mod scaled_ui_amount;
+/// Token-group extensions
+pub mod token_group;
 /// Token-metadata extension
 pub mod token_metadata;
 /// Transfer Fee extension
 pub mod transfer_fee;
 /// Transfer Hook extension
 pub mod transfer_hook;
+/// Vesting extension
+pub mod vesting;
 
 /// Length in TLV structure
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
@@ -136,9 +151,16 @@ struct TlvIndices {
 fn get_extension_indices<V: Extension>(
     tlv_data: &[u8],
     init: bool,
+) -> Result<TlvIndices, ProgramError> {
+    get_extension_indices_for_type(tlv_data, V::TYPE, init)
+}
+fn get_extension_indices_for_type(
+    tlv_data: &[u8],
+    v_type: ExtensionType,
+    init: bool,
 ) -> Result<TlvIndices, ProgramError> {
     let mut start_index = 0;
-    let v_account_type = V::TYPE.get_account_type();
+    let v_account_type = v_type.get_account_type();
     while start_index < tlv_data.len() {
         let tlv_indices = get_tlv_indices(start_index);
         if tlv_data.len() < tlv_indices.value_start {
@@ -147,7 +169,7 @@ fn get_extension_indices<V: Extension>(
         let extension_type =
             ExtensionType::try_from(&tlv_data[tlv_indices.type_start..tlv_indices.length_start])?;
         let account_type = extension_type.get_account_type();
-        if extension_type == V::TYPE {
+        if extension_type == v_type {
             // found an instance of the extension that we're initializing, return!
             return Ok(tlv_indices);
         // got to an empty spot, init here, or error if we're searching, since
@@ -720,6 +742,29 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
         Ok(&mut self.tlv_data[value_start..new_value_end])
     }
 
+    /// Remove the TLV entry for the given extension type, compacting the rest
+    /// of the buffer and zeroing out the freed bytes at the end.
+    ///
+    /// Returns an error if the extension is not present.
+    pub fn remove_extension(&mut self, extension_type: ExtensionType) -> Result<(), ProgramError> {
+        if extension_type.get_account_type() != S::ACCOUNT_TYPE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let TlvIndices {
+            type_start,
+            length_start,
+            value_start,
+        } = get_extension_indices_for_type(self.tlv_data, extension_type, false)?;
+        let tlv_len = get_tlv_data_info(self.tlv_data).map(|x| x.used_len)?;
+        let length = pod_from_bytes::<Length>(&self.tlv_data[length_start..value_start])?;
+        let value_end = value_start.saturating_add(usize::from(*length));
+
+        self.tlv_data.copy_within(value_end..tlv_len, type_start);
+        let new_tlv_len = tlv_len.saturating_sub(value_end.saturating_sub(type_start));
+        self.tlv_data[new_tlv_len..tlv_len].fill(0);
+        Ok(())
+    }
+
     /// Allocate the given number of bytes for the given variable-length extension
     /// and write its contents into the TLV buffer.
     ///
@@ -799,6 +844,9 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
             ExtensionType::TransferHookAccount => {
                 self.init_extension::<TransferHookAccount>(true).map(|_| ())
             }
+            ExtensionType::PausableAccount => {
+                self.init_extension::<PausableAccount>(true).map(|_| ())
+            }
             // ConfidentialTransfers are currently opt-in only, so this is a no-op for extra safety
             // on InitializeAccount
             ExtensionType::ConfidentialTransferAccount => Ok(()),
@@ -859,7 +907,7 @@ pub fn set_account_type<S: BaseState>(input: &mut [u8]) -> Result<(), ProgramErr
 /// Different kinds of accounts. Note that `Mint`, `Account`, and `Multisig` types
 /// are determined exclusively by the size of the account, and are not included in
 /// the account data. `AccountType` is only included if extensions have been
-/// initialized.
+/// initialized, or if the account is a `WeightedMultisig`.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive, IntoPrimitive)]
 pub enum AccountType {
@@ -869,6 +917,8 @@ pub enum AccountType {
     Mint,
     /// Token holding account with additional extensions
     Account,
+    /// Weighted multisig, which always includes its account type
+    WeightedMultisig,
 }
 impl Default for AccountType {
     fn default() -> Self {
@@ -927,6 +977,24 @@ pub enum ExtensionType {
     /// Mint contains a pointer to another account (or the same account) that holds group
     /// configurations
     GroupPointer,
+    /// Mint-level pause of minting, transferring and burning
+    Pausable,
+    /// Indicates that the tokens in this account belong to a pausable mint
+    PausableAccount,
+    /// Tokens have a scaled UI amount
+    ScaledUiAmount,
+    /// Tokens can be minted and burned confidentially, with an encrypted supply
+    ConfidentialMintBurn,
+    /// Account is exempt from the mint transfer fee or charged a lower, authority-managed fee
+    TransferFeeOverride,
+    /// Part of the account balance is locked until it vests
+    Vesting,
+    /// Mint opts into calling its transfer hook program on mint and burn
+    TransferHookMintBurn,
+    /// Mint contains token group configurations
+    TokenGroup,
+    /// Mint contains token group member configurations
+    TokenGroupMember,
     /// Test variable-length mint extension
     #[cfg(test)]
     VariableLenMintTest = u16::MAX - 2,
@@ -1002,6 +1070,15 @@ impl ExtensionType {
             ExtensionType::MetadataPointer => pod_get_packed_len::<MetadataPointer>(),
             ExtensionType::TokenMetadata => unreachable!(),
             ExtensionType::GroupPointer => pod_get_packed_len::<GroupPointer>(),
+            ExtensionType::Pausable => pod_get_packed_len::<PausableConfig>(),
+            ExtensionType::PausableAccount => pod_get_packed_len::<PausableAccount>(),
+            ExtensionType::ScaledUiAmount => pod_get_packed_len::<ScaledUiAmountConfig>(),
+            ExtensionType::ConfidentialMintBurn => pod_get_packed_len::<ConfidentialMintBurn>(),
+            ExtensionType::TransferFeeOverride => pod_get_packed_len::<TransferFeeOverride>(),
+            ExtensionType::Vesting => pod_get_packed_len::<VestingSchedule>(),
+            ExtensionType::TransferHookMintBurn => pod_get_packed_len::<TransferHookMintBurn>(),
+            ExtensionType::TokenGroup => pod_get_packed_len::<TokenGroup>(),
+            ExtensionType::TokenGroupMember => pod_get_packed_len::<TokenGroupMember>(),
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => pod_get_packed_len::<AccountPaddingTest>(),
             #[cfg(test)]
@@ -1062,7 +1139,13 @@ impl ExtensionType {
             | ExtensionType::ConfidentialTransferFeeConfig
             | ExtensionType::MetadataPointer
             | ExtensionType::TokenMetadata
-            | ExtensionType::GroupPointer => AccountType::Mint,
+            | ExtensionType::GroupPointer
+            | ExtensionType::Pausable
+            | ExtensionType::ScaledUiAmount
+            | ExtensionType::ConfidentialMintBurn
+            | ExtensionType::TransferHookMintBurn
+            | ExtensionType::TokenGroup
+            | ExtensionType::TokenGroupMember => AccountType::Mint,
             ExtensionType::ImmutableOwner
             | ExtensionType::TransferFeeAmount
             | ExtensionType::ConfidentialTransferAccount
@@ -1070,7 +1153,10 @@ impl ExtensionType {
             | ExtensionType::NonTransferableAccount
             | ExtensionType::TransferHookAccount
             | ExtensionType::CpiGuard
-            | ExtensionType::ConfidentialTransferFeeAmount => AccountType::Account,
+            | ExtensionType::ConfidentialTransferFeeAmount
+            | ExtensionType::PausableAccount
+            | ExtensionType::TransferFeeOverride
+            | ExtensionType::Vesting => AccountType::Account,
             #[cfg(test)]
             ExtensionType::VariableLenMintTest => AccountType::Mint,
             #[cfg(test)]
@@ -1095,6 +1181,9 @@ impl ExtensionType {
                 ExtensionType::TransferHook => {
                     account_extension_types.push(ExtensionType::TransferHookAccount);
                 }
+                ExtensionType::Pausable => {
+                    account_extension_types.push(ExtensionType::PausableAccount);
+                }
                 #[cfg(test)]
                 ExtensionType::MintPaddingTest => {
                     account_extension_types.push(ExtensionType::AccountPaddingTest);
@@ -1112,6 +1201,11 @@ impl ExtensionType {
         let mut transfer_fee_config = false;
         let mut confidential_transfer_mint = false;
         let mut confidential_transfer_fee_config = false;
+        let mut interest_bearing = false;
+        let mut scaled_ui_amount = false;
+        let mut confidential_mint_burn = false;
+        let mut transfer_hook = false;
+        let mut transfer_hook_mint_burn = false;
 
         for extension_type in mint_extension_types {
             match extension_type {
@@ -1120,6 +1214,11 @@ impl ExtensionType {
                 ExtensionType::ConfidentialTransferFeeConfig => {
                     confidential_transfer_fee_config = true
                 }
+                ExtensionType::InterestBearingConfig => interest_bearing = true,
+                ExtensionType::ScaledUiAmount => scaled_ui_amount = true,
+                ExtensionType::ConfidentialMintBurn => confidential_mint_burn = true,
+                ExtensionType::TransferHook => transfer_hook = true,
+                ExtensionType::TransferHookMintBurn => transfer_hook_mint_burn = true,
                 _ => (),
             }
         }
@@ -1133,6 +1232,21 @@ impl ExtensionType {
             return Err(TokenError::InvalidExtensionCombination);
         }
 
+        // both extensions rescale the UI amount, so they can't be combined
+        if interest_bearing && scaled_ui_amount {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
+        // confidentially minted tokens can only be used through confidential transfers
+        if confidential_mint_burn && !confidential_transfer_mint {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
+        // mint and burn hooks call into the transfer hook program
+        if transfer_hook_mint_burn && !transfer_hook {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
         Ok(())
     }
 }
@@ -1307,7 +1421,10 @@ pub fn alloc_and_serialize_variable_len_extension<S: BaseState, V: Extension + V
 mod test {
     use {
         super::*,
-        crate::state::test::{TEST_ACCOUNT, TEST_ACCOUNT_SLICE, TEST_MINT, TEST_MINT_SLICE},
+        crate::state::{
+            test::{TEST_ACCOUNT, TEST_ACCOUNT_SLICE, TEST_MINT, TEST_MINT_SLICE},
+            WeightedMultisig,
+        },
         bytemuck::Pod,
         solana_program::{
             account_info::{Account as GetAccount, IntoAccountInfo},
@@ -1943,6 +2060,68 @@ mod test {
         assert_eq!(expect, buffer);
     }
 
+    #[test]
+    fn mint_and_account_with_weighted_multisig_len() {
+        // Weighted multisigs are 1190 bytes long: their account type is at the
+        // same offset as for extended mints and accounts, after 121 bytes of
+        // padding, and is followed by 32 signers. Mints and accounts with
+        // extensions may have the same length, but their account type tells
+        // them apart.
+        let len = WeightedMultisig::LEN;
+        let mut buffer = vec![0; len];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut buffer).unwrap();
+        state.base = TEST_MINT;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_extension::<MintCloseAuthority>(true).unwrap();
+
+        let state = StateWithExtensions::<Mint>::unpack(&buffer).unwrap();
+        assert_eq!(state.base, TEST_MINT);
+        assert_eq!(
+            state.get_extension_types().unwrap(),
+            vec![ExtensionType::MintCloseAuthority]
+        );
+        assert!(!WeightedMultisig::is_weighted_multisig(&buffer));
+
+        let mut buffer = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut buffer).unwrap();
+        state.base = TEST_ACCOUNT;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_extension::<ImmutableOwner>(true).unwrap();
+
+        let state = StateWithExtensions::<Account>::unpack(&buffer).unwrap();
+        assert_eq!(state.base, TEST_ACCOUNT);
+        assert_eq!(
+            state.get_extension_types().unwrap(),
+            vec![ExtensionType::ImmutableOwner]
+        );
+        assert!(!WeightedMultisig::is_weighted_multisig(&buffer));
+
+        // a weighted multisig is neither a mint nor an account
+        let mut buffer = vec![0; WeightedMultisig::LEN];
+        let multisig = WeightedMultisig {
+            threshold: 1,
+            n: 1,
+            is_initialized: true,
+            ..WeightedMultisig::default()
+        };
+        WeightedMultisig::pack(multisig, &mut buffer).unwrap();
+        assert_eq!(
+            StateWithExtensions::<Mint>::unpack(&buffer).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            StateWithExtensions::<Account>::unpack(&buffer).unwrap_err(),
+            ProgramError::UninitializedAccount
+        );
+        assert_eq!(
+            StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut buffer).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
     #[test]
     fn test_set_account_type() {
         // account with buffer big enough for AccountType and Extension
@@ -2332,6 +2511,73 @@ mod test {
         );
     }
 
+    #[test]
+    fn remove_extension() {
+        let variable_len = VariableLenMintTest {
+            data: vec![1, 2, 3, 4],
+        };
+        let variable_tlv_len = add_type_and_length_to_len(variable_len.get_packed_len().unwrap());
+        let account_size =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])
+                .unwrap()
+                + variable_tlv_len;
+        let mut buffer = vec![0; account_size];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut buffer).unwrap();
+
+        state
+            .init_variable_len_extension(&variable_len, false)
+            .unwrap();
+        let max_pubkey =
+            OptionalNonZeroPubkey::try_from(Some(Pubkey::new_from_array([255; 32]))).unwrap();
+        let extension = state.init_extension::<MetadataPointer>(false).unwrap();
+        extension.authority = max_pubkey;
+        extension.metadata_address = max_pubkey;
+
+        // wrong account type
+        assert_eq!(
+            state
+                .remove_extension(ExtensionType::MemoTransfer)
+                .unwrap_err(),
+            ProgramError::InvalidAccountData,
+        );
+        // not present in a full buffer
+        assert_eq!(
+            state
+                .remove_extension(ExtensionType::MintCloseAuthority)
+                .unwrap_err(),
+            ProgramError::InvalidAccountData,
+        );
+
+        // remove the first entry, the second one is moved up
+        state
+            .remove_extension(ExtensionType::VariableLenMintTest)
+            .unwrap();
+        assert_eq!(
+            state.get_extension_types().unwrap(),
+            vec![ExtensionType::MetadataPointer]
+        );
+        let extension = state.get_extension::<MetadataPointer>().unwrap();
+        assert_eq!(extension.authority, max_pubkey);
+        assert_eq!(extension.metadata_address, max_pubkey);
+        assert_eq!(
+            &buffer[account_size - variable_tlv_len..],
+            vec![0; variable_tlv_len]
+        );
+
+        // remove the last entry, leaving an empty TLV buffer
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut buffer).unwrap();
+        state
+            .remove_extension(ExtensionType::MetadataPointer)
+            .unwrap();
+        assert_eq!(state.get_extension_types().unwrap(), vec![]);
+        assert_eq!(
+            state
+                .remove_extension(ExtensionType::MetadataPointer)
+                .unwrap_err(),
+            TokenError::ExtensionNotFound.into(),
+        );
+    }
+
     #[test]
     fn account_len() {
         let small_variable_len = VariableLenMintTest {
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        error::TokenError,
+        extension::{BaseStateWithExtensions, Extension, ExtensionType},
+        state::Mint,
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program::program_error::ProgramError,
+    spl_pod::{optional_keys::OptionalNonZeroPubkey, primitives::PodBool},
+};
+
+#[cfg(feature = "serde-traits")]
+use serde::{Deserialize, Serialize};
+
+/// Instructions for the Pausable extension
+pub mod instruction;
+/// Instruction processor for the Pausable extension
+pub mod processor;
+
+/// Pausable extension data for mints.
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PausableConfig {
+    /// Authority that can pause or resume activity on the mint
+    pub authority: OptionalNonZeroPubkey,
+    /// Whether minting, transferring and burning are currently paused
+    pub paused: PodBool,
+}
+
+/// Indicates that the tokens from this account belong to a pausable mint
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PausableAccount;
+
+impl Extension for PausableConfig {
+    const TYPE: ExtensionType = ExtensionType::Pausable;
+}
+
+impl Extension for PausableAccount {
+    const TYPE: ExtensionType = ExtensionType::PausableAccount;
+}
+
+/// Fails if the mint includes the pausable extension and is currently paused
+pub fn check_not_paused<BSE: BaseStateWithExtensions<Mint>>(
+    mint: &BSE,
+) -> Result<(), ProgramError> {
+    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
+        if bool::from(extension.paused) {
+            return Err(TokenError::MintPaused.into());
+        }
+    }
+    Ok(())
+}
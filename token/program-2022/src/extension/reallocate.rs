@@ -2,7 +2,8 @@ use {
     crate::{
         error::TokenError,
         extension::{
-            set_account_type, AccountType, BaseStateWithExtensions, ExtensionType,
+            cpi_guard::CpiGuard, memo_transfer::MemoTransfer, set_account_type,
+            transfer_fee::TransferFeeOverride, AccountType, BaseStateWithExtensions, ExtensionType,
             StateWithExtensions, StateWithExtensionsMut,
         },
         processor::Processor,
@@ -13,6 +14,7 @@ use {
         entrypoint::ProgramResult,
         msg,
         program::invoke,
+        program_error::ProgramError,
         program_option::COption,
         pubkey::Pubkey,
         system_instruction,
@@ -65,6 +67,24 @@ pub fn process_reallocate(
         return Ok(());
     }
 
+    reallocate_account(
+        token_account_info,
+        payer_info,
+        system_program_info,
+        needed_account_len,
+        native_token_amount,
+    )
+}
+
+/// Grows a token account to `needed_account_len` bytes, with `payer_info`
+/// topping up its rent-exempt reserve
+pub(crate) fn reallocate_account<'a>(
+    token_account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    needed_account_len: usize,
+    native_token_amount: Option<u64>,
+) -> ProgramResult {
     // reallocate
     msg!(
         "account needs realloc, +{:?} bytes",
@@ -113,3 +133,121 @@ pub fn process_reallocate(
 
     Ok(())
 }
+
+/// Checks that an account extension is currently disabled and may be removed
+fn check_extension_removable(
+    account: &StateWithExtensionsMut<Account>,
+    extension_type: ExtensionType,
+) -> ProgramResult {
+    let enabled = match extension_type {
+        ExtensionType::MemoTransfer => {
+            account
+                .get_extension::<MemoTransfer>()?
+                .require_incoming_transfer_memos
+        }
+        ExtensionType::CpiGuard => account.get_extension::<CpiGuard>()?.lock_cpi,
+        ExtensionType::TransferFeeOverride => {
+            account.get_extension::<TransferFeeOverride>()?.enabled
+        }
+        _ => {
+            msg!("Extension {:?} cannot be removed", extension_type);
+            return Err(TokenError::ExtensionNotRemovable.into());
+        }
+    };
+    if bool::from(enabled) {
+        msg!(
+            "Extension {:?} must be disabled before removal",
+            extension_type
+        );
+        Err(TokenError::ExtensionNotRemovable.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Processes a [RemoveExtensions](enum.TokenInstruction.html) instruction
+pub fn process_remove_extensions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    extension_types: Vec<ExtensionType>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    if token_account_info.key == destination_info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // check that all extensions are for the right account type
+    if extension_types
+        .iter()
+        .any(|extension_type| extension_type.get_account_type() != AccountType::Account)
+    {
+        return Err(TokenError::InvalidState.into());
+    }
+
+    // validate owner, then remove the extensions and compact the TLV data
+    let (remaining_extension_types, native_token_amount) = {
+        let mut token_account_data = token_account_info.data.borrow_mut();
+        let mut token_account = StateWithExtensionsMut::<Account>::unpack(&mut token_account_data)?;
+        Processor::validate_owner(
+            program_id,
+            &token_account.base.owner,
+            authority_info,
+            authority_info_data_len,
+            account_info_iter.as_slice(),
+        )?;
+        for extension_type in extension_types {
+            check_extension_removable(&token_account, extension_type)?;
+            token_account.remove_extension(extension_type)?;
+        }
+        let native_token_amount = token_account
+            .base
+            .is_native()
+            .then_some(token_account.base.amount);
+        (token_account.get_extension_types()?, native_token_amount)
+    };
+
+    let needed_account_len =
+        ExtensionType::try_calculate_account_len::<Account>(&remaining_extension_types)?;
+    if token_account_info.data_len() > needed_account_len {
+        msg!(
+            "account needs realloc, -{:?} bytes",
+            token_account_info.data_len() - needed_account_len
+        );
+        token_account_info.realloc(needed_account_len, false)?;
+    }
+
+    // move any lamports no longer needed for rent exemption to the destination
+    let rent = Rent::get()?;
+    let new_rent_exempt_reserve = rent.minimum_balance(token_account_info.data_len());
+    let minimum_lamports = new_rent_exempt_reserve
+        .checked_add(native_token_amount.unwrap_or(0))
+        .ok_or(TokenError::Overflow)?;
+    let excess_lamports = token_account_info
+        .lamports()
+        .saturating_sub(minimum_lamports);
+
+    let token_account_starting_lamports = token_account_info.lamports();
+    **token_account_info.lamports.borrow_mut() = token_account_starting_lamports
+        .checked_sub(excess_lamports)
+        .ok_or(TokenError::Overflow)?;
+
+    let destination_starting_lamports = destination_info.lamports();
+    **destination_info.lamports.borrow_mut() = destination_starting_lamports
+        .checked_add(excess_lamports)
+        .ok_or(TokenError::Overflow)?;
+
+    // sync the rent exempt reserve for native accounts
+    if native_token_amount.is_some() {
+        let mut token_account_data = token_account_info.data.borrow_mut();
+        let mut token_account = StateWithExtensionsMut::<Account>::unpack(&mut token_account_data)?;
+        token_account.base.is_native = COption::Some(new_rent_exempt_reserve);
+        token_account.pack_base();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use {
+    crate::extension::{Extension, ExtensionType},
+    bytemuck::{Pod, Zeroable},
+    solana_program::program_error::ProgramError,
+    spl_pod::{optional_keys::OptionalNonZeroPubkey, primitives::PodI64},
+};
+
+#[cfg(feature = "serde-traits")]
+use serde::{Deserialize, Serialize};
+
+/// Scaled UI amount extension instructions
+pub mod instruction;
+
+/// Scaled UI amount extension processor
+pub mod processor;
+
+/// UnixTimestamp expressed with an alignment-independent type
+pub type UnixTimestamp = PodI64;
+
+/// `f64` type that can be used in `Pod`s
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(from = "f64", into = "f64"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodF64([u8; 8]);
+impl From<f64> for PodF64 {
+    fn from(n: f64) -> Self {
+        Self(n.to_le_bytes())
+    }
+}
+impl From<PodF64> for f64 {
+    fn from(pod: PodF64) -> Self {
+        Self::from_le_bytes(pod.0)
+    }
+}
+
+/// Scaled UI amount extension data for mints
+///
+/// The UI amount of a token is its raw amount, scaled by `multiplier`, which
+/// can be used to model stock splits, rebasing tokens or dividends.
+///
+/// To support scheduling a change in advance, the config also holds a new
+/// multiplier along with the timestamp at which it takes effect.
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ScaledUiAmountConfig {
+    /// Authority that can set the scaling amount and authority
+    pub authority: OptionalNonZeroPubkey,
+    /// Amount to multiply raw amounts by, outside of the decimal
+    pub multiplier: PodF64,
+    /// Unix timestamp at which `new_multiplier` comes into effect
+    pub new_multiplier_effective_timestamp: UnixTimestamp,
+    /// Next multiplier, once `new_multiplier_effective_timestamp` is reached
+    pub new_multiplier: PodF64,
+}
+impl ScaledUiAmountConfig {
+    /// Get the multiplier in effect at the given timestamp
+    pub fn current_multiplier(&self, unix_timestamp: i64) -> f64 {
+        if unix_timestamp >= i64::from(self.new_multiplier_effective_timestamp) {
+            self.new_multiplier.into()
+        } else {
+            self.multiplier.into()
+        }
+    }
+
+    fn total_multiplier(&self, decimals: u8, unix_timestamp: i64) -> f64 {
+        self.current_multiplier(unix_timestamp) / 10_f64.powi(decimals as i32)
+    }
+
+    /// Convert a raw amount to its UI representation using the given decimals field
+    /// Excess zeroes or unneeded decimal point are trimmed.
+    pub fn amount_to_ui_amount(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Option<String> {
+        let scaled_amount = (amount as f64) * self.total_multiplier(decimals, unix_timestamp);
+        if scaled_amount.is_finite() {
+            Some(scaled_amount.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Try to convert a UI representation of a token amount to its raw amount using the given decimals
+    /// field
+    pub fn try_ui_amount_into_amount(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        let scaled_amount = ui_amount
+            .parse::<f64>()
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        let amount = scaled_amount / self.total_multiplier(decimals, unix_timestamp);
+        if amount > (u64::MAX as f64) || amount < (u64::MIN as f64) || amount.is_nan() {
+            Err(ProgramError::InvalidArgument)
+        } else {
+            Ok(amount.round() as u64) // this is important, if you round earlier, you'll get wrong "inf" answers
+        }
+    }
+}
+impl Extension for ScaledUiAmountConfig {
+    const TYPE: ExtensionType = ExtensionType::ScaledUiAmount;
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, proptest::prelude::*};
+
+    const TEST_DECIMALS: u8 = 2;
+
+    #[test]
+    fn multiplier_choice() {
+        let multiplier = 5.0;
+        let new_multiplier = 10.0;
+        let new_multiplier_effective_timestamp = 1;
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(multiplier),
+            new_multiplier: PodF64::from(new_multiplier),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(
+                new_multiplier_effective_timestamp,
+            ),
+        };
+        assert_eq!(
+            config.current_multiplier(new_multiplier_effective_timestamp),
+            new_multiplier
+        );
+        assert_eq!(
+            config.current_multiplier(new_multiplier_effective_timestamp - 1),
+            multiplier
+        );
+        assert_eq!(
+            config.current_multiplier(new_multiplier_effective_timestamp + 1),
+            new_multiplier
+        );
+    }
+
+    #[test]
+    fn specific_amount_to_ui_amount() {
+        // 5x
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(5.0),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+            ..Default::default()
+        };
+        let ui_amount = config.amount_to_ui_amount(1, 0, 0).unwrap();
+        assert_eq!(ui_amount, "5");
+        // with 1 decimal place
+        let ui_amount = config.amount_to_ui_amount(1, 1, 0).unwrap();
+        assert_eq!(ui_amount, "0.5");
+        // with 10 decimal places
+        let ui_amount = config.amount_to_ui_amount(1, 10, 0).unwrap();
+        assert_eq!(ui_amount, "0.0000000005");
+
+        // huge amount with 10 decimal places
+        let ui_amount = config.amount_to_ui_amount(10_000_000_000, 10, 0).unwrap();
+        assert_eq!(ui_amount, "5");
+
+        // fractional multiplier, e.g. a reverse split
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(0.25),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+            ..Default::default()
+        };
+        let ui_amount = config.amount_to_ui_amount(4, 0, 0).unwrap();
+        assert_eq!(ui_amount, "1");
+
+        // huge values
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(f64::MAX),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+            ..Default::default()
+        };
+        let ui_amount = config.amount_to_ui_amount(1, 0, 0).unwrap();
+        assert_eq!(ui_amount, f64::MAX.to_string());
+        // overflows to infinity
+        assert_eq!(config.amount_to_ui_amount(u64::MAX, 0, 0), None);
+    }
+
+    #[test]
+    fn specific_ui_amount_to_amount() {
+        // 5x
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(5.0),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+            ..Default::default()
+        };
+        let amount = config.try_ui_amount_into_amount("5.0", 0, 0).unwrap();
+        assert_eq!(1, amount);
+        // with 1 decimal place
+        let amount = config
+            .try_ui_amount_into_amount("0.500000000", 1, 0)
+            .unwrap();
+        assert_eq!(amount, 1);
+        // with 10 decimal places
+        let amount = config
+            .try_ui_amount_into_amount("0.00000000050000000000000000", 10, 0)
+            .unwrap();
+        assert_eq!(amount, 1);
+
+        // huge amount with 10 decimal places
+        let amount = config.try_ui_amount_into_amount("5.0", 10, 0).unwrap();
+        assert_eq!(amount, 10_000_000_000);
+
+        // scientific notation "e"
+        let amount = config.try_ui_amount_into_amount("5e0", 0, 0).unwrap();
+        assert_eq!(amount, 1);
+        // scientific notation "E"
+        let amount = config.try_ui_amount_into_amount("5E-1", 1, 0).unwrap();
+        assert_eq!(amount, 1);
+
+        // overflow u64 fail
+        assert_eq!(
+            Err(ProgramError::InvalidArgument),
+            config.try_ui_amount_into_amount("184467440737095516160", 0, 0)
+        );
+
+        for fail_ui_amount in ["-0.0000000000000000000001", "inf", "-inf", "NaN"] {
+            assert_eq!(
+                Err(ProgramError::InvalidArgument),
+                config.try_ui_amount_into_amount(fail_ui_amount, 0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn specific_amount_to_ui_amount_no_scale() {
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(1.0),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+            ..Default::default()
+        };
+        for (amount, expected) in [(23, "0.23"), (110, "1.1"), (4200, "42"), (0, "0")] {
+            let ui_amount = config
+                .amount_to_ui_amount(amount, TEST_DECIMALS, 0)
+                .unwrap();
+            assert_eq!(ui_amount, expected);
+        }
+    }
+
+    #[test]
+    fn specific_ui_amount_to_amount_no_scale() {
+        let config = ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: PodF64::from(1.0),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+            ..Default::default()
+        };
+        for (ui_amount, expected) in [
+            ("0.23", 23),
+            ("0.20", 20),
+            ("0.2000", 20),
+            (".2", 20),
+            ("1.1", 110),
+            ("1.10", 110),
+            ("42", 4200),
+            ("42.", 4200),
+            ("0", 0),
+        ] {
+            let amount = config
+                .try_ui_amount_into_amount(ui_amount, TEST_DECIMALS, 0)
+                .unwrap();
+            assert_eq!(expected, amount);
+        }
+
+        // this is invalid with normal mints, but rounding for this mint makes it ok
+        let amount = config
+            .try_ui_amount_into_amount("0.111", TEST_DECIMALS, 0)
+            .unwrap();
+        assert_eq!(11, amount);
+
+        // fail if invalid ui_amount passed in
+        for ui_amount in ["", ".", "0.t"] {
+            assert_eq!(
+                Err(ProgramError::InvalidArgument),
+                config.try_ui_amount_into_amount(ui_amount, TEST_DECIMALS, 0),
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn amount_to_ui_amount(
+            scale in 0f64..=f64::MAX,
+            amount in 0..=u64::MAX,
+            decimals in 0u8..20u8,
+        ) {
+            let config = ScaledUiAmountConfig {
+                authority: OptionalNonZeroPubkey::default(),
+                multiplier: scale.into(),
+                new_multiplier_effective_timestamp: UnixTimestamp::from(i64::MAX),
+                ..Default::default()
+            };
+            let ui_amount = config.amount_to_ui_amount(amount, decimals, 0);
+            if let Some(ui_amount) = ui_amount {
+                assert!(ui_amount.parse::<f64>().unwrap().is_finite());
+            }
+        }
+    }
+}
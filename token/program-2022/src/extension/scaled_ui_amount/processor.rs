@@ -0,0 +1,126 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            scaled_ui_amount::{
+                instruction::{
+                    InitializeInstructionData, ScaledUiAmountMintInstruction,
+                    UpdateMultiplierInstructionData,
+                },
+                PodF64, ScaledUiAmountConfig, UnixTimestamp,
+            },
+            StateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        processor::Processor,
+        state::Mint,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+};
+
+fn try_validate_multiplier(multiplier: &PodF64) -> ProgramResult {
+    let float_multiplier = f64::from(*multiplier);
+    if float_multiplier.is_sign_positive() && float_multiplier.is_normal() {
+        Ok(())
+    } else {
+        Err(TokenError::InvalidScale.into())
+    }
+}
+
+fn process_initialize(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority: &OptionalNonZeroPubkey,
+    multiplier: &PodF64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data)?;
+
+    try_validate_multiplier(multiplier)?;
+
+    let extension = mint.init_extension::<ScaledUiAmountConfig>(true)?;
+    extension.authority = *authority;
+    extension.multiplier = *multiplier;
+    extension.new_multiplier_effective_timestamp = 0.into();
+    extension.new_multiplier = *multiplier;
+    Ok(())
+}
+
+fn process_update_multiplier(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_multiplier: &PodF64,
+    effective_timestamp: &UnixTimestamp,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let extension = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+    let authority =
+        Option::<Pubkey>::from(extension.authority).ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    try_validate_multiplier(new_multiplier)?;
+
+    let clock = Clock::get()?;
+    // A previously scheduled multiplier that's already in effect becomes the
+    // current one, so that it isn't lost when scheduling the next change
+    if clock.unix_timestamp >= i64::from(extension.new_multiplier_effective_timestamp) {
+        extension.multiplier = extension.new_multiplier;
+    }
+    // The new multiplier applies right away if the timestamp has passed
+    if i64::from(*effective_timestamp) <= clock.unix_timestamp {
+        extension.multiplier = *new_multiplier;
+    }
+    extension.new_multiplier_effective_timestamp = *effective_timestamp;
+    extension.new_multiplier = *new_multiplier;
+    Ok(())
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+    match decode_instruction_type(input)? {
+        ScaledUiAmountMintInstruction::Initialize => {
+            msg!("ScaledUiAmountMintInstruction::Initialize");
+            let InitializeInstructionData {
+                authority,
+                multiplier,
+            } = decode_instruction_data(input)?;
+            process_initialize(program_id, accounts, authority, multiplier)
+        }
+        ScaledUiAmountMintInstruction::UpdateMultiplier => {
+            msg!("ScaledUiAmountMintInstruction::UpdateMultiplier");
+            let UpdateMultiplierInstructionData {
+                effective_timestamp,
+                multiplier,
+            } = decode_instruction_data(input)?;
+            process_update_multiplier(program_id, accounts, multiplier, effective_timestamp)
+        }
+    }
+}
@@ -144,6 +144,49 @@ pub enum TransferFeeInstruction {
         /// Maximum fee assessed on transfers
         maximum_fee: u64,
     },
+    /// Set a transfer fee override on a token account, exempting it from the mint transfer fee or
+    /// charging it a lower fee. The override applies to transfers from and to the account, and
+    /// takes effect immediately. If both accounts of a transfer have an override, the lowest fee
+    /// applies. Overrides can never raise the fee above the mint transfer fee.
+    ///
+    /// The token account must have enough space for the `TransferFeeOverride` extension, which
+    /// can be allocated by the account owner with `Reallocate`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The mint. Must include the `TransferFeeConfig` extension.
+    ///   2. `[signer]` The mint's `transfer_fee_config_authority`.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The mint.
+    ///   2. `[]` The mint's multisignature `transfer_fee_config_authority`.
+    ///   3. ..3+M `[signer]` M signer accounts.
+    SetTransferFeeOverride {
+        /// Amount of transfer collected as fees, expressed as basis points of the
+        /// transfer amount
+        transfer_fee_basis_points: u16,
+        /// Maximum fee assessed on transfers
+        maximum_fee: u64,
+    },
+    /// Disable the transfer fee override of a token account, so that the mint transfer fee
+    /// applies again.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The token account. Must include the `TransferFeeOverride` extension.
+    ///   1. `[]` The mint. Must include the `TransferFeeConfig` extension.
+    ///   2. `[signer]` The mint's `transfer_fee_config_authority`.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The mint.
+    ///   2. `[]` The mint's multisignature `transfer_fee_config_authority`.
+    ///   3. ..3+M `[signer]` M signer accounts.
+    DisableTransferFeeOverride,
 }
 impl TransferFeeInstruction {
     /// Unpacks a byte buffer into a TransferFeeInstruction
@@ -193,6 +236,16 @@ impl TransferFeeInstruction {
                 };
                 (instruction, rest)
             }
+            6 => {
+                let (transfer_fee_basis_points, rest) = TokenInstruction::unpack_u16(rest)?;
+                let (maximum_fee, rest) = TokenInstruction::unpack_u64(rest)?;
+                let instruction = Self::SetTransferFeeOverride {
+                    transfer_fee_basis_points,
+                    maximum_fee,
+                };
+                (instruction, rest)
+            }
+            7 => (Self::DisableTransferFeeOverride, rest),
             _ => return Err(TokenError::InvalidInstruction.into()),
         })
     }
@@ -240,6 +293,17 @@ impl TransferFeeInstruction {
                 buffer.extend_from_slice(&transfer_fee_basis_points.to_le_bytes());
                 buffer.extend_from_slice(&maximum_fee.to_le_bytes());
             }
+            Self::SetTransferFeeOverride {
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => {
+                buffer.push(6);
+                buffer.extend_from_slice(&transfer_fee_basis_points.to_le_bytes());
+                buffer.extend_from_slice(&maximum_fee.to_le_bytes());
+            }
+            Self::DisableTransferFeeOverride => {
+                buffer.push(7);
+            }
         }
     }
 }
@@ -421,6 +485,66 @@ pub fn set_transfer_fee(
     })
 }
 
+/// Creates a `SetTransferFeeOverride` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn set_transfer_fee_override(
+    token_program_id: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(*account, false));
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::TransferFeeExtension(
+            TransferFeeInstruction::SetTransferFeeOverride {
+                transfer_fee_basis_points,
+                maximum_fee,
+            },
+        )
+        .pack(),
+    })
+}
+
+/// Creates a `DisableTransferFeeOverride` instruction
+pub fn disable_transfer_fee_override(
+    token_program_id: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(*account, false));
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::TransferFeeExtension(
+            TransferFeeInstruction::DisableTransferFeeOverride,
+        )
+        .pack(),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -503,5 +627,28 @@ mod test {
         assert_eq!(packed, expect);
         let unpacked = TokenInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
+
+        let check = TokenInstruction::TransferFeeExtension(
+            TransferFeeInstruction::SetTransferFeeOverride {
+                transfer_fee_basis_points: 0,
+                maximum_fee: u64::MAX,
+            },
+        );
+        let packed = check.pack();
+        let mut expect = vec![TRANSFER_FEE_PREFIX, 6];
+        expect.extend_from_slice(&0u16.to_le_bytes());
+        expect.extend_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = TokenInstruction::TransferFeeExtension(
+            TransferFeeInstruction::DisableTransferFeeOverride,
+        );
+        let packed = check.pack();
+        let expect = [TRANSFER_FEE_PREFIX, 7];
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
     }
 }
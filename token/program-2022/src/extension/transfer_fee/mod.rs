@@ -1,13 +1,14 @@
 use {
     crate::{
         error::TokenError,
-        extension::{Extension, ExtensionType},
+        extension::{BaseStateWithExtensions, Extension, ExtensionType, StateWithExtensions},
+        state::Account,
     },
     bytemuck::{Pod, Zeroable},
-    solana_program::{clock::Epoch, entrypoint::ProgramResult},
+    solana_program::{account_info::AccountInfo, clock::Epoch, entrypoint::ProgramResult},
     spl_pod::{
         optional_keys::OptionalNonZeroPubkey,
-        primitives::{PodU16, PodU64},
+        primitives::{PodBool, PodU16, PodU64},
     },
     std::{
         cmp,
@@ -143,6 +144,23 @@ impl TransferFeeConfig {
     pub fn calculate_epoch_fee(&self, epoch: Epoch, pre_fee_amount: u64) -> Option<u64> {
         self.get_epoch_fee(epoch).calculate_fee(pre_fee_amount)
     }
+    /// Calculate the fee for the given epoch and input amount, taking into account the fee
+    /// overrides of the accounts involved in the transfer.
+    ///
+    /// Overrides take effect immediately, unlike new mint fees, so they can only lower the fee:
+    /// the lowest of the mint fee and the overridden fees is charged.
+    pub fn calculate_epoch_fee_with_overrides<'a>(
+        &self,
+        epoch: Epoch,
+        pre_fee_amount: u64,
+        overrides: impl IntoIterator<Item = &'a TransferFeeOverride>,
+    ) -> Option<u64> {
+        let mut fee = self.calculate_epoch_fee(epoch, pre_fee_amount)?;
+        for transfer_fee in overrides.into_iter().filter_map(|o| o.get_transfer_fee()) {
+            fee = cmp::min(fee, transfer_fee.calculate_fee(pre_fee_amount)?);
+        }
+        Some(fee)
+    }
     /// Calculate the fee for the given epoch and output amount
     pub fn calculate_inverse_epoch_fee(&self, epoch: Epoch, post_fee_amount: u64) -> Option<u64> {
         self.get_epoch_fee(epoch)
@@ -176,6 +194,48 @@ impl Extension for TransferFeeAmount {
     const TYPE: ExtensionType = ExtensionType::TransferFeeAmount;
 }
 
+/// Transfer fee override extension data for accounts.
+///
+/// Set by the mint's `transfer_fee_config_authority` to exempt an account from transfer fees, or
+/// to charge it a lower fee than the mint, e.g. for exchange, pool or treasury accounts.
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferFeeOverride {
+    /// Whether the override is in effect
+    pub enabled: PodBool,
+    /// Maximum fee assessed on transfers involving the account
+    pub maximum_fee: PodU64,
+    /// Amount of transfer collected as fees, expressed as basis points of the
+    /// transfer amount
+    pub transfer_fee_basis_points: PodU16,
+}
+impl TransferFeeOverride {
+    /// Get the overridden transfer fee, if the override is in effect
+    pub fn get_transfer_fee(&self) -> Option<TransferFee> {
+        if bool::from(self.enabled) {
+            Some(TransferFee {
+                epoch: PodU64::default(),
+                maximum_fee: self.maximum_fee,
+                transfer_fee_basis_points: self.transfer_fee_basis_points,
+            })
+        } else {
+            None
+        }
+    }
+}
+impl Extension for TransferFeeOverride {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeOverride;
+}
+
+/// Get the transfer fee override of a token account, if it can be read and has one
+pub fn get_transfer_fee_override(account_info: &AccountInfo) -> Option<TransferFeeOverride> {
+    let account_data = account_info.try_borrow_data().ok()?;
+    let account = StateWithExtensions::<Account>::unpack(&account_data).ok()?;
+    account.get_extension::<TransferFeeOverride>().ok().copied()
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use {super::*, proptest::prelude::*, solana_program::pubkey::Pubkey, std::convert::TryFrom};
@@ -238,6 +298,63 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    fn epoch_fee_with_overrides() {
+        let transfer_fee_config = test_transfer_fee_config();
+        let amount = 100_000;
+        let mint_fee = transfer_fee_config
+            .calculate_epoch_fee(NEWER_EPOCH, amount)
+            .unwrap();
+
+        // no override or a disabled override uses the mint fee
+        let disabled = TransferFeeOverride {
+            enabled: false.into(),
+            maximum_fee: PodU64::from(0),
+            transfer_fee_basis_points: PodU16::from(0),
+        };
+        assert_eq!(
+            transfer_fee_config.calculate_epoch_fee_with_overrides(NEWER_EPOCH, amount, None),
+            Some(mint_fee)
+        );
+        assert_eq!(
+            transfer_fee_config.calculate_epoch_fee_with_overrides(
+                NEWER_EPOCH,
+                amount,
+                [&disabled]
+            ),
+            Some(mint_fee)
+        );
+
+        // an override cannot raise the fee above the mint fee
+        let higher = TransferFeeOverride {
+            enabled: true.into(),
+            maximum_fee: PodU64::from(u64::MAX),
+            transfer_fee_basis_points: PodU16::from(500),
+        };
+        assert_eq!(
+            transfer_fee_config.calculate_epoch_fee_with_overrides(
+                NEWER_EPOCH,
+                amount,
+                [&higher, &disabled]
+            ),
+            Some(mint_fee)
+        );
+
+        // the lowest fee wins, e.g. for an exemption
+        let exempt = TransferFeeOverride {
+            enabled: true.into(),
+            ..disabled
+        };
+        assert_eq!(
+            transfer_fee_config.calculate_epoch_fee_with_overrides(
+                NEWER_EPOCH,
+                amount,
+                [&higher, &exempt]
+            ),
+            Some(0)
+        );
+    }
+
     #[test]
     fn calculate_fee_max() {
         let one = u64::try_from(ONE_IN_BASIS_POINTS).unwrap();
@@ -5,7 +5,7 @@ use {
         extension::{
             transfer_fee::{
                 instruction::TransferFeeInstruction, TransferFee, TransferFeeAmount,
-                TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                TransferFeeConfig, TransferFeeOverride, MAX_FEE_BASIS_POINTS,
             },
             BaseStateWithExtensions, StateWithExtensions, StateWithExtensionsMut,
         },
@@ -109,6 +109,60 @@ fn process_set_transfer_fee(
     Ok(())
 }
 
+/// Set or disable the transfer fee override of a token account, initializing the extension if
+/// not already present.
+fn process_set_transfer_fee_override(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    transfer_fee_override: Option<(u16, u64)>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    let mint_data = mint_account_info.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let extension = mint.get_extension::<TransferFeeConfig>()?;
+
+    let transfer_fee_config_authority =
+        Option::<Pubkey>::from(extension.transfer_fee_config_authority)
+            .ok_or(TokenError::NoAuthorityExists)?;
+    Processor::validate_owner(
+        program_id,
+        &transfer_fee_config_authority,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let mut account_data = token_account_info.data.borrow_mut();
+    let mut account = StateWithExtensionsMut::<Account>::unpack(&mut account_data)?;
+    if account.base.mint != *mint_account_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    if let Some((transfer_fee_basis_points, maximum_fee)) = transfer_fee_override {
+        if transfer_fee_basis_points > MAX_FEE_BASIS_POINTS {
+            return Err(TokenError::TransferFeeExceedsMaximum.into());
+        }
+        let extension = if let Ok(extension) = account.get_extension_mut::<TransferFeeOverride>() {
+            extension
+        } else {
+            account.init_extension::<TransferFeeOverride>(true)?
+        };
+        extension.enabled = true.into();
+        extension.transfer_fee_basis_points = transfer_fee_basis_points.into();
+        extension.maximum_fee = maximum_fee.into();
+    } else {
+        let extension = account.get_extension_mut::<TransferFeeOverride>()?;
+        extension.enabled = false.into();
+    }
+
+    Ok(())
+}
+
 fn process_withdraw_withheld_tokens_from_mint(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -323,5 +377,20 @@ pub(crate) fn process_instruction(
             msg!("TransferFeeInstruction: SetTransferFee");
             process_set_transfer_fee(program_id, accounts, transfer_fee_basis_points, maximum_fee)
         }
+        TransferFeeInstruction::SetTransferFeeOverride {
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => {
+            msg!("TransferFeeInstruction: SetTransferFeeOverride");
+            process_set_transfer_fee_override(
+                program_id,
+                accounts,
+                Some((transfer_fee_basis_points, maximum_fee)),
+            )
+        }
+        TransferFeeInstruction::DisableTransferFeeOverride => {
+            msg!("TransferFeeInstruction: DisableTransferFeeOverride");
+            process_set_transfer_fee_override(program_id, accounts, None)
+        }
     }
 }
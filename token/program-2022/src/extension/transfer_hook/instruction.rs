@@ -10,7 +10,7 @@ use {
         program_error::ProgramError,
         pubkey::Pubkey,
     },
-    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_pod::{optional_keys::OptionalNonZeroPubkey, primitives::PodBool},
     std::convert::TryInto,
 };
 
@@ -58,6 +58,38 @@ pub enum TransferHookInstruction {
     ///   `crate::extension::transfer_hook::UpdateInstructionData`
     ///
     Update,
+    /// Opt a new mint into calling its transfer hook program on mint and
+    /// burn. The mint must also include the `TransferHook` extension.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::transfer_hook::instruction::MintBurnHooksInstructionData`
+    ///
+    InitializeMintBurnHooks,
+    /// Update which of mint and burn invoke the transfer hook program. Only
+    /// supported for mints that include the `TransferHookMintBurn` extension.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The transfer hook authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's transfer hook authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::transfer_hook::instruction::MintBurnHooksInstructionData`
+    ///
+    UpdateMintBurnHooks,
 }
 
 /// Data expected by `Initialize`
@@ -82,6 +114,18 @@ pub struct UpdateInstructionData {
     pub program_id: OptionalNonZeroPubkey,
 }
 
+/// Data expected by `InitializeMintBurnHooks` and `UpdateMintBurnHooks`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct MintBurnHooksInstructionData {
+    /// Whether `MintTo` should invoke the transfer hook program
+    pub invoke_on_mint: PodBool,
+    /// Whether `Burn` should invoke the transfer hook program
+    pub invoke_on_burn: PodBool,
+}
+
 /// Create an `Initialize` instruction
 pub fn initialize(
     token_program_id: &Pubkey,
@@ -129,3 +173,53 @@ pub fn update(
         },
     ))
 }
+
+/// Create an `InitializeMintBurnHooks` instruction
+pub fn initialize_mint_burn_hooks(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    invoke_on_mint: bool,
+    invoke_on_burn: bool,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TransferHookExtension,
+        TransferHookInstruction::InitializeMintBurnHooks,
+        &MintBurnHooksInstructionData {
+            invoke_on_mint: invoke_on_mint.into(),
+            invoke_on_burn: invoke_on_burn.into(),
+        },
+    ))
+}
+
+/// Create an `UpdateMintBurnHooks` instruction
+pub fn update_mint_burn_hooks(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+    invoke_on_mint: bool,
+    invoke_on_burn: bool,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TransferHookExtension,
+        TransferHookInstruction::UpdateMintBurnHooks,
+        &MintBurnHooksInstructionData {
+            invoke_on_mint: invoke_on_mint.into(),
+            invoke_on_burn: invoke_on_burn.into(),
+        },
+    ))
+}
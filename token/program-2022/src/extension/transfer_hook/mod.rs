@@ -3,7 +3,7 @@ use {
         extension::{
             BaseState, BaseStateWithExtensions, Extension, ExtensionType, StateWithExtensionsMut,
         },
-        state::Account,
+        state::{Account, Mint},
     },
     bytemuck::{Pod, Zeroable},
     solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
@@ -40,6 +40,22 @@ pub struct TransferHookAccount {
     pub transferring: PodBool,
 }
 
+/// Opt-in flags for calling the transfer hook program on mint and burn
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferHookMintBurn {
+    /// If true, `MintTo` invokes `MintExecute` on the transfer hook program
+    pub invoke_on_mint: PodBool,
+    /// If true, `Burn` invokes `BurnExecute` on the transfer hook program
+    pub invoke_on_burn: PodBool,
+    /// Flag to indicate that the mint is in the middle of a mint
+    pub minting: PodBool,
+    /// Flag to indicate that the mint is in the middle of a burn
+    pub burning: PodBool,
+}
+
 impl Extension for TransferHook {
     const TYPE: ExtensionType = ExtensionType::TransferHook;
 }
@@ -59,6 +75,34 @@ pub fn get_program_id<S: BaseState, BSE: BaseStateWithExtensions<S>>(
         .and_then(|e| Option::<Pubkey>::from(e.program_id))
 }
 
+impl Extension for TransferHookMintBurn {
+    const TYPE: ExtensionType = ExtensionType::TransferHookMintBurn;
+}
+
+/// Attempts to get the transfer hook program id to invoke on mint, returning
+/// None if the mint has not opted into mint hooks
+pub fn get_mint_hook_program_id<S: BaseState, BSE: BaseStateWithExtensions<S>>(
+    state: &BSE,
+) -> Option<Pubkey> {
+    state
+        .get_extension::<TransferHookMintBurn>()
+        .ok()
+        .filter(|e| bool::from(e.invoke_on_mint))
+        .and_then(|_| get_program_id(state))
+}
+
+/// Attempts to get the transfer hook program id to invoke on burn, returning
+/// None if the mint has not opted into burn hooks
+pub fn get_burn_hook_program_id<S: BaseState, BSE: BaseStateWithExtensions<S>>(
+    state: &BSE,
+) -> Option<Pubkey> {
+    state
+        .get_extension::<TransferHookMintBurn>()
+        .ok()
+        .filter(|e| bool::from(e.invoke_on_burn))
+        .and_then(|_| get_program_id(state))
+}
+
 /// Helper function to set the transferring flag before calling into transfer hook
 pub fn set_transferring(account: &mut StateWithExtensionsMut<Account>) -> Result<(), ProgramError> {
     let account_extension = account.get_extension_mut::<TransferHookAccount>()?;
@@ -74,3 +118,35 @@ pub fn unset_transferring(account_info: &AccountInfo) -> Result<(), ProgramError
     account_extension.transferring = false.into();
     Ok(())
 }
+
+/// Helper function to set the minting flag before calling into the mint hook
+pub fn set_minting(mint: &mut StateWithExtensionsMut<Mint>) -> Result<(), ProgramError> {
+    let mint_extension = mint.get_extension_mut::<TransferHookMintBurn>()?;
+    mint_extension.minting = true.into();
+    Ok(())
+}
+
+/// Helper function to unset the minting flag after a mint
+pub fn unset_minting(mint_info: &AccountInfo) -> Result<(), ProgramError> {
+    let mut mint_data = mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let mint_extension = mint.get_extension_mut::<TransferHookMintBurn>()?;
+    mint_extension.minting = false.into();
+    Ok(())
+}
+
+/// Helper function to set the burning flag before calling into the burn hook
+pub fn set_burning(mint: &mut StateWithExtensionsMut<Mint>) -> Result<(), ProgramError> {
+    let mint_extension = mint.get_extension_mut::<TransferHookMintBurn>()?;
+    mint_extension.burning = true.into();
+    Ok(())
+}
+
+/// Helper function to unset the burning flag after a burn
+pub fn unset_burning(mint_info: &AccountInfo) -> Result<(), ProgramError> {
+    let mut mint_data = mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let mint_extension = mint.get_extension_mut::<TransferHookMintBurn>()?;
+    mint_extension.burning = false.into();
+    Ok(())
+}
@@ -5,11 +5,12 @@ use {
         extension::{
             transfer_hook::{
                 instruction::{
-                    InitializeInstructionData, TransferHookInstruction, UpdateInstructionData,
+                    InitializeInstructionData, MintBurnHooksInstructionData,
+                    TransferHookInstruction, UpdateInstructionData,
                 },
-                TransferHook,
+                TransferHook, TransferHookMintBurn,
             },
-            StateWithExtensionsMut,
+            BaseStateWithExtensions, StateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
         processor::Processor,
@@ -85,6 +86,50 @@ fn process_update(
     Ok(())
 }
 
+fn process_initialize_mint_burn_hooks(
+    accounts: &[AccountInfo],
+    data: &MintBurnHooksInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data)?;
+
+    let extension = mint.init_extension::<TransferHookMintBurn>(true)?;
+    extension.invoke_on_mint = data.invoke_on_mint;
+    extension.invoke_on_burn = data.invoke_on_burn;
+    Ok(())
+}
+
+fn process_update_mint_burn_hooks(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &MintBurnHooksInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let authority = Option::<Pubkey>::from(mint.get_extension::<TransferHook>()?.authority)
+        .ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = mint.get_extension_mut::<TransferHookMintBurn>()?;
+    extension.invoke_on_mint = data.invoke_on_mint;
+    extension.invoke_on_burn = data.invoke_on_burn;
+    Ok(())
+}
+
 pub(crate) fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -107,5 +152,15 @@ pub(crate) fn process_instruction(
             } = decode_instruction_data(input)?;
             process_update(program_id, accounts, transfer_hook_program_id)
         }
+        TransferHookInstruction::InitializeMintBurnHooks => {
+            msg!("TransferHookInstruction::InitializeMintBurnHooks");
+            let data = decode_instruction_data(input)?;
+            process_initialize_mint_burn_hooks(accounts, data)
+        }
+        TransferHookInstruction::UpdateMintBurnHooks => {
+            msg!("TransferHookInstruction::UpdateMintBurnHooks");
+            let data = decode_instruction_data(input)?;
+            process_update_mint_burn_hooks(program_id, accounts, data)
+        }
     }
 }
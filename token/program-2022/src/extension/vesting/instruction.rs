@@ -0,0 +1,116 @@
+use {
+    crate::{
+        check_program_account,
+        extension::vesting::UnixTimestamp,
+        instruction::{encode_instruction, TokenInstruction},
+    },
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+    spl_pod::primitives::PodU64,
+};
+
+#[cfg(feature = "serde-traits")]
+use serde::{Deserialize, Serialize};
+
+/// Vesting extension instructions
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum VestingInstruction {
+    /// Mints new tokens to an account and locks them under a vesting schedule.
+    /// The tokens are locked until the cliff, then unlock linearly between the
+    /// start and the end of the schedule.
+    ///
+    /// If the account does not have space for the `Vesting` extension yet, it
+    /// is reallocated and the payer funds the additional rent, so the holder
+    /// does not need to call `TokenInstruction::Reallocate` first. Fails if
+    /// the account holds a schedule that has not fully vested yet.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[writable]` The account to mint tokens to.
+    ///   2. `[signer]` The mint's minting authority.
+    ///   3. `[signer, writable]` The payer for the reallocation, if needed.
+    ///   4. `[]` System program.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[writable]` The account to mint tokens to.
+    ///   2. `[]` The mint's multisignature mint-tokens authority.
+    ///   3. `[signer, writable]` The payer for the reallocation, if needed.
+    ///   4. `[]` System program.
+    ///   5. ..5+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::vesting::instruction::MintToInstructionData`
+    ///
+    MintTo,
+}
+
+/// Data expected by `VestingInstruction::MintTo`
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct MintToInstructionData {
+    /// The amount of new tokens to mint and lock
+    pub amount: PodU64,
+    /// Expected number of base 10 digits to the right of the decimal place
+    pub decimals: u8,
+    /// Timestamp from which tokens start vesting linearly
+    pub start_timestamp: UnixTimestamp,
+    /// Timestamp before which no tokens are unlocked
+    pub cliff_timestamp: UnixTimestamp,
+    /// Timestamp at which all tokens are unlocked
+    pub end_timestamp: UnixTimestamp,
+}
+
+/// Create a `MintTo` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn mint_to(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    account: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+    amount: u64,
+    decimals: u8,
+    start_timestamp: i64,
+    cliff_timestamp: i64,
+    end_timestamp: i64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*mint_authority, signers.is_empty()),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::VestingExtension,
+        VestingInstruction::MintTo,
+        &MintToInstructionData {
+            amount: amount.into(),
+            decimals,
+            start_timestamp: start_timestamp.into(),
+            cliff_timestamp: cliff_timestamp.into(),
+            end_timestamp: end_timestamp.into(),
+        },
+    ))
+}
@@ -0,0 +1,143 @@
+use {
+    crate::{
+        error::TokenError,
+        extension::{BaseStateWithExtensions, Extension, ExtensionType},
+        state::Account,
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program::{clock::Clock, program_error::ProgramError, sysvar::Sysvar},
+    spl_pod::primitives::{PodI64, PodU64},
+};
+
+#[cfg(feature = "serde-traits")]
+use serde::{Deserialize, Serialize};
+
+/// Vesting extension instructions
+pub mod instruction;
+
+/// Vesting extension processor
+pub mod processor;
+
+/// UnixTimestamp expressed with an alignment-independent type
+pub type UnixTimestamp = PodI64;
+
+/// Vesting extension data for accounts
+///
+/// `total_amount` tokens are locked until `cliff_timestamp`, then unlock
+/// linearly between `start_timestamp` and `end_timestamp`. Tokens that are
+/// still locked cannot be transferred, burned, approved or deposited by the
+/// owner or a delegate, but remain available to the mint's permanent delegate.
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct VestingSchedule {
+    /// Amount of tokens subject to the schedule
+    pub total_amount: PodU64,
+    /// Timestamp from which tokens start vesting linearly
+    pub start_timestamp: UnixTimestamp,
+    /// Timestamp before which no tokens are unlocked
+    pub cliff_timestamp: UnixTimestamp,
+    /// Timestamp at which all tokens are unlocked
+    pub end_timestamp: UnixTimestamp,
+}
+impl VestingSchedule {
+    /// Get the amount of tokens that are still locked at the given timestamp
+    pub fn locked_amount(&self, unix_timestamp: i64) -> Option<u64> {
+        let total_amount = u64::from(self.total_amount);
+        let start_timestamp = i64::from(self.start_timestamp);
+        let end_timestamp = i64::from(self.end_timestamp);
+        if unix_timestamp < i64::from(self.cliff_timestamp) {
+            Some(total_amount)
+        } else if unix_timestamp >= end_timestamp {
+            Some(0)
+        } else {
+            let elapsed = u128::try_from(unix_timestamp.checked_sub(start_timestamp)?).ok()?;
+            let duration = u128::try_from(end_timestamp.checked_sub(start_timestamp)?).ok()?;
+            let unlocked = u128::from(total_amount)
+                .checked_mul(elapsed)?
+                .checked_div(duration)?;
+            u64::try_from(unlocked)
+                .ok()
+                .and_then(|unlocked| total_amount.checked_sub(unlocked))
+        }
+    }
+
+    /// Check whether all tokens under the schedule are unlocked at the given
+    /// timestamp
+    pub fn is_fully_vested(&self, unix_timestamp: i64) -> bool {
+        self.locked_amount(unix_timestamp) == Some(0)
+    }
+}
+impl Extension for VestingSchedule {
+    const TYPE: ExtensionType = ExtensionType::Vesting;
+}
+
+/// Fails if moving `amount` tokens out of the account would dip into its
+/// locked balance
+pub fn check_unlocked_amount<BSE: BaseStateWithExtensions<Account>>(
+    account: &BSE,
+    balance: u64,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    if let Ok(schedule) = account.get_extension::<VestingSchedule>() {
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let locked_amount = schedule
+            .locked_amount(unix_timestamp)
+            .ok_or(TokenError::Overflow)?;
+        if balance.saturating_sub(locked_amount) < amount {
+            return Err(TokenError::TokensLocked.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_schedule() -> VestingSchedule {
+        VestingSchedule {
+            total_amount: 1_000.into(),
+            start_timestamp: 100.into(),
+            cliff_timestamp: 150.into(),
+            end_timestamp: 200.into(),
+        }
+    }
+
+    #[test]
+    fn locked_amount() {
+        let schedule = test_schedule();
+        // before the start and the cliff, everything is locked
+        assert_eq!(schedule.locked_amount(0), Some(1_000));
+        assert_eq!(schedule.locked_amount(100), Some(1_000));
+        assert_eq!(schedule.locked_amount(149), Some(1_000));
+        // at the cliff, the linear portion since the start unlocks at once
+        assert_eq!(schedule.locked_amount(150), Some(500));
+        assert_eq!(schedule.locked_amount(175), Some(250));
+        // rounding keeps tokens locked
+        assert_eq!(schedule.locked_amount(199), Some(10));
+        // after the end, everything is unlocked
+        assert_eq!(schedule.locked_amount(200), Some(0));
+        assert_eq!(schedule.locked_amount(i64::MAX), Some(0));
+        assert!(!schedule.is_fully_vested(199));
+        assert!(schedule.is_fully_vested(200));
+    }
+
+    #[test]
+    fn locked_amount_without_cliff() {
+        let schedule = VestingSchedule {
+            cliff_timestamp: 100.into(),
+            ..test_schedule()
+        };
+        assert_eq!(schedule.locked_amount(99), Some(1_000));
+        assert_eq!(schedule.locked_amount(100), Some(1_000));
+        assert_eq!(schedule.locked_amount(101), Some(990));
+
+        let schedule = VestingSchedule {
+            total_amount: u64::MAX.into(),
+            ..schedule
+        };
+        assert_eq!(schedule.locked_amount(150), Some(u64::MAX - u64::MAX / 2));
+    }
+}
@@ -0,0 +1,115 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            reallocate::reallocate_account,
+            vesting::{
+                instruction::{MintToInstructionData, VestingInstruction},
+                VestingSchedule,
+            },
+            BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        processor::Processor,
+        state::Account,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+/// Mint tokens to an account and lock them under a vesting schedule
+fn process_mint_to(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &MintToInstructionData,
+) -> ProgramResult {
+    let amount = u64::from(data.amount);
+    let start_timestamp = i64::from(data.start_timestamp);
+    let cliff_timestamp = i64::from(data.cliff_timestamp);
+    let end_timestamp = i64::from(data.end_timestamp);
+    let unix_timestamp = Clock::get()?.unix_timestamp;
+    if start_timestamp > cliff_timestamp
+        || cliff_timestamp > end_timestamp
+        || start_timestamp >= end_timestamp
+        || end_timestamp <= unix_timestamp
+    {
+        msg!("Vesting schedule must end in the future, with start <= cliff <= end");
+        return Err(TokenError::InvalidVestingSchedule.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let destination_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // the payer and system program sit between the authority and its signers
+    let mut mint_to_accounts = vec![
+        mint_info.clone(),
+        destination_account_info.clone(),
+        authority_info.clone(),
+    ];
+    mint_to_accounts.extend_from_slice(account_info_iter.as_slice());
+
+    // handles all mint authority and account checks
+    Processor::process_mint_to(program_id, &mint_to_accounts, amount, Some(data.decimals))?;
+
+    let needed_account_len = {
+        let destination_account_data = destination_account_info.data.borrow();
+        let destination_account =
+            StateWithExtensions::<Account>::unpack(&destination_account_data)?;
+        let mut extension_types = destination_account.get_extension_types()?;
+        extension_types.push(ExtensionType::Vesting);
+        ExtensionType::try_calculate_account_len::<Account>(&extension_types)?
+    };
+    if destination_account_info.data_len() < needed_account_len {
+        // the minted account is never native, so it holds no lamports as tokens
+        reallocate_account(
+            destination_account_info,
+            payer_info,
+            system_program_info,
+            needed_account_len,
+            None,
+        )?;
+    }
+
+    let mut destination_account_data = destination_account_info.data.borrow_mut();
+    let mut destination_account =
+        StateWithExtensionsMut::<Account>::unpack(&mut destination_account_data)?;
+    if let Ok(schedule) = destination_account.get_extension::<VestingSchedule>() {
+        if !schedule.is_fully_vested(unix_timestamp) {
+            msg!("Account already has an active vesting schedule");
+            return Err(TokenError::InvalidVestingSchedule.into());
+        }
+    }
+    let schedule = destination_account.init_extension::<VestingSchedule>(true)?;
+    schedule.total_amount = amount.into();
+    schedule.start_timestamp = start_timestamp.into();
+    schedule.cliff_timestamp = cliff_timestamp.into();
+    schedule.end_timestamp = end_timestamp.into();
+    Ok(())
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match decode_instruction_type(input)? {
+        VestingInstruction::MintTo => {
+            msg!("VestingInstruction::MintTo");
+            let data = decode_instruction_data::<MintToInstructionData>(input)?;
+            process_mint_to(program_id, accounts, data)
+        }
+    }
+}
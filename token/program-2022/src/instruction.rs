@@ -10,6 +10,7 @@ use {
     },
     bytemuck::Pod,
     solana_program::{
+        hash::{Hash, Hasher, HASH_BYTES},
         instruction::{AccountMeta, Instruction},
         program_error::ProgramError,
         program_option::COption,
@@ -34,6 +35,8 @@ use {
 pub const MIN_SIGNERS: usize = 1;
 /// Maximum number of multisignature signers (max N)
 pub const MAX_SIGNERS: usize = 11;
+/// Maximum number of weighted multisignature signers
+pub const MAX_WEIGHTED_SIGNERS: usize = 32;
 /// Serialized length of a u16, for unpacking
 const U16_BYTES: usize = 2;
 /// Serialized length of a u64, for unpacking
@@ -676,7 +679,221 @@ pub enum TokenInstruction<'a> {
     /// for further details about the extended instructions that share this instruction
     /// prefix
     GroupPointerExtension,
+    /// The common instruction prefix for pausable extension instructions.
+    ///
+    /// See `extension::pausable::instruction::PausableInstruction`
+    /// for further details about the extended instructions that share this instruction
+    /// prefix
+    PausableExtension,
+    /// The common instruction prefix for scaled UI amount extension instructions.
+    ///
+    /// See `extension::scaled_ui_amount::instruction::ScaledUiAmountMintInstruction`
+    /// for further details about the extended instructions that share this instruction
+    /// prefix
+    ScaledUiAmountExtension,
+    /// The common instruction prefix for confidential mint-burn extension instructions.
+    ///
+    /// See `extension::confidential_mint_burn::instruction::ConfidentialMintBurnInstruction`
+    /// for further details about the extended instructions that share this instruction
+    /// prefix
+    ConfidentialMintBurnExtension,
+    /// Remove the given extensions from a token account, shrink the account to
+    /// fit the remaining extensions, and move the lamports that are no longer
+    /// needed for rent exemption to the destination.
+    ///
+    /// Only extensions that are currently disabled, such as `MemoTransfer`
+    /// without required memos or an unlocked `CpiGuard`, may be removed.
+    /// Extensions required by the mint or that protect the account, such as
+    /// `ImmutableOwner`, `TransferHookAccount` or `NonTransferableAccount`,
+    /// can never be removed. Any space not used by the remaining extensions is
+    /// released as well, so an empty list simply shrinks the account to fit.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[writable]` The account to shrink.
+    ///   1. `[writable]` The destination account for the excess lamports.
+    ///   2. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature owner
+    ///   0. `[writable]` The account to shrink.
+    ///   1. `[writable]` The destination account for the excess lamports.
+    ///   2. `[]` The account's multisignature owner.
+    ///   3. ..3+M `[signer]` M signer accounts.
+    ///
+    RemoveExtensions {
+        /// Extension types to remove from the account
+        extension_types: Vec<ExtensionType>,
+    },
+    /// The common instruction prefix for Vesting extension instructions.
+    ///
+    /// See `extension::vesting::instruction::VestingInstruction` for further
+    /// details about the extended instructions that share this instruction prefix
+    VestingExtension,
+    /// Executes a sequence of token instructions in order, in a single
+    /// invocation of the program. Useful for programs that would otherwise
+    /// CPI into the token program many times.
+    ///
+    /// Each batched instruction takes the next `num_accounts` accounts from
+    /// the account list. Batches cannot be nested, and the whole batch fails
+    /// if any batched instruction fails.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. ..N The accounts of every batched instruction, in order.
+    ///
+    Batch {
+        /// The batched instructions, in execution order
+        instructions: Vec<BatchedInstruction<'a>>,
+    },
+    /// Initializes a weighted multisignature account with up to
+    /// `MAX_WEIGHTED_SIGNERS` signers, each with its own weight. The
+    /// multisig may be used anywhere a `Multisig` may be used, and is
+    /// satisfied once the weights of the provided signers add up to the
+    /// threshold.
+    ///
+    /// If `min_delay` is non-zero, the multisig cannot directly authorize
+    /// `MintTo`, `MintToChecked` or `SetAuthority`. Those instructions must
+    /// instead go through `CreateMultisigProposal`, `ApproveMultisigProposal`
+    /// and `ExecuteMultisigProposal`, and may only execute `min_delay`
+    /// seconds after the proposal was approved.
+    ///
+    /// The `InitializeWeightedMultisig` instruction requires no signers and
+    /// MUST be included within the same Transaction as the system program's
+    /// `CreateAccount` instruction that creates the account being initialized.
+    /// Otherwise another party can acquire ownership of the uninitialized
+    /// account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The multisignature account to initialize.
+    ///   1. ..1+N `[]` The signer accounts, must equal to N where 1 <= N <=
+    ///      32.
+    ///
+    InitializeWeightedMultisig {
+        /// The total weight of signatures required.
+        threshold: u16,
+        /// The minimum number of seconds between the approval and the
+        /// execution of a proposal. Zero disables proposals for minting and
+        /// authority changes.
+        min_delay: i64,
+        /// The weight of each signer, in the order of the signer accounts.
+        weights: Vec<u8>,
+    },
+    /// Proposes an instruction to be authorized by a weighted multisig.
+    /// The proposer's approval is counted immediately.
+    ///
+    /// The proposal account must be allocated with `MultisigProposal::LEN`
+    /// bytes, be rent-exempt and be owned by the token program. As with
+    /// other initialization instructions, it MUST be included within the same
+    /// Transaction as the system program's `CreateAccount` instruction.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The proposal account to initialize.
+    ///   1. `[]` The weighted multisig.
+    ///   2. `[signer]` The proposer, one of the multisig signers.
+    ///
+    CreateMultisigProposal {
+        /// Hash of the proposed instruction, see `hash_proposed_instruction`
+        #[cfg_attr(feature = "serde-traits", serde(with = "As::<DisplayFromStr>"))]
+        instruction_hash: Hash,
+    },
+    /// Approves a pending multisig proposal. Once the approvals reach the
+    /// multisig's threshold, the proposal's delay starts.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The proposal account.
+    ///   1. `[]` The weighted multisig.
+    ///   2. ..2+M `[signer]` M multisig signers approving the proposal.
+    ///
+    ApproveMultisigProposal,
+    /// Executes an approved multisig proposal, once its delay has elapsed.
+    /// A proposal can only be executed once.
+    ///
+    /// The proposed instruction must use the multisig as its authority and
+    /// the proposal as the only multisig signer, and must not be another
+    /// `ExecuteMultisigProposal`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The proposal account.
+    ///   1. `[]` The weighted multisig.
+    ///   2. ..2+N The accounts of the proposed instruction.
+    ///
+    ExecuteMultisigProposal {
+        /// The proposed instruction data
+        instruction_data: &'a [u8],
+    },
+    /// Cancels a multisig proposal that is not being executed, closing the
+    /// proposal account and sending its lamports to the destination. Executed
+    /// proposals may also be closed this way to reclaim their rent.
+    ///
+    /// Cancelling requires the multisig's threshold, but is never delayed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The proposal account to close.
+    ///   1. `[]` The weighted multisig.
+    ///   2. `[writable]` The destination account.
+    ///   3. ..3+M `[signer]` M multisig signers.
+    ///
+    CancelMultisigProposal,
+}
+
+/// A single instruction within a `Batch`
+#[repr(C)]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-traits", serde(rename_all = "camelCase"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchedInstruction<'a> {
+    /// Number of accounts used by the instruction
+    num_accounts: u8,
+    /// Encoded instruction, at most 255 bytes long
+    #[cfg_attr(
+        feature = "serde-traits",
+        serde(borrow, deserialize_with = "batched_instruction_data")
+    )]
+    data: &'a [u8],
 }
+
+impl<'a> BatchedInstruction<'a> {
+    /// Creates a batched instruction, failing if the data is too long to be
+    /// batched
+    pub fn new(num_accounts: u8, data: &'a [u8]) -> Result<Self, ProgramError> {
+        if data.len() > u8::MAX as usize {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { num_accounts, data })
+    }
+
+    /// Number of accounts used by the instruction
+    pub fn num_accounts(&self) -> u8 {
+        self.num_accounts
+    }
+
+    /// Encoded instruction
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+#[cfg(feature = "serde-traits")]
+fn batched_instruction_data<'de: 'a, 'a, D>(deserializer: D) -> Result<&'a [u8], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let data = <&'a [u8]>::deserialize(deserializer)?;
+    if data.len() > u8::MAX as usize {
+        return Err(serde::de::Error::invalid_length(
+            data.len(),
+            &"at most 255 bytes",
+        ));
+    }
+    Ok(data)
+}
+
 impl<'a> TokenInstruction<'a> {
     /// Unpacks a byte buffer into a [TokenInstruction](enum.TokenInstruction.html).
     pub fn unpack(input: &'a [u8]) -> Result<Self, ProgramError> {
@@ -816,6 +1033,62 @@ impl<'a> TokenInstruction<'a> {
             38 => Self::WithdrawExcessLamports,
             39 => Self::MetadataPointerExtension,
             40 => Self::GroupPointerExtension,
+            41 => Self::PausableExtension,
+            42 => Self::ScaledUiAmountExtension,
+            43 => Self::ConfidentialMintBurnExtension,
+            44 => {
+                let mut extension_types = vec![];
+                for chunk in rest.chunks(size_of::<ExtensionType>()) {
+                    extension_types.push(chunk.try_into()?);
+                }
+                Self::RemoveExtensions { extension_types }
+            }
+            45 => Self::VestingExtension,
+            46 => {
+                let mut instructions = vec![];
+                let mut rest = rest;
+                while !rest.is_empty() {
+                    let (&num_accounts, remaining) =
+                        rest.split_first().ok_or(InvalidInstruction)?;
+                    let (&data_len, remaining) =
+                        remaining.split_first().ok_or(InvalidInstruction)?;
+                    if remaining.len() < data_len as usize {
+                        return Err(InvalidInstruction.into());
+                    }
+                    let (data, remaining) = remaining.split_at(data_len as usize);
+                    instructions.push(BatchedInstruction { num_accounts, data });
+                    rest = remaining;
+                }
+                Self::Batch { instructions }
+            }
+            47 => {
+                let (threshold, rest) = Self::unpack_u16(rest)?;
+                let (min_delay, rest) = Self::unpack_u64(rest)?;
+                Self::InitializeWeightedMultisig {
+                    threshold,
+                    min_delay: min_delay as i64,
+                    weights: rest.to_vec(),
+                }
+            }
+            48 => {
+                let instruction_hash = rest
+                    .get(..HASH_BYTES)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(Hash::new_from_array)
+                    .ok_or(InvalidInstruction)?;
+                Self::CreateMultisigProposal { instruction_hash }
+            }
+            49 => Self::ApproveMultisigProposal,
+            50 => {
+                // proposals cannot be nested
+                if rest.first() == Some(&tag) {
+                    return Err(InvalidInstruction.into());
+                }
+                Self::ExecuteMultisigProposal {
+                    instruction_data: rest,
+                }
+            }
+            51 => Self::CancelMultisigProposal,
             _ => return Err(TokenError::InvalidInstruction.into()),
         })
     }
@@ -984,6 +1257,56 @@ impl<'a> TokenInstruction<'a> {
             &Self::GroupPointerExtension => {
                 buf.push(40);
             }
+            &Self::PausableExtension => {
+                buf.push(41);
+            }
+            &Self::ScaledUiAmountExtension => {
+                buf.push(42);
+            }
+            &Self::ConfidentialMintBurnExtension => {
+                buf.push(43);
+            }
+            Self::RemoveExtensions { extension_types } => {
+                buf.push(44);
+                for extension_type in extension_types {
+                    buf.extend_from_slice(&<[u8; 2]>::from(*extension_type));
+                }
+            }
+            &Self::VestingExtension => {
+                buf.push(45);
+            }
+            Self::Batch { instructions } => {
+                buf.push(46);
+                for instruction in instructions {
+                    buf.push(instruction.num_accounts);
+                    buf.push(instruction.data.len() as u8);
+                    buf.extend_from_slice(instruction.data);
+                }
+            }
+            Self::InitializeWeightedMultisig {
+                threshold,
+                min_delay,
+                weights,
+            } => {
+                buf.push(47);
+                buf.extend_from_slice(&threshold.to_le_bytes());
+                buf.extend_from_slice(&min_delay.to_le_bytes());
+                buf.extend_from_slice(weights);
+            }
+            Self::CreateMultisigProposal { instruction_hash } => {
+                buf.push(48);
+                buf.extend_from_slice(instruction_hash.as_ref());
+            }
+            &Self::ApproveMultisigProposal => {
+                buf.push(49);
+            }
+            Self::ExecuteMultisigProposal { instruction_data } => {
+                buf.push(50);
+                buf.extend_from_slice(instruction_data);
+            }
+            &Self::CancelMultisigProposal => {
+                buf.push(51);
+            }
         };
         buf
     }
@@ -1079,6 +1402,11 @@ pub enum AuthorityType {
     MetadataPointer,
     /// Authority to set the group address
     GroupPointer,
+    /// Authority to pause or resume minting, transferring and burning for a
+    /// mint
+    Pause,
+    /// Authority to set the UI amount multiplier
+    ScaledUiAmount,
 }
 
 impl AuthorityType {
@@ -1098,6 +1426,8 @@ impl AuthorityType {
             AuthorityType::ConfidentialTransferFeeConfig => 11,
             AuthorityType::MetadataPointer => 12,
             AuthorityType::GroupPointer => 13,
+            AuthorityType::Pause => 14,
+            AuthorityType::ScaledUiAmount => 15,
         }
     }
 
@@ -1117,6 +1447,8 @@ impl AuthorityType {
             11 => Ok(AuthorityType::ConfidentialTransferFeeConfig),
             12 => Ok(AuthorityType::MetadataPointer),
             13 => Ok(AuthorityType::GroupPointer),
+            14 => Ok(AuthorityType::Pause),
+            15 => Ok(AuthorityType::ScaledUiAmount),
             _ => Err(TokenError::InvalidInstruction.into()),
         }
     }
@@ -1836,6 +2168,262 @@ pub fn reallocate(
     })
 }
 
+/// Creates a `RemoveExtensions` instruction
+pub fn remove_extensions(
+    token_program_id: &Pubkey,
+    account_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    owner_pubkey: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    extension_types: &[ExtensionType],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+
+    let mut accounts = Vec::with_capacity(3 + signer_pubkeys.len());
+    accounts.push(AccountMeta::new(*account_pubkey, false));
+    accounts.push(AccountMeta::new(*destination_pubkey, false));
+    accounts.push(AccountMeta::new_readonly(
+        *owner_pubkey,
+        signer_pubkeys.is_empty(),
+    ));
+    for signer_pubkey in signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::RemoveExtensions {
+            extension_types: extension_types.to_vec(),
+        }
+        .pack(),
+    })
+}
+
+/// Creates a `Batch` instruction that executes the given token instructions
+/// in order. Each instruction may use at most 255 accounts and 255 bytes of
+/// data.
+pub fn batch(
+    token_program_id: &Pubkey,
+    instructions: &[Instruction],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+
+    let mut accounts = vec![];
+    let mut batched_instructions = Vec::with_capacity(instructions.len());
+    for instruction in instructions {
+        if instruction.program_id != *token_program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if matches!(
+            TokenInstruction::unpack(&instruction.data),
+            Ok(TokenInstruction::Batch { .. })
+        ) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let num_accounts =
+            u8::try_from(instruction.accounts.len()).map_err(|_| ProgramError::InvalidArgument)?;
+        accounts.extend_from_slice(&instruction.accounts);
+        batched_instructions.push(BatchedInstruction::new(num_accounts, &instruction.data)?);
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::Batch {
+            instructions: batched_instructions,
+        }
+        .pack(),
+    })
+}
+
+/// Computes the hash of an instruction proposed to a weighted multisig, over
+/// the multisig, the instruction's accounts with their privileges, and its
+/// data. The accounts and data are prefixed by their lengths, so that bytes
+/// cannot be moved from one to the other without changing the hash.
+pub fn hash_proposed_instruction(
+    multisig_pubkey: &Pubkey,
+    accounts: &[AccountMeta],
+    data: &[u8],
+) -> Hash {
+    let mut hasher = Hasher::default();
+    hasher.hash(multisig_pubkey.as_ref());
+    hasher.hash(&(accounts.len() as u64).to_le_bytes());
+    for meta in accounts {
+        hasher.hash(meta.pubkey.as_ref());
+        hasher.hash(&[meta.is_signer as u8, meta.is_writable as u8]);
+    }
+    hasher.hash(&(data.len() as u64).to_le_bytes());
+    hasher.hash(data);
+    hasher.result()
+}
+
+/// Returns the accounts of a proposed instruction with the privileges they
+/// have once executed through `ExecuteMultisigProposal`: no account signs, and
+/// the proposal is writable.
+fn proposed_instruction_accounts(
+    proposal_pubkey: &Pubkey,
+    proposed_instruction: &Instruction,
+) -> Vec<AccountMeta> {
+    proposed_instruction
+        .accounts
+        .iter()
+        .map(|meta| AccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: false,
+            is_writable: meta.is_writable || meta.pubkey == *proposal_pubkey,
+        })
+        .collect()
+}
+
+/// Creates an `InitializeWeightedMultisig` instruction
+pub fn initialize_weighted_multisig(
+    token_program_id: &Pubkey,
+    multisig_pubkey: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    weights: &[u8],
+    threshold: u16,
+    min_delay: i64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let total_weight = weights.iter().map(|weight| *weight as u16).sum::<u16>();
+    if signer_pubkeys.is_empty()
+        || signer_pubkeys.len() > MAX_WEIGHTED_SIGNERS
+        || signer_pubkeys.len() != weights.len()
+        || threshold == 0
+        || threshold > total_weight
+    {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let data = TokenInstruction::InitializeWeightedMultisig {
+        threshold,
+        min_delay,
+        weights: weights.to_vec(),
+    }
+    .pack();
+
+    let mut accounts = Vec::with_capacity(1 + signer_pubkeys.len());
+    accounts.push(AccountMeta::new(*multisig_pubkey, false));
+    for signer_pubkey in signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CreateMultisigProposal` instruction for the given proposed
+/// instruction
+pub fn create_multisig_proposal(
+    token_program_id: &Pubkey,
+    proposal_pubkey: &Pubkey,
+    multisig_pubkey: &Pubkey,
+    proposer_pubkey: &Pubkey,
+    proposed_instruction: &Instruction,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    if proposed_instruction.program_id != *token_program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let instruction_hash = hash_proposed_instruction(
+        multisig_pubkey,
+        &proposed_instruction_accounts(proposal_pubkey, proposed_instruction),
+        &proposed_instruction.data,
+    );
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*proposal_pubkey, false),
+            AccountMeta::new_readonly(*multisig_pubkey, false),
+            AccountMeta::new_readonly(*proposer_pubkey, true),
+        ],
+        data: TokenInstruction::CreateMultisigProposal { instruction_hash }.pack(),
+    })
+}
+
+/// Creates an `ApproveMultisigProposal` instruction
+pub fn approve_multisig_proposal(
+    token_program_id: &Pubkey,
+    proposal_pubkey: &Pubkey,
+    multisig_pubkey: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+
+    let mut accounts = Vec::with_capacity(2 + signer_pubkeys.len());
+    accounts.push(AccountMeta::new(*proposal_pubkey, false));
+    accounts.push(AccountMeta::new_readonly(*multisig_pubkey, false));
+    for signer_pubkey in signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::ApproveMultisigProposal.pack(),
+    })
+}
+
+/// Creates an `ExecuteMultisigProposal` instruction for the given proposed
+/// instruction, which must use the proposal as its only multisig signer
+pub fn execute_multisig_proposal(
+    token_program_id: &Pubkey,
+    proposal_pubkey: &Pubkey,
+    multisig_pubkey: &Pubkey,
+    proposed_instruction: &Instruction,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    if proposed_instruction.program_id != *token_program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut accounts = Vec::with_capacity(2 + proposed_instruction.accounts.len());
+    accounts.push(AccountMeta::new(*proposal_pubkey, false));
+    accounts.push(AccountMeta::new_readonly(*multisig_pubkey, false));
+    accounts.extend(proposed_instruction_accounts(
+        proposal_pubkey,
+        proposed_instruction,
+    ));
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::ExecuteMultisigProposal {
+            instruction_data: &proposed_instruction.data,
+        }
+        .pack(),
+    })
+}
+
+/// Creates a `CancelMultisigProposal` instruction
+pub fn cancel_multisig_proposal(
+    token_program_id: &Pubkey,
+    proposal_pubkey: &Pubkey,
+    multisig_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+
+    let mut accounts = Vec::with_capacity(3 + signer_pubkeys.len());
+    accounts.push(AccountMeta::new(*proposal_pubkey, false));
+    accounts.push(AccountMeta::new_readonly(*multisig_pubkey, false));
+    accounts.push(AccountMeta::new(*destination_pubkey, false));
+    for signer_pubkey in signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: TokenInstruction::CancelMultisigProposal.pack(),
+    })
+}
+
 /// Creates a `CreateNativeMint` instruction
 pub fn create_native_mint(
     token_program_id: &Pubkey,
@@ -2240,6 +2828,111 @@ mod test {
         assert_eq!(packed, expect);
         let unpacked = TokenInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
+
+        let check = TokenInstruction::RemoveExtensions {
+            extension_types: vec![ExtensionType::MemoTransfer, ExtensionType::CpiGuard],
+        };
+        let packed = check.pack();
+        let mut expect = vec![44u8];
+        expect.extend_from_slice(&<[u8; 2]>::from(ExtensionType::MemoTransfer));
+        expect.extend_from_slice(&<[u8; 2]>::from(ExtensionType::CpiGuard));
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let transfer_data = TokenInstruction::Transfer { amount: 1 }.pack();
+        let burn_data = TokenInstruction::Burn { amount: 2 }.pack();
+        let check = TokenInstruction::Batch {
+            instructions: vec![
+                BatchedInstruction::new(3, &transfer_data).unwrap(),
+                BatchedInstruction::new(3, &burn_data).unwrap(),
+            ],
+        };
+        let packed = check.pack();
+        let mut expect = vec![46u8, 3, 9, 3];
+        expect.extend_from_slice(&1u64.to_le_bytes());
+        expect.extend_from_slice(&[3, 9, 8]);
+        expect.extend_from_slice(&2u64.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        // truncated data
+        assert_eq!(
+            TokenInstruction::unpack(&expect[..expect.len() - 1]),
+            Err(TokenError::InvalidInstruction.into())
+        );
+        // batched instructions are not decoded, since token-metadata and
+        // token-group instructions may start with any byte
+        let interface_data = [46, 1, 2, 3, 4, 5, 6, 7];
+        let mut expect = vec![46u8, 2, 8];
+        expect.extend_from_slice(&interface_data);
+        assert_eq!(
+            TokenInstruction::unpack(&expect).unwrap(),
+            TokenInstruction::Batch {
+                instructions: vec![BatchedInstruction::new(2, &interface_data).unwrap()],
+            }
+        );
+        // batched instruction data must fit in a single length byte
+        assert_eq!(
+            BatchedInstruction::new(0, &[0; 256]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        let check = TokenInstruction::InitializeWeightedMultisig {
+            threshold: 300,
+            min_delay: 3600,
+            weights: vec![1, 200, 100],
+        };
+        let packed = check.pack();
+        let mut expect = vec![47u8];
+        expect.extend_from_slice(&300u16.to_le_bytes());
+        expect.extend_from_slice(&3600i64.to_le_bytes());
+        expect.extend_from_slice(&[1, 200, 100]);
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = TokenInstruction::CreateMultisigProposal {
+            instruction_hash: Hash::new_from_array([12u8; 32]),
+        };
+        let packed = check.pack();
+        let mut expect = vec![48u8];
+        expect.extend_from_slice(&[12u8; 32]);
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = TokenInstruction::ApproveMultisigProposal;
+        let packed = check.pack();
+        let expect = vec![49u8];
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let mint_to_data = TokenInstruction::MintTo { amount: 1 }.pack();
+        let check = TokenInstruction::ExecuteMultisigProposal {
+            instruction_data: &mint_to_data,
+        };
+        let packed = check.pack();
+        let mut expect = vec![50u8, 7];
+        expect.extend_from_slice(&1u64.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        // nested proposal
+        assert_eq!(
+            TokenInstruction::unpack(&[50, 50, 7]),
+            Err(TokenError::InvalidInstruction.into())
+        );
+
+        let check = TokenInstruction::CancelMultisigProposal;
+        let packed = check.pack();
+        let expect = vec![51u8];
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
     }
 
     macro_rules! test_instruction {
@@ -2472,6 +3165,36 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_hash_proposed_instruction() {
+        let multisig = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let accounts = [AccountMeta::new(key, false)];
+        let hash = hash_proposed_instruction(&multisig, &accounts, &[1, 2]);
+
+        // the multisig is covered
+        assert_ne!(
+            hash,
+            hash_proposed_instruction(&Pubkey::new_unique(), &accounts, &[1, 2])
+        );
+        // the account privileges are covered
+        assert_ne!(
+            hash,
+            hash_proposed_instruction(&multisig, &[AccountMeta::new_readonly(key, false)], &[1, 2])
+        );
+        assert_ne!(
+            hash,
+            hash_proposed_instruction(&multisig, &[AccountMeta::new(key, true)], &[1, 2])
+        );
+        // bytes cannot move between the accounts and the data
+        let mut shifted_data = vec![1, 0];
+        shifted_data.extend_from_slice(key.as_ref());
+        assert_ne!(
+            hash_proposed_instruction(&multisig, &[], &shifted_data),
+            hash_proposed_instruction(&multisig, &[AccountMeta::new(key, false)], &[])
+        );
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(1024))]
         #[test]
@@ -56,3 +56,61 @@ where
     }
     Ok(())
 }
+
+/// Offchain helper to get all additional required account metas for a mint,
+/// if the mint has opted into calling its transfer hook program on mint
+///
+/// Takes the same fetch function as `resolve_extra_transfer_account_metas`.
+pub async fn resolve_extra_mint_to_account_metas<F, Fut>(
+    instruction: &mut Instruction,
+    fetch_account_data_fn: F,
+    mint_address: &Pubkey,
+) -> Result<(), AccountFetchError>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    let mint_data = fetch_account_data_fn(*mint_address)
+        .await?
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    if let Some(program_id) = transfer_hook::get_mint_hook_program_id(&mint) {
+        spl_transfer_hook_interface::offchain::resolve_extra_mint_account_metas(
+            instruction,
+            fetch_account_data_fn,
+            mint_address,
+            &program_id,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Offchain helper to get all additional required account metas for a burn,
+/// if the mint has opted into calling its transfer hook program on burn
+///
+/// Takes the same fetch function as `resolve_extra_transfer_account_metas`.
+pub async fn resolve_extra_burn_account_metas<F, Fut>(
+    instruction: &mut Instruction,
+    fetch_account_data_fn: F,
+    mint_address: &Pubkey,
+) -> Result<(), AccountFetchError>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    let mint_data = fetch_account_data_fn(*mint_address)
+        .await?
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    if let Some(program_id) = transfer_hook::get_burn_hook_program_id(&mint) {
+        spl_transfer_hook_interface::offchain::resolve_extra_burn_account_metas(
+            instruction,
+            fetch_account_data_fn,
+            mint_address,
+            &program_id,
+        )
+        .await?;
+    }
+    Ok(())
+}
@@ -5,6 +5,7 @@ use {
         check_program_account, cmp_pubkeys,
         error::TokenError,
         extension::{
+            confidential_mint_burn::{self, ConfidentialMintBurn},
             confidential_transfer::{self, ConfidentialTransferAccount, ConfidentialTransferMint},
             confidential_transfer_fee::{
                 self, ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
@@ -18,30 +19,46 @@ use {
             metadata_pointer::{self, MetadataPointer},
             mint_close_authority::MintCloseAuthority,
             non_transferable::{NonTransferable, NonTransferableAccount},
+            pausable::{self, check_not_paused, PausableAccount, PausableConfig},
             permanent_delegate::{get_permanent_delegate, PermanentDelegate},
-            reallocate, token_metadata,
-            transfer_fee::{self, TransferFeeAmount, TransferFeeConfig},
+            reallocate,
+            scaled_ui_amount::{self, ScaledUiAmountConfig},
+            token_group, token_metadata,
+            transfer_fee::{
+                self, get_transfer_fee_override, TransferFeeAmount, TransferFeeConfig,
+                TransferFeeOverride,
+            },
             transfer_hook::{self, TransferHook, TransferHookAccount},
+            vesting::{self, check_unlocked_amount},
             AccountType, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
             StateWithExtensionsMut,
         },
-        instruction::{is_valid_signer_index, AuthorityType, TokenInstruction, MAX_SIGNERS},
+        instruction::{
+            hash_proposed_instruction, is_valid_signer_index, AuthorityType, BatchedInstruction,
+            TokenInstruction, MAX_SIGNERS, MAX_WEIGHTED_SIGNERS,
+        },
         native_mint,
-        state::{Account, AccountState, Mint, Multisig},
+        state::{
+            Account, AccountState, Mint, Multisig, MultisigProposal, ProposalState,
+            WeightedMultisig,
+        },
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         clock::Clock,
         entrypoint::ProgramResult,
+        hash::Hash,
+        instruction::AccountMeta,
         msg,
         program::{invoke, invoke_signed, set_return_data},
         program_error::ProgramError,
         program_option::COption,
-        program_pack::Pack,
+        program_pack::{IsInitialized, Pack},
         pubkey::Pubkey,
         system_instruction, system_program,
         sysvar::{rent::Rent, Sysvar},
     },
+    spl_token_group_interface::instruction::TokenGroupInstruction,
     spl_token_metadata_interface::instruction::TokenMetadataInstruction,
     std::convert::{TryFrom, TryInto},
 };
@@ -265,6 +282,58 @@ impl Processor {
         Self::_process_initialize_multisig(accounts, m, false)
     }
 
+    /// Processes an [InitializeWeightedMultisig](enum.TokenInstruction.html) instruction.
+    pub fn process_initialize_weighted_multisig(
+        accounts: &[AccountInfo],
+        threshold: u16,
+        min_delay: i64,
+        weights: Vec<u8>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let multisig_info = next_account_info(account_info_iter)?;
+        let multisig_info_data_len = multisig_info.data_len();
+
+        let mut multisig = WeightedMultisig::unpack_unchecked(&multisig_info.data.borrow())?;
+        if multisig.is_initialized {
+            return Err(TokenError::AlreadyInUse.into());
+        }
+
+        if !Rent::get()?.is_exempt(multisig_info.lamports(), multisig_info_data_len) {
+            return Err(TokenError::NotRentExempt.into());
+        }
+
+        let signer_infos = account_info_iter.as_slice();
+        if signer_infos.is_empty()
+            || signer_infos.len() > MAX_WEIGHTED_SIGNERS
+            || signer_infos.len() != weights.len()
+        {
+            return Err(TokenError::InvalidNumberOfProvidedSigners.into());
+        }
+        let total_weight = weights.iter().map(|weight| *weight as u16).sum::<u16>();
+        if threshold == 0 || threshold > total_weight {
+            return Err(TokenError::InvalidNumberOfRequiredSigners.into());
+        }
+        if min_delay < 0 {
+            return Err(TokenError::InvalidInstruction.into());
+        }
+        for (i, signer_info) in signer_infos.iter().enumerate() {
+            // each signer may only be counted once
+            if multisig.signer_position(signer_info.key).is_some() {
+                return Err(ProgramError::InvalidArgument);
+            }
+            multisig.signers[i] = *signer_info.key;
+            multisig.weights[i] = weights[i];
+            multisig.n = (i + 1) as u8;
+        }
+        multisig.threshold = threshold;
+        multisig.min_delay = min_delay;
+        multisig.is_initialized = true;
+
+        WeightedMultisig::pack(multisig, &mut multisig_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
     /// Processes a [Transfer](enum.TokenInstruction.html) instruction.
     pub fn process_transfer(
         program_id: &Pubkey,
@@ -315,10 +384,28 @@ impl Processor {
                     return Err(TokenError::MintDecimalsMismatch.into());
                 }
 
+                check_not_paused(&mint)?;
+
                 let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>()
                 {
+                    let source_override = source_account
+                        .get_extension::<TransferFeeOverride>()
+                        .ok()
+                        .copied();
+                    // The destination is only read to find its fee override here, it is fully
+                    // validated before being credited
+                    let destination_override =
+                        if cmp_pubkeys(source_account_info.key, destination_account_info.key) {
+                            None
+                        } else {
+                            get_transfer_fee_override(destination_account_info)
+                        };
                     transfer_fee_config
-                        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                        .calculate_epoch_fee_with_overrides(
+                            Clock::get()?.epoch,
+                            amount,
+                            source_override.iter().chain(destination_override.iter()),
+                        )
                         .ok_or(TokenError::Overflow)?
                 } else {
                     0
@@ -342,6 +429,12 @@ impl Processor {
                     return Err(TokenError::MintRequiredForTransfer.into());
                 }
 
+                // Pausable extension exists on the account, but no mint was
+                // provided to check whether it's paused, abort
+                if source_account.get_extension::<PausableAccount>().is_ok() {
+                    return Err(TokenError::MintRequiredForTransfer.into());
+                }
+
                 // Transfer fee amount extension exists on the account, but no mint
                 // was provided to calculate the fee, abort
                 if source_account
@@ -382,6 +475,7 @@ impl Processor {
                 if source_account.base.delegated_amount < amount {
                     return Err(TokenError::InsufficientFunds.into());
                 }
+                check_unlocked_amount(&source_account, source_account.base.amount, amount)?;
                 if !self_transfer {
                     source_account.base.delegated_amount = source_account
                         .base
@@ -407,6 +501,7 @@ impl Processor {
                         return Err(TokenError::CpiGuardTransferBlocked.into());
                     }
                 }
+                check_unlocked_amount(&source_account, source_account.base.amount, amount)?;
             }
         }
 
@@ -568,6 +663,8 @@ impl Processor {
             }
         }
 
+        check_unlocked_amount(&source_account, source_account.base.amount, amount)?;
+
         source_account.base.delegate = COption::Some(*delegate_info.key);
         source_account.base.delegated_amount = amount;
         source_account.pack_base();
@@ -620,6 +717,7 @@ impl Processor {
         let account_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
         let authority_info_data_len = authority_info.data_len();
+        Self::check_multisig_time_lock(program_id, authority_info, account_info_iter.as_slice())?;
 
         let mut account_data = account_info.data.borrow_mut();
         if let Ok(mut account) = StateWithExtensionsMut::<Account>::unpack(&mut account_data) {
@@ -860,6 +958,32 @@ impl Processor {
                     )?;
                     extension.authority = new_authority.try_into()?;
                 }
+                AuthorityType::Pause => {
+                    let extension = mint.get_extension_mut::<PausableConfig>()?;
+                    let maybe_authority: Option<Pubkey> = extension.authority.into();
+                    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &authority,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.authority = new_authority.try_into()?;
+                }
+                AuthorityType::ScaledUiAmount => {
+                    let extension = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+                    let maybe_authority: Option<Pubkey> = extension.authority.into();
+                    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &authority,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.authority = new_authority.try_into()?;
+                }
                 _ => {
                     return Err(TokenError::AuthorityTypeNotSupported.into());
                 }
@@ -883,6 +1007,7 @@ impl Processor {
         let destination_account_info = next_account_info(account_info_iter)?;
         let owner_info = next_account_info(account_info_iter)?;
         let owner_info_data_len = owner_info.data_len();
+        Self::check_multisig_time_lock(program_id, owner_info, account_info_iter.as_slice())?;
 
         let mut destination_account_data = destination_account_info.data.borrow_mut();
         let mut destination_account =
@@ -901,6 +1026,14 @@ impl Processor {
         let mut mint_data = mint_info.data.borrow_mut();
         let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
 
+        check_not_paused(&mint)?;
+
+        // Public minting would reveal the amounts and make the public supply drift
+        // from the encrypted supply
+        if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+            return Err(TokenError::IllegalMintBurnConversion.into());
+        }
+
         // If the mint if non-transferable, only allow minting to accounts
         // with immutable ownership.
         if mint.get_extension::<NonTransferable>().is_ok()
@@ -949,6 +1082,26 @@ impl Processor {
         mint.pack_base();
         destination_account.pack_base();
 
+        if let Some(hook_program_id) = transfer_hook::get_mint_hook_program_id(&mint) {
+            // set minting flag
+            transfer_hook::set_minting(&mut mint)?;
+
+            // must drop these to avoid the double-borrow during CPI
+            drop(mint_data);
+            drop(destination_account_data);
+            spl_transfer_hook_interface::onchain::invoke_mint_execute(
+                &hook_program_id,
+                mint_info.clone(),
+                destination_account_info.clone(),
+                owner_info.clone(),
+                account_info_iter.as_slice(),
+                amount,
+            )?;
+
+            // unset minting flag
+            transfer_hook::unset_minting(mint_info)?;
+        }
+
         Ok(())
     }
 
@@ -972,6 +1125,12 @@ impl Processor {
         let mut mint_data = mint_info.data.borrow_mut();
         let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
 
+        // Public burning would reveal the amounts and make the public supply drift
+        // from the encrypted supply
+        if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+            return Err(TokenError::IllegalMintBurnConversion.into());
+        }
+
         if source_account.base.is_frozen() {
             return Err(TokenError::AccountFrozen.into());
         }
@@ -990,6 +1149,7 @@ impl Processor {
                 return Err(TokenError::MintDecimalsMismatch.into());
             }
         }
+        check_not_paused(&mint)?;
         let maybe_permanent_delegate = get_permanent_delegate(&mint);
 
         if !source_account
@@ -1018,6 +1178,7 @@ impl Processor {
                     if source_account.base.delegated_amount < amount {
                         return Err(TokenError::InsufficientFunds.into());
                     }
+                    check_unlocked_amount(&source_account, source_account.base.amount, amount)?;
                     source_account.base.delegated_amount = source_account
                         .base
                         .delegated_amount
@@ -1041,6 +1202,7 @@ impl Processor {
                             return Err(TokenError::CpiGuardBurnBlocked.into());
                         }
                     }
+                    check_unlocked_amount(&source_account, source_account.base.amount, amount)?;
                 }
             }
         }
@@ -1065,6 +1227,26 @@ impl Processor {
         source_account.pack_base();
         mint.pack_base();
 
+        if let Some(hook_program_id) = transfer_hook::get_burn_hook_program_id(&mint) {
+            // set burning flag
+            transfer_hook::set_burning(&mut mint)?;
+
+            // must drop these to avoid the double-borrow during CPI
+            drop(source_account_data);
+            drop(mint_data);
+            spl_transfer_hook_interface::onchain::invoke_burn_execute(
+                &hook_program_id,
+                source_account_info.clone(),
+                mint_info.clone(),
+                authority_info.clone(),
+                account_info_iter.as_slice(),
+                amount,
+            )?;
+
+            // unset burning flag
+            transfer_hook::unset_burning(mint_info)?;
+        }
+
         Ok(())
     }
 
@@ -1307,6 +1489,11 @@ impl Processor {
             extension
                 .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
                 .ok_or(ProgramError::InvalidArgument)?
+        } else if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
+            let unix_timestamp = Clock::get()?.unix_timestamp;
+            extension
+                .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
+                .ok_or(ProgramError::InvalidArgument)?
         } else {
             crate::amount_to_ui_amount_string_trimmed(amount, mint.base.decimals)
         };
@@ -1327,6 +1514,9 @@ impl Processor {
         let amount = if let Ok(extension) = mint.get_extension::<InterestBearingConfig>() {
             let unix_timestamp = Clock::get()?.unix_timestamp;
             extension.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
+        } else if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
+            let unix_timestamp = Clock::get()?.unix_timestamp;
+            extension.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
         } else {
             crate::try_ui_amount_into_amount(ui_amount.to_string(), mint.base.decimals)?
         };
@@ -1446,7 +1636,9 @@ impl Processor {
             } else {
                 return Err(TokenError::AuthorityTypeNotSupported.into());
             }
-        } else if source_data.len() == Multisig::LEN {
+        } else if source_data.len() == Multisig::LEN
+            || WeightedMultisig::is_weighted_multisig(&source_data)
+        {
             Self::validate_owner(
                 program_id,
                 source_info.key,
@@ -1478,6 +1670,213 @@ impl Processor {
         Ok(())
     }
 
+    /// Processes a [Batch](enum.TokenInstruction.html) instruction.
+    pub fn process_batch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instructions: Vec<BatchedInstruction>,
+    ) -> ProgramResult {
+        let mut remaining_accounts = accounts;
+        for instruction in instructions {
+            // Batches cannot be nested. The data is checked the same way
+            // `process` decodes it, since token-metadata and token-group
+            // instructions may start with any byte.
+            if matches!(
+                TokenInstruction::unpack(instruction.data()),
+                Ok(TokenInstruction::Batch { .. })
+            ) {
+                return Err(TokenError::InvalidInstruction.into());
+            }
+            let num_accounts = instruction.num_accounts() as usize;
+            if remaining_accounts.len() < num_accounts {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            let (instruction_accounts, rest) = remaining_accounts.split_at(num_accounts);
+            Self::process(program_id, instruction_accounts, instruction.data())?;
+            remaining_accounts = rest;
+        }
+        Ok(())
+    }
+
+    /// Processes a [CreateMultisigProposal](enum.TokenInstruction.html) instruction.
+    pub fn process_create_multisig_proposal(
+        accounts: &[AccountInfo],
+        instruction_hash: Hash,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let proposal_info = next_account_info(account_info_iter)?;
+        let proposal_info_data_len = proposal_info.data_len();
+        let multisig_info = next_account_info(account_info_iter)?;
+        let proposer_info = next_account_info(account_info_iter)?;
+
+        check_program_account(multisig_info.owner)?;
+        let multisig = WeightedMultisig::unpack(&multisig_info.data.borrow())?;
+
+        let mut proposal = MultisigProposal::unpack_unchecked(&proposal_info.data.borrow())?;
+        if proposal.is_initialized() {
+            return Err(TokenError::AlreadyInUse.into());
+        }
+        if !Rent::get()?.is_exempt(proposal_info.lamports(), proposal_info_data_len) {
+            return Err(TokenError::NotRentExempt.into());
+        }
+
+        let position = multisig
+            .signer_position(proposer_info.key)
+            .ok_or(TokenError::OwnerMismatch)?;
+        if !proposer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        proposal.state = ProposalState::Pending;
+        proposal.multisig = *multisig_info.key;
+        proposal.approvals = 1 << position;
+        proposal.instruction_hash = instruction_hash;
+        if multisig.approved_weight(proposal.approvals) >= multisig.threshold {
+            proposal.approved_at = Clock::get()?.unix_timestamp;
+        }
+
+        MultisigProposal::pack(proposal, &mut proposal_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Processes an [ApproveMultisigProposal](enum.TokenInstruction.html) instruction.
+    pub fn process_approve_multisig_proposal(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let proposal_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+
+        check_program_account(proposal_info.owner)?;
+        let mut proposal = MultisigProposal::unpack(&proposal_info.data.borrow())?;
+        if proposal.state != ProposalState::Pending {
+            return Err(TokenError::InvalidState.into());
+        }
+        if !cmp_pubkeys(&proposal.multisig, multisig_info.key) {
+            return Err(TokenError::MultisigProposalMismatch.into());
+        }
+        check_program_account(multisig_info.owner)?;
+        let multisig = WeightedMultisig::unpack(&multisig_info.data.borrow())?;
+
+        for signer_info in account_info_iter {
+            let position = multisig
+                .signer_position(signer_info.key)
+                .ok_or(TokenError::OwnerMismatch)?;
+            if !signer_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            proposal.approvals |= 1 << position;
+        }
+        // the delay starts once, when the threshold is first reached
+        if proposal.approved_at == 0
+            && multisig.approved_weight(proposal.approvals) >= multisig.threshold
+        {
+            proposal.approved_at = Clock::get()?.unix_timestamp;
+        }
+
+        MultisigProposal::pack(proposal, &mut proposal_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Processes an [ExecuteMultisigProposal](enum.TokenInstruction.html) instruction.
+    pub fn process_execute_multisig_proposal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let proposal_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+        let instruction_accounts = account_info_iter.as_slice();
+
+        check_program_account(proposal_info.owner)?;
+        let mut proposal = MultisigProposal::unpack(&proposal_info.data.borrow())?;
+        if proposal.state != ProposalState::Pending {
+            return Err(TokenError::InvalidState.into());
+        }
+        if !cmp_pubkeys(&proposal.multisig, multisig_info.key) {
+            return Err(TokenError::MultisigProposalMismatch.into());
+        }
+        check_program_account(multisig_info.owner)?;
+        let multisig = WeightedMultisig::unpack(&multisig_info.data.borrow())?;
+
+        if proposal.approved_at == 0
+            || Clock::get()?.unix_timestamp
+                < proposal.approved_at.saturating_add(multisig.min_delay)
+        {
+            return Err(TokenError::MultisigProposalNotReady.into());
+        }
+
+        let account_metas = instruction_accounts
+            .iter()
+            .map(|account_info| AccountMeta {
+                pubkey: *account_info.key,
+                is_signer: account_info.is_signer,
+                is_writable: account_info.is_writable,
+            })
+            .collect::<Vec<_>>();
+        if hash_proposed_instruction(multisig_info.key, &account_metas, instruction_data)
+            != proposal.instruction_hash
+        {
+            return Err(TokenError::MultisigProposalMismatch.into());
+        }
+
+        // While executing, the proposal acts as a signature of the multisig,
+        // see `validate_owner`
+        proposal.state = ProposalState::Executing;
+        MultisigProposal::pack(proposal, &mut proposal_info.data.borrow_mut())?;
+
+        Self::process(program_id, instruction_accounts, instruction_data)?;
+
+        proposal.state = ProposalState::Executed;
+        MultisigProposal::pack(proposal, &mut proposal_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Processes a [CancelMultisigProposal](enum.TokenInstruction.html) instruction.
+    pub fn process_cancel_multisig_proposal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let proposal_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+        let multisig_info_data_len = multisig_info.data_len();
+        let destination_info = next_account_info(account_info_iter)?;
+
+        if cmp_pubkeys(proposal_info.key, destination_info.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        check_program_account(proposal_info.owner)?;
+        let proposal = MultisigProposal::unpack(&proposal_info.data.borrow())?;
+        if proposal.state == ProposalState::Executing {
+            return Err(TokenError::InvalidState.into());
+        }
+        if !cmp_pubkeys(&proposal.multisig, multisig_info.key) {
+            return Err(TokenError::MultisigProposalMismatch.into());
+        }
+
+        Self::validate_owner(
+            program_id,
+            multisig_info.key,
+            multisig_info,
+            multisig_info_data_len,
+            account_info_iter.as_slice(),
+        )?;
+
+        let destination_starting_lamports = destination_info.lamports();
+        **destination_info.lamports.borrow_mut() = destination_starting_lamports
+            .checked_add(proposal_info.lamports())
+            .ok_or(TokenError::Overflow)?;
+
+        **proposal_info.lamports.borrow_mut() = 0;
+        delete_account(proposal_info)?;
+
+        Ok(())
+    }
+
     /// Processes an [Instruction](enum.Instruction.html).
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
         if let Ok(instruction) = TokenInstruction::unpack(input) {
@@ -1678,9 +2077,65 @@ impl Processor {
                 TokenInstruction::GroupPointerExtension => {
                     group_pointer::processor::process_instruction(program_id, accounts, &input[1..])
                 }
+                TokenInstruction::PausableExtension => {
+                    pausable::processor::process_instruction(program_id, accounts, &input[1..])
+                }
+                TokenInstruction::ScaledUiAmountExtension => {
+                    scaled_ui_amount::processor::process_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
+                TokenInstruction::ConfidentialMintBurnExtension => {
+                    confidential_mint_burn::processor::process_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
+                TokenInstruction::RemoveExtensions { extension_types } => {
+                    msg!("Instruction: RemoveExtensions");
+                    reallocate::process_remove_extensions(program_id, accounts, extension_types)
+                }
+                TokenInstruction::VestingExtension => {
+                    vesting::processor::process_instruction(program_id, accounts, &input[1..])
+                }
+                TokenInstruction::Batch { instructions } => {
+                    msg!("Instruction: Batch");
+                    Self::process_batch(program_id, accounts, instructions)
+                }
+                TokenInstruction::InitializeWeightedMultisig {
+                    threshold,
+                    min_delay,
+                    weights,
+                } => {
+                    msg!("Instruction: InitializeWeightedMultisig");
+                    Self::process_initialize_weighted_multisig(
+                        accounts, threshold, min_delay, weights,
+                    )
+                }
+                TokenInstruction::CreateMultisigProposal { instruction_hash } => {
+                    msg!("Instruction: CreateMultisigProposal");
+                    Self::process_create_multisig_proposal(accounts, instruction_hash)
+                }
+                TokenInstruction::ApproveMultisigProposal => {
+                    msg!("Instruction: ApproveMultisigProposal");
+                    Self::process_approve_multisig_proposal(accounts)
+                }
+                TokenInstruction::ExecuteMultisigProposal { instruction_data } => {
+                    msg!("Instruction: ExecuteMultisigProposal");
+                    Self::process_execute_multisig_proposal(program_id, accounts, instruction_data)
+                }
+                TokenInstruction::CancelMultisigProposal => {
+                    msg!("Instruction: CancelMultisigProposal");
+                    Self::process_cancel_multisig_proposal(program_id, accounts)
+                }
             }
         } else if let Ok(instruction) = TokenMetadataInstruction::unpack(input) {
             token_metadata::processor::process_instruction(program_id, accounts, instruction)
+        } else if let Ok(instruction) = TokenGroupInstruction::unpack(input) {
+            token_group::processor::process_instruction(program_id, accounts, instruction)
         } else {
             Err(TokenError::InvalidInstruction.into())
         }
@@ -1719,12 +2174,87 @@ impl Processor {
                 return Err(ProgramError::MissingRequiredSignature);
             }
             return Ok(());
+        } else if Self::is_weighted_multisig(program_id, owner_account_info, owner_account_data_len)
+        {
+            let multisig = WeightedMultisig::unpack(&owner_account_info.data.borrow())?;
+            if Self::has_executing_proposal(program_id, owner_account_info.key, signers) {
+                return Ok(());
+            }
+            let mut approvals = 0u32;
+            for signer in signers.iter() {
+                if let Some(position) = multisig.signer_position(signer.key) {
+                    if !signer.is_signer {
+                        return Err(ProgramError::MissingRequiredSignature);
+                    }
+                    approvals |= 1 << position;
+                }
+            }
+            if multisig.approved_weight(approvals) < multisig.threshold {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            return Ok(());
         } else if !owner_account_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         Ok(())
     }
 
+    /// Checks whether the account is a weighted multisig. The length is checked
+    /// first, so that accounts already borrowed by the caller, like a
+    /// self-owned token account, are only borrowed again if they may be one.
+    fn is_weighted_multisig(
+        program_id: &Pubkey,
+        account_info: &AccountInfo,
+        account_data_len: usize,
+    ) -> bool {
+        cmp_pubkeys(program_id, account_info.owner)
+            && account_data_len == WeightedMultisig::get_packed_len()
+            && account_info
+                .try_borrow_data()
+                .map(|data| WeightedMultisig::is_weighted_multisig(&data))
+                .unwrap_or(false)
+    }
+
+    /// Checks whether one of the signers is a proposal of the given weighted
+    /// multisig that is currently being executed
+    fn has_executing_proposal(
+        program_id: &Pubkey,
+        multisig_key: &Pubkey,
+        signers: &[AccountInfo],
+    ) -> bool {
+        signers.iter().any(|signer| {
+            cmp_pubkeys(program_id, signer.owner)
+                && signer.data_len() == MultisigProposal::get_packed_len()
+                && signer
+                    .try_borrow_data()
+                    .ok()
+                    .and_then(|data| MultisigProposal::unpack(&data).ok())
+                    .map(|proposal| {
+                        proposal.state == ProposalState::Executing
+                            && cmp_pubkeys(&proposal.multisig, multisig_key)
+                    })
+                    .unwrap_or(false)
+        })
+    }
+
+    /// Time-locked weighted multisigs can only authorize minting and authority
+    /// changes through an executing proposal
+    pub(crate) fn check_multisig_time_lock(
+        program_id: &Pubkey,
+        authority_info: &AccountInfo,
+        signers: &[AccountInfo],
+    ) -> ProgramResult {
+        if Self::is_weighted_multisig(program_id, authority_info, authority_info.data_len()) {
+            let multisig = WeightedMultisig::unpack(&authority_info.try_borrow_data()?)?;
+            if multisig.is_time_locked()
+                && !Self::has_executing_proposal(program_id, authority_info.key, signers)
+            {
+                return Err(TokenError::MultisigProposalRequired.into());
+            }
+        }
+        Ok(())
+    }
+
     fn get_required_account_extensions(
         mint_account_info: &AccountInfo,
     ) -> Result<Vec<ExtensionType>, ProgramError> {
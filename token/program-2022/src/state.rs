@@ -4,11 +4,13 @@ use {
     crate::{
         extension::AccountType,
         generic_token_account::{is_initialized_account, GenericTokenAccount},
-        instruction::MAX_SIGNERS,
+        instruction::{MAX_SIGNERS, MAX_WEIGHTED_SIGNERS},
     },
     arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
     num_enum::{IntoPrimitive, TryFromPrimitive},
     solana_program::{
+        clock::UnixTimestamp,
+        hash::Hash,
         program_error::ProgramError,
         program_option::COption,
         program_pack::{IsInitialized, Pack, Sealed},
@@ -251,6 +253,225 @@ impl Pack for Multisig {
     }
 }
 
+/// Weighted multisignature data, with an optional time lock on minting and
+/// authority changes.
+///
+/// Unlike `Multisig`, a weighted multisig is not identified by its length:
+/// its packed data stores `AccountType::WeightedMultisig` at the same offset
+/// as the account type of extended mints and accounts.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WeightedMultisig {
+    /// Total weight of signatures required
+    pub threshold: u16,
+    /// Number of valid signers
+    pub n: u8,
+    /// Is `true` if this structure has been initialized
+    pub is_initialized: bool,
+    /// Minimum number of seconds between a proposal reaching the threshold
+    /// and its execution. If non-zero, `MintTo` and `SetAuthority` may only
+    /// be authorized by executing an approved proposal.
+    pub min_delay: UnixTimestamp,
+    /// Signer public keys
+    pub signers: [Pubkey; MAX_WEIGHTED_SIGNERS],
+    /// Weight of each signer
+    pub weights: [u8; MAX_WEIGHTED_SIGNERS],
+}
+impl WeightedMultisig {
+    /// Get the position of a signer, if it belongs to the multisig
+    pub fn signer_position(&self, key: &Pubkey) -> Option<usize> {
+        self.signers[0..self.n as usize]
+            .iter()
+            .position(|signer| signer == key)
+    }
+
+    /// Get the total weight of the signers set in the `approvals` bitmap
+    pub fn approved_weight(&self, approvals: u32) -> u16 {
+        self.weights[0..self.n as usize]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| approvals & (1 << i) != 0)
+            .fold(0u16, |total, (_, weight)| {
+                total.saturating_add(*weight as u16)
+            })
+    }
+
+    /// Is `true` if the multisig requires proposals for minting and authority
+    /// changes
+    pub fn is_time_locked(&self) -> bool {
+        self.min_delay > 0
+    }
+
+    /// Is `true` if the data belongs to an initialized weighted multisig
+    pub fn is_weighted_multisig(data: &[u8]) -> bool {
+        data.len() == Self::LEN
+            && data[WEIGHTED_MULTISIG_ACCOUNT_TYPE_INDEX] == AccountType::WeightedMultisig as u8
+    }
+}
+
+// The account type is stored at the same offset as for extended mints and
+// accounts, so that a weighted multisig is never mistaken for one of them
+const WEIGHTED_MULTISIG_ACCOUNT_TYPE_INDEX: usize = Account::LEN;
+impl Sealed for WeightedMultisig {}
+impl IsInitialized for WeightedMultisig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+impl Pack for WeightedMultisig {
+    const LEN: usize = 1190;
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, 1190];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (
+            threshold,
+            n,
+            is_initialized,
+            min_delay,
+            weights,
+            _padding,
+            account_type,
+            signers_flat,
+        ) = array_refs![
+            src,
+            2,
+            1,
+            1,
+            8,
+            MAX_WEIGHTED_SIGNERS,
+            121,
+            1,
+            32 * MAX_WEIGHTED_SIGNERS
+        ];
+        let mut result = WeightedMultisig {
+            threshold: u16::from_le_bytes(*threshold),
+            n: n[0],
+            is_initialized: match is_initialized {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            min_delay: i64::from_le_bytes(*min_delay),
+            signers: [Pubkey::new_from_array([0u8; 32]); MAX_WEIGHTED_SIGNERS],
+            weights: *weights,
+        };
+        let expected_account_type = if result.is_initialized {
+            AccountType::WeightedMultisig
+        } else {
+            AccountType::Uninitialized
+        };
+        if account_type[0] != u8::from(expected_account_type) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if result.n as usize > MAX_WEIGHTED_SIGNERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        for (src, dst) in signers_flat.chunks(32).zip(result.signers.iter_mut()) {
+            *dst = Pubkey::try_from(src).map_err(|_| ProgramError::InvalidAccountData)?;
+        }
+        Ok(result)
+    }
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, 1190];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let refs = mut_array_refs![
+            dst,
+            2,
+            1,
+            1,
+            8,
+            MAX_WEIGHTED_SIGNERS,
+            121,
+            1,
+            32 * MAX_WEIGHTED_SIGNERS
+        ];
+        let (threshold, n, is_initialized, min_delay, weights, padding, account_type, signers) =
+            refs;
+        *threshold = self.threshold.to_le_bytes();
+        *n = [self.n];
+        *is_initialized = [self.is_initialized as u8];
+        *min_delay = self.min_delay.to_le_bytes();
+        *weights = self.weights;
+        *padding = [0; 121];
+        account_type[0] = if self.is_initialized {
+            AccountType::WeightedMultisig
+        } else {
+            AccountType::Uninitialized
+        }
+        .into();
+        for (i, src) in self.signers.iter().enumerate() {
+            let dst_array = array_mut_ref![signers, 32 * i, 32];
+            dst_array.copy_from_slice(src.as_ref());
+        }
+    }
+}
+
+/// Multisig proposal state.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum ProposalState {
+    /// Proposal is not yet initialized
+    #[default]
+    Uninitialized,
+    /// Proposal is collecting approvals or waiting for its delay to elapse
+    Pending,
+    /// Proposed instruction is being executed
+    Executing,
+    /// Proposed instruction was executed, so the proposal cannot be used again
+    Executed,
+}
+
+/// An instruction proposed to a weighted multisig, executed once enough
+/// signers approve it and the multisig's delay has elapsed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MultisigProposal {
+    /// The proposal state
+    pub state: ProposalState,
+    /// The weighted multisig that must approve the proposal
+    pub multisig: Pubkey,
+    /// Bitmap of the positions of the signers that approved the proposal
+    pub approvals: u32,
+    /// Time at which the approvals reached the threshold, or 0 if they have
+    /// not yet
+    pub approved_at: UnixTimestamp,
+    /// Hash of the proposed instruction, see
+    /// `instruction::hash_proposed_instruction`
+    pub instruction_hash: Hash,
+}
+impl Sealed for MultisigProposal {}
+impl IsInitialized for MultisigProposal {
+    fn is_initialized(&self) -> bool {
+        self.state != ProposalState::Uninitialized
+    }
+}
+impl Pack for MultisigProposal {
+    const LEN: usize = 77;
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, 77];
+        let (state, multisig, approvals, approved_at, instruction_hash) =
+            array_refs![src, 1, 32, 4, 8, 32];
+        Ok(MultisigProposal {
+            state: ProposalState::try_from_primitive(state[0])
+                .or(Err(ProgramError::InvalidAccountData))?,
+            multisig: Pubkey::new_from_array(*multisig),
+            approvals: u32::from_le_bytes(*approvals),
+            approved_at: i64::from_le_bytes(*approved_at),
+            instruction_hash: Hash::new_from_array(*instruction_hash),
+        })
+    }
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, 77];
+        let (state, multisig, approvals, approved_at, instruction_hash) =
+            mut_array_refs![dst, 1, 32, 4, 8, 32];
+        state[0] = self.state.into();
+        multisig.copy_from_slice(self.multisig.as_ref());
+        *approvals = self.approvals.to_le_bytes();
+        *approved_at = self.approved_at.to_le_bytes();
+        instruction_hash.copy_from_slice(self.instruction_hash.as_ref());
+    }
+}
+
 // Helpers
 pub(crate) fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
     let (tag, body) = mut_array_refs![dst, 4, 32];
@@ -422,6 +643,64 @@ pub(crate) mod test {
         assert_eq!(packed, expect);
         let unpacked = Multisig::unpack(&packed).unwrap();
         assert_eq!(unpacked, check);
+
+        // WeightedMultisig
+        let check = WeightedMultisig {
+            threshold: 300,
+            n: 2,
+            is_initialized: true,
+            min_delay: 60,
+            signers: [Pubkey::new_from_array([3; 32]); MAX_WEIGHTED_SIGNERS],
+            weights: [4; MAX_WEIGHTED_SIGNERS],
+        };
+        let mut packed = vec![0; WeightedMultisig::get_packed_len() - 1];
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            WeightedMultisig::pack(check, &mut packed)
+        );
+        let mut packed = vec![0; WeightedMultisig::get_packed_len()];
+        WeightedMultisig::pack(check, &mut packed).unwrap();
+        assert_eq!(&packed[..12], &[44, 1, 2, 1, 60, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(packed[12..12 + MAX_WEIGHTED_SIGNERS]
+            .iter()
+            .all(|b| *b == 4));
+        assert!(packed[12 + MAX_WEIGHTED_SIGNERS..Account::LEN]
+            .iter()
+            .all(|b| *b == 0));
+        assert_eq!(packed[Account::LEN], AccountType::WeightedMultisig as u8);
+        assert!(packed[Account::LEN + 1..].iter().all(|b| *b == 3));
+        assert!(WeightedMultisig::is_weighted_multisig(&packed));
+        let unpacked = WeightedMultisig::unpack(&packed).unwrap();
+        assert_eq!(unpacked, check);
+        assert_eq!(unpacked.approved_weight(0b11), 8);
+        assert_eq!(unpacked.approved_weight(0b110), 4);
+
+        // an extended mint of the same length is not a weighted multisig
+        packed[Account::LEN] = AccountType::Mint as u8;
+        assert!(!WeightedMultisig::is_weighted_multisig(&packed));
+        assert_eq!(
+            WeightedMultisig::unpack(&packed),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // MultisigProposal
+        let check = MultisigProposal {
+            state: ProposalState::Pending,
+            multisig: Pubkey::new_from_array([1; 32]),
+            approvals: 2,
+            approved_at: 3,
+            instruction_hash: Hash::new_from_array([4; 32]),
+        };
+        let mut packed = vec![0; MultisigProposal::get_packed_len()];
+        MultisigProposal::pack(check, &mut packed).unwrap();
+        let mut expect = vec![1];
+        expect.extend_from_slice(&[1; 32]);
+        expect.extend_from_slice(&[2, 0, 0, 0]);
+        expect.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0]);
+        expect.extend_from_slice(&[4; 32]);
+        assert_eq!(packed, expect);
+        let unpacked = MultisigProposal::unpack(&packed).unwrap();
+        assert_eq!(unpacked, check);
     }
 
     #[test]
@@ -4,6 +4,11 @@ mod action;
 use {
     solana_program_test::{processor, tokio, ProgramTest},
     solana_sdk::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
+        program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
@@ -18,6 +23,7 @@ use {
 };
 
 const TRANSFER_AMOUNT: u64 = 1_000_000_000_000_000;
+const CPI_TRANSFERS_PROGRAM_ID: Pubkey = Pubkey::new_from_array([1; 32]);
 
 #[tokio::test]
 async fn initialize_mint() {
@@ -330,3 +336,185 @@ async fn close_account() {
     );
     banks_client.process_transaction(transaction).await.unwrap();
 }
+
+#[tokio::test]
+async fn batch_transfers() {
+    let mut pt = ProgramTest::new("spl_token_2022", id(), processor!(Processor::process));
+    // the calling program is always native, even if the token program is SBF
+    pt.prefer_bpf(false);
+    pt.add_program(
+        "cpi_transfers",
+        CPI_TRANSFERS_PROGRAM_ID,
+        processor!(process_cpi_transfers),
+    );
+    pt.set_compute_max_units(16_000);
+    let (mut banks_client, payer, recent_blockhash) = pt.start().await;
+
+    let owner = Keypair::new();
+    let mint = Keypair::new();
+    let source = Keypair::new();
+    let destination = Keypair::new();
+    let decimals = 9;
+
+    action::create_mint(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &mint,
+        &owner.pubkey(),
+        decimals,
+    )
+    .await
+    .unwrap();
+    action::create_account(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &source,
+        &mint.pubkey(),
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    action::create_account(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &destination,
+        &mint.pubkey(),
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    action::mint_to(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &mint.pubkey(),
+        &source.pubkey(),
+        &owner,
+        TRANSFER_AMOUNT,
+    )
+    .await
+    .unwrap();
+
+    // separate CPIs
+    let transaction = Transaction::new_signed_with_payer(
+        &[cpi_transfers_instruction(
+            &source.pubkey(),
+            &mint.pubkey(),
+            &destination.pubkey(),
+            &owner.pubkey(),
+            decimals,
+            false,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &owner],
+        recent_blockhash,
+    );
+    let result = banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    let separate_units = result.metadata.unwrap().compute_units_consumed;
+
+    // the same transfers in one batched CPI
+    let transaction = Transaction::new_signed_with_payer(
+        &[cpi_transfers_instruction(
+            &source.pubkey(),
+            &mint.pubkey(),
+            &destination.pubkey(),
+            &owner.pubkey(),
+            decimals,
+            true,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &owner],
+        recent_blockhash,
+    );
+    let result = banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    let batch_units = result.metadata.unwrap().compute_units_consumed;
+
+    assert!(batch_units < separate_units);
+
+    let account = banks_client
+        .get_account(destination.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let account = Account::unpack(&account.data).unwrap();
+    assert_eq!(account.amount, 12);
+}
+
+/// Program that transfers 1, 2 and 3 tokens through CPIs into the token
+/// program, with one CPI per transfer or with a single `Batch` CPI
+fn process_cpi_transfers(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let account_infos = [
+        source_info.clone(),
+        mint_info.clone(),
+        destination_info.clone(),
+        owner_info.clone(),
+    ];
+    let [decimals, batched] = input else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let transfers = (1..=3)
+        .map(|amount| {
+            instruction::transfer_checked(
+                &id(),
+                source_info.key,
+                mint_info.key,
+                destination_info.key,
+                owner_info.key,
+                &[],
+                amount,
+                *decimals,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if *batched != 0 {
+        invoke(&instruction::batch(&id(), &transfers)?, &account_infos)
+    } else {
+        for transfer in transfers.iter() {
+            invoke(transfer, &account_infos)?;
+        }
+        Ok(())
+    }
+}
+
+fn cpi_transfers_instruction(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    decimals: u8,
+    batched: bool,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        CPI_TRANSFERS_PROGRAM_ID,
+        &[decimals, batched.into()],
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(id(), false),
+        ],
+    )
+}
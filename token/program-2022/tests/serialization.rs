@@ -165,3 +165,19 @@ fn serde_instruction_basis_points() {
 
     serde_json::from_str::<InitializeInstructionData>(&serialized_expected).unwrap();
 }
+
+#[test]
+fn serde_instruction_multiplier() {
+    use spl_token_2022::extension::scaled_ui_amount::instruction::InitializeInstructionData;
+
+    let inst = InitializeInstructionData {
+        authority: OptionalNonZeroPubkey::default(),
+        multiplier: 5.0.into(),
+    };
+
+    let serialized = serde_json::to_string(&inst).unwrap();
+    let serialized_expected = "{\"authority\":null,\"multiplier\":5.0}";
+    assert_eq!(&serialized, serialized_expected);
+
+    serde_json::from_str::<InitializeInstructionData>(&serialized_expected).unwrap();
+}
@@ -13,7 +13,8 @@ use {
     spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
     spl_token_2022::{
         extension::{
-            transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+            transfer_hook::{TransferHookAccount, TransferHookMintBurn},
+            BaseStateWithExtensions, StateWithExtensions,
         },
         state::{Account, Mint},
     },
@@ -36,6 +37,28 @@ fn check_token_account_is_transferring(account_info: &AccountInfo) -> Result<(),
     }
 }
 
+fn check_mint_is_minting(mint_info: &AccountInfo) -> Result<(), ProgramError> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let extension = mint.get_extension::<TransferHookMintBurn>()?;
+    if bool::from(extension.minting) {
+        Ok(())
+    } else {
+        Err(TransferHookError::ProgramCalledOutsideOfMint.into())
+    }
+}
+
+fn check_mint_is_burning(mint_info: &AccountInfo) -> Result<(), ProgramError> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let extension = mint.get_extension::<TransferHookMintBurn>()?;
+    if bool::from(extension.burning) {
+        Ok(())
+    } else {
+        Err(TransferHookError::ProgramCalledOutsideOfBurn.into())
+    }
+}
+
 /// Processes an [Execute](enum.TransferHookInstruction.html) instruction.
 pub fn process_execute(
     program_id: &Pubkey,
@@ -73,6 +96,50 @@ pub fn process_execute(
     Ok(())
 }
 
+/// Processes a [MintExecute](enum.TransferHookInstruction.html) instruction.
+///
+/// The example program does not configure extra accounts for minting, so it
+/// only checks the "minting" flag and the validation pubkey.
+pub fn process_mint_execute(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let mint_info = next_account_info(account_info_iter)?;
+    let _destination_info = next_account_info(account_info_iter)?;
+    let _mint_authority_info = next_account_info(account_info_iter)?;
+    let extra_account_metas_info = next_account_info(account_info_iter)?;
+
+    // Check that the mint is properly in "minting" mode
+    check_mint_is_minting(mint_info)?;
+
+    let expected_validation_address = get_extra_account_metas_address(mint_info.key, program_id);
+    if expected_validation_address != *extra_account_metas_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(())
+}
+
+/// Processes a [BurnExecute](enum.TransferHookInstruction.html) instruction.
+///
+/// The example program does not configure extra accounts for burning, so it
+/// only checks the "burning" flag and the validation pubkey.
+pub fn process_burn_execute(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let _source_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let _authority_info = next_account_info(account_info_iter)?;
+    let extra_account_metas_info = next_account_info(account_info_iter)?;
+
+    // Check that the mint is properly in "burning" mode
+    check_mint_is_burning(mint_info)?;
+
+    let expected_validation_address = get_extra_account_metas_address(mint_info.key, program_id);
+    if expected_validation_address != *extra_account_metas_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(())
+}
+
 /// Processes a [InitializeExtraAccountMetaList](enum.TransferHookInstruction.html) instruction.
 pub fn process_initialize_extra_account_meta_list(
     program_id: &Pubkey,
@@ -147,5 +214,13 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> P
             msg!("Instruction: InitializeExtraAccountMetaList");
             process_initialize_extra_account_meta_list(program_id, accounts, &extra_account_metas)
         }
+        TransferHookInstruction::MintExecute { .. } => {
+            msg!("Instruction: MintExecute");
+            process_mint_execute(program_id, accounts)
+        }
+        TransferHookInstruction::BurnExecute { .. } => {
+            msg!("Instruction: BurnExecute");
+            process_burn_execute(program_id, accounts)
+        }
     }
 }
@@ -22,13 +22,19 @@ use {
         state::ExtraAccountMetaList,
     },
     spl_token_2022::{
-        extension::{transfer_hook::TransferHookAccount, ExtensionType, StateWithExtensionsMut},
+        extension::{
+            transfer_hook::{TransferHookAccount, TransferHookMintBurn},
+            ExtensionType, StateWithExtensionsMut,
+        },
         state::{Account, AccountState, Mint},
     },
     spl_transfer_hook_interface::{
         error::TransferHookError,
         get_extra_account_metas_address,
-        instruction::{execute_with_extra_account_metas, initialize_extra_account_meta_list},
+        instruction::{
+            burn_execute, execute_with_extra_account_metas, initialize_extra_account_meta_list,
+            mint_execute,
+        },
         onchain,
     },
 };
@@ -747,3 +753,130 @@ async fn fail_without_transferring_flag() {
         )
     );
 }
+
+#[tokio::test]
+async fn fail_without_minting_or_burning_flag() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = setup(&program_id);
+
+    let token_program_id = spl_token_2022::id();
+    let wallet = Keypair::new();
+    let mint_address = Pubkey::new_unique();
+    let mint_authority = Keypair::new();
+    let mint_authority_pubkey = mint_authority.pubkey();
+    let account = Pubkey::new_unique();
+
+    // add a mint that opted into mint and burn hooks, but is neither minting
+    // nor burning
+    let mint_size =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferHookMintBurn])
+            .unwrap();
+    let mut mint_data = vec![0; mint_size];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+    let extension = state.init_extension::<TransferHookMintBurn>(true).unwrap();
+    extension.invoke_on_mint = true.into();
+    extension.invoke_on_burn = true.into();
+    state.base = Mint {
+        mint_authority: COption::Some(mint_authority_pubkey),
+        supply: 0,
+        decimals: 2,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    program_test.add_account(
+        mint_address,
+        SolanaAccount {
+            lamports: 1_000_000_000,
+            data: mint_data,
+            owner: token_program_id,
+            ..SolanaAccount::default()
+        },
+    );
+
+    let extra_account_metas_address = get_extra_account_metas_address(&mint_address, &program_id);
+    let init_extra_account_metas = [];
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_lamports = rent
+        .minimum_balance(ExtraAccountMetaList::size_of(init_extra_account_metas.len()).unwrap());
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::transfer(
+                &context.payer.pubkey(),
+                &extra_account_metas_address,
+                rent_lamports,
+            ),
+            initialize_extra_account_meta_list(
+                &program_id,
+                &extra_account_metas_address,
+                &mint_address,
+                &mint_authority_pubkey,
+                &init_extra_account_metas,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint_authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[mint_execute(
+            &program_id,
+            &mint_address,
+            &account,
+            &mint_authority_pubkey,
+            &extra_account_metas_address,
+            0,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TransferHookError::ProgramCalledOutsideOfMint as u32)
+        )
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[burn_execute(
+            &program_id,
+            &account,
+            &mint_address,
+            &wallet.pubkey(),
+            &extra_account_metas_address,
+            0,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TransferHookError::ProgramCalledOutsideOfBurn as u32)
+        )
+    );
+}
@@ -17,4 +17,10 @@ pub enum TransferHookError {
     /// Program called outside of a token transfer
     #[error("Program called outside of a token transfer")]
     ProgramCalledOutsideOfTransfer,
+    /// Program called outside of a token mint
+    #[error("Program called outside of a token mint")]
+    ProgramCalledOutsideOfMint,
+    /// Program called outside of a token burn
+    #[error("Program called outside of a token burn")]
+    ProgramCalledOutsideOfBurn,
 }
@@ -46,6 +46,36 @@ pub enum TransferHookInstruction {
         /// List of `ExtraAccountMeta`s to write into the account
         extra_account_metas: Vec<ExtraAccountMeta>,
     },
+    /// Runs additional logic when tokens are minted. Only invoked by the
+    /// token program if the mint has opted into mint hooks.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Token mint
+    ///   1. `[]` Destination account
+    ///   2. `[]` Mint authority
+    ///   3. `[]` Validation account
+    ///   4. ..4+M `[]` `M` additional accounts, written in validation account data
+    ///
+    MintExecute {
+        /// Amount of tokens to mint
+        amount: u64,
+    },
+    /// Runs additional logic when tokens are burned. Only invoked by the
+    /// token program if the mint has opted into burn hooks.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` Source account
+    ///   1. `[]` Token mint
+    ///   2. `[]` Source account's owner/delegate
+    ///   3. `[]` Validation account
+    ///   4. ..4+M `[]` `M` additional accounts, written in validation account data
+    ///
+    BurnExecute {
+        /// Amount of tokens to burn
+        amount: u64,
+    },
 }
 /// TLV instruction type only used to define the discriminator. The actual data
 /// is entirely managed by `ExtraAccountMetaList`, and it is the only data contained
@@ -54,6 +84,20 @@ pub enum TransferHookInstruction {
 #[discriminator_hash_input("spl-transfer-hook-interface:execute")]
 pub struct ExecuteInstruction;
 
+/// TLV instruction type only used to define the discriminator for the mint
+/// hook. Extra account metas for minting are stored in the validation account
+/// under this discriminator.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("spl-transfer-hook-interface:mint-execute")]
+pub struct MintExecuteInstruction;
+
+/// TLV instruction type only used to define the discriminator for the burn
+/// hook. Extra account metas for burning are stored in the validation account
+/// under this discriminator.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("spl-transfer-hook-interface:burn-execute")]
+pub struct BurnExecuteInstruction;
+
 /// TLV instruction type used to initialize extra account metas
 /// for the transfer hook
 #[derive(SplDiscriminate)]
@@ -69,13 +113,17 @@ impl TransferHookInstruction {
         let (discriminator, rest) = input.split_at(ArrayDiscriminator::LENGTH);
         Ok(match discriminator {
             ExecuteInstruction::SPL_DISCRIMINATOR_SLICE => {
-                let amount = rest
-                    .get(..8)
-                    .and_then(|slice| slice.try_into().ok())
-                    .map(u64::from_le_bytes)
-                    .ok_or(ProgramError::InvalidInstructionData)?;
+                let amount = unpack_amount(rest)?;
                 Self::Execute { amount }
             }
+            MintExecuteInstruction::SPL_DISCRIMINATOR_SLICE => {
+                let amount = unpack_amount(rest)?;
+                Self::MintExecute { amount }
+            }
+            BurnExecuteInstruction::SPL_DISCRIMINATOR_SLICE => {
+                let amount = unpack_amount(rest)?;
+                Self::BurnExecute { amount }
+            }
             InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE => {
                 let pod_slice = PodSlice::<ExtraAccountMeta>::unpack(rest)?;
                 let extra_account_metas = pod_slice.data().to_vec();
@@ -104,11 +152,27 @@ impl TransferHookInstruction {
                 buf.extend_from_slice(&(extra_account_metas.len() as u32).to_le_bytes());
                 buf.extend_from_slice(pod_slice_to_bytes(extra_account_metas));
             }
+            Self::MintExecute { amount } => {
+                buf.extend_from_slice(MintExecuteInstruction::SPL_DISCRIMINATOR_SLICE);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::BurnExecute { amount } => {
+                buf.extend_from_slice(BurnExecuteInstruction::SPL_DISCRIMINATOR_SLICE);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
         };
         buf
     }
 }
 
+fn unpack_amount(input: &[u8]) -> Result<u64, ProgramError> {
+    input
+        .get(..8)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
 /// Creates an `Execute` instruction, provided all of the additional required
 /// account metas
 #[allow(clippy::too_many_arguments)]
@@ -161,6 +225,52 @@ pub fn execute(
     }
 }
 
+/// Creates a `MintExecute` instruction, without the additional accounts
+pub fn mint_execute(
+    program_id: &Pubkey,
+    mint_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    mint_authority_pubkey: &Pubkey,
+    validate_state_pubkey: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let data = TransferHookInstruction::MintExecute { amount }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*mint_pubkey, false),
+        AccountMeta::new_readonly(*destination_pubkey, false),
+        AccountMeta::new_readonly(*mint_authority_pubkey, false),
+        AccountMeta::new_readonly(*validate_state_pubkey, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates a `BurnExecute` instruction, without the additional accounts
+pub fn burn_execute(
+    program_id: &Pubkey,
+    source_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    validate_state_pubkey: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let data = TransferHookInstruction::BurnExecute { amount }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*source_pubkey, false),
+        AccountMeta::new_readonly(*mint_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*validate_state_pubkey, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
 /// Creates a `InitializeExtraAccountMetaList` instruction.
 pub fn initialize_extra_account_meta_list(
     program_id: &Pubkey,
@@ -209,6 +319,31 @@ mod test {
         assert_eq!(unpacked, check);
     }
 
+    #[test]
+    fn validate_mint_and_burn_packing() {
+        let amount = 222_222_222;
+        for (check, name) in [
+            (
+                TransferHookInstruction::MintExecute { amount },
+                "mint-execute",
+            ),
+            (
+                TransferHookInstruction::BurnExecute { amount },
+                "burn-execute",
+            ),
+        ] {
+            let packed = check.pack();
+            let preimage = hash::hashv(&[format!("{NAMESPACE}:{name}").as_bytes()]);
+            let discriminator = &preimage.as_ref()[..ArrayDiscriminator::LENGTH];
+            let mut expect = vec![];
+            expect.extend_from_slice(discriminator.as_ref());
+            expect.extend_from_slice(&amount.to_le_bytes());
+            assert_eq!(packed, expect);
+            let unpacked = TransferHookInstruction::unpack(&expect).unwrap();
+            assert_eq!(unpacked, check);
+        }
+    }
+
     #[test]
     fn initialize_validation_pubkeys_packing() {
         let extra_meta_len_bytes = &[
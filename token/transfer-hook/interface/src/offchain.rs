@@ -2,13 +2,18 @@
 
 pub use spl_tlv_account_resolution::state::{AccountDataResult, AccountFetchError};
 use {
-    crate::{get_extra_account_metas_address, instruction::ExecuteInstruction},
+    crate::{
+        get_extra_account_metas_address,
+        instruction::{BurnExecuteInstruction, ExecuteInstruction, MintExecuteInstruction},
+    },
     solana_program::{
         instruction::{AccountMeta, Instruction},
         program_error::ProgramError,
         pubkey::Pubkey,
     },
+    spl_discriminator::SplDiscriminate,
     spl_tlv_account_resolution::state::ExtraAccountMetaList,
+    spl_type_length_value::state::{TlvState, TlvStateBorrowed},
     std::future::Future,
 };
 
@@ -68,3 +73,90 @@ where
 
     Ok(())
 }
+
+/// Offchain helper to get all additional required account metas for a
+/// `MintTo` on a mint that has opted into mint hooks
+///
+/// Works the same as `resolve_extra_account_metas`, but resolves the extra
+/// accounts stored under `MintExecuteInstruction`. If the validation account
+/// has no such entry, only the program id and validation account are added.
+pub async fn resolve_extra_mint_account_metas<F, Fut>(
+    instruction: &mut Instruction,
+    fetch_account_data_fn: F,
+    mint: &Pubkey,
+    permissioned_transfer_program_id: &Pubkey,
+) -> Result<(), AccountFetchError>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    resolve_optional_extra_account_metas::<MintExecuteInstruction, _, _>(
+        instruction,
+        fetch_account_data_fn,
+        mint,
+        permissioned_transfer_program_id,
+    )
+    .await
+}
+
+/// Offchain helper to get all additional required account metas for a
+/// `Burn` on a mint that has opted into burn hooks
+///
+/// Works the same as `resolve_extra_account_metas`, but resolves the extra
+/// accounts stored under `BurnExecuteInstruction`. If the validation account
+/// has no such entry, only the program id and validation account are added.
+pub async fn resolve_extra_burn_account_metas<F, Fut>(
+    instruction: &mut Instruction,
+    fetch_account_data_fn: F,
+    mint: &Pubkey,
+    permissioned_transfer_program_id: &Pubkey,
+) -> Result<(), AccountFetchError>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    resolve_optional_extra_account_metas::<BurnExecuteInstruction, _, _>(
+        instruction,
+        fetch_account_data_fn,
+        mint,
+        permissioned_transfer_program_id,
+    )
+    .await
+}
+
+async fn resolve_optional_extra_account_metas<T: SplDiscriminate, F, Fut>(
+    instruction: &mut Instruction,
+    fetch_account_data_fn: F,
+    mint: &Pubkey,
+    permissioned_transfer_program_id: &Pubkey,
+) -> Result<(), AccountFetchError>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    let validation_address =
+        get_extra_account_metas_address(mint, permissioned_transfer_program_id);
+    let validation_account_data = fetch_account_data_fn(validation_address)
+        .await?
+        .ok_or(ProgramError::InvalidAccountData)?;
+    if TlvStateBorrowed::unpack(&validation_account_data)?
+        .get_first_bytes::<T>()
+        .is_ok()
+    {
+        ExtraAccountMetaList::add_to_instruction::<T, _, _>(
+            instruction,
+            fetch_account_data_fn,
+            &validation_account_data,
+        )
+        .await?;
+    }
+    instruction.accounts.push(AccountMeta::new_readonly(
+        *permissioned_transfer_program_id,
+        false,
+    ));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(validation_address, false));
+
+    Ok(())
+}
@@ -9,7 +9,9 @@ use {
         program::invoke,
         pubkey::Pubkey,
     },
+    spl_discriminator::SplDiscriminate,
     spl_tlv_account_resolution::state::ExtraAccountMetaList,
+    spl_type_length_value::state::{TlvState, TlvStateBorrowed},
 };
 /// Helper to CPI into a transfer-hook program on-chain, looking through the
 /// additional account infos to create the proper instruction
@@ -53,6 +55,118 @@ pub fn invoke_execute<'a>(
     invoke(&cpi_instruction, &cpi_account_infos)
 }
 
+/// Helper to CPI into a transfer-hook program on-chain after minting tokens,
+/// looking through the additional account infos to create the proper
+/// instruction
+///
+/// Extra accounts are only added if the validation account contains an
+/// entry for `MintExecuteInstruction`. Token-2022 sets the `minting` flag of
+/// the mint's `TransferHookMintBurn` extension for the duration of this call.
+pub fn invoke_mint_execute<'a>(
+    program_id: &Pubkey,
+    mint_info: AccountInfo<'a>,
+    destination_info: AccountInfo<'a>,
+    mint_authority_info: AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+    amount: u64,
+) -> ProgramResult {
+    let validation_pubkey = get_extra_account_metas_address(mint_info.key, program_id);
+    let validation_info = additional_accounts
+        .iter()
+        .find(|&x| *x.key == validation_pubkey)
+        .ok_or(TransferHookError::IncorrectAccount)?;
+    let mut cpi_instruction = instruction::mint_execute(
+        program_id,
+        mint_info.key,
+        destination_info.key,
+        mint_authority_info.key,
+        &validation_pubkey,
+        amount,
+    );
+
+    let mut cpi_account_infos = vec![
+        mint_info,
+        destination_info,
+        mint_authority_info,
+        validation_info.clone(),
+    ];
+    add_optional_cpi_accounts::<instruction::MintExecuteInstruction>(
+        &mut cpi_instruction,
+        &mut cpi_account_infos,
+        validation_info,
+        additional_accounts,
+    )?;
+    invoke(&cpi_instruction, &cpi_account_infos)
+}
+
+/// Helper to CPI into a transfer-hook program on-chain after burning tokens,
+/// looking through the additional account infos to create the proper
+/// instruction
+///
+/// Extra accounts are only added if the validation account contains an
+/// entry for `BurnExecuteInstruction`. Token-2022 sets the `burning` flag of
+/// the mint's `TransferHookMintBurn` extension for the duration of this call.
+pub fn invoke_burn_execute<'a>(
+    program_id: &Pubkey,
+    source_info: AccountInfo<'a>,
+    mint_info: AccountInfo<'a>,
+    authority_info: AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+    amount: u64,
+) -> ProgramResult {
+    let validation_pubkey = get_extra_account_metas_address(mint_info.key, program_id);
+    let validation_info = additional_accounts
+        .iter()
+        .find(|&x| *x.key == validation_pubkey)
+        .ok_or(TransferHookError::IncorrectAccount)?;
+    let mut cpi_instruction = instruction::burn_execute(
+        program_id,
+        source_info.key,
+        mint_info.key,
+        authority_info.key,
+        &validation_pubkey,
+        amount,
+    );
+
+    let mut cpi_account_infos = vec![
+        source_info,
+        mint_info,
+        authority_info,
+        validation_info.clone(),
+    ];
+    add_optional_cpi_accounts::<instruction::BurnExecuteInstruction>(
+        &mut cpi_instruction,
+        &mut cpi_account_infos,
+        validation_info,
+        additional_accounts,
+    )?;
+    invoke(&cpi_instruction, &cpi_account_infos)
+}
+
+/// Adds the extra accounts stored under `T` in the validation account, if any.
+/// Hook programs that only care about transfers never write an entry for the
+/// mint and burn discriminators, so a missing entry is not an error.
+fn add_optional_cpi_accounts<'a, T: SplDiscriminate>(
+    cpi_instruction: &mut Instruction,
+    cpi_account_infos: &mut Vec<AccountInfo<'a>>,
+    validation_info: &AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let validation_data = validation_info.try_borrow_data()?;
+    if TlvStateBorrowed::unpack(&validation_data)?
+        .get_first_bytes::<T>()
+        .is_err()
+    {
+        return Ok(());
+    }
+    ExtraAccountMetaList::add_to_cpi_instruction::<T>(
+        cpi_instruction,
+        cpi_account_infos,
+        &validation_data,
+        additional_accounts,
+    )
+}
+
 /// Helper to add accounts required for the transfer-hook program on-chain, looking
 /// through the additional account infos to add the proper accounts
 pub fn add_cpi_accounts_for_execute<'a>(